@@ -0,0 +1,500 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use program_structure::ast::Expression;
+use program_structure::error_definition::Report;
+use program_structure::program_archive::ProgramArchive;
+use type_analysis::check_types::check_types;
+
+pub use crate::error::ZkFuzzError;
+use crate::executor::symbolic_execution::SymbolicExecutor;
+use crate::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+    SymbolicExecutorSetting, WhitelistMode,
+};
+use crate::executor::symbolic_state::PotentialDivByZero;
+use crate::executor::symbolic_value::{OwnerName, SymbolicLibrary};
+use crate::mutator::mutation_config::MutationConfig;
+use crate::mutator::mutation_test::mutation_test_search;
+use crate::mutator::mutation_test_evolution_fn::simple_evolution;
+use crate::mutator::mutation_test_operators::OperatorRegistry;
+use crate::mutator::mutation_test_trace_fitness_fn::{
+    evaluate_trace_fitness_by_distance, evaluate_trace_fitness_by_error,
+};
+use crate::mutator::mutation_test_update_input_fn::{
+    update_input_population_with_coverage_maximization, update_input_population_with_fitness_score,
+    update_input_population_with_random_sampling,
+};
+use crate::mutator::shrink::shrink_counterexample;
+use crate::mutator::unused_outputs::check_unused_outputs;
+use crate::mutator::utils::{BaseVerificationConfig, CounterExample, SearchCompleteness};
+use crate::stats::constraint_diff::normalize_constraints;
+use crate::stats::execution_metrics::ExecutionMetrics;
+use crate::stats::symbolic_stats::ConstraintStatistics;
+use crate::VERSION;
+
+/// Configuration of `analyze_circuit`.
+///
+/// The default mirrors the default command-line options: the BN128 prime, the `IsZero` and
+/// `Num2Bits` whitelist, and the genetic search with the default `MutationConfig`.
+pub struct AnalysisConfig {
+    pub prime: BigInt,
+    pub link_libraries: Vec<PathBuf>,
+    pub whitelist: FxHashSet<String>,
+    /// Whether the whitelisted templates are inlined or trusted as black boxes.
+    pub whitelist_mode: WhitelistMode,
+    /// Either `ga` (genetic search) or `off` (only the unused-output check).
+    pub search_mode: String,
+    pub mutation_config: MutationConfig,
+    pub constraint_assert_dissabled: bool,
+    pub lessthan_dissabled: bool,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        AnalysisConfig {
+            prime: BigInt::from_str(
+                "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+            )
+            .unwrap(),
+            link_libraries: Vec::new(),
+            whitelist: FxHashSet::from_iter(["IsZero".to_string(), "Num2Bits".to_string()]),
+            whitelist_mode: WhitelistMode::Inline,
+            search_mode: "ga".to_string(),
+            mutation_config: MutationConfig::default(),
+            constraint_assert_dissabled: false,
+            lessthan_dissabled: false,
+        }
+    }
+}
+
+/// Result of `analyze_circuit`.
+pub struct AnalysisReport {
+    pub main_template_name: String,
+    pub trace_statistics: ConstraintStatistics,
+    pub side_constraint_statistics: ConstraintStatistics,
+    /// Components, nesting, and function calls of the symbolic execution.
+    pub execution_metrics: ExecutionMetrics,
+    /// Number of side constraints over the number of trace constraints.
+    pub compression_rate: f64,
+    pub counter_example: Option<CounterExample>,
+    /// Whether the search covered every assignment, which tells whether the absence of a
+    /// counterexample proves the template safe.
+    pub search_completeness: SearchCompleteness,
+    /// Divisions whose divisor is not a constant and may thus be zero.
+    pub potential_div_by_zeros: Vec<PotentialDivByZero>,
+    /// Lookup table to render the symbolic names of the counterexample.
+    pub id2name: FxHashMap<usize, String>,
+}
+
+fn report_messages(reports: &[Report]) -> Vec<String> {
+    reports.iter().map(|r| r.get_message().clone()).collect()
+}
+
+fn parse_and_analyse(path: &str, config: &AnalysisConfig) -> Result<ProgramArchive, ZkFuzzError> {
+    let mut program_archive = parser::run_parser(
+        path.to_string(),
+        VERSION,
+        config.link_libraries.clone(),
+        &config.prime,
+    )
+    .map_err(|(_, reports)| ZkFuzzError::Parse(report_messages(&reports)))?
+    .0;
+    check_types(&mut program_archive)
+        .map_err(|reports| ZkFuzzError::TypeCheck(report_messages(&reports)))?;
+    Ok(program_archive)
+}
+
+fn check_config(config: &AnalysisConfig) -> Result<(), ZkFuzzError> {
+    if config.search_mode != "ga" && config.search_mode != "off" {
+        return Err(ZkFuzzError::InvalidConfig(format!(
+            "search_mode={} is not supported",
+            config.search_mode
+        )));
+    }
+    let registry = OperatorRegistry::default();
+    let mutation_config = &config.mutation_config;
+    if registry
+        .trace_mutation(&mutation_config.trace_mutation_method)
+        .is_none()
+    {
+        return Err(ZkFuzzError::InvalidConfig(format!(
+            "trace_mutation_method={} is not supported",
+            mutation_config.trace_mutation_method
+        )));
+    }
+    if registry
+        .crossover(&mutation_config.crossover_method)
+        .is_none()
+    {
+        return Err(ZkFuzzError::InvalidConfig(format!(
+            "crossover_method={} is not supported",
+            mutation_config.crossover_method
+        )));
+    }
+    if registry
+        .selection(&mutation_config.selection_method)
+        .is_none()
+    {
+        return Err(ZkFuzzError::InvalidConfig(format!(
+            "selection_method={} is not supported",
+            mutation_config.selection_method
+        )));
+    }
+    match config.mutation_config.input_initialization_method.as_str() {
+        "random" | "fitness" | "coverage" => {}
+        method => {
+            return Err(ZkFuzzError::InvalidConfig(format!(
+                "input_initialization_method={} is not supported",
+                method
+            )))
+        }
+    }
+    match config.mutation_config.fitness_method.as_str() {
+        "error" | "distance" => {}
+        method => {
+            return Err(ZkFuzzError::InvalidConfig(format!(
+                "fitness_method={} is not supported",
+                method
+            )))
+        }
+    }
+    Ok(())
+}
+
+fn build_symbolic_library(
+    program_archive: &ProgramArchive,
+    config: &AnalysisConfig,
+) -> SymbolicLibrary {
+    let mut symbolic_library = SymbolicLibrary::default();
+    symbolic_library.register_source_files(&program_archive.file_library);
+    for (k, v) in program_archive.templates.iter() {
+        symbolic_library.register_template(
+            k.clone(),
+            v.get_body(),
+            v.get_name_of_params(),
+            &config.whitelist,
+            config.lessthan_dissabled,
+        );
+    }
+    for (k, v) in program_archive.functions.iter() {
+        symbolic_library.register_function(k.clone(), v.get_body().clone(), v.get_name_of_params());
+    }
+    for (k, v) in program_archive.buses.iter() {
+        symbolic_library.register_bus(k.clone(), v.get_body().clone(), v.get_name_of_params());
+    }
+    symbolic_library
+}
+
+/// Symbolically executes `main_template_name` as the main component and returns the executor,
+/// whose state holds the gathered trace and side constraints.
+fn execute_template<'a>(
+    symbolic_library: &'a mut SymbolicLibrary,
+    setting: &'a SymbolicExecutorSetting,
+    program_archive: &ProgramArchive,
+    main_template_name: &str,
+    args: Option<&Vec<Expression>>,
+) -> Result<SymbolicExecutor<'a>, ZkFuzzError> {
+    symbolic_library.clear_function_counter();
+    let mut sym_executor = SymbolicExecutor::new(symbolic_library, setting);
+    let template_param_names = program_archive.templates[main_template_name]
+        .get_name_of_params()
+        .clone();
+    sym_executor.symbolic_library.name2id.insert(
+        "main".to_string(),
+        sym_executor.symbolic_library.name2id.len(),
+    );
+    sym_executor.symbolic_library.id2name.insert(
+        sym_executor.symbolic_library.name2id["main"],
+        "main".to_string(),
+    );
+    sym_executor.cur_state.add_owner(&OwnerName {
+        id: sym_executor.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    });
+    sym_executor
+        .cur_state
+        .set_template_id(sym_executor.symbolic_library.name2id[main_template_name]);
+    if let Some(args) = args {
+        sym_executor
+            .feed_arguments(&template_param_names, args)
+            .map_err(|e| {
+                ZkFuzzError::InvalidTemplateArguments(main_template_name.to_string(), e)
+            })?;
+    }
+    let body = sym_executor.symbolic_library.template_library
+        [&sym_executor.symbolic_library.name2id[main_template_name]]
+        .body
+        .clone();
+    sym_executor.execute(&body, 0);
+    Ok(sym_executor)
+}
+
+/// Analyzes `main_template_name` as if it were the main component.
+///
+/// When `args` is `None`, the template parameters are left as symbolic values, like
+/// `--symbolic_template_params` does for the main template.
+fn analyze_template(
+    mut symbolic_library: SymbolicLibrary,
+    program_archive: &ProgramArchive,
+    config: &AnalysisConfig,
+    main_template_name: &str,
+    args: Option<&Vec<Expression>>,
+) -> Result<AnalysisReport, ZkFuzzError> {
+    let mut mutation_config = config.mutation_config.clone();
+    mutation_config.quiet = true;
+
+    // The methods were validated by `check_config`
+    let registry = OperatorRegistry::default();
+    let trace_mutation = registry
+        .trace_mutation(&mutation_config.trace_mutation_method)
+        .expect("Unknown trace_mutation_method");
+    let trace_crossover = registry
+        .crossover(&mutation_config.crossover_method)
+        .expect("Unknown crossover_method");
+    let trace_selection = registry
+        .selection(&mutation_config.selection_method)
+        .expect("Unknown selection_method");
+    let update_input_fn = match mutation_config.input_initialization_method.as_str() {
+        "random" => update_input_population_with_random_sampling,
+        "fitness" => update_input_population_with_fitness_score,
+        _ => update_input_population_with_coverage_maximization,
+    };
+    let trace_fitness_fn = match mutation_config.fitness_method.as_str() {
+        "distance" => evaluate_trace_fitness_by_distance,
+        _ => evaluate_trace_fitness_by_error,
+    };
+
+    let mut setting = get_default_setting_for_symbolic_execution(
+        config.prime.clone(),
+        config.constraint_assert_dissabled,
+    );
+    setting.whitelist_mode = config.whitelist_mode;
+    let mut off_trace_setting = setting.clone();
+    off_trace_setting.off_trace = true;
+    let concrete_setting = get_default_setting_for_concrete_execution(
+        config.prime.clone(),
+        config.constraint_assert_dissabled,
+    );
+
+    let mut sym_executor = execute_template(
+        &mut symbolic_library,
+        &setting,
+        program_archive,
+        main_template_name,
+        args,
+    )?;
+
+    let mut trace_statistics = ConstraintStatistics::new();
+    let mut side_constraint_statistics = ConstraintStatistics::new();
+    for c in &sym_executor.cur_state.symbolic_trace {
+        trace_statistics.update(c);
+    }
+    for c in &sym_executor.cur_state.side_constraints {
+        side_constraint_statistics.update(c);
+    }
+    let compression_rate = side_constraint_statistics.total_constraints as f64
+        / trace_statistics.total_constraints as f64;
+    let execution_metrics = ExecutionMetrics::from_executor(&sym_executor);
+    let potential_div_by_zeros = sym_executor.cur_state.potential_div_by_zeros.clone();
+
+    let (template_param_names, template_param_values) = match args {
+        Some(args) => (
+            program_archive.templates[main_template_name]
+                .get_name_of_params()
+                .clone(),
+            args.clone(),
+        ),
+        None => (Vec::new(), Vec::new()),
+    };
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: config.prime.clone(),
+        range: BigInt::from(100),
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    sym_executor.setting = &off_trace_setting;
+    // The report holds a single counterexample, so only the first unused output is reported
+    let mut counter_example = check_unused_outputs(&mut sym_executor, &verification_base_config)
+        .into_iter()
+        .next();
+    if counter_example.is_none() && config.search_mode == "ga" {
+        let symbolic_trace = sym_executor.cur_state.symbolic_trace.clone();
+        let side_constraints = sym_executor.cur_state.side_constraints.clone();
+        let mut conc_executor =
+            SymbolicExecutor::new(&mut sym_executor.symbolic_library, &concrete_setting);
+        conc_executor
+            .feed_arguments(
+                &verification_base_config.template_param_names,
+                &verification_base_config.template_param_values,
+            )
+            .map_err(|e| {
+                ZkFuzzError::InvalidTemplateArguments(main_template_name.to_string(), e)
+            })?;
+        counter_example = mutation_test_search(
+            &mut conc_executor,
+            &symbolic_trace,
+            &side_constraints,
+            &verification_base_config,
+            &mutation_config,
+            update_input_fn,
+            trace_fitness_fn,
+            simple_evolution,
+            trace_mutation,
+            trace_crossover,
+            trace_selection,
+            &mut |_| {},
+        )
+        .counter_example
+        .map(|ce| {
+            let mut ce = shrink_counterexample(
+                &mut conc_executor,
+                &symbolic_trace,
+                &side_constraints,
+                &ce,
+                &verification_base_config,
+            );
+            ce.explain(&sym_executor.cur_state);
+            ce
+        });
+    }
+
+    Ok(AnalysisReport {
+        main_template_name: main_template_name.to_string(),
+        trace_statistics: trace_statistics,
+        side_constraint_statistics: side_constraint_statistics,
+        execution_metrics: execution_metrics,
+        compression_rate: compression_rate,
+        counter_example: counter_example,
+        // Neither the genetic search nor `off` covers every assignment
+        search_completeness: SearchCompleteness::Incomplete,
+        potential_div_by_zeros: potential_div_by_zeros,
+        id2name: sym_executor.symbolic_library.id2name.clone(),
+    })
+}
+
+/// Returns the name and the arguments of the template instantiated as the main component.
+pub fn main_template_call(
+    program_archive: &ProgramArchive,
+) -> Result<(String, Vec<Expression>), ZkFuzzError> {
+    match &program_archive.initial_template_call {
+        Expression::Call { id, args, .. } => Ok((id.clone(), args.clone())),
+        _ => Err(ZkFuzzError::NoMainComponent),
+    }
+}
+
+/// Runs the whole zkFuzz pipeline on a circuit: parsing, symbolic execution, and the search
+/// for a counterexample.
+///
+/// Unlike the command-line tool, this function never writes to stdout or stderr, which makes it
+/// suitable for embedding in other tools such as CI checks.
+///
+/// # Parameters
+/// - `path`: Path to a circuit with a main component.
+/// - `config`: The analysis configuration.
+///
+/// # Returns
+/// An `AnalysisReport` with the statistics of the gathered constraints and the counterexample,
+/// if any, or a `ZkFuzzError` when the circuit cannot be analyzed.
+pub fn analyze_circuit(path: &str, config: AnalysisConfig) -> Result<AnalysisReport, ZkFuzzError> {
+    check_config(&config)?;
+    let program_archive = parse_and_analyse(path, &config)?;
+    let (main_template_name, args) = main_template_call(&program_archive)?;
+
+    let symbolic_library = build_symbolic_library(&program_archive, &config);
+    analyze_template(
+        symbolic_library,
+        &program_archive,
+        &config,
+        &main_template_name,
+        Some(&args),
+    )
+}
+
+/// Analyzes every template of a circuit, each as if it were the main component.
+///
+/// The template parameters are left as symbolic values, and every template starts from a fresh
+/// copy of the symbolic library, so that the function counters and the `main` owner of one
+/// template do not leak into the next.
+///
+/// # Parameters
+/// - `path`: Path to a circuit with a main component.
+/// - `config`: The analysis configuration.
+///
+/// # Returns
+/// The name and the `AnalysisReport` of each template, sorted by name, or a `ZkFuzzError` when
+/// the circuit cannot be analyzed.
+pub fn analyze_all_templates(
+    path: &str,
+    config: AnalysisConfig,
+) -> Result<Vec<(String, AnalysisReport)>, ZkFuzzError> {
+    check_config(&config)?;
+    let program_archive = parse_and_analyse(path, &config)?;
+    let symbolic_library = build_symbolic_library(&program_archive, &config);
+
+    let mut template_names = program_archive
+        .templates
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    template_names.sort();
+    template_names
+        .into_iter()
+        .map(|name| {
+            let report = analyze_template(
+                symbolic_library.clone(),
+                &program_archive,
+                &config,
+                &name,
+                None,
+            )?;
+            Ok((name, report))
+        })
+        .collect()
+}
+
+/// Symbolically executes the main component of a circuit and returns its side constraints in
+/// the canonical form of `normalize_constraints`.
+///
+/// Only `prime`, `link_libraries`, `whitelist`, `whitelist_mode`, `constraint_assert_dissabled`,
+/// and `lessthan_dissabled` of the configuration are used. Pair it with `diff_constraints` to
+/// compare the constraints of two versions of a circuit.
+///
+/// # Parameters
+/// - `path`: Path to a circuit with a main component.
+/// - `config`: The analysis configuration.
+///
+/// # Returns
+/// The normalized side constraints, or a `ZkFuzzError` when the circuit cannot be analyzed.
+pub fn gather_side_constraints(
+    path: &str,
+    config: &AnalysisConfig,
+) -> Result<Vec<String>, ZkFuzzError> {
+    let program_archive = parse_and_analyse(path, config)?;
+    let (main_template_name, args) = main_template_call(&program_archive)?;
+
+    let mut symbolic_library = build_symbolic_library(&program_archive, config);
+    let mut setting = get_default_setting_for_symbolic_execution(
+        config.prime.clone(),
+        config.constraint_assert_dissabled,
+    );
+    setting.whitelist_mode = config.whitelist_mode;
+    let sym_executor = execute_template(
+        &mut symbolic_library,
+        &setting,
+        &program_archive,
+        &main_template_name,
+        Some(&args),
+    )?;
+    Ok(normalize_constraints(
+        &sym_executor.cur_state.side_constraints,
+        &sym_executor.symbolic_library.id2name,
+    ))
+}
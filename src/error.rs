@@ -0,0 +1,109 @@
+use std::fmt;
+
+/// Errors that stop the analysis of a circuit.
+#[derive(Debug)]
+pub enum ZkFuzzError {
+    /// The command line arguments are invalid; the details are printed by `Input::new`.
+    InvalidArguments,
+    /// The circuit cannot be parsed. The messages are empty when the reports were already printed.
+    Parse(Vec<String>),
+    /// The circuit is ill-typed. The messages are empty when the reports were already printed.
+    TypeCheck(Vec<String>),
+    NoMainComponent,
+    InvalidConfig(String),
+    InvalidPrime(String),
+    InvalidRange(String),
+    UnsupportedSearchMode(String),
+    UnsupportedWhitelistMode(String),
+    /// The whitelist file cannot be read: the path and the reason.
+    WhitelistReadError(String, String),
+    InvalidInputConstraints(String),
+    /// The input JSON given to `--evaluate_input` is invalid.
+    InvalidInput(String),
+    InvalidParamBounds(String),
+    /// The bit widths given with `--signal_range` are invalid.
+    InvalidSignalRange(String),
+    /// An assumption given with `--assume` is invalid: the assumption and the reason.
+    InvalidAssumption(String, String),
+    /// The regular expression of `--templates_filter` is invalid: the expression and the reason.
+    InvalidTemplatesFilter(String, String),
+    /// The main component is instantiated with wrong arguments: the template and the reason.
+    InvalidTemplateArguments(String, String),
+    /// A file cannot be read or written: the path and the reason.
+    Io(String, String),
+    /// `--warn_as_error` is given and the analysis reported counterexamples or warnings: their
+    /// number.
+    FindingsAsErrors(usize),
+}
+
+fn join_messages(f: &mut fmt::Formatter<'_>, kind: &str, messages: &[String]) -> fmt::Result {
+    if messages.is_empty() {
+        write!(f, "{}, see the reports above", kind)
+    } else {
+        write!(f, "{}: {}", kind, messages.join("; "))
+    }
+}
+
+impl fmt::Display for ZkFuzzError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZkFuzzError::InvalidArguments => {
+                write!(f, "invalid arguments, see the messages above")
+            }
+            ZkFuzzError::Parse(messages) => join_messages(f, "parse error", messages),
+            ZkFuzzError::TypeCheck(messages) => join_messages(f, "type error", messages),
+            ZkFuzzError::NoMainComponent => write!(f, "the circuit has no main component"),
+            ZkFuzzError::InvalidConfig(message) => write!(f, "invalid config: {}", message),
+            ZkFuzzError::InvalidPrime(value) => {
+                write!(f, "`{}` is not a valid prime number", value)
+            }
+            ZkFuzzError::InvalidRange(value) => {
+                write!(f, "`{}` is not a valid heuristics range", value)
+            }
+            ZkFuzzError::UnsupportedSearchMode(mode) => {
+                write!(f, "search_mode={} is not supported", mode)
+            }
+            ZkFuzzError::UnsupportedWhitelistMode(mode) => {
+                write!(f, "whitelist_mode={} is not supported", mode)
+            }
+            ZkFuzzError::WhitelistReadError(path, reason) => {
+                write!(f, "unable to read the whitelist `{}`: {}", path, reason)
+            }
+            ZkFuzzError::InvalidInputConstraints(message) => {
+                write!(f, "invalid input constraints: {}", message)
+            }
+            ZkFuzzError::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            ZkFuzzError::InvalidParamBounds(message) => {
+                write!(f, "invalid parameter bounds: {}", message)
+            }
+            ZkFuzzError::InvalidSignalRange(message) => {
+                write!(f, "invalid signal range: {}", message)
+            }
+            ZkFuzzError::InvalidAssumption(assumption, reason) => {
+                write!(f, "`{}` is not a valid assumption: {}", assumption, reason)
+            }
+            ZkFuzzError::InvalidTemplatesFilter(filter, reason) => {
+                write!(
+                    f,
+                    "`{}` is not a valid templates filter: {}",
+                    filter, reason
+                )
+            }
+            ZkFuzzError::InvalidTemplateArguments(template, reason) => {
+                write!(
+                    f,
+                    "invalid arguments of template `{}`: {}",
+                    template, reason
+                )
+            }
+            ZkFuzzError::Io(path, reason) => write!(f, "unable to access `{}`: {}", path, reason),
+            ZkFuzzError::FindingsAsErrors(num_findings) => write!(
+                f,
+                "{} finding(s) reported, which fail the run with --warn_as_error",
+                num_findings
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ZkFuzzError {}
@@ -13,6 +13,8 @@ use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 ///   where the key is a branch identifier, and the value is the visit count.
 /// - `current_path`: A vector (`Vec<(usize, usize, bool)>`) storing the sequence of branches taken in the current execution path.
 ///   Each entry is a tuple of the branch ID, visit count, and the branch condition.
+/// - `branch_hits`: A hash map (`FxHashMap<usize, (bool, bool)>`) recording, for each branch ID, whether the
+///   `true` and the `false` outcomes have been taken in any recorded execution.
 ///
 /// # Methods
 /// ## `new`
@@ -31,6 +33,7 @@ use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 /// ### Behavior
 /// - Increments the visit count for the branch ID in `visit_counter`.
 /// - Appends a tuple containing the branch ID, its visit count, and the branch condition to `current_path`.
+/// - Marks the outcome of the branch as hit in `branch_hits`.
 ///
 /// ## `record_path`
 /// Finalizes and records the current execution path by hashing it and adding the result to `paths`.
@@ -42,17 +45,32 @@ use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 /// A `u64` hash value representing the current path.
 ///
 /// ## `clear`
-/// Resets all tracking data, clearing both the recorded paths and the current execution path.
+/// Resets all tracking data, clearing the recorded paths, the branch hits, and the current execution path.
 ///
 /// ## `clear_current_path`
 /// Clears only the current execution path and its associated visit counters.
 ///
+/// ## `branch_hits`
+/// Returns the per-branch hit bitmap, which can be cloned to serve as a baseline.
+///
+/// ## `new_coverage_since`
+/// Counts the branch outcomes hit now but not in the given baseline.
+///
+/// ### Parameters
+/// - `baseline`: A bitmap previously returned by `branch_hits`.
+///
+/// ### Returns
+/// The number of newly covered branch outcomes, where each branch has a `true` and a `false` outcome.
+///
 /// ## `coverage_count`
 /// Returns the total number of unique paths recorded.
 ///
 /// ### Returns
 /// The size of the `paths` set, representing the count of unique execution paths.
 ///
+/// ## `merge`
+/// Adds the unique paths and the branch hits recorded by another tracker to this one.
+///
 /// # Example
 /// ```rust
 /// use zkfuzz::executor::coverage::CoverageTracker;
@@ -70,6 +88,14 @@ use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 /// tracker.record_path();
 ///
 /// assert_eq!(tracker.coverage_count(), 2);
+///
+/// let baseline = tracker.branch_hits().clone();
+/// tracker.clear_current_path();
+/// tracker.record_branch(1, true);
+/// tracker.record_branch(2, true);
+/// tracker.record_path();
+///
+/// assert_eq!(tracker.new_coverage_since(&baseline), 1);
 /// tracker.clear();
 /// assert_eq!(tracker.coverage_count(), 0);
 /// ```
@@ -82,6 +108,7 @@ pub struct CoverageTracker {
     paths: FxHashSet<u64>,
     visit_counter: FxHashMap<usize, usize>,
     current_path: Vec<(usize, usize, bool)>,
+    branch_hits: FxHashMap<usize, (bool, bool)>,
 }
 
 impl CoverageTracker {
@@ -90,6 +117,7 @@ impl CoverageTracker {
             paths: FxHashSet::default(),
             visit_counter: FxHashMap::default(),
             current_path: Vec::new(),
+            branch_hits: FxHashMap::default(),
         }
     }
 
@@ -97,6 +125,15 @@ impl CoverageTracker {
         *self.visit_counter.entry(meta_elem_id).or_insert(0) += 1;
         self.current_path
             .push((meta_elem_id, self.visit_counter[&meta_elem_id], branch_cond));
+        let hits = self
+            .branch_hits
+            .entry(meta_elem_id)
+            .or_insert((false, false));
+        if branch_cond {
+            hits.0 = true;
+        } else {
+            hits.1 = true;
+        }
     }
 
     pub fn record_path(&mut self) {
@@ -113,6 +150,7 @@ impl CoverageTracker {
     pub fn clear(&mut self) {
         self.clear_current_path();
         self.paths.clear();
+        self.branch_hits.clear();
     }
 
     pub fn clear_current_path(&mut self) {
@@ -123,4 +161,28 @@ impl CoverageTracker {
     pub fn coverage_count(&self) -> usize {
         self.paths.len()
     }
+
+    pub fn branch_hits(&self) -> &FxHashMap<usize, (bool, bool)> {
+        &self.branch_hits
+    }
+
+    pub fn new_coverage_since(&self, baseline: &FxHashMap<usize, (bool, bool)>) -> usize {
+        self.branch_hits
+            .iter()
+            .map(|(elem_id, (hit_true, hit_false))| {
+                let (base_true, base_false) =
+                    baseline.get(elem_id).copied().unwrap_or((false, false));
+                (*hit_true && !base_true) as usize + (*hit_false && !base_false) as usize
+            })
+            .sum()
+    }
+
+    pub fn merge(&mut self, other: &CoverageTracker) {
+        self.paths.extend(other.paths.iter().copied());
+        for (elem_id, (hit_true, hit_false)) in &other.branch_hits {
+            let hits = self.branch_hits.entry(*elem_id).or_insert((false, false));
+            hits.0 |= *hit_true;
+            hits.1 |= *hit_false;
+        }
+    }
 }
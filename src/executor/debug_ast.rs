@@ -1,5 +1,7 @@
 use num_bigint_dig::BigInt;
 use rustc_hash::FxHashMap;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 
@@ -462,6 +464,67 @@ impl Hash for DebuggableExpressionPrefixOpcode {
 
 impl Eq for DebuggableExpressionPrefixOpcode {}
 
+// The opcodes are (de)serialized by the names printed by their `Debug` implementations.
+impl Serialize for DebuggableExpressionInfixOpcode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:?}", self))
+    }
+}
+
+impl<'de> Deserialize<'de> for DebuggableExpressionInfixOpcode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        let opcode = match name.as_str() {
+            "Mul" => ExpressionInfixOpcode::Mul,
+            "Div" => ExpressionInfixOpcode::Div,
+            "Add" => ExpressionInfixOpcode::Add,
+            "Sub" => ExpressionInfixOpcode::Sub,
+            "Pow" => ExpressionInfixOpcode::Pow,
+            "IntDiv" => ExpressionInfixOpcode::IntDiv,
+            "Mod" => ExpressionInfixOpcode::Mod,
+            "ShL" => ExpressionInfixOpcode::ShiftL,
+            "ShR" => ExpressionInfixOpcode::ShiftR,
+            "LEq" => ExpressionInfixOpcode::LesserEq,
+            "GEq" => ExpressionInfixOpcode::GreaterEq,
+            "Lt" => ExpressionInfixOpcode::Lesser,
+            "Gt" => ExpressionInfixOpcode::Greater,
+            "Eq" => ExpressionInfixOpcode::Eq,
+            "NEq" => ExpressionInfixOpcode::NotEq,
+            "BoolOr" => ExpressionInfixOpcode::BoolOr,
+            "BoolAnd" => ExpressionInfixOpcode::BoolAnd,
+            "BitOr" => ExpressionInfixOpcode::BitOr,
+            "BitAnd" => ExpressionInfixOpcode::BitAnd,
+            "BitXor" => ExpressionInfixOpcode::BitXor,
+            _ => return Err(D::Error::custom(format!("unknown infix opcode `{}`", name))),
+        };
+        Ok(DebuggableExpressionInfixOpcode(opcode))
+    }
+}
+
+impl Serialize for DebuggableExpressionPrefixOpcode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:?}", self))
+    }
+}
+
+impl<'de> Deserialize<'de> for DebuggableExpressionPrefixOpcode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        let opcode = match name.as_str() {
+            "Minus" => ExpressionPrefixOpcode::Sub,
+            "BoolNot" => ExpressionPrefixOpcode::BoolNot,
+            "Complement" => ExpressionPrefixOpcode::Complement,
+            _ => {
+                return Err(D::Error::custom(format!(
+                    "unknown prefix opcode `{}`",
+                    name
+                )))
+            }
+        };
+        Ok(DebuggableExpressionPrefixOpcode(opcode))
+    }
+}
+
 impl fmt::Debug for DebuggableSignalType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.0 {
@@ -718,6 +781,25 @@ impl DebuggableExpression {
 }
 
 impl DebuggableStatement {
+    /// Returns the metadata of the statement, or `None` for `Ret`, which is not in the source.
+    pub fn get_meta(&self) -> Option<&Meta> {
+        match self {
+            DebuggableStatement::IfThenElse { meta, .. }
+            | DebuggableStatement::While { meta, .. }
+            | DebuggableStatement::Return { meta, .. }
+            | DebuggableStatement::InitializationBlock { meta, .. }
+            | DebuggableStatement::Declaration { meta, .. }
+            | DebuggableStatement::Substitution { meta, .. }
+            | DebuggableStatement::MultSubstitution { meta, .. }
+            | DebuggableStatement::UnderscoreSubstitution { meta, .. }
+            | DebuggableStatement::ConstraintEquality { meta, .. }
+            | DebuggableStatement::LogCall { meta }
+            | DebuggableStatement::Block { meta, .. }
+            | DebuggableStatement::Assert { meta, .. } => Some(meta),
+            DebuggableStatement::Ret => None,
+        }
+    }
+
     pub fn apply_iterative<F>(&mut self, mut func: F)
     where
         F: FnMut(&mut DebuggableStatement),
@@ -0,0 +1,194 @@
+use num_bigint_dig::BigInt;
+use num_traits::{Signed, ToPrimitive};
+
+use program_structure::ast::ExpressionInfixOpcode;
+
+use crate::executor::symbolic_value::SymbolicValue;
+use crate::executor::utils::{moddiv, modpow};
+
+/// Arithmetic over a prime field.
+///
+/// All operands are expected to be canonical field elements, i.e., integers in `[0, p)`, and
+/// every result is again a canonical field element.
+pub trait FieldOps {
+    fn prime(&self) -> BigInt;
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt;
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt;
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt;
+    /// Multiplies `a` by the inverse of `b`. Following circom, the result is `0` when either
+    /// operand is `0`, and also when `b` has no inverse, which only happens for a non-prime
+    /// modulus.
+    fn div(&self, a: &BigInt, b: &BigInt) -> BigInt;
+    fn pow(&self, a: &BigInt, e: &BigInt) -> BigInt;
+}
+
+/// Field arithmetic on arbitrary-precision integers, usable with any prime.
+pub struct BigIntFieldOps {
+    pub prime: BigInt,
+}
+
+impl FieldOps for BigIntFieldOps {
+    fn prime(&self) -> BigInt {
+        self.prime.clone()
+    }
+
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a + b) % &self.prime
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let mut tmp = (a - b) % &self.prime;
+        if tmp.is_negative() {
+            tmp += &self.prime;
+        }
+        tmp
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a * b) % &self.prime
+    }
+
+    fn div(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        moddiv(a, b, &self.prime)
+    }
+
+    fn pow(&self, a: &BigInt, e: &BigInt) -> BigInt {
+        modpow(a, e, &self.prime)
+    }
+}
+
+/// Field arithmetic for primes below `2^64` (e.g., Goldilocks), computed with `u128`
+/// intermediates instead of arbitrary-precision integers.
+pub struct U64FieldOps {
+    pub prime: u64,
+}
+
+impl U64FieldOps {
+    /// Returns the fast path for `prime` if it fits in 64 bits.
+    pub fn new(prime: &BigInt) -> Option<Self> {
+        if prime.is_positive() && prime.bits() <= 64 {
+            prime.to_u64().map(|p| U64FieldOps { prime: p })
+        } else {
+            None
+        }
+    }
+
+    fn add_u64(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 + b as u128) % self.prime as u128) as u64
+    }
+
+    fn sub_u64(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 + self.prime as u128 - b as u128) % self.prime as u128) as u64
+    }
+
+    fn mul_u64(&self, a: u64, b: u64) -> u64 {
+        ((a as u128 * b as u128) % self.prime as u128) as u64
+    }
+
+    fn pow_u64(&self, a: u64, mut e: u64) -> u64 {
+        let mut result = 1 % self.prime;
+        let mut base = a % self.prime;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = self.mul_u64(result, base);
+            }
+            base = self.mul_u64(base, base);
+            e >>= 1;
+        }
+        result
+    }
+
+    /// Returns the inverse of `a` by the extended Euclidean algorithm, which, unlike Fermat's
+    /// little theorem, also holds for a non-prime modulus, or `None` when it does not exist.
+    fn inv_u64(&self, a: u64) -> Option<u64> {
+        let (mut r0, mut r1) = (self.prime as i128, a as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        if r0 != 1 {
+            return None;
+        }
+        Some(t0.rem_euclid(self.prime as i128) as u64)
+    }
+
+    fn div_u64(&self, a: u64, b: u64) -> Option<u64> {
+        if a == 0 || b == 0 {
+            Some(0)
+        } else {
+            self.inv_u64(b).map(|b_inv| self.mul_u64(a, b_inv))
+        }
+    }
+
+    /// Evaluates a binary operator on two canonical field elements, or returns `None` when the
+    /// operands or the operator are not covered by the fast path, including a division by an
+    /// element without inverse.
+    pub fn evaluate(
+        &self,
+        lhs: &BigInt,
+        rhs: &BigInt,
+        op: &ExpressionInfixOpcode,
+    ) -> Option<SymbolicValue> {
+        let a = self.to_element(lhs)?;
+        let b = self.to_element(rhs)?;
+        let value = match op {
+            ExpressionInfixOpcode::Add => self.add_u64(a, b),
+            ExpressionInfixOpcode::Sub => self.sub_u64(a, b),
+            ExpressionInfixOpcode::Mul => self.mul_u64(a, b),
+            ExpressionInfixOpcode::Div => self.div_u64(a, b)?,
+            ExpressionInfixOpcode::Pow => self.pow_u64(a, b),
+            ExpressionInfixOpcode::Eq => return Some(SymbolicValue::ConstantBool(a == b)),
+            ExpressionInfixOpcode::NotEq => return Some(SymbolicValue::ConstantBool(a != b)),
+            _ => return None,
+        };
+        Some(SymbolicValue::ConstantInt(BigInt::from(value)))
+    }
+
+    fn to_element(&self, value: &BigInt) -> Option<u64> {
+        value.to_u64().filter(|v| *v < self.prime)
+    }
+}
+
+impl FieldOps for U64FieldOps {
+    fn prime(&self) -> BigInt {
+        BigInt::from(self.prime)
+    }
+
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::from(self.add_u64(a.to_u64().unwrap(), b.to_u64().unwrap()))
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::from(self.sub_u64(a.to_u64().unwrap(), b.to_u64().unwrap()))
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::from(self.mul_u64(a.to_u64().unwrap(), b.to_u64().unwrap()))
+    }
+
+    fn div(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        BigInt::from(
+            self.div_u64(a.to_u64().unwrap(), b.to_u64().unwrap())
+                .unwrap_or(0),
+        )
+    }
+
+    fn pow(&self, a: &BigInt, e: &BigInt) -> BigInt {
+        match e.to_u64() {
+            Some(e) => BigInt::from(self.pow_u64(a.to_u64().unwrap(), e)),
+            None => modpow(a, e, &self.prime()),
+        }
+    }
+}
+
+/// Selects the fastest `FieldOps` implementation available for `prime`.
+pub fn field_ops_for(prime: &BigInt) -> Box<dyn FieldOps> {
+    match U64FieldOps::new(prime) {
+        Some(ops) => Box::new(ops),
+        None => Box::new(BigIntFieldOps {
+            prime: prime.clone(),
+        }),
+    }
+}
@@ -1,5 +1,7 @@
 pub mod coverage;
 pub mod debug_ast;
+pub mod field_ops;
+pub mod observer;
 pub mod symbolic_execution;
 pub mod symbolic_setting;
 pub mod symbolic_state;
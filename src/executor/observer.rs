@@ -0,0 +1,22 @@
+use program_structure::ast::Meta;
+
+use crate::executor::debug_ast::DebuggableStatement;
+use crate::executor::symbolic_value::{SymbolicName, SymbolicValue};
+
+/// Callbacks invoked by a `SymbolicExecutor` while it executes a circuit, so that custom
+/// analyses, such as taint tracking, coverage, or instrumentation, can be built on top of the
+/// symbolic execution.
+///
+/// Every method does nothing by default, and an observer only overrides the events it needs.
+/// The observer of an executor is shared with the executors of its components and functions,
+/// but not with the ones evaluating the initialization blocks of a template.
+pub trait ExecutionObserver {
+    /// Called before each statement of the circuit is executed.
+    fn on_statement(&mut self, _meta: &Meta, _statement: &DebuggableStatement) {}
+
+    /// Called when a side constraint is gathered.
+    fn on_constraint(&mut self, _constraint: &SymbolicValue) {}
+
+    /// Called when a component is instantiated, before its initialization blocks are executed.
+    fn on_component_instantiated(&mut self, _component_name: &SymbolicName) {}
+}
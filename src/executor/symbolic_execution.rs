@@ -1,13 +1,15 @@
 use core::panic;
 use std::cmp::max;
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
+use std::time::Instant;
 
 use colored::Colorize;
-use log::trace;
+use log::{trace, warn};
 use num_bigint_dig::BigInt;
 use num_traits::cast::ToPrimitive;
 use num_traits::FromPrimitive;
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
 
 use program_structure::ast::{
     AssignOp, Expression, ExpressionInfixOpcode, ExpressionPrefixOpcode, Meta, SignalType,
@@ -19,20 +21,32 @@ use crate::executor::debug_ast::{
     DebugAccess, DebuggableAssignOp, DebuggableExpression, DebuggableExpressionInfixOpcode,
     DebuggableStatement, DebuggableVariableType,
 };
-use crate::executor::symbolic_setting::SymbolicExecutorSetting;
-use crate::executor::symbolic_state::SymbolicState;
+use crate::executor::observer::ExecutionObserver;
+use crate::executor::symbolic_setting::{SymbolicExecutorSetting, WhitelistMode};
+use crate::executor::symbolic_state::{
+    FieldReductionEvent, PotentialDivByZero, SymbolBindingMap, SymbolicState,
+};
 use crate::executor::symbolic_value::{
     access_multidimensional_array, decompose_uniform_array, enumerate_array, evaluate_binary_op,
-    extract_variables_from_symbolic_value, generate_lessthan_constraint,
-    get_coefficient_of_polynomials, get_degree_polynomial,
-    initialize_symbolic_nested_array_with_value, is_concrete_array, register_array_elements,
-    update_nested_array, OwnerName, SymbolicAccess, SymbolicComponent, SymbolicLibrary,
-    SymbolicName, SymbolicTemplate, SymbolicValue, SymbolicValueRef,
+    extract_non_constant_divisors, extract_variables_from_symbolic_value, fold_constants,
+    generate_lessthan_constraint, generate_num2bits_model, get_coefficient_of_polynomials,
+    get_degree_polynomial, initialize_symbolic_nested_array_with_value, is_concrete_array,
+    register_array_elements, update_nested_array, OwnerName, SourceLocation, SymbolicAccess,
+    SymbolicComponent, SymbolicLibrary, SymbolicName, SymbolicTemplate, SymbolicValue,
+    SymbolicValueRef,
 };
 use crate::executor::utils::generate_cartesian_product_indices;
 
 use super::symbolic_value::ExecutionResult;
 
+/// Upper bound on the number of rounds performed by `simplify_to_fixpoint`.
+const MAX_SIMPLIFICATION_ROUNDS: usize = 32;
+
+/// The name of the opaque variables that replace the values exceeding `max_expression_nodes`.
+/// It is not a valid identifier, so that it never collides with a name of the circuit.
+pub const OPAQUE_VALUE_NAME: &str = "#opaque";
+
+#[derive(Clone)]
 pub struct SymbolicStore {
     pub components_store: FxHashMap<SymbolicName, SymbolicComponent>,
     pub variable_types: FxHashMap<usize, DebuggableVariableType>,
@@ -46,6 +60,16 @@ impl SymbolicStore {
     }
 }
 
+/// The outcome of the symbolic execution of a function by `SymbolicExecutor::execute_function`.
+pub struct FunctionExecution {
+    /// The value returned by the function.
+    pub return_value: SymbolicValue,
+    /// The final state of the function, whose owner is the one of its local variables.
+    pub state: SymbolicState,
+    /// The dimensions of the variables declared in the function.
+    pub id2dimensions: FxHashMap<usize, Vec<usize>>,
+}
+
 /// A symbolic execution engine for analyzing and executing statements symbolically.
 ///
 /// The `SymbolicExecutor` maintains multiple execution states, handles branching logic,
@@ -62,25 +86,53 @@ impl SymbolicStore {
 /// * `setting` – A reference to execution settings that configure runtime behavior.
 /// * `symbolic_store` – A structured store maintaining components, variable types, and execution states.
 /// * `cur_state` – The current symbolic execution state being processed.
-/// * `violated_condition` – An optional tuple indicating a violated condition (index and symbolic value).
+/// * `violated_condition` – An optional tuple indicating a violated condition (index, symbolic value, and source location).
 /// * `id2dimensions` – A mapping from identifiers to their respective dimensional constraints.
 /// * `mindim` – The minimum dimensionality constraint applied during execution.
 /// * `execution_failed` – A flag indicating whether execution has encountered a failure.
 /// * `coverage_tracker` – An internal tracker for execution path coverage (enabled when configured).
 /// * `enable_coverage_tracking` – A flag controlling whether execution path coverage is tracked.
 /// * `is_concrete_mode` – A flag indicating whether the engine is running in concrete execution mode.
+/// * `bit_width_warnings` – Warnings about `Num2Bits`/`Bits2Num` instantiations wider than the field.
+/// * `bit_width_hints` – The width of the `Num2Bits` decomposing each signal passed as its input.
+/// * `num_blackboxed_components` – The number of components left unexecuted due to `max_component_depth`.
+/// * `num_steps` – The number of executed steps, counted against `max_steps` of the setting.
+/// * `component_templates` – The template of each executed component, keyed by its owner chain.
+/// * `num_opaque_values` – The number of values replaced by opaque variables due to `max_expression_nodes`.
+/// * `enable_simplification_cache` – A flag controlling whether `simplify_variables` memoizes its results.
+/// * `simplification_cache` – Results of `simplify_variables`, keyed by the hash of the input and the flags.
+/// * `simplification_cache_version` – The binding version of `cur_state` the cache is valid for.
+/// * `observer` – The observer notified of the statements, constraints, and components, if any.
 pub struct SymbolicExecutor<'a> {
     pub symbolic_library: &'a mut SymbolicLibrary,
     pub setting: &'a SymbolicExecutorSetting,
     pub symbolic_store: SymbolicStore,
     pub cur_state: SymbolicState,
-    pub violated_condition: Option<(usize, SymbolicValue)>,
+    pub violated_condition: Option<(usize, SymbolicValue, Option<SourceLocation>)>,
     pub id2dimensions: FxHashMap<usize, Vec<usize>>,
     pub mindim: usize,
     pub execution_failed: bool,
     coverage_tracker: CoverageTracker,
     enable_coverage_tracking: bool,
     is_concrete_mode: bool,
+    pub bit_width_warnings: Vec<String>,
+    pub bit_width_hints: FxHashMap<SymbolicName, usize>,
+    pub num_blackboxed_components: usize,
+    /// The number of components of whitelisted templates replaced by calls to the template.
+    pub num_trusted_components: usize,
+    pub num_steps: usize,
+    pub component_templates: FxHashMap<Vec<OwnerName>, usize>,
+    pub num_opaque_values: usize,
+    /// The ids of the inputs of the main component declared `public`, the others being private.
+    ///
+    /// The search backends do not read them: the public inputs are held fixed only through the
+    /// values of `--input_constraints`, which must cover all of them, and are searched like the
+    /// private ones otherwise.
+    pub public_input_ids: FxHashSet<usize>,
+    enable_simplification_cache: bool,
+    simplification_cache: FxHashMap<u64, (SymbolicValue, SymbolicValue)>,
+    simplification_cache_version: u64,
+    observer: Option<&'a mut (dyn ExecutionObserver + 'static)>,
 }
 
 impl<'a> SymbolicExecutor<'a> {
@@ -100,6 +152,8 @@ impl<'a> SymbolicExecutor<'a> {
         symbolic_library: &'a mut SymbolicLibrary,
         setting: &'a SymbolicExecutorSetting,
     ) -> Self {
+        let mut cur_state = SymbolicState::new();
+        cur_state.dedup_on_push = setting.dedup_constraints;
         SymbolicExecutor {
             symbolic_library: symbolic_library,
             symbolic_store: SymbolicStore {
@@ -107,7 +161,7 @@ impl<'a> SymbolicExecutor<'a> {
                 variable_types: FxHashMap::default(),
                 max_depth: 0,
             },
-            cur_state: SymbolicState::new(),
+            cur_state: cur_state,
             violated_condition: None,
             id2dimensions: FxHashMap::default(),
             mindim: std::usize::MAX,
@@ -116,9 +170,74 @@ impl<'a> SymbolicExecutor<'a> {
             setting: setting,
             enable_coverage_tracking: false,
             is_concrete_mode: false,
+            bit_width_warnings: Vec::new(),
+            bit_width_hints: FxHashMap::default(),
+            num_blackboxed_components: 0,
+            num_trusted_components: 0,
+            num_steps: 0,
+            component_templates: FxHashMap::default(),
+            num_opaque_values: 0,
+            public_input_ids: FxHashSet::default(),
+            enable_simplification_cache: true,
+            simplification_cache: FxHashMap::default(),
+            simplification_cache_version: u64::MAX,
+            observer: None,
         }
     }
 
+    /// Creates an independent copy of this executor that runs on `symbolic_library`.
+    ///
+    /// A `SymbolicExecutor` borrows its library mutably, so it cannot implement `Clone`. Instead,
+    /// the caller clones the library (which shares the template, function, and bus definitions)
+    /// and passes it here. The copy starts from the same state, store, and coverage, but any
+    /// later execution on either executor, including updates of `function_counter`, leaves the
+    /// other untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `symbolic_library` - The library used by the copy, typically a clone of `self.symbolic_library`.
+    ///
+    /// # Returns
+    ///
+    /// A new `SymbolicExecutor` with the same setting and a copy of the current state, without
+    /// the observer, which is borrowed by this executor.
+    pub fn fork<'b>(&self, symbolic_library: &'b mut SymbolicLibrary) -> SymbolicExecutor<'b>
+    where
+        'a: 'b,
+    {
+        SymbolicExecutor {
+            symbolic_library: symbolic_library,
+            setting: self.setting,
+            symbolic_store: self.symbolic_store.clone(),
+            cur_state: self.cur_state.clone(),
+            violated_condition: self.violated_condition.clone(),
+            id2dimensions: self.id2dimensions.clone(),
+            mindim: self.mindim,
+            execution_failed: self.execution_failed,
+            coverage_tracker: self.coverage_tracker.clone(),
+            enable_coverage_tracking: self.enable_coverage_tracking,
+            is_concrete_mode: self.is_concrete_mode,
+            bit_width_warnings: self.bit_width_warnings.clone(),
+            bit_width_hints: self.bit_width_hints.clone(),
+            num_blackboxed_components: self.num_blackboxed_components,
+            num_trusted_components: self.num_trusted_components,
+            num_steps: self.num_steps,
+            component_templates: self.component_templates.clone(),
+            num_opaque_values: self.num_opaque_values,
+            public_input_ids: self.public_input_ids.clone(),
+            enable_simplification_cache: self.enable_simplification_cache,
+            simplification_cache: self.simplification_cache.clone(),
+            simplification_cache_version: self.simplification_cache_version,
+            observer: None,
+        }
+    }
+
+    /// Registers `observer`, which is notified of the execution of this executor and of the
+    /// executors of its components and functions.
+    pub fn set_observer(&mut self, observer: &'a mut (dyn ExecutionObserver + 'static)) {
+        self.observer = Some(observer);
+    }
+
     pub fn turn_on_coverage_tracking(&mut self) {
         self.enable_coverage_tracking = true;
     }
@@ -127,6 +246,15 @@ impl<'a> SymbolicExecutor<'a> {
         self.enable_coverage_tracking = false;
     }
 
+    pub fn turn_on_simplification_cache(&mut self) {
+        self.enable_simplification_cache = true;
+    }
+
+    pub fn turn_off_simplification_cache(&mut self) {
+        self.enable_simplification_cache = false;
+        self.simplification_cache.clear();
+    }
+
     pub fn record_path(&mut self) {
         self.coverage_tracker.record_path();
     }
@@ -139,15 +267,51 @@ impl<'a> SymbolicExecutor<'a> {
         self.coverage_tracker.clear();
     }
 
+    pub fn coverage_tracker(&self) -> &CoverageTracker {
+        &self.coverage_tracker
+    }
+
+    /// Records the inputs of the main component declared `public`, as in
+    /// `component main {public [a, b]} = T();`.
+    ///
+    /// # Arguments
+    ///
+    /// * `names` - The names of the public inputs, as given by `ProgramArchive::public_inputs`.
+    pub fn register_public_inputs(&mut self, names: &[String]) {
+        self.public_input_ids = names
+            .iter()
+            .filter_map(|name| self.symbolic_library.name2id.get(name).copied())
+            .collect();
+    }
+
+    /// Returns whether the trace and the side constraints abstract away part of the circuit, i.e.,
+    /// whether components were black-boxed or trusted, values were made opaque, or loops were
+    /// left symbolic, so that a search covering every assignment does not prove the circuit safe.
+    pub fn has_abstractions(&self) -> bool {
+        self.num_blackboxed_components > 0
+            || self.num_trusted_components > 0
+            || self.num_opaque_values > 0
+            || self.cur_state.contains_symbolic_loop
+    }
+
     /// Clears the current state and resets the symbolic executor.
     ///
     /// This method resets the current state, clears the symbolic store,
     /// and resets the function counter in the symbolic library.
     pub fn clear(&mut self) {
         self.cur_state = SymbolicState::new();
+        self.cur_state.dedup_on_push = self.setting.dedup_constraints;
         self.symbolic_store.clear();
         self.symbolic_library.clear_function_counter();
         self.coverage_tracker.clear_current_path();
+        self.bit_width_warnings.clear();
+        self.bit_width_hints.clear();
+        self.num_blackboxed_components = 0;
+        self.num_trusted_components = 0;
+        self.num_steps = 0;
+        self.component_templates.clear();
+        self.num_opaque_values = 0;
+        self.simplification_cache.clear();
     }
 
     /// Feeds arguments into current state variables.
@@ -159,7 +323,23 @@ impl<'a> SymbolicExecutor<'a> {
     ///
     /// * `names` - Vector containing names corresponding with expressions being fed as arguments.
     /// * `args` - Vector containing expressions whose evaluated results will be assigned as argument values.
-    pub fn feed_arguments(&mut self, names: &Vec<String>, args: &Vec<Expression>) {
+    ///
+    /// # Returns
+    ///
+    /// An error message when the numbers of names and arguments differ, in which case nothing is bound.
+    pub fn feed_arguments(
+        &mut self,
+        names: &Vec<String>,
+        args: &Vec<Expression>,
+    ) -> Result<(), String> {
+        if names.len() != args.len() {
+            return Err(format!(
+                "{} arguments are given while the template parameters are [{}]",
+                args.len(),
+                names.join(", ")
+            ));
+        }
+
         let mut name2id = self.symbolic_library.name2id.clone();
         let mut id2name = self.symbolic_library.id2name.clone();
         for (n, a) in names.iter().zip(args.iter()) {
@@ -177,23 +357,78 @@ impl<'a> SymbolicExecutor<'a> {
             );
             self.cur_state.set_sym_val(sym_name, simplified_a);
             if self.setting.keep_track_constraints {
-                self.cur_state.push_symbolic_trace(&cond);
-                self.cur_state.push_side_constraint(&cond);
+                self.push_trace_and_side_constraint(&cond);
+            }
+        }
+        Ok(())
+    }
+
+    /// Binds template parameters of the current template to concrete values.
+    ///
+    /// This is the counterpart of `feed_arguments` for the values enumerated from the bounds of
+    /// symbolic template parameters, which are not expressions of the circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `binding` - The names of the template parameters and their values.
+    pub fn feed_param_values(&mut self, binding: &[(String, BigInt)]) {
+        for (n, value) in binding {
+            let sym_name = SymbolicName::new(
+                self.symbolic_library.name2id[n],
+                self.cur_state.owner_name.clone(),
+                None,
+            );
+            let value = SymbolicValue::ConstantInt(value.clone());
+            let cond = SymbolicValue::AssignTemplParam(
+                Rc::new(SymbolicValue::Variable(sym_name.clone())),
+                Rc::new(value.clone()),
+            );
+            self.cur_state.set_sym_val(sym_name, value);
+            if self.setting.keep_track_constraints {
+                self.push_trace_and_side_constraint(&cond);
             }
         }
     }
 
+    /// Evaluates an expression that is not part of the circuit, such as an assumption given
+    /// with `--assume`, in the scope of the current owner.
+    ///
+    /// As for the arguments of `feed_arguments`, only the constant parts of the result are
+    /// simplified, so that the signals it refers to are kept as variables.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression to evaluate, whose names are registered in the symbolic library.
+    pub fn evaluate_standalone_expression(&mut self, expr: &Expression) -> SymbolicValue {
+        let expr = DebuggableExpression::from(
+            expr.clone(),
+            &mut self.symbolic_library.name2id,
+            &mut self.symbolic_library.id2name,
+        );
+        let evaled_expr = self.evaluate_expression(&expr, usize::MAX);
+        let mut memo = FxHashSet::default();
+        self.simplify_variables(&evaled_expr, usize::MAX, true, false, &mut memo)
+    }
+
     /// Executes a sequence of statements symbolically.
     ///
     /// This method starts execution from a specified block index, updating internal states
     /// and handling control structures like if-else and loops appropriately.
     ///
+    /// The statements of a sequence are executed one after another in a loop, and only nested
+    /// blocks and function or component calls recurse, so that the depth of the native stack is
+    /// bounded by the nesting of the program rather than by the length of a template.
+    ///
     /// # Arguments
     ///
     /// * `statements` - A vector of extended statements representing program logic to execute symbolically.
     /// * `cur_bid` - Current block index to start execution from.
     pub fn execute(&mut self, statements: &Vec<DebuggableStatement>, cur_bid: usize) {
-        if cur_bid < statements.len() {
+        let mut cur_bid = cur_bid;
+        while cur_bid < statements.len() {
+            if self.is_out_of_budget() {
+                return;
+            }
             self.symbolic_store.max_depth =
                 max(self.symbolic_store.max_depth, self.cur_state.get_depth());
 
@@ -203,13 +438,19 @@ impl<'a> SymbolicExecutor<'a> {
                     | DebuggableStatement::Block { .. } => {}
                     _ => {
                         if !self.cur_state.is_within_initialization_block {
-                            self.execute(statements, cur_bid + 1);
-                            return;
+                            cur_bid += 1;
+                            continue;
                         }
                     }
                 }
             }
 
+            if let Some(observer) = self.observer.as_deref_mut() {
+                if let Some(meta) = statements[cur_bid].get_meta() {
+                    observer.on_statement(meta, &statements[cur_bid]);
+                }
+            }
+
             match &statements[cur_bid] {
                 DebuggableStatement::InitializationBlock { .. } => {
                     self.handle_initialization_block(statements, cur_bid);
@@ -241,23 +482,18 @@ impl<'a> SymbolicExecutor<'a> {
                 DebuggableStatement::Assert { .. } => {
                     self.handle_assert(statements, cur_bid);
                 }
-                DebuggableStatement::UnderscoreSubstitution {
-                    meta,
-                    op: _,
-                    rhe: _,
-                    ..
-                } => {
-                    self.trace_if_enabled(&meta);
-                    self.execute(statements, cur_bid + 1);
+                DebuggableStatement::UnderscoreSubstitution { .. } => {
+                    self.handle_underscore_substitution(statements, cur_bid);
                 }
                 DebuggableStatement::LogCall { meta, .. } => {
                     self.trace_if_enabled(&meta);
-                    self.execute(statements, cur_bid + 1);
                 }
                 DebuggableStatement::Ret => {
                     self.handle_ret();
+                    return;
                 }
             }
+            cur_bid += 1;
         }
     }
 
@@ -282,12 +518,8 @@ impl<'a> SymbolicExecutor<'a> {
             );
         }
 
-        self.execute(
-            &self.symbolic_library.template_library[&self.cur_state.template_id]
-                .body
-                .clone(),
-            0,
-        );
+        let template = self.symbolic_library.template_library[&self.cur_state.template_id].clone();
+        self.execute(&template.body, 0);
     }
 }
 
@@ -395,6 +627,8 @@ impl<'a> SymbolicExecutor<'a> {
     ///   recursively simplifies each element or branch.
     /// - Conditional branches are further simplified based on the evaluation of their condition.
     ///   For example, a branch with a true or false condition can reduce to a single branch.
+    ///   A conditional whose simplified branches are equal also reduces to that branch, whatever
+    ///   its condition.
     /// - Unary and binary operations are evaluated when possible, leveraging any simplified
     ///   components.
     /// - Array and tuple elements are recursively simplified.
@@ -405,6 +639,8 @@ impl<'a> SymbolicExecutor<'a> {
     ///   `symbolic_library`, `cur_state`, and `setting`.
     /// - The implementation respects the `enable_coverage_tracking` setting to track branch
     ///   execution during simplification.
+    /// - Outside of concrete mode, the results for composite values are memoized until the
+    ///   bindings of `cur_state` change (see `turn_off_simplification_cache`).
     ///
     /// # Performance
     /// - Recursive simplification can have significant computational overhead for deeply nested
@@ -417,6 +653,71 @@ impl<'a> SymbolicExecutor<'a> {
         only_constatant_simplification: bool,
         only_variable_simplification: bool,
         memo: &mut FxHashSet<SymbolicValue>,
+    ) -> SymbolicValue {
+        // In concrete mode, the result depends on `memo`, which cuts cyclic bindings, and the
+        // bindings change after every statement. With coverage tracking, skipping the
+        // simplification of a conditional would lose the recorded branches, and the same holds
+        // for the field reductions.
+        let is_cacheable = self.enable_simplification_cache
+            && !self.is_concrete_mode
+            && !self.enable_coverage_tracking
+            && !self.setting.track_field_reductions
+            && matches!(
+                sym_val,
+                SymbolicValue::BinaryOp(..)
+                    | SymbolicValue::AuxBinaryOp(..)
+                    | SymbolicValue::Conditional(..)
+                    | SymbolicValue::UnaryOp(..)
+                    | SymbolicValue::Array(..)
+                    | SymbolicValue::UniformArray(..)
+                    | SymbolicValue::Call(..)
+            );
+        if !is_cacheable {
+            return self.simplify_variables_uncached(
+                sym_val,
+                elem_id,
+                only_constatant_simplification,
+                only_variable_simplification,
+                memo,
+            );
+        }
+
+        if self.simplification_cache_version != self.cur_state.binding_version() {
+            self.simplification_cache.clear();
+            self.simplification_cache_version = self.cur_state.binding_version();
+        }
+        let mut hasher = FxHasher::default();
+        sym_val.hash(&mut hasher);
+        only_constatant_simplification.hash(&mut hasher);
+        only_variable_simplification.hash(&mut hasher);
+        self.cur_state.template_id.hash(&mut hasher);
+        self.setting.substitute_output.hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some((cached_input, cached_output)) = self.simplification_cache.get(&key) {
+            if cached_input == sym_val {
+                return cached_output.clone();
+            }
+        }
+        let simplified = self.simplify_variables_uncached(
+            sym_val,
+            elem_id,
+            only_constatant_simplification,
+            only_variable_simplification,
+            memo,
+        );
+        self.simplification_cache
+            .insert(key, (sym_val.clone(), simplified.clone()));
+        simplified
+    }
+
+    fn simplify_variables_uncached(
+        &mut self,
+        sym_val: &SymbolicValue,
+        elem_id: usize,
+        only_constatant_simplification: bool,
+        only_variable_simplification: bool,
+        memo: &mut FxHashSet<SymbolicValue>,
     ) -> SymbolicValue {
         match &sym_val {
             SymbolicValue::NOP => SymbolicValue::NOP,
@@ -430,6 +731,9 @@ impl<'a> SymbolicExecutor<'a> {
                         if let Some(VariableType::Signal(_, _)) = template.id2type.get(&sym_name.id)
                         {
                             return sym_val.clone();
+                        } else if self.is_opaque_variable(sym_name) {
+                            // Inlining the value would undo `max_expression_nodes`
+                            return sym_val.clone();
                         } else {
                             let new_sym_val = self.cur_state.get_sym_val_or_make_symvar(&sym_name);
                             memo.insert(sym_val.clone());
@@ -510,7 +814,15 @@ impl<'a> SymbolicExecutor<'a> {
                     only_variable_simplification,
                     memo,
                 );
-                evaluate_binary_op(&lhs, &rhs, &self.setting.prime, infix_op)
+                let result = evaluate_binary_op(&lhs, &rhs, &self.setting.prime, infix_op);
+                if self.setting.track_field_reductions {
+                    if let Some(event) =
+                        FieldReductionEvent::detect(elem_id, &lhs, &rhs, infix_op, &result)
+                    {
+                        self.cur_state.push_field_reduction(event);
+                    }
+                }
+                result
             }
             SymbolicValue::Conditional(cond, then_val, else_val) => {
                 let simplified_cond = self.simplify_variables(
@@ -545,29 +857,41 @@ impl<'a> SymbolicExecutor<'a> {
                             memo,
                         )
                     }
-                    _ => SymbolicValue::Conditional(
-                        Rc::new(self.simplify_variables(
+                    _ => {
+                        // The condition is simplified even when the branches turn out to be
+                        // equal, so that the branches it contains are still recorded
+                        let simplified_cond = self.simplify_variables(
                             cond,
                             elem_id,
                             only_constatant_simplification,
                             only_variable_simplification,
                             memo,
-                        )),
-                        Rc::new(self.simplify_variables(
+                        );
+                        let simplified_then = self.simplify_variables(
                             then_val,
                             elem_id,
                             only_constatant_simplification,
                             only_variable_simplification,
                             memo,
-                        )),
-                        Rc::new(self.simplify_variables(
+                        );
+                        let simplified_else = self.simplify_variables(
                             else_val,
                             elem_id,
                             only_constatant_simplification,
                             only_variable_simplification,
                             memo,
-                        )),
-                    ),
+                        );
+                        // Whatever the condition, both branches give the same value
+                        if simplified_then == simplified_else {
+                            simplified_then
+                        } else {
+                            SymbolicValue::Conditional(
+                                Rc::new(simplified_cond),
+                                Rc::new(simplified_then),
+                                Rc::new(simplified_else),
+                            )
+                        }
+                    }
                 }
             }
             SymbolicValue::UnaryOp(prefix_op, value) => {
@@ -642,6 +966,75 @@ impl<'a> SymbolicExecutor<'a> {
         }
     }
 
+    /// Repeatedly applies `simplify_variables` and `fold_constants` until the value stops
+    /// changing or `MAX_SIMPLIFICATION_ROUNDS` rounds have been performed.
+    ///
+    /// A single call to `simplify_variables` folds constant sub-expressions bottom-up, while
+    /// `fold_constants` removes algebraic identities. Each of them can expose new opportunities
+    /// for the other, e.g. `((x * 0) + 2) * 3` only reduces to `6` after two rounds.
+    ///
+    /// # Parameters
+    /// - `sym_val`: The symbolic value to simplify.
+    /// - `elem_id`: The element ID used for coverage tracking.
+    /// - `only_constatant_simplification`: Forwarded to `simplify_variables`.
+    /// - `only_variable_simplification`: Forwarded to `simplify_variables`.
+    ///
+    /// # Returns
+    /// The maximally reduced `SymbolicValue`.
+    pub fn simplify_to_fixpoint(
+        &mut self,
+        sym_val: &SymbolicValue,
+        elem_id: usize,
+        only_constatant_simplification: bool,
+        only_variable_simplification: bool,
+    ) -> SymbolicValue {
+        let mut current = sym_val.clone();
+        for _ in 0..MAX_SIMPLIFICATION_ROUNDS {
+            let mut memo = FxHashSet::default();
+            let simplified = self.simplify_variables(
+                &current,
+                elem_id,
+                only_constatant_simplification,
+                only_variable_simplification,
+                &mut memo,
+            );
+            let folded = fold_constants(&simplified, &self.setting.prime);
+            if folded == current {
+                break;
+            }
+            current = folded;
+        }
+        current
+    }
+
+    /// Simplifies a value that is about to be recorded in the trace or side constraints,
+    /// iterating to a fixed point when `simplify_to_fixpoint` is enabled in the setting.
+    fn simplify_for_trace(
+        &mut self,
+        sym_val: &SymbolicValue,
+        elem_id: usize,
+        only_constatant_simplification: bool,
+        only_variable_simplification: bool,
+        memo: &mut FxHashSet<SymbolicValue>,
+    ) -> SymbolicValue {
+        if self.setting.simplify_to_fixpoint {
+            self.simplify_to_fixpoint(
+                sym_val,
+                elem_id,
+                only_constatant_simplification,
+                only_variable_simplification,
+            )
+        } else {
+            self.simplify_variables(
+                sym_val,
+                elem_id,
+                only_constatant_simplification,
+                only_variable_simplification,
+                memo,
+            )
+        }
+    }
+
     /// Evaluates a symbolic expression, converting it into a `SymbolicValue`.
     ///
     /// This function handles various types of expressions, including constants, variables,
@@ -762,80 +1155,60 @@ impl<'a> SymbolicExecutor<'a> {
                 if self.symbolic_library.template_library.contains_key(id) {
                     SymbolicValue::Call(*id, simplified_args)
                 } else if self.symbolic_library.function_library.contains_key(id) {
-                    let symbolic_library = &mut self.symbolic_library;
-                    let mut subse_setting = self.setting.clone();
-                    subse_setting.only_initialization_blocks = false;
-                    let mut subse = SymbolicExecutor::new(symbolic_library, &subse_setting);
-
-                    let mut updated_owner_list = (*self.cur_state.owner_name).clone();
-                    updated_owner_list.push(OwnerName {
-                        id: *id,
-                        counter: subse.symbolic_library.function_counter[id],
-                        access: None,
-                    });
-                    subse.cur_state.owner_name = Rc::new(updated_owner_list);
-                    subse
-                        .symbolic_library
-                        .function_counter
-                        .insert(*id, subse.symbolic_library.function_counter[id] + 1);
-                    subse.cur_state.set_template_id(*id);
-
-                    let func = &subse.symbolic_library.function_library[id];
-                    for i in 0..(func.function_argument_names.len()) {
-                        let sym_name = SymbolicName::new(
-                            func.function_argument_names[i],
-                            subse.cur_state.owner_name.clone(),
-                            None,
-                        );
-                        subse
-                            .cur_state
-                            .set_rc_sym_val(sym_name.clone(), simplified_args[i].clone());
+                    if self.is_out_of_budget() {
+                        return SymbolicValue::Call(*id, simplified_args);
                     }
-
-                    if !subse.setting.off_trace {
-                        trace!("{}", format!("{}", "===========================").cyan());
-                        trace!("📞 Call {}", subse.symbolic_library.id2name[id]);
-                    }
-
-                    subse.execute(&func.body.clone(), 0);
-                    self.mindim = std::cmp::min(subse.mindim, self.mindim);
-
-                    if !subse.setting.off_trace {
-                        trace!("{}", format!("{}", "===========================").cyan());
-                    }
-
-                    if !subse.cur_state.contains_symbolic_loop {
-                        // NOTE: a function does not produce any constraint
-                        self.cur_state
-                            .symbolic_trace
-                            .append(&mut subse.cur_state.symbolic_trace);
-                        self.execution_failed = subse.execution_failed;
-
-                        let return_sym_name =
-                            SymbolicName::new(usize::MAX, subse.cur_state.owner_name.clone(), None);
-                        let return_value =
-                            (*subse.cur_state.symbol_binding_map[&return_sym_name]).clone();
-                        match return_value {
+                    match self.execute_function(*id, &simplified_args) {
+                        Some(execution) => match execution.return_value {
                             SymbolicValue::ConstantBool(_) | SymbolicValue::ConstantInt(_) => {
-                                return_value
+                                execution.return_value
                             }
                             _ => {
-                                if is_concrete_array(&return_value) {
-                                    return_value
+                                if is_concrete_array(&execution.return_value) {
+                                    execution.return_value
                                 } else {
-                                    SymbolicValue::Call(*id, simplified_args)
+                                    // Inline the elements of a symbolic array so that callers
+                                    // can access and constrain them element-wise.
+                                    let mut visited = FxHashSet::default();
+                                    match inline_function_locals(
+                                        &execution.state,
+                                        &execution.return_value,
+                                        &mut visited,
+                                    ) {
+                                        Some(inlined @ SymbolicValue::Array(..)) => inlined,
+                                        _ => SymbolicValue::Call(*id, simplified_args),
+                                    }
                                 }
                             }
-                        }
-                    } else {
-                        SymbolicValue::Call(*id, simplified_args)
+                        },
+                        None => SymbolicValue::Call(*id, simplified_args),
                     }
                 } else {
                     panic!("Unknown Callee: {}", self.symbolic_library.id2name[id]);
                 }
             }
+            DebuggableExpression::BusCall { id, .. } => {
+                // A bus instance evaluates to the array of its fields. When the instance is bound
+                // to a variable, `handle_substitution` names the fields after that variable instead.
+                if let Some(bus) = self.symbolic_library.bus_library.get(id) {
+                    SymbolicValue::Array(
+                        bus.field_ids
+                            .iter()
+                            .map(|field_id| {
+                                Rc::new(SymbolicValue::Variable(SymbolicName::new(
+                                    *field_id,
+                                    self.cur_state.owner_name.clone(),
+                                    None,
+                                )))
+                            })
+                            .collect(),
+                    )
+                } else {
+                    panic!("Unknown Bus: {}", self.symbolic_library.id2name[id]);
+                }
+            }
             _ => {
-                // We currently do not support BusCall and AnonymousComp.
+                // We currently do not support AnonymousComp.
                 panic!(
                     "Unhandled expression type: {}",
                     expr.lookup_fmt(&self.symbolic_library.id2name, 0)
@@ -876,7 +1249,6 @@ impl<'a> SymbolicExecutor<'a> {
             }
 
             self.cur_state.is_within_initialization_block = false;
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -896,7 +1268,6 @@ impl<'a> SymbolicExecutor<'a> {
         if let DebuggableStatement::Block { meta, stmts, .. } = &statements[cur_bid] {
             self.trace_if_enabled(&meta);
             self.execute(&stmts, 0);
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -951,7 +1322,6 @@ impl<'a> SymbolicExecutor<'a> {
                     self.cur_state.contains_symbolic_loop = true;
                 }
             }
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -985,6 +1355,12 @@ impl<'a> SymbolicExecutor<'a> {
         {
             self.trace_if_enabled(meta);
 
+            if let DebuggableExpression::BusCall { id, .. } = rhe {
+                let (_, bus_name) = self.construct_symbolic_name(*var, access, meta.elem_id);
+                self.handle_bus_substitution(&bus_name, id);
+                return;
+            }
+
             let evaled_rhe = self.evaluate_expression(rhe, meta.elem_id);
             let mut memo = FxHashSet::default();
             let mut simplified_rhe =
@@ -997,6 +1373,9 @@ impl<'a> SymbolicExecutor<'a> {
             let mut right_values = Vec::new();
             let mut symbolic_positions = Vec::new();
 
+            self.record_potential_div_by_zeros(&simplified_rhe, meta);
+            simplified_rhe = self.cap_expression_size(simplified_rhe);
+
             match (&evaled_rhe, &simplified_rhe) {
                 (SymbolicValue::Variable(right_var_name), SymbolicValue::UniformArray(..)) => {
                     simplified_rhe =
@@ -1062,7 +1441,7 @@ impl<'a> SymbolicExecutor<'a> {
                 } else if !is_array_assignment {
                     let mut memo = FxHashSet::default();
                     let semi_simplified_rhe =
-                        self.simplify_variables(&evaled_rhe, meta.elem_id, true, true, &mut memo);
+                        self.simplify_for_trace(&evaled_rhe, meta.elem_id, true, true, &mut memo);
                     self.handle_non_call_substitution(op, &left_var_name, &semi_simplified_rhe);
                 }
             }
@@ -1087,7 +1466,6 @@ impl<'a> SymbolicExecutor<'a> {
                     );
                 }
             }
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -1102,10 +1480,10 @@ impl<'a> SymbolicExecutor<'a> {
             let rhe_val = self.evaluate_expression(rhe, meta.elem_id);
             let mut memo_left = FxHashSet::default();
             let simplified_lhe_val =
-                self.simplify_variables(&lhe_val, meta.elem_id, true, false, &mut memo_left);
+                self.simplify_for_trace(&lhe_val, meta.elem_id, true, false, &mut memo_left);
             let mut memo_right = FxHashSet::default();
             let simplified_rhe_val =
-                self.simplify_variables(&rhe_val, meta.elem_id, true, false, &mut memo_right);
+                self.simplify_for_trace(&rhe_val, meta.elem_id, true, false, &mut memo_right);
 
             if self.setting.keep_track_constraints {
                 match op {
@@ -1114,8 +1492,7 @@ impl<'a> SymbolicExecutor<'a> {
                             Rc::new(simplified_lhe_val),
                             Rc::new(simplified_rhe_val),
                         );
-                        self.cur_state.push_symbolic_trace(&cont);
-                        self.cur_state.push_side_constraint(&cont);
+                        self.push_trace_and_side_constraint(&cont);
                     }
                     DebuggableAssignOp(AssignOp::AssignSignal) => {
                         let cont = SymbolicValue::Assign(
@@ -1125,14 +1502,143 @@ impl<'a> SymbolicExecutor<'a> {
                                 .is_safe,
                             None,
                         );
-                        self.cur_state.push_symbolic_trace(&cont);
+                        self.push_symbolic_trace(&cont);
                     }
                     _ => {}
                 }
             }
+        }
+    }
+
+    /// Handles a substitution whose result is discarded, such as `_ <== Comp()(in)`.
+    ///
+    /// The value is discarded, but the right-hand side is still evaluated for its effects: a
+    /// template called there is instantiated under a name unique to the statement and executed
+    /// once ready, so that its constraints are gathered as for a named component, and a function
+    /// called there contributes its trace as in any other expression.
+    fn handle_underscore_substitution(
+        &mut self,
+        statements: &Vec<DebuggableStatement>,
+        cur_bid: usize,
+    ) {
+        if let DebuggableStatement::UnderscoreSubstitution { meta, op, rhe } = &statements[cur_bid]
+        {
+            self.trace_if_enabled(meta);
+
+            // The parser rewrites anonymous components into named ones
+            if let DebuggableExpression::AnonymousComp { .. } = rhe {
+                return;
+            }
+
+            let evaled_rhe = self.evaluate_expression(rhe, meta.elem_id);
+            let mut memo = FxHashSet::default();
+            let simplified_rhe =
+                self.simplify_variables(&evaled_rhe, meta.elem_id, true, false, &mut memo);
+            self.record_potential_div_by_zeros(&simplified_rhe, meta);
+
+            if let SymbolicValue::Call(callee_id, args) = &simplified_rhe {
+                if self
+                    .symbolic_library
+                    .template_library
+                    .contains_key(callee_id)
+                {
+                    let component_name = self.discarded_component_name(meta.elem_id);
+                    self.handle_call_substitution(
+                        op,
+                        callee_id,
+                        args,
+                        &component_name,
+                        &simplified_rhe,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Returns the name of the component instantiated by the discarding substitution `elem_id`.
+    fn discarded_component_name(&mut self, elem_id: usize) -> SymbolicName {
+        let next_id = self.symbolic_library.name2id.len();
+        let id = *self
+            .symbolic_library
+            .name2id
+            .entry("_".to_string())
+            .or_insert(next_id);
+        self.symbolic_library.id2name.insert(id, "_".to_string());
+        SymbolicName::new(
+            id,
+            self.cur_state.owner_name.clone(),
+            Some(vec![SymbolicAccess::ArrayAccess(
+                SymbolicValue::ConstantInt(BigInt::from(elem_id)),
+            )]),
+        )
+    }
+
+    /// Replaces an operation with more nodes than `max_expression_nodes` by a fresh opaque
+    /// variable of the current owner.
+    ///
+    /// The opaque variable is bound to the original value, so that the concrete execution still
+    /// computes it, while the symbolic execution keeps referring to the variable instead of
+    /// inlining the value. When the constraints are gathered, the binding is also recorded as a
+    /// safe assignment in the symbolic trace, so that the emulation of the trace assigns the
+    /// variable before its first use.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The simplified right-hand side of a substitution.
+    ///
+    /// # Returns
+    ///
+    /// The opaque variable if `value` was too large, and `value` itself otherwise.
+    fn cap_expression_size(&mut self, value: SymbolicValue) -> SymbolicValue {
+        let max_nodes = match self.setting.max_expression_nodes {
+            Some(max_nodes) => max_nodes,
+            None => return value,
+        };
+        let is_operation = matches!(
+            value,
+            SymbolicValue::BinaryOp(..)
+                | SymbolicValue::AuxBinaryOp(..)
+                | SymbolicValue::UnaryOp(..)
+                | SymbolicValue::Conditional(..)
+        );
+        if !is_operation || value.count_nodes(max_nodes) <= max_nodes {
+            return value;
+        }
+
+        let next_id = self.symbolic_library.name2id.len();
+        let id = *self
+            .symbolic_library
+            .name2id
+            .entry(OPAQUE_VALUE_NAME.to_string())
+            .or_insert(next_id);
+        self.symbolic_library
+            .id2name
+            .insert(id, OPAQUE_VALUE_NAME.to_string());
+        let opaque_name = SymbolicName::new(
+            id,
+            self.cur_state.owner_name.clone(),
+            Some(vec![SymbolicAccess::ArrayAccess(
+                SymbolicValue::ConstantInt(BigInt::from(self.num_opaque_values)),
+            )]),
+        );
+        self.num_opaque_values += 1;
 
-            self.execute(statements, cur_bid + 1);
+        if self.setting.keep_track_constraints {
+            self.push_symbolic_trace(&SymbolicValue::Assign(
+                Rc::new(SymbolicValue::Variable(opaque_name.clone())),
+                Rc::new(value.clone()),
+                true,
+                None,
+            ));
         }
+        self.cur_state.set_sym_val(opaque_name.clone(), value);
+        SymbolicValue::Variable(opaque_name)
+    }
+
+    /// Returns whether `sym_name` is an opaque variable introduced by `cap_expression_size`.
+    fn is_opaque_variable(&self, sym_name: &SymbolicName) -> bool {
+        self.setting.max_expression_nodes.is_some()
+            && self.symbolic_library.name2id.get(OPAQUE_VALUE_NAME) == Some(&sym_name.id)
     }
 
     /// Handles the execution of a `While` loop statement during symbolic evaluation.
@@ -1150,32 +1656,126 @@ impl<'a> SymbolicExecutor<'a> {
     /// - If the condition evaluates to a constant boolean:
     ///   - `true`: Executes the loop body (`stmt`) and re-evaluates the `While` statement.
     ///   - `false`: Skips the loop body and proceeds to the next statement.
-    /// - If the condition cannot be fully resolved (symbolic loop), marks the current state as containing
-    ///   a symbolic loop and skips the loop execution.
+    /// - If the condition cannot be fully resolved (symbolic loop):
+    ///   - When `max_loop_unroll` is set, unrolls the loop under guarded conditions (see
+    ///     `unroll_symbolic_loop`).
+    ///   - Otherwise, marks the current state as containing a symbolic loop and skips the loop execution.
     fn handle_while(&mut self, statements: &Vec<DebuggableStatement>, cur_bid: usize) {
         if let DebuggableStatement::While {
             meta, cond, stmt, ..
         } = &statements[cur_bid]
         {
-            self.trace_if_enabled(&meta);
-            // Symbolic execution of loops is complex. This is a simplified approach.
-            let tmp_cond = self.evaluate_expression(cond, meta.elem_id);
-            let mut memo = FxHashSet::default();
-            let evaled_condition =
-                self.simplify_variables(&tmp_cond, meta.elem_id, true, false, &mut memo);
+            let body = vec![*stmt.clone()];
+            loop {
+                self.trace_if_enabled(&meta);
+                // Symbolic execution of loops is complex. This is a simplified approach.
+                let tmp_cond = self.evaluate_expression(cond, meta.elem_id);
+                let mut memo = FxHashSet::default();
+                let evaled_condition =
+                    self.simplify_variables(&tmp_cond, meta.elem_id, true, false, &mut memo);
 
-            if let SymbolicValue::ConstantBool(flag) = evaled_condition {
-                if flag {
-                    self.execute(&vec![*stmt.clone()], 0);
-                    self.execute(statements, cur_bid);
+                if let SymbolicValue::ConstantBool(flag) = evaled_condition {
+                    if !flag {
+                        break;
+                    }
+                    self.execute(&body, 0);
+                    if self.is_out_of_budget() {
+                        break;
+                    }
+                } else if self.setting.max_loop_unroll > 0 {
+                    self.unroll_symbolic_loop(cond, stmt, evaled_condition, meta.elem_id);
+                    break;
                 } else {
-                    self.execute(statements, cur_bid + 1);
+                    self.cur_state.contains_symbolic_loop = true;
+                    // symbolic loop can occur only within functions that always do not produce any constraints.
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Unrolls a `While` loop whose condition is symbolic at most `max_loop_unroll` times.
+    ///
+    /// Each iteration runs under a guard, the conjunction of the symbolic loop conditions seen so
+    /// far. The constraints produced by the iteration only hold under that guard (see
+    /// `guard_constraint`), and every variable bound by the iteration is merged as
+    /// `Conditional(guard, new, old)`, so that the result holds whether or not the loop actually
+    /// runs that many times.
+    ///
+    /// # Parameters
+    /// - `cond`: The loop condition.
+    /// - `stmt`: The loop body.
+    /// - `first_condition`: The simplified loop condition before the first iteration.
+    /// - `elem_id`: The unique identifier of the loop statement.
+    ///
+    /// # Notes
+    /// If the condition is still unresolved after the last iteration, the state before the loop is
+    /// restored and marked as containing a symbolic loop, as without unrolling, and a warning is
+    /// emitted.
+    fn unroll_symbolic_loop(
+        &mut self,
+        cond: &DebuggableExpression,
+        stmt: &DebuggableStatement,
+        first_condition: SymbolicValue,
+        elem_id: usize,
+    ) {
+        let state_before_loop = self.cur_state.clone();
+        let store_before_loop = self.symbolic_store.clone();
+        let mut guard: Option<Rc<SymbolicValue>> = None;
+        let mut condition = first_condition;
+        for _ in 0..self.setting.max_loop_unroll {
+            match condition {
+                SymbolicValue::ConstantBool(false) => return,
+                SymbolicValue::ConstantBool(true) => {}
+                _ => {
+                    guard = Some(Rc::new(match guard {
+                        None => condition,
+                        Some(g) => SymbolicValue::BinaryOp(
+                            g,
+                            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::BoolAnd),
+                            Rc::new(condition),
+                        ),
+                    }));
                 }
-            } else {
-                self.cur_state.contains_symbolic_loop = true;
-                // symbolic loop can occur only within functions that always do not produce any constraints.
-                self.execute(statements, cur_bid + 1);
             }
+
+            let trace_len = self.cur_state.symbolic_trace.len();
+            let side_constraints_len = self.cur_state.side_constraints.len();
+            let bindings_before = self.cur_state.symbol_binding_map.clone();
+            self.execute(&vec![stmt.clone()], 0);
+
+            if let Some(g) = &guard {
+                self.cur_state
+                    .map_constraints_since(trace_len, side_constraints_len, |c| {
+                        guard_constraint(g, c, &bindings_before)
+                    });
+                for (k, v) in self.cur_state.symbol_binding_map.iter_mut() {
+                    // A variable first bound by the iteration is unknown when it does not run
+                    let old = match bindings_before.get(k) {
+                        Some(old) => old.clone(),
+                        None => Rc::new(SymbolicValue::Variable(k.clone())),
+                    };
+                    if old != *v {
+                        *v = Rc::new(SymbolicValue::Conditional(g.clone(), v.clone(), old));
+                    }
+                }
+                self.cur_state.mark_bindings_changed();
+            }
+
+            let tmp_cond = self.evaluate_expression(cond, elem_id);
+            let mut memo = FxHashSet::default();
+            condition = self.simplify_variables(&tmp_cond, elem_id, true, false, &mut memo);
+        }
+
+        if condition != SymbolicValue::ConstantBool(false) {
+            warn!(
+                "Symbolic loop is not terminated after {} unrolled iterations",
+                self.setting.max_loop_unroll
+            );
+            self.cur_state = state_before_loop;
+            self.symbolic_store = store_before_loop;
+            self.cur_state.mark_bindings_changed();
+            self.cur_state.contains_symbolic_loop = true;
         }
     }
 
@@ -1201,7 +1801,6 @@ impl<'a> SymbolicExecutor<'a> {
                 SymbolicName::new(usize::MAX, self.cur_state.owner_name.clone(), None),
                 return_value,
             );
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -1278,8 +1877,6 @@ impl<'a> SymbolicExecutor<'a> {
                 self.mindim = std::cmp::min(self.mindim, *md);
             }
             self.id2dimensions.insert(*id, dims);
-
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -1326,22 +1923,23 @@ impl<'a> SymbolicExecutor<'a> {
             let rhe_val = self.evaluate_expression(rhe, meta.elem_id);
             let mut memo_left = FxHashSet::default();
             let simplified_lhe_val =
-                self.simplify_variables(&lhe_val, meta.elem_id, false, true, &mut memo_left);
+                self.simplify_for_trace(&lhe_val, meta.elem_id, false, true, &mut memo_left);
             let mut memo_right = FxHashSet::default();
             let simplified_rhe_val =
-                self.simplify_variables(&rhe_val, meta.elem_id, false, true, &mut memo_right);
+                self.simplify_for_trace(&rhe_val, meta.elem_id, false, true, &mut memo_right);
 
             let cond = SymbolicValue::BinaryOp(
                 Rc::new(simplified_lhe_val),
                 DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
                 Rc::new(simplified_rhe_val),
             );
+            self.record_potential_div_by_zeros(&cond, meta);
 
             if self.setting.keep_track_constraints {
                 if !self.setting.constraint_assert_dissabled {
-                    self.cur_state.push_symbolic_trace(&cond);
+                    self.push_symbolic_trace(&cond);
                 }
-                self.cur_state.push_side_constraint(&cond);
+                self.push_side_constraint(&cond);
             } else {
                 if !self.cur_state.is_failed {
                     let mut memo = FxHashSet::default();
@@ -1354,12 +1952,14 @@ impl<'a> SymbolicExecutor<'a> {
                             DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
                             Rc::new(rhe_val),
                         );
-                        self.violated_condition = Some((meta.elem_id, original_cond));
+                        self.violated_condition = Some((
+                            meta.elem_id,
+                            original_cond,
+                            self.symbolic_library.locate(meta),
+                        ));
                     }
                 }
             }
-
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -1368,11 +1968,21 @@ impl<'a> SymbolicExecutor<'a> {
             self.trace_if_enabled(&meta);
             let expr = self.evaluate_expression(&arg, meta.elem_id);
             let mut memo = FxHashSet::default();
-            let condition = self.simplify_variables(&expr, meta.elem_id, true, true, &mut memo);
+            let condition = self.simplify_for_trace(&expr, meta.elem_id, true, true, &mut memo);
             if self.setting.keep_track_constraints {
-                self.cur_state.push_symbolic_trace(&condition);
+                self.push_symbolic_trace(&condition);
+            } else if !self.cur_state.is_failed {
+                // A failed assertion rejects the input just like a violated `===` does, so that
+                // inputs accepted by the side constraints but not by the assertion are reported
+                let mut memo = FxHashSet::default();
+                let simplified_condition =
+                    self.simplify_variables(&expr, meta.elem_id, false, false, &mut memo);
+                if let SymbolicValue::ConstantBool(false) = simplified_condition {
+                    self.cur_state.is_failed = true;
+                    self.violated_condition =
+                        Some((meta.elem_id, expr, self.symbolic_library.locate(meta)));
+                }
             }
-            self.execute(statements, cur_bid + 1);
         }
     }
 
@@ -1587,7 +2197,36 @@ impl<'a> SymbolicExecutor<'a> {
                 Rc::new(right_call.clone()),
                 is_mutable,
             );
-            self.cur_state.push_symbolic_trace(&cont);
+            self.push_symbolic_trace(&cont);
+        }
+    }
+
+    /// Binds the fields of a bus instance created by a substitution such as `p = Point()`.
+    ///
+    /// Each field of the bus is bound to a fresh symbolic variable owned by the bus instance, so
+    /// that an access such as `p.x` resolves in the same way as a signal of a subcomponent.
+    ///
+    /// # Parameters
+    /// - `bus_name`: The symbolic name of the bus instance.
+    /// - `bus_id`: The identifier of the bus in the symbolic library.
+    fn handle_bus_substitution(&mut self, bus_name: &SymbolicName, bus_id: &usize) {
+        let field_ids = if let Some(bus) = self.symbolic_library.bus_library.get(bus_id) {
+            bus.field_ids.clone()
+        } else {
+            panic!("Unknown Bus: {}", self.symbolic_library.id2name[bus_id]);
+        };
+
+        let mut owner_name = (*self.cur_state.owner_name).clone();
+        owner_name.push(OwnerName {
+            id: bus_name.id,
+            counter: 0,
+            access: bus_name.access.clone(),
+        });
+        let owner_name = Rc::new(owner_name);
+        for field_id in field_ids {
+            let field_name = SymbolicName::new(field_id, owner_name.clone(), None);
+            self.cur_state
+                .set_sym_val(field_name.clone(), SymbolicValue::Variable(field_name));
         }
     }
 
@@ -1628,6 +2267,10 @@ impl<'a> SymbolicExecutor<'a> {
         args: &Vec<Rc<SymbolicValue>>,
         component_name: &SymbolicName,
     ) {
+        if let Some(observer) = self.observer.as_deref_mut() {
+            observer.on_component_instantiated(component_name);
+        }
+
         let mut subse_setting = self.setting.clone();
         subse_setting.only_initialization_blocks = true;
         subse_setting.off_trace = true;
@@ -1661,7 +2304,10 @@ impl<'a> SymbolicExecutor<'a> {
                 .set_rc_sym_val(tp_name, args[i].clone());
         }
 
+        se_for_initialization.num_steps = self.num_steps;
         se_for_initialization.execute(&template.body, 0);
+        self.num_steps = se_for_initialization.num_steps;
+        self.cur_state.timed_out |= se_for_initialization.cur_state.timed_out;
 
         let mut inputs_binding_map = FxHashMap::default();
         let mut id2dimensions = FxHashMap::default();
@@ -1868,11 +2514,10 @@ impl<'a> SymbolicExecutor<'a> {
                         Rc::new(SymbolicValue::Variable(var_name.clone())),
                         Rc::new(value.clone()),
                     );
-                    self.cur_state.push_symbolic_trace(&cont);
-                    self.cur_state.push_side_constraint(&cont);
+                    self.push_trace_and_side_constraint(&cont);
                 }
                 DebuggableAssignOp(AssignOp::AssignSignal) => {
-                    // handling zero-division pattern
+                    // handling zero-division pattern of `/`, `\`, and `%`
                     let zero_div_info = if !self.is_concrete_mode {
                         self.is_concrete_mode = true;
                         let mut memo = FxHashSet::default();
@@ -1885,7 +2530,11 @@ impl<'a> SymbolicExecutor<'a> {
                         );
                         if let SymbolicValue::BinaryOp(
                             left,
-                            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Div),
+                            DebuggableExpressionInfixOpcode(
+                                ExpressionInfixOpcode::Div
+                                | ExpressionInfixOpcode::IntDiv
+                                | ExpressionInfixOpcode::Mod,
+                            ),
                             right,
                         ) = simplified_value
                         {
@@ -1936,7 +2585,7 @@ impl<'a> SymbolicExecutor<'a> {
                         self.symbolic_library.template_library[&self.cur_state.template_id].is_safe,
                         zero_div_info,
                     );
-                    self.cur_state.push_symbolic_trace(&cont);
+                    self.push_symbolic_trace(&cont);
                 }
                 _ => {}
             }
@@ -2091,6 +2740,12 @@ impl<'a> SymbolicExecutor<'a> {
     /// - The function ensures that the component is executed only once by checking its `is_done` flag in the `components_store`.
     /// - The `SymbolicExecutor` is re-initialized for the component with an updated owner name that incorporates the component's ID, counter, and access dimensions.
     /// - Handles template parameters and inputs before execution to ensure consistency with the symbolic model.
+    /// - Components nested deeper than `max_component_depth` are not executed, leaving their outputs as fresh symbols.
+    /// - With `WhitelistMode::TrustAsBlackBox`, components of whitelisted templates are not executed either, and each of
+    ///   their outputs is bound to a `SymbolicValue::Call` to the template, both in the symbolic trace and in the side
+    ///   constraints, so that the outputs are as constrained as the trusted template makes them.
+    ///   With `precise_primitives`, a component of a modeled primitive such as `Num2Bits` gets its exact constraints
+    ///   instead (see `generate_num2bits_model`).
     fn execute_ready_component(
         &mut self,
         component_id: usize,
@@ -2098,6 +2753,20 @@ impl<'a> SymbolicExecutor<'a> {
         pre_dims: &Vec<SymbolicAccess>,
     ) {
         if !self.symbolic_store.components_store[component_name].is_done {
+            // The owner chain of a component contains `main` followed by one entry per level
+            if let Some(max_depth) = self.setting.max_component_depth {
+                if self.cur_state.owner_name.len() > max_depth {
+                    if !self.setting.off_trace {
+                        trace!(
+                            "⬛ Blackbox {}",
+                            component_name.lookup_fmt(&self.symbolic_library.id2name)
+                        );
+                    }
+                    self.num_blackboxed_components += 1;
+                    return;
+                }
+            }
+
             let mut subse = SymbolicExecutor::new(&mut self.symbolic_library, self.setting);
             let mut updated_owner_list = (*self.cur_state.owner_name).clone();
             updated_owner_list.push(OwnerName {
@@ -2110,9 +2779,16 @@ impl<'a> SymbolicExecutor<'a> {
                 },
             });
             subse.cur_state.owner_name = Rc::new(updated_owner_list);
+            if self.setting.keep_track_constraints {
+                self.component_templates.insert(
+                    (*subse.cur_state.owner_name).clone(),
+                    self.symbolic_store.components_store[component_name].template_id,
+                );
+            }
 
-            let templ = &subse.symbolic_library.template_library
-                [&self.symbolic_store.components_store[component_name].template_id];
+            let templ = subse.symbolic_library.template_library
+                [&self.symbolic_store.components_store[component_name].template_id]
+                .clone();
             subse
                 .cur_state
                 .set_template_id(self.symbolic_store.components_store[component_name].template_id);
@@ -2140,6 +2816,101 @@ impl<'a> SymbolicExecutor<'a> {
                 subse.cur_state.set_sym_val(n, v.clone().unwrap());
             }
 
+            if templ.is_safe
+                && self.setting.keep_track_constraints
+                && self.setting.whitelist_mode == WhitelistMode::TrustAsBlackBox
+            {
+                if self.setting.precise_primitives {
+                    if let Some((assignments, constraints)) = Self::model_primitive(
+                        &self.symbolic_store.components_store[component_name],
+                        &subse,
+                    ) {
+                        for assignment in assignments {
+                            self.push_symbolic_trace(&assignment);
+                        }
+                        for constraint in constraints {
+                            self.push_trace_and_side_constraint(&constraint);
+                        }
+                        if !self.setting.off_trace {
+                            trace!(
+                                "⬛ Model {}",
+                                component_name.lookup_fmt(&self.symbolic_library.id2name)
+                            );
+                        }
+                        return;
+                    }
+                }
+
+                // The call receives the template parameters, the bindings of the inputs, and
+                // the output to compute, so that the trace can be emulated without the body
+                let mut inputs: Vec<(SymbolicName, SymbolicValue)> =
+                    self.symbolic_store.components_store[component_name]
+                        .inputs_binding_map
+                        .iter()
+                        .map(|(k, v)| {
+                            (
+                                SymbolicName::new(
+                                    k.id,
+                                    subse.cur_state.owner_name.clone(),
+                                    k.access.clone(),
+                                ),
+                                v.clone().unwrap(),
+                            )
+                        })
+                        .collect();
+                inputs.sort_by(|a, b| a.0.cmp(&b.0));
+                let mut args = self.symbolic_store.components_store[component_name]
+                    .args
+                    .clone();
+                for (name, value) in inputs {
+                    args.push(Rc::new(SymbolicValue::AssignEq(
+                        Rc::new(SymbolicValue::Variable(name)),
+                        Rc::new(value),
+                    )));
+                }
+
+                let mut outputs: FxHashMap<SymbolicName, Option<SymbolicValue>> =
+                    FxHashMap::default();
+                for output_id in &templ.output_ids {
+                    let dims = subse
+                        .evaluate_dimension(&templ.id2dimension_expressions[output_id], usize::MAX);
+                    register_array_elements(
+                        *output_id,
+                        &dims,
+                        Some(subse.cur_state.owner_name.clone()),
+                        &mut outputs,
+                    );
+                }
+                let mut outputs: Vec<SymbolicName> = outputs.into_keys().collect();
+                outputs.sort();
+
+                let template_id = self.symbolic_store.components_store[component_name].template_id;
+                for output in outputs {
+                    let mut call_args = args.clone();
+                    call_args.push(Rc::new(SymbolicValue::Variable(output.clone())));
+                    let output = Rc::new(SymbolicValue::Variable(output));
+                    let call = Rc::new(SymbolicValue::Call(template_id, call_args));
+                    self.push_symbolic_trace(&SymbolicValue::Assign(
+                        output.clone(),
+                        call.clone(),
+                        true,
+                        None,
+                    ));
+                    // The output is pinned to the trusted call, as the constraints of the
+                    // template would pin it
+                    self.push_side_constraint(&SymbolicValue::AssignEq(output, call));
+                }
+                self.num_trusted_components += 1;
+
+                if !self.setting.off_trace {
+                    trace!(
+                        "⬛ Trust {}",
+                        component_name.lookup_fmt(&self.symbolic_library.id2name)
+                    );
+                }
+                return;
+            }
+
             if !self.setting.off_trace {
                 trace!("{}", "===========================".cyan());
                 trace!(
@@ -2149,15 +2920,82 @@ impl<'a> SymbolicExecutor<'a> {
                 );
             }
 
+            if self.setting.keep_track_constraints {
+                let template_name = &subse.symbolic_library.id2name
+                    [&self.symbolic_store.components_store[component_name].template_id];
+                if template_name == "Num2Bits" || template_name == "Bits2Num" {
+                    if let Some(SymbolicValue::ConstantInt(n)) =
+                        self.symbolic_store.components_store[component_name]
+                            .args
+                            .first()
+                            .map(|a| a.as_ref())
+                    {
+                        let field_bits = self.setting.prime.bits();
+                        if *n > BigInt::from(field_bits) {
+                            self.bit_width_warnings.push(format!(
+                                "{}({}) instantiated as `{}` exceeds the {}-bit field",
+                                template_name,
+                                n,
+                                component_name.lookup_fmt(&subse.symbolic_library.id2name),
+                                field_bits
+                            ));
+                        }
+                        // A signal bound as is to the input of `Num2Bits(n)` is meant to fit
+                        // in `n` bits
+                        if template_name == "Num2Bits" {
+                            let in_name =
+                                subse.symbolic_library.name2id.get("in").map(|in_id| {
+                                    SymbolicName::new(*in_id, Rc::new(Vec::new()), None)
+                                });
+                            if let (Some(in_name), Some(bits)) = (in_name, n.to_usize()) {
+                                if let Some(Some(SymbolicValue::Variable(signal))) =
+                                    self.symbolic_store.components_store[component_name]
+                                        .inputs_binding_map
+                                        .get(&in_name)
+                                {
+                                    let hint =
+                                        self.bit_width_hints.entry(signal.clone()).or_insert(bits);
+                                    *hint = (*hint).min(bits);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             let is_lessthan = templ.is_lessthan;
-            subse.execute(&templ.body.clone(), 0);
+            subse.num_steps = self.num_steps;
+            subse.observer = self.observer.as_deref_mut();
+            subse.execute(&templ.body, 0);
+            self.bit_width_warnings
+                .append(&mut subse.bit_width_warnings);
+            for (name, bits) in subse.bit_width_hints.drain() {
+                let hint = self.bit_width_hints.entry(name).or_insert(bits);
+                *hint = (*hint).min(bits);
+            }
+            self.num_blackboxed_components += subse.num_blackboxed_components;
+            self.num_trusted_components += subse.num_trusted_components;
+            self.num_opaque_values += subse.num_opaque_values;
+            self.num_steps = subse.num_steps;
+            self.component_templates
+                .extend(subse.component_templates.drain());
+            self.cur_state.timed_out |= subse.cur_state.timed_out;
+            self.cur_state.contains_symbolic_loop |= subse.cur_state.contains_symbolic_loop;
 
             self.cur_state
-                .symbolic_trace
-                .append(&mut subse.cur_state.symbolic_trace);
+                .append_symbolic_trace(&mut subse.cur_state.symbolic_trace);
+            self.cur_state
+                .append_side_constraints(&mut subse.cur_state.side_constraints);
+            self.cur_state.num_trace_duplicates += subse.cur_state.num_trace_duplicates;
+            self.cur_state.num_side_constraint_duplicates +=
+                subse.cur_state.num_side_constraint_duplicates;
             self.cur_state
-                .side_constraints
-                .append(&mut subse.cur_state.side_constraints);
+                .field_reductions
+                .append(&mut subse.cur_state.field_reductions);
+            for potential_div_by_zero in subse.cur_state.potential_div_by_zeros.drain(..) {
+                self.cur_state
+                    .push_potential_div_by_zero(potential_div_by_zero);
+            }
             self.execution_failed = subse.execution_failed;
             if self.setting.propagate_assignments {
                 for (k, v) in subse.cur_state.symbol_binding_map.iter() {
@@ -2170,7 +3008,7 @@ impl<'a> SymbolicExecutor<'a> {
                     &subse.symbolic_library.name2id,
                     subse.cur_state.owner_name,
                 );
-                self.cur_state.push_symbolic_trace(&cond);
+                self.push_symbolic_trace(&cond);
             }
 
             if !self.setting.off_trace {
@@ -2182,6 +3020,80 @@ impl<'a> SymbolicExecutor<'a> {
 
 // Other utility methods
 impl<'a> SymbolicExecutor<'a> {
+    /// Returns `value` with its variables shared through the `name_interner` of the symbolic
+    /// library when `intern_names` is enabled, or a copy of it otherwise.
+    fn intern_names(&mut self, value: &SymbolicValue) -> SymbolicValue {
+        if self.setting.intern_names {
+            self.symbolic_library.name_interner.intern_constraint(value)
+        } else {
+            value.clone()
+        }
+    }
+
+    /// Adds a trace entry to the current state, with its variables interned.
+    fn push_symbolic_trace(&mut self, constraint: &SymbolicValue) {
+        let constraint = self.intern_names(constraint);
+        self.cur_state.push_symbolic_trace(&constraint);
+    }
+
+    /// Adds a side constraint to the current state, with its variables interned, and notifies
+    /// the observer, unless the constraint is dropped as a duplicate.
+    fn push_side_constraint(&mut self, constraint: &SymbolicValue) {
+        let constraint = self.intern_names(constraint);
+        let num_side_constraints = self.cur_state.side_constraints.len();
+        self.cur_state.push_side_constraint(&constraint);
+        if self.cur_state.side_constraints.len() > num_side_constraints {
+            if let Some(observer) = self.observer.as_deref_mut() {
+                observer.on_constraint(&constraint);
+            }
+        }
+    }
+
+    /// Adds `constraint` both to the trace and to the side constraints.
+    ///
+    /// The constraint is interned once beforehand, so that both entries keep sharing their
+    /// subexpressions.
+    fn push_trace_and_side_constraint(&mut self, constraint: &SymbolicValue) {
+        let constraint = self.intern_names(constraint);
+        self.push_symbolic_trace(&constraint);
+        self.push_side_constraint(&constraint);
+    }
+
+    /// Generates the exact semantics of a component of a modeled primitive, which is currently
+    /// `Num2Bits` with a constant number of bits.
+    ///
+    /// # Parameters
+    /// - `component`: The component.
+    /// - `subse`: The executor of the component, whose owner is the component.
+    ///
+    /// # Returns
+    /// The assignments and constraints of `generate_num2bits_model`, or `None` if the template is
+    /// not modeled, its number of bits is not constant, or its input is not bound.
+    fn model_primitive(
+        component: &SymbolicComponent,
+        subse: &SymbolicExecutor,
+    ) -> Option<(Vec<SymbolicValue>, Vec<SymbolicValue>)> {
+        if subse.symbolic_library.id2name[&component.template_id] != "Num2Bits" {
+            return None;
+        }
+        let bits = match component.args.first().map(|a| a.as_ref()) {
+            Some(SymbolicValue::ConstantInt(n)) => n.to_usize()?,
+            _ => return None,
+        };
+        let in_name = SymbolicName::new(
+            *subse.symbolic_library.name2id.get("in")?,
+            Rc::new(Vec::new()),
+            None,
+        );
+        let input = component.inputs_binding_map.get(&in_name)?.clone()?;
+        Some(generate_num2bits_model(
+            &subse.symbolic_library.name2id,
+            subse.cur_state.owner_name.clone(),
+            &Rc::new(input),
+            bits,
+        ))
+    }
+
     /// Traces the current state if tracing is enabled.
     ///
     /// This method logs the current state information if tracing is not disabled.
@@ -2199,6 +3111,146 @@ impl<'a> SymbolicExecutor<'a> {
         }
     }
 
+    /// Symbolically executes the function `id` over `args`, as a callee of the current state.
+    ///
+    /// The function runs in a sub-executor whose owner is the current owner extended with the
+    /// function. Unless it contains a symbolic loop or times out, its trace, field reductions, and
+    /// potential divisions by zero are appended to the current state.
+    ///
+    /// # Returns
+    ///
+    /// The return value of the function along with its final state, or `None` if the execution
+    /// contains a symbolic loop or timed out.
+    pub fn execute_function(
+        &mut self,
+        id: usize,
+        args: &[SymbolicValueRef],
+    ) -> Option<FunctionExecution> {
+        let symbolic_library = &mut self.symbolic_library;
+        let mut subse_setting = self.setting.clone();
+        subse_setting.only_initialization_blocks = false;
+        let mut subse = SymbolicExecutor::new(symbolic_library, &subse_setting);
+
+        let mut updated_owner_list = (*self.cur_state.owner_name).clone();
+        updated_owner_list.push(OwnerName {
+            id: id,
+            counter: subse.symbolic_library.function_counter[&id],
+            access: None,
+        });
+        subse.cur_state.owner_name = Rc::new(updated_owner_list);
+        subse
+            .symbolic_library
+            .function_counter
+            .insert(id, subse.symbolic_library.function_counter[&id] + 1);
+        subse.cur_state.set_template_id(id);
+
+        let func = subse.symbolic_library.function_library[&id].clone();
+        for i in 0..(func.function_argument_names.len()) {
+            let sym_name = SymbolicName::new(
+                func.function_argument_names[i],
+                subse.cur_state.owner_name.clone(),
+                None,
+            );
+            subse
+                .cur_state
+                .set_rc_sym_val(sym_name.clone(), args[i].clone());
+        }
+
+        if !subse.setting.off_trace {
+            trace!("{}", format!("{}", "===========================").cyan());
+            trace!("📞 Call {}", subse.symbolic_library.id2name[&id]);
+        }
+
+        subse.num_steps = self.num_steps;
+        subse.observer = self.observer.as_deref_mut();
+        subse.execute(&func.body, 0);
+        self.mindim = std::cmp::min(subse.mindim, self.mindim);
+        self.num_steps = subse.num_steps;
+        self.cur_state.timed_out |= subse.cur_state.timed_out;
+
+        if !subse.setting.off_trace {
+            trace!("{}", format!("{}", "===========================").cyan());
+        }
+
+        if subse.cur_state.contains_symbolic_loop || subse.cur_state.timed_out {
+            return None;
+        }
+
+        // NOTE: a function does not produce any constraint
+        self.cur_state
+            .append_symbolic_trace(&mut subse.cur_state.symbolic_trace);
+        self.cur_state
+            .field_reductions
+            .append(&mut subse.cur_state.field_reductions);
+        for potential_div_by_zero in subse.cur_state.potential_div_by_zeros.drain(..) {
+            self.cur_state
+                .push_potential_div_by_zero(potential_div_by_zero);
+        }
+        self.execution_failed = subse.execution_failed;
+
+        let return_sym_name =
+            SymbolicName::new(usize::MAX, subse.cur_state.owner_name.clone(), None);
+        let return_value = (*subse.cur_state.symbol_binding_map[&return_sym_name]).clone();
+        Some(FunctionExecution {
+            return_value: return_value,
+            state: subse.cur_state,
+            id2dimensions: subse.id2dimensions,
+        })
+    }
+
+    /// Counts one step and checks it against the `deadline` and `max_steps` of the setting.
+    ///
+    /// Once the budget is exhausted, `cur_state` is marked as timed out and every further
+    /// check fails, so that the remaining statements are skipped and the execution unwinds
+    /// with the constraints gathered so far.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the execution must stop.
+    fn is_out_of_budget(&mut self) -> bool {
+        if self.cur_state.timed_out {
+            return true;
+        }
+        self.num_steps += 1;
+        let is_exhausted = self
+            .setting
+            .max_steps
+            .map_or(false, |max_steps| self.num_steps > max_steps)
+            || self
+                .setting
+                .deadline
+                .map_or(false, |deadline| Instant::now() >= deadline);
+        if is_exhausted {
+            self.cur_state.timed_out = true;
+        }
+        is_exhausted
+    }
+
+    /// Records the divisions by non-constant divisors in `value`.
+    ///
+    /// Only the symbolic execution gathering the constraints records them, since the divisors
+    /// are constants during concrete execution.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The evaluated right-hand side or constraint of a statement.
+    /// * `meta` - The metadata of the statement.
+    fn record_potential_div_by_zeros(&mut self, value: &SymbolicValue, meta: &Meta) {
+        if !self.setting.keep_track_constraints {
+            return;
+        }
+        let mut divisors = Vec::new();
+        extract_non_constant_divisors(value, &mut divisors);
+        for divisor in divisors {
+            self.cur_state
+                .push_potential_div_by_zero(PotentialDivByZero {
+                    elem_id: meta.elem_id,
+                    divisor: divisor,
+                    location: self.symbolic_library.locate(meta),
+                });
+        }
+    }
+
     /// Constructs symbolic names for a given base ID and access pattern.
     ///
     /// This function parses a sequence of accesses to create symbolic names
@@ -2460,3 +3512,105 @@ impl<'a> SymbolicExecutor<'a> {
         sym_array
     }
 }
+
+/// Rewrites a value returned from a function so that it only refers to variables visible
+/// from the caller.
+///
+/// Every variable owned by the function, i.e., by the owner of `state`, the final state of the
+/// function, is replaced with its binding in `state`, recursively. Returns `None` if some
+/// function-local variable has no binding, or if the value contains a construct that cannot be
+/// inlined safely.
+fn inline_function_locals(
+    state: &SymbolicState,
+    value: &SymbolicValue,
+    visited: &mut FxHashSet<SymbolicName>,
+) -> Option<SymbolicValue> {
+    match value {
+        SymbolicValue::ConstantInt(_) | SymbolicValue::ConstantBool(_) => Some(value.clone()),
+        SymbolicValue::Variable(sym_name) => {
+            if sym_name.owner != state.owner_name {
+                return Some(value.clone());
+            }
+            if !visited.insert(sym_name.clone()) {
+                return None;
+            }
+            let bound = match state.get_sym_val(sym_name) {
+                Some(v) if **v != *value => inline_function_locals(state, v, visited),
+                _ => None,
+            };
+            visited.remove(sym_name);
+            bound
+        }
+        SymbolicValue::BinaryOp(lv, op, rv) => Some(SymbolicValue::BinaryOp(
+            Rc::new(inline_function_locals(state, lv, visited)?),
+            op.clone(),
+            Rc::new(inline_function_locals(state, rv, visited)?),
+        )),
+        SymbolicValue::AuxBinaryOp(lv, op, rv) => Some(SymbolicValue::AuxBinaryOp(
+            Rc::new(inline_function_locals(state, lv, visited)?),
+            op.clone(),
+            Rc::new(inline_function_locals(state, rv, visited)?),
+        )),
+        SymbolicValue::UnaryOp(op, v) => Some(SymbolicValue::UnaryOp(
+            op.clone(),
+            Rc::new(inline_function_locals(state, v, visited)?),
+        )),
+        SymbolicValue::Conditional(cond, then_val, else_val) => Some(SymbolicValue::Conditional(
+            Rc::new(inline_function_locals(state, cond, visited)?),
+            Rc::new(inline_function_locals(state, then_val, visited)?),
+            Rc::new(inline_function_locals(state, else_val, visited)?),
+        )),
+        SymbolicValue::Array(elements) => {
+            let mut inlined = Vec::with_capacity(elements.len());
+            for e in elements {
+                inlined.push(Rc::new(inline_function_locals(state, e, visited)?));
+            }
+            Some(SymbolicValue::Array(inlined))
+        }
+        _ => None,
+    }
+}
+
+/// Weakens a constraint produced by an unrolled iteration of a symbolic loop so that it only
+/// holds under `guard`.
+///
+/// The right-hand side of an assignment or of an equality becomes `Conditional(guard, rhs, old)`,
+/// `old` being the value of the left-hand side before the iteration, so that the constraint keeps
+/// the top-level shape that the emulation, the mutators, and the SMT encoder match on. Any other
+/// constraint `c` becomes `Conditional(guard, c, true)`.
+fn guard_constraint(
+    guard: &SymbolicValueRef,
+    constraint: &SymbolicValueRef,
+    bindings_before: &SymbolBindingMap,
+) -> SymbolicValueRef {
+    let guarded = |lhs: &SymbolicValueRef, rhs: &SymbolicValueRef| {
+        let old = match lhs.as_ref() {
+            SymbolicValue::Variable(name) => bindings_before.get(name).cloned(),
+            _ => None,
+        };
+        Rc::new(SymbolicValue::Conditional(
+            guard.clone(),
+            rhs.clone(),
+            old.unwrap_or_else(|| lhs.clone()),
+        ))
+    };
+    Rc::new(match constraint.as_ref() {
+        SymbolicValue::Assign(lhs, rhs, is_safe, _) => {
+            SymbolicValue::Assign(lhs.clone(), guarded(lhs, rhs), *is_safe, None)
+        }
+        SymbolicValue::AssignEq(lhs, rhs) => {
+            SymbolicValue::AssignEq(lhs.clone(), guarded(lhs, rhs))
+        }
+        SymbolicValue::AssignCall(lhs, rhs, is_mutable) => {
+            SymbolicValue::AssignCall(lhs.clone(), guarded(lhs, rhs), *is_mutable)
+        }
+        SymbolicValue::BinaryOp(lhs, op, rhs) if matches!(op.0, ExpressionInfixOpcode::Eq) => {
+            SymbolicValue::BinaryOp(lhs.clone(), op.clone(), guarded(lhs, rhs))
+        }
+        _ => SymbolicValue::Conditional(
+            guard.clone(),
+            constraint.clone(),
+            Rc::new(SymbolicValue::ConstantBool(true)),
+        ),
+    })
+}
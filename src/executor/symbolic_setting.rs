@@ -1,4 +1,66 @@
+use std::time::Instant;
+
 use num_bigint_dig::BigInt;
+use num_traits::One;
+
+/// How the templates of the whitelist are handled by the symbolic execution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WhitelistMode {
+    /// The body of a whitelisted template is executed like any other template, and only its
+    /// assignments are protected from the mutations.
+    #[default]
+    Inline,
+    /// A whitelisted template is trusted to be correct. Its body is not executed, and each of its
+    /// outputs is bound to a `SymbolicValue::Call` to the template, in the trace and in the side
+    /// constraints, so that none of its own constraints appears in either.
+    TrustAsBlackBox,
+}
+
+/// The range of the values explored for a symbolic template parameter of the main template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamBound {
+    pub name: String,
+    /// The smallest value, included.
+    pub lower: BigInt,
+    /// The largest value, included.
+    pub upper: BigInt,
+}
+
+/// The largest number of combinations of template parameter values explored with
+/// `--param_bounds`, since the circuit is executed again for each of them.
+pub const MAX_PARAM_BINDINGS: usize = 64;
+
+/// Counts the combinations that `enumerate_param_bindings` would return, without enumerating
+/// them, so that an oversized grid can be rejected upfront.
+pub fn count_param_bindings(bounds: &[ParamBound]) -> BigInt {
+    bounds.iter().fold(BigInt::one(), |count, bound| {
+        count * (&bound.upper - &bound.lower + BigInt::one())
+    })
+}
+
+/// Enumerates every combination of the values of the bounded template parameters.
+///
+/// # Returns
+/// One binding per combination, where the parameters appear in the order of `bounds`, and the
+/// values of the last parameter vary fastest. A single empty binding is returned when `bounds`
+/// is empty, so that the caller always executes the template at least once.
+pub fn enumerate_param_bindings(bounds: &[ParamBound]) -> Vec<Vec<(String, BigInt)>> {
+    let mut bindings = vec![Vec::new()];
+    for bound in bounds {
+        let mut extended = Vec::new();
+        for binding in &bindings {
+            let mut value = bound.lower.clone();
+            while value <= bound.upper {
+                let mut extended_binding: Vec<(String, BigInt)> = binding.clone();
+                extended_binding.push((bound.name.clone(), value.clone()));
+                extended.push(extended_binding);
+                value += BigInt::one();
+            }
+        }
+        bindings = extended;
+    }
+    bindings
+}
 
 #[derive(Clone)]
 pub struct SymbolicExecutorSetting {
@@ -10,6 +72,30 @@ pub struct SymbolicExecutorSetting {
     pub substitute_output: bool,
     pub propagate_assignments: bool,
     pub constraint_assert_dissabled: bool,
+    pub simplify_to_fixpoint: bool,
+    pub max_component_depth: Option<usize>,
+    pub max_loop_unroll: usize,
+    pub track_field_reductions: bool,
+    /// Stops the execution, marking the state as timed out, once this instant has passed.
+    pub deadline: Option<Instant>,
+    /// Stops the execution, marking the state as timed out, after this many steps, where a
+    /// step is a statement or a function call.
+    pub max_steps: Option<usize>,
+    /// Drops every trace entry or side constraint whose canonical form was already gathered,
+    /// keeping the first occurrence.
+    pub dedup_constraints: bool,
+    /// Applies to the components of whitelisted templates while gathering the constraints.
+    pub whitelist_mode: WhitelistMode,
+    /// Replaces each whitelisted `Num2Bits` trusted as a black box with its exact constraints,
+    /// i.e., its outputs are boolean and reconstruct its input, instead of an opaque call.
+    pub precise_primitives: bool,
+    /// Replaces the value bound by a substitution with a fresh opaque variable once it has more
+    /// nodes than this, so that chains of propagated assignments cannot grow without bound.
+    pub max_expression_nodes: Option<usize>,
+    /// Shares the variables of each trace entry and side constraint through the `name_interner`
+    /// of the symbolic library as they are gathered, so that a name is allocated once however
+    /// many constraints it appears in.
+    pub intern_names: bool,
 }
 
 pub fn get_default_setting_for_symbolic_execution(
@@ -25,6 +111,17 @@ pub fn get_default_setting_for_symbolic_execution(
         substitute_output: false,
         propagate_assignments: false,
         constraint_assert_dissabled: constraint_assert_dissabled,
+        simplify_to_fixpoint: false,
+        max_component_depth: None,
+        max_loop_unroll: 0,
+        track_field_reductions: false,
+        deadline: None,
+        max_steps: None,
+        dedup_constraints: false,
+        whitelist_mode: WhitelistMode::Inline,
+        precise_primitives: false,
+        max_expression_nodes: None,
+        intern_names: true,
     }
 }
 
@@ -41,5 +138,16 @@ pub fn get_default_setting_for_concrete_execution(
         substitute_output: true,
         propagate_assignments: true,
         constraint_assert_dissabled: constraint_assert_dissabled,
+        simplify_to_fixpoint: false,
+        max_component_depth: None,
+        max_loop_unroll: 0,
+        track_field_reductions: false,
+        deadline: None,
+        max_steps: None,
+        dedup_constraints: false,
+        whitelist_mode: WhitelistMode::Inline,
+        precise_primitives: false,
+        max_expression_nodes: None,
+        intern_names: false,
     }
 }
@@ -1,10 +1,19 @@
+use std::hash::{Hash, Hasher};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use colored::Colorize;
-use rustc_hash::FxHashMap;
+use num_bigint_dig::BigInt;
+use program_structure::ast::ExpressionInfixOpcode;
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use serde_with::{serde_as, DisplayFromStr};
 
+use crate::executor::debug_ast::DebuggableExpressionInfixOpcode;
 use crate::executor::symbolic_value::{
-    OwnerName, SymbolicAccess, SymbolicName, SymbolicValue, SymbolicValueRef,
+    canonicalize, extract_variables_from_symbolic_value, OwnerName, SourceLocation, SymbolicAccess,
+    SymbolicName, SymbolicValue, SymbolicValueRef,
 };
 use crate::executor::utils::italic;
 
@@ -12,6 +21,101 @@ pub type SymbolBindingMap = FxHashMap<SymbolicName, SymbolicValueRef>;
 pub type SymbolicTrace = Vec<SymbolicValueRef>;
 pub type SymbolicConstraints = Vec<SymbolicValueRef>;
 
+/// Source of the versions of binding maps. A process-wide counter keeps every version unique,
+/// even across the clones of a state that are taken when execution branches.
+static NEXT_BINDING_VERSION: AtomicU64 = AtomicU64::new(0);
+
+fn next_binding_version() -> u64 {
+    NEXT_BINDING_VERSION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// A point where the result of an arithmetic operation on two constants was reduced modulo the
+/// prime, recorded when `track_field_reductions` is enabled.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FieldReductionEvent {
+    pub elem_id: usize,
+    pub op: String,
+    #[serde_as(as = "DisplayFromStr")]
+    pub lhs: BigInt,
+    #[serde_as(as = "DisplayFromStr")]
+    pub rhs: BigInt,
+    #[serde_as(as = "DisplayFromStr")]
+    pub unreduced: BigInt,
+    #[serde_as(as = "DisplayFromStr")]
+    pub reduced: BigInt,
+}
+
+impl FieldReductionEvent {
+    /// Returns the reduction performed by `lhs op rhs = result`, or `None` when the operation is
+    /// not an addition, subtraction, or multiplication of two constants, or when the integer
+    /// result already is a field element.
+    pub fn detect(
+        elem_id: usize,
+        lhs: &SymbolicValue,
+        rhs: &SymbolicValue,
+        op: &DebuggableExpressionInfixOpcode,
+        result: &SymbolicValue,
+    ) -> Option<Self> {
+        let (lv, rv, reduced) = match (lhs, rhs, result) {
+            (
+                SymbolicValue::ConstantInt(lv),
+                SymbolicValue::ConstantInt(rv),
+                SymbolicValue::ConstantInt(reduced),
+            ) => (lv, rv, reduced),
+            _ => return None,
+        };
+        let unreduced = match op.0 {
+            ExpressionInfixOpcode::Add => lv + rv,
+            ExpressionInfixOpcode::Sub => lv - rv,
+            ExpressionInfixOpcode::Mul => lv * rv,
+            _ => return None,
+        };
+        if &unreduced == reduced {
+            return None;
+        }
+        Some(FieldReductionEvent {
+            elem_id: elem_id,
+            op: format!("{:?}", op),
+            lhs: lv.clone(),
+            rhs: rv.clone(),
+            unreduced: unreduced,
+            reduced: reduced.clone(),
+        })
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "elem_id": self.elem_id,
+            "op": self.op,
+            "lhs": self.lhs.to_string(),
+            "rhs": self.rhs.to_string(),
+            "unreduced": self.unreduced.to_string(),
+            "reduced": self.reduced.to_string(),
+        })
+    }
+}
+
+/// A division whose divisor is not a constant, so that it divides by zero unless the circuit
+/// rules out a zero divisor.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PotentialDivByZero {
+    pub elem_id: usize,
+    pub divisor: SymbolicValue,
+    /// The location of the statement containing the division, if known.
+    pub location: Option<SourceLocation>,
+}
+
+impl PotentialDivByZero {
+    pub fn lookup_fmt(&self, lookup: &FxHashMap<usize, String>) -> String {
+        format!(
+            "{} (elem_id={})",
+            self.divisor.lookup_fmt(lookup),
+            self.elem_id
+        )
+    }
+}
+
 /// Represents the state of symbolic execution, holding symbolic values,
 /// trace constraints, side constraints, and depth information.
 #[derive(Clone)]
@@ -25,6 +129,23 @@ pub struct SymbolicState {
     pub symbolic_trace: SymbolicTrace,
     pub side_constraints: SymbolicConstraints,
     pub is_failed: bool,
+    pub field_reductions: Vec<FieldReductionEvent>,
+    pub potential_div_by_zeros: Vec<PotentialDivByZero>,
+    /// Whether the execution stopped early due to `deadline` or `max_steps` of the setting.
+    pub timed_out: bool,
+    /// Whether constraints already in the symbolic trace or the side constraints are dropped
+    /// when pushed again, mirroring `dedup_constraints` of the setting.
+    pub dedup_on_push: bool,
+    /// The number of trace entries dropped by `dedup_on_push`.
+    pub num_trace_duplicates: usize,
+    /// The number of side constraints dropped by `dedup_on_push`.
+    pub num_side_constraint_duplicates: usize,
+    /// The indices in `side_constraints` of the constraints referencing each variable, in
+    /// increasing order, kept up to date as the side constraints change.
+    pub constraint_provenance: FxHashMap<SymbolicName, Vec<usize>>,
+    seen_trace_hashes: FxHashSet<u64>,
+    seen_side_constraint_hashes: FxHashSet<u64>,
+    binding_version: u64,
 }
 
 impl SymbolicState {
@@ -44,6 +165,16 @@ impl SymbolicState {
             symbolic_trace: SymbolicTrace::new(),
             side_constraints: SymbolicConstraints::new(),
             is_failed: false,
+            field_reductions: Vec::new(),
+            potential_div_by_zeros: Vec::new(),
+            timed_out: false,
+            dedup_on_push: false,
+            num_trace_duplicates: 0,
+            num_side_constraint_duplicates: 0,
+            constraint_provenance: FxHashMap::default(),
+            seen_trace_hashes: FxHashSet::default(),
+            seen_side_constraint_hashes: FxHashSet::default(),
+            binding_version: next_binding_version(),
         }
     }
 
@@ -115,6 +246,7 @@ impl SymbolicState {
     /// * `sym_val` - The symbolic value to associate with the variable.
     pub fn set_sym_val(&mut self, sym_name: SymbolicName, sym_val: SymbolicValue) {
         self.symbol_binding_map.insert(sym_name, Rc::new(sym_val));
+        self.mark_bindings_changed();
     }
 
     /// Sets a reference-counted symbolic value for a given variable name in the state.
@@ -125,6 +257,18 @@ impl SymbolicState {
     /// * `sym_val` - The reference-counted symbolic value to associate with the variable.
     pub fn set_rc_sym_val(&mut self, sym_name: SymbolicName, sym_val: SymbolicValueRef) {
         self.symbol_binding_map.insert(sym_name, sym_val);
+        self.mark_bindings_changed();
+    }
+
+    /// Returns a version that changes whenever `symbol_binding_map` is updated through this
+    /// state. Two states with the same version hold the same bindings.
+    pub fn binding_version(&self) -> u64 {
+        self.binding_version
+    }
+
+    /// Bumps the binding version. Must be called after mutating `symbol_binding_map` directly.
+    pub fn mark_bindings_changed(&mut self) {
+        self.binding_version = next_binding_version();
     }
 
     /// Retrieves a symbolic value associated with a given variable name.
@@ -154,6 +298,10 @@ impl SymbolicState {
     ///
     /// * `constraint` - The symbolic value representing the constraint.
     pub fn push_symbolic_trace(&mut self, constraint: &SymbolicValue) {
+        if self.dedup_on_push && !self.seen_trace_hashes.insert(constraint_hash(constraint)) {
+            self.num_trace_duplicates += 1;
+            return;
+        }
         self.symbolic_trace.push(Rc::new(constraint.clone()));
     }
 
@@ -163,13 +311,177 @@ impl SymbolicState {
     ///
     /// * `constraint` - The symbolic value representing the constraint.
     pub fn push_side_constraint(&mut self, constraint: &SymbolicValue) {
+        if self.dedup_on_push
+            && !self
+                .seen_side_constraint_hashes
+                .insert(constraint_hash(constraint))
+        {
+            self.num_side_constraint_duplicates += 1;
+            return;
+        }
         self.side_constraints.push(Rc::new(constraint.clone()));
+        self.record_provenance(self.side_constraints.len() - 1);
+    }
+
+    /// Moves the trace entries gathered by another state, e.g., a sub-component, to the end of
+    /// the symbolic trace, dropping the ones already present when `dedup_on_push` is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `constraints` - The trace entries to move, which is left empty.
+    pub fn append_symbolic_trace(&mut self, constraints: &mut SymbolicTrace) {
+        if self.dedup_on_push {
+            let num_constraints = constraints.len();
+            let seen = &mut self.seen_trace_hashes;
+            constraints.retain(|c| seen.insert(constraint_hash(c)));
+            self.num_trace_duplicates += num_constraints - constraints.len();
+        }
+        self.symbolic_trace.append(constraints);
+    }
+
+    /// Moves the side constraints gathered by another state to the end of the side constraints,
+    /// dropping the ones already present when `dedup_on_push` is enabled.
+    ///
+    /// # Arguments
+    ///
+    /// * `constraints` - The side constraints to move, which is left empty.
+    pub fn append_side_constraints(&mut self, constraints: &mut SymbolicConstraints) {
+        if self.dedup_on_push {
+            let num_constraints = constraints.len();
+            let seen = &mut self.seen_side_constraint_hashes;
+            constraints.retain(|c| seen.insert(constraint_hash(c)));
+            self.num_side_constraint_duplicates += num_constraints - constraints.len();
+        }
+        let side_constraints_len = self.side_constraints.len();
+        self.side_constraints.append(constraints);
+        self.record_provenance(side_constraints_len);
+    }
+
+    /// Records in `constraint_provenance` the variables of the side constraints from the index
+    /// `side_constraints_len` on, which must not be recorded yet.
+    fn record_provenance(&mut self, side_constraints_len: usize) {
+        for (i, constraint) in self.side_constraints[side_constraints_len..]
+            .iter()
+            .enumerate()
+        {
+            let mut variables = FxHashSet::default();
+            extract_variables_from_symbolic_value(constraint, &mut variables);
+            for variable in variables {
+                self.constraint_provenance
+                    .entry(variable)
+                    .or_default()
+                    .push(side_constraints_len + i);
+            }
+        }
+    }
+
+    /// Returns the side constraints referencing `name`, along with their indices.
+    pub fn constraints_referencing(&self, name: &SymbolicName) -> Vec<(usize, SymbolicValueRef)> {
+        self.constraint_provenance
+            .get(name)
+            .map(|indices| {
+                indices
+                    .iter()
+                    .map(|i| (*i, self.side_constraints[*i].clone()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Rewrites the trace entries and side constraints pushed since the given lengths, keeping
+    /// the hashes used by `dedup_on_push` in sync with the rewritten entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `trace_len` - The length of the symbolic trace before the entries to rewrite.
+    /// * `side_constraints_len` - The length of the side constraints before the entries to rewrite.
+    /// * `f` - The rewriting of an entry.
+    pub fn map_constraints_since<F>(&mut self, trace_len: usize, side_constraints_len: usize, f: F)
+    where
+        F: Fn(&SymbolicValueRef) -> SymbolicValueRef,
+    {
+        for c in self.symbolic_trace[trace_len..].iter_mut() {
+            let rewritten = f(c);
+            if self.dedup_on_push {
+                self.seen_trace_hashes.remove(&constraint_hash(c));
+                self.seen_trace_hashes.insert(constraint_hash(&rewritten));
+            }
+            *c = rewritten;
+        }
+        for c in self.side_constraints[side_constraints_len..].iter_mut() {
+            let rewritten = f(c);
+            if self.dedup_on_push {
+                self.seen_side_constraint_hashes.remove(&constraint_hash(c));
+                self.seen_side_constraint_hashes
+                    .insert(constraint_hash(&rewritten));
+            }
+            *c = rewritten;
+        }
+
+        // The rewriting may change the variables of the rewritten constraints
+        self.constraint_provenance.retain(|_, indices| {
+            while indices.last().map_or(false, |i| *i >= side_constraints_len) {
+                indices.pop();
+            }
+            !indices.is_empty()
+        });
+        self.record_provenance(side_constraints_len);
+    }
+
+    /// Records a reduction modulo the prime, unless the same statement already recorded it, since
+    /// a statement may simplify its right-hand side more than once.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The reduction to record.
+    pub fn push_field_reduction(&mut self, event: FieldReductionEvent) {
+        let is_recorded = self
+            .field_reductions
+            .iter()
+            .rev()
+            .take_while(|e| e.elem_id == event.elem_id)
+            .any(|e| *e == event);
+        if !is_recorded {
+            self.field_reductions.push(event);
+        }
+    }
+
+    /// Records a division by a non-constant divisor, unless the same statement already recorded
+    /// the same divisor, e.g., in an earlier iteration of a loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `potential_div_by_zero` - The division to record.
+    pub fn push_potential_div_by_zero(&mut self, potential_div_by_zero: PotentialDivByZero) {
+        if !self.potential_div_by_zeros.contains(&potential_div_by_zero) {
+            self.potential_div_by_zeros.push(potential_div_by_zero);
+        }
+    }
+
+    /// Removes duplicated entries from the symbolic trace and the side constraints.
+    ///
+    /// Two entries are duplicates when their canonical forms are equal. The first occurrence
+    /// of each entry is kept, so the relative order of the remaining entries is unchanged.
+    ///
+    /// # Returns
+    ///
+    /// The number of entries removed from the symbolic trace and from the side constraints.
+    pub fn dedup_constraints(&mut self) -> (usize, usize) {
+        let removed = (
+            dedup_symbolic_values(&mut self.symbolic_trace),
+            dedup_symbolic_values(&mut self.side_constraints),
+        );
+        self.constraint_provenance.clear();
+        self.record_provenance(0);
+        removed
     }
 
     /// Formats the symbolic state for lookup and display.
     ///
     /// This method creates a string representation of the symbolic state,
     /// including owner, depth, values, trace constraints, and side constraints.
+    /// The values are listed in the order of their names, so that the output is the same for
+    /// equal states.
     ///
     /// # Arguments
     ///
@@ -188,14 +500,23 @@ impl SymbolicState {
         );
         s += &format!("  📏 {} {}\n", format!("{}", "depth:").cyan(), self.depth);
         s += &format!("  📋 {}\n", format!("{}", "values:").cyan());
-        for (k, v) in self.symbol_binding_map.iter() {
-            s += &format!(
-                "      {}: {}\n",
-                k.lookup_fmt(id2name),
-                format!("{}", v.lookup_fmt(id2name))
-                    .replace("\n", "")
-                    .replace("  ", " ")
-            );
+        // The bindings are sorted by name, since the iteration order of the map depends on its
+        // history, so that two dumps of the same state can be diffed
+        let mut bindings: Vec<(String, String)> = self
+            .symbol_binding_map
+            .iter()
+            .map(|(k, v)| {
+                (
+                    k.lookup_fmt(id2name),
+                    format!("{}", v.lookup_fmt(id2name))
+                        .replace("\n", "")
+                        .replace("  ", " "),
+                )
+            })
+            .collect();
+        bindings.sort();
+        for (k, v) in bindings {
+            s += &format!("      {}: {}\n", k, v);
         }
         s += &format!(
             "  {} {}\n",
@@ -238,3 +559,18 @@ impl SymbolicState {
         s
     }
 }
+
+/// Hashes the canonical form of a constraint, so that `dedup_on_push` agrees with
+/// `dedup_constraints` on which constraints are duplicates.
+fn constraint_hash(value: &SymbolicValue) -> u64 {
+    let mut hasher = FxHasher::default();
+    canonicalize(value).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn dedup_symbolic_values(values: &mut Vec<SymbolicValueRef>) -> usize {
+    let original_len = values.len();
+    let mut seen = FxHashSet::default();
+    values.retain(|v| seen.insert(canonicalize(v)));
+    original_len - values.len()
+}
@@ -1,32 +1,49 @@
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
+use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
+use std::sync::Arc;
 
 use colored::Colorize;
+use log::warn;
 use num_bigint_dig::BigInt;
 use num_traits::FromPrimitive;
 use num_traits::ToPrimitive;
 use num_traits::{One, Signed, Zero};
 use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, DisplayFromStr};
 
-use program_structure::ast::{ExpressionInfixOpcode, SignalType, Statement, VariableType};
+use program_structure::ast::{
+    ExpressionInfixOpcode, ExpressionPrefixOpcode, Meta, SignalType, Statement, VariableType,
+};
+use program_structure::file_definition::FileLibrary;
 
 use crate::executor::debug_ast::{
     DebuggableExpression, DebuggableExpressionInfixOpcode, DebuggableExpressionPrefixOpcode,
     DebuggableStatement,
 };
-use crate::executor::utils::{extended_euclidean, generate_cartesian_product_indices, modpow};
+use crate::executor::field_ops::U64FieldOps;
+use crate::executor::utils::{generate_cartesian_product_indices, modinv, modpow};
 
 /// Represents the access type within a symbolic expression, such as component or array access.
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum SymbolicAccess {
     ComponentAccess(usize),
     ArrayAccess(SymbolicValue),
 }
 
 impl SymbolicAccess {
+    /// See `SymbolicValue::deep_clone`.
+    pub fn deep_clone(&self) -> SymbolicAccess {
+        match self {
+            SymbolicAccess::ComponentAccess(id) => SymbolicAccess::ComponentAccess(*id),
+            SymbolicAccess::ArrayAccess(val) => SymbolicAccess::ArrayAccess(val.deep_clone()),
+        }
+    }
+
     /// Provides a compact format for displaying symbolic access in expressions.
     ///
     /// # Arguments
@@ -49,13 +66,25 @@ impl SymbolicAccess {
             }
         }
     }
+
+    /// Formats the access in circom syntax, e.g., `[i + 1]` or `.out`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookup` - A hash map containing mappings of usize keys to String values.
+    pub fn to_circom_source(&self, lookup: &FxHashMap<usize, String>) -> String {
+        match &self {
+            SymbolicAccess::ComponentAccess(name) => format!(".{}", lookup[name]),
+            SymbolicAccess::ArrayAccess(val) => format!("[{}]", val.to_circom_source(lookup)),
+        }
+    }
 }
 
 /// Represents a symbolic value used in symbolic execution.
 ///
 /// This enum can represent constants, variables, or operations such as binary, unary,
 /// conditional, arrays, tuples, uniform arrays, and function calls.
-#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize, Deserialize)]
 pub struct OwnerName {
     pub id: usize,
     pub access: Option<Vec<SymbolicAccess>>,
@@ -69,15 +98,41 @@ pub enum ExecutionResult<T> {
     Failure,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SymbolicName {
     pub id: usize,
     pub owner: Rc<Vec<OwnerName>>,
     pub access: Option<Vec<SymbolicAccess>>,
+    #[serde(skip)]
     precomputed_hash: RefCell<Option<u64>>,
 }
 
+impl OwnerName {
+    /// See `SymbolicValue::deep_clone`.
+    pub fn deep_clone(&self) -> OwnerName {
+        OwnerName {
+            id: self.id,
+            access: self
+                .access
+                .as_ref()
+                .map(|a| a.iter().map(|s| s.deep_clone()).collect()),
+            counter: self.counter,
+        }
+    }
+}
+
 impl SymbolicName {
+    /// See `SymbolicValue::deep_clone`.
+    pub fn deep_clone(&self) -> SymbolicName {
+        SymbolicName::new(
+            self.id,
+            Rc::new(self.owner.iter().map(|o| o.deep_clone()).collect()),
+            self.access
+                .as_ref()
+                .map(|a| a.iter().map(|s| s.deep_clone()).collect()),
+        )
+    }
+
     pub fn new(id: usize, owner: Rc<Vec<OwnerName>>, access: Option<Vec<SymbolicAccess>>) -> Self {
         SymbolicName {
             id,
@@ -127,6 +182,32 @@ impl SymbolicName {
         )
     }
 
+    /// Formats the name as it is written inside the main template, e.g., `in[0]` or `c.out`.
+    ///
+    /// The `main` owner is left out, and nested owners are joined with `.`.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookup` - A hash map containing mappings of usize keys to String values.
+    pub fn to_circom_source(&self, lookup: &FxHashMap<usize, String>) -> String {
+        let fmt_accesses = |accesses: &Option<Vec<SymbolicAccess>>| {
+            accesses
+                .iter()
+                .flatten()
+                .map(|s| s.to_circom_source(lookup))
+                .collect::<Vec<_>>()
+                .join("")
+        };
+        let mut path = self
+            .owner
+            .iter()
+            .skip_while(|e| lookup.get(&e.id).map(String::as_str) == Some("main"))
+            .map(|e| lookup[&e.id].clone() + &fmt_accesses(&e.access))
+            .collect::<Vec<_>>();
+        path.push(lookup[&self.id].clone() + &fmt_accesses(&self.access));
+        path.join(".")
+    }
+
     fn compute_hash(&self) -> u64 {
         let mut hasher = FxHasher::default(); // Use FxHasher for consistency with FxHashMap
         self.id.hash(&mut hasher);
@@ -201,10 +282,16 @@ pub type QuadraticPoly = (SymbolicName, [SymbolicValueRef; 3]);
 ///
 /// This enum can represent constants, variables, or operations such as binary, unary,
 /// conditional, arrays, tuples, uniform arrays, and function calls.
-#[derive(Clone, Hash, Eq, PartialEq, Debug)]
+///
+/// Constants are serialized as decimal strings, since JSON numbers cannot hold field elements.
+#[serde_as]
+#[derive(Clone, Hash, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub enum SymbolicValue {
     NOP,
-    ConstantInt(BigInt),
+    /// The result of a division by an element without inverse, which only exists when the
+    /// modulus is not a prime. Any operation involving it is undefined as well.
+    Undefined,
+    ConstantInt(#[serde_as(as = "DisplayFromStr")] BigInt),
     ConstantBool(bool),
     Variable(SymbolicName),
     Assign(
@@ -246,6 +333,7 @@ impl SymbolicValue {
     pub fn lookup_fmt(&self, lookup: &FxHashMap<usize, String>) -> String {
         match self {
             SymbolicValue::NOP => "NOP".to_string(),
+            SymbolicValue::Undefined => "Undefined".to_string(),
             SymbolicValue::ConstantInt(value) => format!("{}", value),
             SymbolicValue::ConstantBool(flag) => {
                 format!(
@@ -375,6 +463,270 @@ impl SymbolicValue {
             }
         }
     }
+
+    /// Formats the symbolic value as a circom expression or statement, e.g., `a * b + 1` or
+    /// `in[0] <== out`.
+    ///
+    /// Unlike `lookup_fmt`, the output uses infix notation with the operator precedence of
+    /// circom and only the parentheses needed to preserve the structure, so that it can be
+    /// pasted back into a circuit.
+    ///
+    /// # Arguments
+    ///
+    /// * `lookup` - A hash map containing mappings of usize keys to String values.
+    ///
+    /// # Returns
+    ///
+    /// A String representation of the symbolic value in circom syntax.
+    pub fn to_circom_source(&self, lookup: &FxHashMap<usize, String>) -> String {
+        match self {
+            SymbolicValue::NOP => String::new(),
+            SymbolicValue::Undefined => "0 /* undefined */".to_string(),
+            SymbolicValue::ConstantInt(value) => format!("{}", value),
+            SymbolicValue::ConstantBool(flag) => (if *flag { "1" } else { "0" }).to_string(),
+            SymbolicValue::Variable(sym_name) => sym_name.to_circom_source(lookup),
+            SymbolicValue::Assign(lhs, rhs, ..) => {
+                format!(
+                    "{} <-- {}",
+                    lhs.to_circom_source(lookup),
+                    rhs.to_circom_source(lookup)
+                )
+            }
+            SymbolicValue::AssignEq(lhs, rhs) => {
+                format!(
+                    "{} <== {}",
+                    lhs.to_circom_source(lookup),
+                    rhs.to_circom_source(lookup)
+                )
+            }
+            SymbolicValue::AssignTemplParam(lhs, rhs) | SymbolicValue::AssignCall(lhs, rhs, _) => {
+                format!(
+                    "{} = {}",
+                    lhs.to_circom_source(lookup),
+                    rhs.to_circom_source(lookup)
+                )
+            }
+            SymbolicValue::BinaryOp(lhs, op, rhs) | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+                // Every binary operator of circom is left-associative.
+                let precedence = self.circom_precedence();
+                let lhs_str = lhs.to_circom_source(lookup);
+                let rhs_str = rhs.to_circom_source(lookup);
+                format!(
+                    "{} {} {}",
+                    if lhs.circom_precedence() < precedence {
+                        format!("({})", lhs_str)
+                    } else {
+                        lhs_str
+                    },
+                    circom_infix_operator(&op.0),
+                    if rhs.circom_precedence() <= precedence {
+                        format!("({})", rhs_str)
+                    } else {
+                        rhs_str
+                    }
+                )
+            }
+            SymbolicValue::Conditional(cond, if_branch, else_branch) => {
+                let wrap = |v: &SymbolicValue| {
+                    if let SymbolicValue::Conditional(..) = v {
+                        format!("({})", v.to_circom_source(lookup))
+                    } else {
+                        v.to_circom_source(lookup)
+                    }
+                };
+                format!(
+                    "{} ? {} : {}",
+                    wrap(cond.as_ref()),
+                    wrap(if_branch.as_ref()),
+                    wrap(else_branch.as_ref())
+                )
+            }
+            SymbolicValue::UnaryOp(op, expr) => {
+                let expr_str = expr.to_circom_source(lookup);
+                format!(
+                    "{}{}",
+                    match &op.0 {
+                        ExpressionPrefixOpcode::Sub => "-",
+                        ExpressionPrefixOpcode::BoolNot => "!",
+                        ExpressionPrefixOpcode::Complement => "~",
+                    },
+                    // `--x` would not parse as a double negation
+                    if expr.circom_precedence() <= self.circom_precedence() {
+                        format!("({})", expr_str)
+                    } else {
+                        expr_str
+                    }
+                )
+            }
+            SymbolicValue::Call(name, args) => {
+                format!(
+                    "{}({})",
+                    lookup[&name],
+                    args.iter()
+                        .map(|a| a.to_circom_source(lookup))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            SymbolicValue::Array(elems) => {
+                format!(
+                    "[{}]",
+                    elems
+                        .iter()
+                        .map(|a| a.to_circom_source(lookup))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }
+            SymbolicValue::UniformArray(elem, counts) => match counts.as_ref() {
+                SymbolicValue::ConstantInt(n) => {
+                    let elem_str = elem.to_circom_source(lookup);
+                    format!("[{}]", vec![elem_str; n.to_usize().unwrap_or(0)].join(", "))
+                }
+                // circom has no syntax for arrays of a symbolic length
+                _ => self.lookup_fmt(lookup),
+            },
+        }
+    }
+
+    /// Returns the binding strength of the value in circom expressions, where a higher value
+    /// binds tighter.
+    fn circom_precedence(&self) -> u8 {
+        match self {
+            SymbolicValue::Conditional(..) => 1,
+            SymbolicValue::BinaryOp(_, op, _) | SymbolicValue::AuxBinaryOp(_, op, _) => {
+                match &op.0 {
+                    ExpressionInfixOpcode::BoolOr => 2,
+                    ExpressionInfixOpcode::BoolAnd => 3,
+                    ExpressionInfixOpcode::Eq
+                    | ExpressionInfixOpcode::NotEq
+                    | ExpressionInfixOpcode::LesserEq
+                    | ExpressionInfixOpcode::GreaterEq
+                    | ExpressionInfixOpcode::Lesser
+                    | ExpressionInfixOpcode::Greater => 4,
+                    ExpressionInfixOpcode::BitOr => 5,
+                    ExpressionInfixOpcode::BitXor => 6,
+                    ExpressionInfixOpcode::BitAnd => 7,
+                    ExpressionInfixOpcode::ShiftL | ExpressionInfixOpcode::ShiftR => 8,
+                    ExpressionInfixOpcode::Add | ExpressionInfixOpcode::Sub => 9,
+                    ExpressionInfixOpcode::Mul
+                    | ExpressionInfixOpcode::Div
+                    | ExpressionInfixOpcode::IntDiv
+                    | ExpressionInfixOpcode::Mod => 10,
+                    ExpressionInfixOpcode::Pow => 11,
+                }
+            }
+            SymbolicValue::UnaryOp(..) => 12,
+            // Statements never appear as operands
+            SymbolicValue::Assign(..)
+            | SymbolicValue::AssignEq(..)
+            | SymbolicValue::AssignTemplParam(..)
+            | SymbolicValue::AssignCall(..) => 0,
+            _ => 13,
+        }
+    }
+
+    /// Copies the symbolic value without sharing any `Rc` with the original.
+    ///
+    /// `clone` only bumps the reference counts of the sub-terms, so a cloned value still
+    /// shares its (non thread-safe) `Rc`s with the original. A deep clone can instead be handed
+    /// over to another thread, as long as the original is not accessed from that thread.
+    pub fn deep_clone(&self) -> SymbolicValue {
+        let deep = |v: &SymbolicValueRef| Rc::new(v.deep_clone());
+        match self {
+            SymbolicValue::NOP => SymbolicValue::NOP,
+            SymbolicValue::Undefined => SymbolicValue::Undefined,
+            SymbolicValue::ConstantInt(v) => SymbolicValue::ConstantInt(v.clone()),
+            SymbolicValue::ConstantBool(b) => SymbolicValue::ConstantBool(*b),
+            SymbolicValue::Variable(name) => SymbolicValue::Variable(name.deep_clone()),
+            SymbolicValue::Assign(lhs, rhs, is_safe, polys) => SymbolicValue::Assign(
+                deep(lhs),
+                deep(rhs),
+                *is_safe,
+                polys.as_ref().map(|(num, div)| {
+                    let deep_polys = |ps: &Vec<QuadraticPoly>| {
+                        ps.iter()
+                            .map(|(name, coefs)| {
+                                (
+                                    name.deep_clone(),
+                                    [deep(&coefs[0]), deep(&coefs[1]), deep(&coefs[2])],
+                                )
+                            })
+                            .collect()
+                    };
+                    (deep_polys(num), deep_polys(div))
+                }),
+            ),
+            SymbolicValue::AssignEq(lhs, rhs) => SymbolicValue::AssignEq(deep(lhs), deep(rhs)),
+            SymbolicValue::AssignTemplParam(lhs, rhs) => {
+                SymbolicValue::AssignTemplParam(deep(lhs), deep(rhs))
+            }
+            SymbolicValue::AssignCall(lhs, rhs, is_mutable) => {
+                SymbolicValue::AssignCall(deep(lhs), deep(rhs), *is_mutable)
+            }
+            SymbolicValue::BinaryOp(lhs, op, rhs) => {
+                SymbolicValue::BinaryOp(deep(lhs), op.clone(), deep(rhs))
+            }
+            SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+                SymbolicValue::AuxBinaryOp(deep(lhs), op.clone(), deep(rhs))
+            }
+            SymbolicValue::Conditional(cond, then_val, else_val) => {
+                SymbolicValue::Conditional(deep(cond), deep(then_val), deep(else_val))
+            }
+            SymbolicValue::UnaryOp(op, expr) => SymbolicValue::UnaryOp(op.clone(), deep(expr)),
+            SymbolicValue::Array(elems) => SymbolicValue::Array(elems.iter().map(deep).collect()),
+            SymbolicValue::UniformArray(elem, counts) => {
+                SymbolicValue::UniformArray(deep(elem), deep(counts))
+            }
+            SymbolicValue::Call(id, args) => {
+                SymbolicValue::Call(*id, args.iter().map(deep).collect())
+            }
+        }
+    }
+    /// Counts the nodes of the symbolic value, each variable or constant being a single node.
+    ///
+    /// Sub-terms shared through `Rc` are counted once per occurrence, so that the count matches
+    /// the size of the expression once formatted. The counting stops as soon as it exceeds
+    /// `limit`, which bounds its cost for values with many shared sub-terms.
+    ///
+    /// # Returns
+    /// The number of nodes, or `limit + 1` if there are more than `limit` of them.
+    pub fn count_nodes(&self, limit: usize) -> usize {
+        let mut count = 0;
+        let mut stack: Vec<&SymbolicValue> = vec![self];
+        while let Some(value) = stack.pop() {
+            if count == limit {
+                return limit + 1;
+            }
+            count += 1;
+            match value {
+                SymbolicValue::Assign(lhs, rhs, _, _)
+                | SymbolicValue::AssignEq(lhs, rhs)
+                | SymbolicValue::AssignTemplParam(lhs, rhs)
+                | SymbolicValue::AssignCall(lhs, rhs, _)
+                | SymbolicValue::BinaryOp(lhs, _, rhs)
+                | SymbolicValue::AuxBinaryOp(lhs, _, rhs)
+                | SymbolicValue::UniformArray(lhs, rhs) => {
+                    stack.push(lhs);
+                    stack.push(rhs);
+                }
+                SymbolicValue::Conditional(cond, then_val, else_val) => {
+                    stack.push(cond);
+                    stack.push(then_val);
+                    stack.push(else_val);
+                }
+                SymbolicValue::UnaryOp(_, expr) => stack.push(expr),
+                SymbolicValue::Array(elems) | SymbolicValue::Call(_, elems) => {
+                    stack.extend(elems.iter().map(|elem| elem.as_ref()))
+                }
+                SymbolicValue::NOP
+                | SymbolicValue::ConstantInt(_)
+                | SymbolicValue::ConstantBool(_)
+                | SymbolicValue::Variable(_) => {}
+            }
+        }
+        count
+    }
 }
 
 pub type SymbolicValueRef = Rc<SymbolicValue>;
@@ -400,6 +752,14 @@ pub struct SymbolicFunction {
     pub body: Vec<DebuggableStatement>,
 }
 
+/// Represents a symbolic bus (a named bundle of signals) used in the symbolic execution process.
+#[derive(Default, Clone)]
+pub struct SymbolicBus {
+    pub bus_parameter_names: Vec<usize>,
+    pub field_ids: Vec<usize>,
+    pub id2dimension_expressions: FxHashMap<usize, Vec<DebuggableExpression>>,
+}
+
 /// Represents a symbolic component used in the symbolic execution process.
 #[derive(Default, Clone)]
 pub struct SymbolicComponent {
@@ -410,14 +770,291 @@ pub struct SymbolicComponent {
     pub is_done: bool,
 }
 
+/// A source file of the circuit, used to map the byte offsets of `Meta` back to lines.
+#[derive(Default, Clone)]
+pub struct SourceFile {
+    pub path: String,
+    pub source: String,
+}
+
+/// A position in a source file of the circuit, with 1-based line and column numbers.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.path, self.line, self.column)
+    }
+}
+
 /// Manages symbolic libraries, templates, and functions for symbolic execution.
+///
+/// Templates, functions and buses are immutable once registered and are shared through `Arc`,
+/// so cloning a library is cheap and only duplicates the name tables, `function_counter`, and
+/// `name_interner`.
 #[derive(Default, Clone)]
 pub struct SymbolicLibrary {
-    pub template_library: FxHashMap<usize, Box<SymbolicTemplate>>,
-    pub function_library: FxHashMap<usize, Box<SymbolicFunction>>,
+    pub template_library: FxHashMap<usize, Arc<SymbolicTemplate>>,
+    pub function_library: FxHashMap<usize, Arc<SymbolicFunction>>,
+    pub bus_library: FxHashMap<usize, Arc<SymbolicBus>>,
     pub name2id: FxHashMap<String, usize>,
     pub id2name: FxHashMap<usize, String>,
     pub function_counter: FxHashMap<usize, usize>,
+    pub source_files: FxHashMap<usize, Arc<SourceFile>>,
+    pub name_interner: SymbolicNameInterner,
+}
+
+/// Interns the variables of symbolic values, so that all the occurrences of a `SymbolicName`
+/// share a single `SymbolicValue::Variable` allocation.
+///
+/// The symbolic execution builds a fresh `SymbolicName`, with its own access vector, every time
+/// a variable is evaluated, so that a name appearing in many constraints is duplicated as many
+/// times. With `intern_names`, the executor interns each trace entry and side constraint as it
+/// is gathered, so that these duplicates do not outlive the statement that built them.
+///
+/// The interner is a cache of shared variables only: `SymbolicValue::Variable` keeps holding the
+/// name itself, and each name is stored once, in its shared variable.
+#[derive(Default, Clone, Debug)]
+pub struct SymbolicNameInterner {
+    variables: FxHashSet<InternedVariable>,
+    /// The nodes rebuilt by the ongoing `intern_values`, kept to reuse its allocation. It is
+    /// empty between two calls, so that it holds no dangling pointer.
+    rebuilt: FxHashMap<*const SymbolicValue, SymbolicValueRef>,
+}
+
+/// A shared variable, hashed and compared by its name so that it can be looked up by name.
+#[derive(Clone, Debug)]
+struct InternedVariable(SymbolicValueRef);
+
+impl InternedVariable {
+    fn name(&self) -> &SymbolicName {
+        match self.0.as_ref() {
+            SymbolicValue::Variable(name) => name,
+            _ => unreachable!("interned values are variables"),
+        }
+    }
+}
+
+impl PartialEq for InternedVariable {
+    fn eq(&self, other: &Self) -> bool {
+        self.name() == other.name()
+    }
+}
+
+impl Eq for InternedVariable {}
+
+impl Hash for InternedVariable {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name().hash(state);
+    }
+}
+
+impl std::borrow::Borrow<SymbolicName> for InternedVariable {
+    fn borrow(&self) -> &SymbolicName {
+        self.name()
+    }
+}
+
+impl SymbolicNameInterner {
+    /// Returns the shared variable of `name`, registering it if it is seen for the first time.
+    pub fn intern(&mut self, name: &SymbolicName) -> SymbolicValueRef {
+        if let Some(interned) = self.variables.get(name) {
+            return interned.0.clone();
+        }
+        let variable = Rc::new(SymbolicValue::Variable(name.clone()));
+        self.variables.insert(InternedVariable(variable.clone()));
+        variable
+    }
+
+    /// Returns the number of unique names interned so far.
+    pub fn len(&self) -> usize {
+        self.variables.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variables.is_empty()
+    }
+
+    /// Replaces every variable of `values` by its shared interned variable.
+    ///
+    /// The subexpressions shared among `values` stay shared, since each of them is rebuilt once,
+    /// and a subexpression whose variables are all interned already is kept as is.
+    pub fn intern_values(&mut self, values: &mut [SymbolicValueRef]) {
+        let mut rebuilt = std::mem::take(&mut self.rebuilt);
+        for value in values.iter_mut() {
+            *value = self.intern_value(value, &mut rebuilt);
+        }
+        rebuilt.clear();
+        self.rebuilt = rebuilt;
+    }
+
+    /// Returns `constraint` with every variable replaced by its shared interned variable.
+    pub fn intern_constraint(&mut self, constraint: &SymbolicValue) -> SymbolicValue {
+        let mut values = [Rc::new(constraint.clone())];
+        self.intern_values(&mut values);
+        let [value] = values;
+        Rc::try_unwrap(value).unwrap_or_else(|value| (*value).clone())
+    }
+
+    fn intern_value(
+        &mut self,
+        value: &SymbolicValueRef,
+        rebuilt: &mut FxHashMap<*const SymbolicValue, SymbolicValueRef>,
+    ) -> SymbolicValueRef {
+        if let Some(interned) = rebuilt.get(&Rc::as_ptr(value)) {
+            return interned.clone();
+        }
+        let interned = match value.as_ref() {
+            SymbolicValue::NOP
+            | SymbolicValue::Undefined
+            | SymbolicValue::ConstantInt(_)
+            | SymbolicValue::ConstantBool(_) => value.clone(),
+            SymbolicValue::Variable(name) => self.intern(name),
+            SymbolicValue::Assign(lhs, rhs, is_safe, polys) => {
+                let (new_lhs, new_rhs) = (
+                    self.intern_value(lhs, rebuilt),
+                    self.intern_value(rhs, rebuilt),
+                );
+                if are_shared(&[(lhs, &new_lhs), (rhs, &new_rhs)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::Assign(
+                        new_lhs,
+                        new_rhs,
+                        *is_safe,
+                        polys.clone(),
+                    ))
+                }
+            }
+            SymbolicValue::AssignEq(lhs, rhs) => {
+                let (new_lhs, new_rhs) = (
+                    self.intern_value(lhs, rebuilt),
+                    self.intern_value(rhs, rebuilt),
+                );
+                if are_shared(&[(lhs, &new_lhs), (rhs, &new_rhs)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::AssignEq(new_lhs, new_rhs))
+                }
+            }
+            SymbolicValue::AssignTemplParam(lhs, rhs) => {
+                let (new_lhs, new_rhs) = (
+                    self.intern_value(lhs, rebuilt),
+                    self.intern_value(rhs, rebuilt),
+                );
+                if are_shared(&[(lhs, &new_lhs), (rhs, &new_rhs)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::AssignTemplParam(new_lhs, new_rhs))
+                }
+            }
+            SymbolicValue::AssignCall(lhs, rhs, is_mutable) => {
+                let (new_lhs, new_rhs) = (
+                    self.intern_value(lhs, rebuilt),
+                    self.intern_value(rhs, rebuilt),
+                );
+                if are_shared(&[(lhs, &new_lhs), (rhs, &new_rhs)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::AssignCall(new_lhs, new_rhs, *is_mutable))
+                }
+            }
+            SymbolicValue::BinaryOp(lhs, op, rhs) => {
+                let (new_lhs, new_rhs) = (
+                    self.intern_value(lhs, rebuilt),
+                    self.intern_value(rhs, rebuilt),
+                );
+                if are_shared(&[(lhs, &new_lhs), (rhs, &new_rhs)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::BinaryOp(new_lhs, op.clone(), new_rhs))
+                }
+            }
+            SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+                let (new_lhs, new_rhs) = (
+                    self.intern_value(lhs, rebuilt),
+                    self.intern_value(rhs, rebuilt),
+                );
+                if are_shared(&[(lhs, &new_lhs), (rhs, &new_rhs)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::AuxBinaryOp(new_lhs, op.clone(), new_rhs))
+                }
+            }
+            SymbolicValue::Conditional(cond, then_val, else_val) => {
+                let (new_cond, new_then_val, new_else_val) = (
+                    self.intern_value(cond, rebuilt),
+                    self.intern_value(then_val, rebuilt),
+                    self.intern_value(else_val, rebuilt),
+                );
+                if are_shared(&[
+                    (cond, &new_cond),
+                    (then_val, &new_then_val),
+                    (else_val, &new_else_val),
+                ]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::Conditional(
+                        new_cond,
+                        new_then_val,
+                        new_else_val,
+                    ))
+                }
+            }
+            SymbolicValue::UnaryOp(op, expr) => {
+                let new_expr = self.intern_value(expr, rebuilt);
+                if are_shared(&[(expr, &new_expr)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::UnaryOp(op.clone(), new_expr))
+                }
+            }
+            SymbolicValue::Array(elems) => {
+                let new_elems: Vec<SymbolicValueRef> = elems
+                    .iter()
+                    .map(|elem| self.intern_value(elem, rebuilt))
+                    .collect();
+                if elems.iter().zip(&new_elems).all(|(e, n)| Rc::ptr_eq(e, n)) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::Array(new_elems))
+                }
+            }
+            SymbolicValue::UniformArray(elem, counts) => {
+                let (new_elem, new_counts) = (
+                    self.intern_value(elem, rebuilt),
+                    self.intern_value(counts, rebuilt),
+                );
+                if are_shared(&[(elem, &new_elem), (counts, &new_counts)]) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::UniformArray(new_elem, new_counts))
+                }
+            }
+            SymbolicValue::Call(id, args) => {
+                let new_args: Vec<SymbolicValueRef> = args
+                    .iter()
+                    .map(|arg| self.intern_value(arg, rebuilt))
+                    .collect();
+                if args.iter().zip(&new_args).all(|(a, n)| Rc::ptr_eq(a, n)) {
+                    value.clone()
+                } else {
+                    Rc::new(SymbolicValue::Call(*id, new_args))
+                }
+            }
+        };
+        rebuilt.insert(Rc::as_ptr(value), interned.clone());
+        interned
+    }
+}
+
+/// Returns whether each rebuilt child is the original one, so that the node can be kept as is.
+fn are_shared(children: &[(&SymbolicValueRef, &SymbolicValueRef)]) -> bool {
+    children.iter().all(|&(old, new)| Rc::ptr_eq(old, new))
 }
 
 fn gather_variables_for_template(
@@ -450,6 +1087,38 @@ fn gather_variables_for_template(
     }
 }
 
+fn find_declaration(stmt: &DebuggableStatement, id: usize) -> Option<&Meta> {
+    match stmt {
+        DebuggableStatement::Declaration { meta, id: i, .. } if *i == id => Some(meta),
+        DebuggableStatement::IfThenElse {
+            if_case, else_case, ..
+        } => find_declaration(if_case, id)
+            .or_else(|| else_case.as_ref().and_then(|e| find_declaration(e, id))),
+        DebuggableStatement::While { stmt, .. } => find_declaration(stmt, id),
+        DebuggableStatement::InitializationBlock {
+            initializations: stmts,
+            ..
+        }
+        | DebuggableStatement::Block { stmts, .. } => {
+            stmts.iter().find_map(|stmt| find_declaration(stmt, id))
+        }
+        _ => None,
+    }
+}
+
+fn gather_fields_for_bus(
+    dbody: &DebuggableStatement,
+    field_ids: &mut Vec<usize>,
+    id2dimensions: &mut FxHashMap<usize, Vec<DebuggableExpression>>,
+) {
+    if let DebuggableStatement::Declaration { id, dimensions, .. } = dbody {
+        if !field_ids.contains(id) {
+            field_ids.push(*id);
+        }
+        id2dimensions.insert(*id, dimensions.clone());
+    }
+}
+
 fn gather_variables_for_function(
     dbody: &DebuggableStatement,
     id2dimensions: &mut FxHashMap<usize, Vec<DebuggableExpression>>,
@@ -460,6 +1129,54 @@ fn gather_variables_for_function(
 }
 
 impl SymbolicLibrary {
+    /// Registers the source files of the circuit so that statements can be located with `locate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `file_library` - The file library of the parsed program.
+    pub fn register_source_files(&mut self, file_library: &FileLibrary) {
+        let files = file_library.to_storage();
+        let mut file_id = 0;
+        while let Some(file) = files.get(file_id) {
+            self.source_files.insert(
+                file_id,
+                Arc::new(SourceFile {
+                    path: file.name().clone(),
+                    source: file.source().clone(),
+                }),
+            );
+            file_id += 1;
+        }
+    }
+
+    /// Maps the start of the span of `meta` to a line and a column of its source file.
+    ///
+    /// Returns `None` if `meta` has no file or if its file was not registered with
+    /// `register_source_files`.
+    pub fn locate(&self, meta: &Meta) -> Option<SourceLocation> {
+        let file = self.source_files.get(&meta.file_id?)?;
+        let prefix = file.source.get(..meta.start)?;
+        let line_start = prefix.rfind('\n').map_or(0, |i| i + 1);
+        Some(SourceLocation {
+            path: file.path.clone(),
+            line: prefix.matches('\n').count() + 1,
+            column: prefix[line_start..].chars().count() + 1,
+        })
+    }
+
+    /// Locates the declaration of the signal or variable `id` in the template `template_id`.
+    ///
+    /// Returns `None` if the template is not registered, if it declares no such signal or
+    /// variable, or if the declaration cannot be located with `locate`.
+    pub fn locate_declaration(&self, template_id: usize, id: usize) -> Option<SourceLocation> {
+        let template = self.template_library.get(&template_id)?;
+        template
+            .body
+            .iter()
+            .find_map(|stmt| find_declaration(stmt, id))
+            .and_then(|meta| self.locate(meta))
+    }
+
     /// Clears the function counter for all registered functions.
     pub fn clear_function_counter(&mut self) {
         for (k, _) in self.function_library.iter() {
@@ -514,7 +1231,7 @@ impl SymbolicLibrary {
 
         self.template_library.insert(
             i,
-            Box::new(SymbolicTemplate {
+            Arc::new(SymbolicTemplate {
                 template_parameter_names: template_parameter_names
                     .iter()
                     .map(|p: &String| {
@@ -567,7 +1284,7 @@ impl SymbolicLibrary {
 
         self.function_library.insert(
             i,
-            Box::new(SymbolicFunction {
+            Arc::new(SymbolicFunction {
                 function_argument_names: function_argument_names
                     .iter()
                     .map(|p: &String| self.name2id[p])
@@ -578,6 +1295,55 @@ impl SymbolicLibrary {
         );
         self.function_counter.insert(i, 0_usize);
     }
+
+    /// Registers a bus in the symbolic library.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the bus to be registered.
+    /// * `body` - The bus body as a Statement, consisting of the declarations of its fields.
+    /// * `bus_parameter_names` - List of parameter names for the bus.
+    pub fn register_bus(
+        &mut self,
+        name: String,
+        body: Statement,
+        bus_parameter_names: &Vec<String>,
+    ) {
+        let mut field_ids = Vec::new();
+        let mut id2dimension_expressions = FxHashMap::default();
+        let i = if let Some(i) = self.name2id.get(&name) {
+            *i
+        } else {
+            self.name2id.insert(name.clone(), self.name2id.len());
+            self.id2name.insert(self.name2id[&name], name);
+            self.name2id.len() - 1
+        };
+
+        let mut dbody = DebuggableStatement::from(body, &mut self.name2id, &mut self.id2name);
+        dbody.apply_iterative(|stmt| {
+            gather_fields_for_bus(stmt, &mut field_ids, &mut id2dimension_expressions);
+        });
+
+        self.bus_library.insert(
+            i,
+            Arc::new(SymbolicBus {
+                bus_parameter_names: bus_parameter_names
+                    .iter()
+                    .map(|p: &String| {
+                        if let Some(i) = self.name2id.get(p) {
+                            *i
+                        } else {
+                            self.name2id.insert(p.clone(), self.name2id.len());
+                            self.id2name.insert(self.name2id[p], p.clone());
+                            self.name2id.len() - 1
+                        }
+                    })
+                    .collect::<Vec<_>>(),
+                field_ids: field_ids,
+                id2dimension_expressions: id2dimension_expressions,
+            }),
+        );
+    }
 }
 
 pub fn access_multidimensional_array(
@@ -820,6 +1586,19 @@ pub fn evaluate_binary_op(
         } //_ => (lhs.clone(), rhs.clone()), // Default case
     };
 
+    if normalized_lhs == SymbolicValue::Undefined || normalized_rhs == SymbolicValue::Undefined {
+        return SymbolicValue::Undefined;
+    }
+
+    // Primes below 2^64 (e.g., Goldilocks) are handled with native integers
+    if let (SymbolicValue::ConstantInt(lv), SymbolicValue::ConstantInt(rv)) =
+        (&normalized_lhs, &normalized_rhs)
+    {
+        if let Some(result) = U64FieldOps::new(prime).and_then(|ops| ops.evaluate(lv, rv, &op.0)) {
+            return result;
+        }
+    }
+
     match (&normalized_lhs, &normalized_rhs) {
         (SymbolicValue::ConstantInt(lv), SymbolicValue::ConstantInt(rv)) => match &op.0 {
             ExpressionInfixOpcode::Add => SymbolicValue::ConstantInt((lv + rv) % prime),
@@ -832,28 +1611,7 @@ pub fn evaluate_binary_op(
             }
             ExpressionInfixOpcode::Mul => SymbolicValue::ConstantInt((lv * rv) % prime),
             ExpressionInfixOpcode::Pow => SymbolicValue::ConstantInt(modpow(lv, rv, prime)),
-            ExpressionInfixOpcode::Div => {
-                if lv.is_zero() || rv.is_zero() {
-                    SymbolicValue::ConstantInt(BigInt::zero())
-                } else {
-                    let mut r = prime.clone();
-                    let mut new_r = rv.clone();
-                    if r.is_negative() {
-                        r += prime;
-                    }
-                    if new_r.is_negative() {
-                        new_r += prime;
-                    }
-
-                    let (_, _, mut rv_inv) = extended_euclidean(r, new_r);
-                    rv_inv %= prime;
-                    if rv_inv.is_negative() {
-                        rv_inv += prime;
-                    }
-
-                    SymbolicValue::ConstantInt((lv * rv_inv) % prime)
-                }
-            }
+            ExpressionInfixOpcode::Div => evaluate_div(lv, rv, prime),
             ExpressionInfixOpcode::IntDiv => {
                 SymbolicValue::ConstantInt(if lv.is_zero() || rv.is_zero() {
                     BigInt::zero()
@@ -868,14 +1626,12 @@ pub fn evaluate_binary_op(
                     lv % rv
                 })
             }
-            ExpressionInfixOpcode::BitOr => SymbolicValue::ConstantInt(lv | rv),
-            ExpressionInfixOpcode::BitAnd => SymbolicValue::ConstantInt(lv & rv),
-            ExpressionInfixOpcode::BitXor => SymbolicValue::ConstantInt(lv ^ rv),
-            ExpressionInfixOpcode::ShiftL => {
-                SymbolicValue::ConstantInt(lv << rv.to_usize().unwrap())
-            }
-            ExpressionInfixOpcode::ShiftR => {
-                SymbolicValue::ConstantInt(lv >> rv.to_usize().unwrap())
+            ExpressionInfixOpcode::BitOr
+            | ExpressionInfixOpcode::BitAnd
+            | ExpressionInfixOpcode::BitXor
+            | ExpressionInfixOpcode::ShiftL
+            | ExpressionInfixOpcode::ShiftR => {
+                SymbolicValue::ConstantInt(evaluate_bitwise_op(lv, rv, prime, &op.0))
             }
             ExpressionInfixOpcode::Lesser => SymbolicValue::ConstantBool(
                 val_for_relational_operators(&(lv % prime), prime)
@@ -940,6 +1696,10 @@ pub fn evaluate_binary_op_integer_mode(
         } //_ => (lhs.clone(), rhs.clone()), // Default case
     };
 
+    if normalized_lhs == SymbolicValue::Undefined || normalized_rhs == SymbolicValue::Undefined {
+        return SymbolicValue::Undefined;
+    }
+
     match (&normalized_lhs, &normalized_rhs) {
         (SymbolicValue::ConstantInt(lv), SymbolicValue::ConstantInt(rv)) => match &op.0 {
             ExpressionInfixOpcode::Add => SymbolicValue::ConstantInt((lv + rv) % prime),
@@ -952,28 +1712,7 @@ pub fn evaluate_binary_op_integer_mode(
             }
             ExpressionInfixOpcode::Mul => SymbolicValue::ConstantInt((lv * rv) % prime),
             ExpressionInfixOpcode::Pow => SymbolicValue::ConstantInt(modpow(lv, rv, prime)),
-            ExpressionInfixOpcode::Div => {
-                if lv.is_zero() || rv.is_zero() {
-                    SymbolicValue::ConstantInt(BigInt::zero())
-                } else {
-                    let mut r = prime.clone();
-                    let mut new_r = rv.clone();
-                    if r.is_negative() {
-                        r += prime;
-                    }
-                    if new_r.is_negative() {
-                        new_r += prime;
-                    }
-
-                    let (_, _, mut rv_inv) = extended_euclidean(r, new_r);
-                    rv_inv %= prime;
-                    if rv_inv.is_negative() {
-                        rv_inv += prime;
-                    }
-
-                    SymbolicValue::ConstantInt((lv * rv_inv) % prime)
-                }
-            }
+            ExpressionInfixOpcode::Div => evaluate_div(lv, rv, prime),
             ExpressionInfixOpcode::IntDiv => {
                 SymbolicValue::ConstantInt(if lv.is_zero() || rv.is_zero() {
                     BigInt::zero()
@@ -988,14 +1727,12 @@ pub fn evaluate_binary_op_integer_mode(
                     lv % rv
                 })
             }
-            ExpressionInfixOpcode::BitOr => SymbolicValue::ConstantInt(lv | rv),
-            ExpressionInfixOpcode::BitAnd => SymbolicValue::ConstantInt(lv & rv),
-            ExpressionInfixOpcode::BitXor => SymbolicValue::ConstantInt(lv ^ rv),
-            ExpressionInfixOpcode::ShiftL => {
-                SymbolicValue::ConstantInt(lv << rv.to_usize().unwrap())
-            }
-            ExpressionInfixOpcode::ShiftR => {
-                SymbolicValue::ConstantInt(lv >> rv.to_usize().unwrap())
+            ExpressionInfixOpcode::BitOr
+            | ExpressionInfixOpcode::BitAnd
+            | ExpressionInfixOpcode::BitXor
+            | ExpressionInfixOpcode::ShiftL
+            | ExpressionInfixOpcode::ShiftR => {
+                SymbolicValue::ConstantInt(evaluate_bitwise_op(lv, rv, prime, &op.0))
             }
             ExpressionInfixOpcode::Lesser => SymbolicValue::ConstantBool(lv % prime < rv % prime),
             ExpressionInfixOpcode::Greater => SymbolicValue::ConstantBool(lv % prime > rv % prime),
@@ -1018,6 +1755,200 @@ pub fn evaluate_binary_op_integer_mode(
     }
 }
 
+/// Divides `lv` by `rv` in the field. Following circom, the result is zero when either operand
+/// is zero.
+///
+/// When `prime` is not a prime, e.g., a composite modulus given for experimentation, `rv` may
+/// share a factor with it and have no inverse, in which case the division is logged and
+/// `SymbolicValue::Undefined` is returned instead of a meaningless value.
+fn evaluate_div(lv: &BigInt, rv: &BigInt, prime: &BigInt) -> SymbolicValue {
+    if lv.is_zero() || rv.is_zero() {
+        return SymbolicValue::ConstantInt(BigInt::zero());
+    }
+    match modinv(rv, prime) {
+        Some(rv_inv) => SymbolicValue::ConstantInt((lv * rv_inv) % prime),
+        None => {
+            warn!(
+                "{} has no inverse modulo {}, so its division is undefined",
+                rv, prime
+            );
+            SymbolicValue::Undefined
+        }
+    }
+}
+
+/// Evaluates a bitwise or shift operator on two constants as circom does.
+///
+/// The operands are taken as field elements in `[0, prime)` and the result is reduced modulo
+/// `prime`. A shift by `k` with `k > prime / 2` shifts by `prime - k` in the opposite direction,
+/// a left shift multiplies by `2^k` in the field, and a right shift by at least the bit width of
+/// `prime` yields zero, so that large shift amounts never build huge intermediate integers.
+fn evaluate_bitwise_op(
+    lv: &BigInt,
+    rv: &BigInt,
+    prime: &BigInt,
+    op: &ExpressionInfixOpcode,
+) -> BigInt {
+    let to_field = |v: &BigInt| {
+        let mut r = v % prime;
+        if r.is_negative() {
+            r += prime;
+        }
+        r
+    };
+    let (lv, rv) = (to_field(lv), to_field(rv));
+    let result = match op {
+        ExpressionInfixOpcode::BitOr => &lv | &rv,
+        ExpressionInfixOpcode::BitAnd => &lv & &rv,
+        ExpressionInfixOpcode::BitXor => &lv ^ &rv,
+        _ => {
+            let is_reversed = rv > (prime >> 1);
+            let k = if is_reversed { prime - &rv } else { rv };
+            if matches!(op, ExpressionInfixOpcode::ShiftL) != is_reversed {
+                lv * modpow(&BigInt::from(2), &k, prime)
+            } else {
+                match k.to_usize() {
+                    Some(k) if k < prime.bits() => lv >> k,
+                    _ => BigInt::zero(),
+                }
+            }
+        }
+    };
+    result % prime
+}
+
+/// Removes algebraic identities from a symbolic value in a single bottom-up pass.
+///
+/// The rewrites applied are `0 + x`, `x + 0`, `x - 0`, `1 * x`, `x * 1`, `x / 1` and
+/// `x ** 1` to `x`, and `0 * x`, `x * 0` to `0`. Constant arithmetic is left to
+/// `evaluate_binary_op`, so a value such as `((x * 0) + 2) * 3` becomes `2 * 3` and needs
+/// another simplification round to reach `6`.
+///
+/// # Parameters
+/// - `value`: The symbolic value to rewrite.
+/// - `prime`: The prime modulus used to recognize zero and one.
+///
+/// # Returns
+/// The rewritten `SymbolicValue`.
+pub fn fold_constants(value: &SymbolicValue, prime: &BigInt) -> SymbolicValue {
+    let is_const = |v: &SymbolicValue, c: u32| match v {
+        SymbolicValue::ConstantInt(num) => {
+            let mut r = num % prime;
+            if r.is_negative() {
+                r += prime;
+            }
+            r == BigInt::from(c)
+        }
+        _ => false,
+    };
+
+    match value {
+        SymbolicValue::BinaryOp(lv, op, rv) => {
+            let lhs = fold_constants(lv, prime);
+            let rhs = fold_constants(rv, prime);
+            match &op.0 {
+                ExpressionInfixOpcode::Add if is_const(&lhs, 0) => rhs,
+                ExpressionInfixOpcode::Add | ExpressionInfixOpcode::Sub if is_const(&rhs, 0) => lhs,
+                ExpressionInfixOpcode::Mul if is_const(&lhs, 0) || is_const(&rhs, 0) => {
+                    SymbolicValue::ConstantInt(BigInt::zero())
+                }
+                ExpressionInfixOpcode::Mul if is_const(&lhs, 1) => rhs,
+                ExpressionInfixOpcode::Mul
+                | ExpressionInfixOpcode::Div
+                | ExpressionInfixOpcode::Pow
+                    if is_const(&rhs, 1) =>
+                {
+                    lhs
+                }
+                _ => SymbolicValue::BinaryOp(Rc::new(lhs), op.clone(), Rc::new(rhs)),
+            }
+        }
+        SymbolicValue::UnaryOp(op, v) => {
+            SymbolicValue::UnaryOp(op.clone(), Rc::new(fold_constants(v, prime)))
+        }
+        SymbolicValue::Conditional(cond, then_val, else_val) => SymbolicValue::Conditional(
+            Rc::new(fold_constants(cond, prime)),
+            Rc::new(fold_constants(then_val, prime)),
+            Rc::new(fold_constants(else_val, prime)),
+        ),
+        SymbolicValue::Array(elements) => SymbolicValue::Array(
+            elements
+                .iter()
+                .map(|e| Rc::new(fold_constants(e, prime)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// Rewrites a symbolic value into a canonical form for structural comparison.
+///
+/// The operands of commutative operators (`+`, `*`, `==`, `!=`, `&&`, `||`, `&`, `|`, `^`)
+/// are ordered by their hash, so that `a * b` and `b * a` canonicalize to the same value.
+/// The result is only meant to be used as a key; it does not preserve the original operand
+/// order.
+///
+/// # Parameters
+/// - `value`: The symbolic value to canonicalize.
+///
+/// # Returns
+/// The canonical `SymbolicValue`.
+pub fn canonicalize(value: &SymbolicValue) -> SymbolicValue {
+    match value {
+        SymbolicValue::BinaryOp(lhs, op, rhs) | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+            let mut lhs = canonicalize(lhs);
+            let mut rhs = canonicalize(rhs);
+            let is_commutative = matches!(
+                op.0,
+                ExpressionInfixOpcode::Add
+                    | ExpressionInfixOpcode::Mul
+                    | ExpressionInfixOpcode::Eq
+                    | ExpressionInfixOpcode::NotEq
+                    | ExpressionInfixOpcode::BoolAnd
+                    | ExpressionInfixOpcode::BoolOr
+                    | ExpressionInfixOpcode::BitAnd
+                    | ExpressionInfixOpcode::BitOr
+                    | ExpressionInfixOpcode::BitXor
+            );
+            if is_commutative && hash_of(&lhs) > hash_of(&rhs) {
+                std::mem::swap(&mut lhs, &mut rhs);
+            }
+            if let SymbolicValue::AuxBinaryOp(..) = value {
+                SymbolicValue::AuxBinaryOp(Rc::new(lhs), op.clone(), Rc::new(rhs))
+            } else {
+                SymbolicValue::BinaryOp(Rc::new(lhs), op.clone(), Rc::new(rhs))
+            }
+        }
+        SymbolicValue::UnaryOp(op, expr) => {
+            SymbolicValue::UnaryOp(op.clone(), Rc::new(canonicalize(expr)))
+        }
+        SymbolicValue::Conditional(cond, then_val, else_val) => SymbolicValue::Conditional(
+            Rc::new(canonicalize(cond)),
+            Rc::new(canonicalize(then_val)),
+            Rc::new(canonicalize(else_val)),
+        ),
+        SymbolicValue::Assign(lhs, rhs, is_safe, zero_div_info) => SymbolicValue::Assign(
+            lhs.clone(),
+            Rc::new(canonicalize(rhs)),
+            *is_safe,
+            zero_div_info.clone(),
+        ),
+        SymbolicValue::AssignEq(lhs, rhs) => {
+            SymbolicValue::AssignEq(lhs.clone(), Rc::new(canonicalize(rhs)))
+        }
+        SymbolicValue::Array(elements) => {
+            SymbolicValue::Array(elements.iter().map(|e| Rc::new(canonicalize(e))).collect())
+        }
+        _ => value.clone(),
+    }
+}
+
+fn hash_of(value: &SymbolicValue) -> u64 {
+    let mut hasher = FxHasher::default();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
 pub fn normalize_to_int(val: &SymbolicValue, prime: &BigInt) -> SymbolicValue {
     match val {
         SymbolicValue::ConstantBool(b) => {
@@ -1097,6 +2028,76 @@ pub fn generate_lessthan_constraint(
     )
 }
 
+/// Generates the exact semantics of `Num2Bits(bits)`, whose input is bound to `input`.
+///
+/// Each output `out[i]` is assigned `(input >> i) & 1` and constrained with
+/// `out[i] * (out[i] - 1) === 0`, and the outputs are constrained to reconstruct the input with
+/// `out[0] * 2^0 + ... + out[bits - 1] * 2^(bits - 1) === input`, as in circomlib.
+///
+/// # Returns
+/// The assignments of the outputs, which are protected from the mutations, and the constraints.
+pub fn generate_num2bits_model(
+    name2id: &FxHashMap<String, usize>,
+    owner_name: Rc<Vec<OwnerName>>,
+    input: &SymbolicValueRef,
+    bits: usize,
+) -> (Vec<SymbolicValue>, Vec<SymbolicValue>) {
+    let mut assignments = Vec::new();
+    let mut constraints = Vec::new();
+    let mut lc = Rc::new(SymbolicValue::ConstantInt(BigInt::zero()));
+    for i in 0..bits {
+        let out_i = Rc::new(SymbolicValue::Variable(SymbolicName::new(
+            name2id["out"],
+            owner_name.clone(),
+            Some(vec![SymbolicAccess::ArrayAccess(
+                SymbolicValue::ConstantInt(BigInt::from(i)),
+            )]),
+        )));
+        assignments.push(SymbolicValue::Assign(
+            out_i.clone(),
+            Rc::new(SymbolicValue::BinaryOp(
+                Rc::new(SymbolicValue::BinaryOp(
+                    input.clone(),
+                    DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::ShiftR),
+                    Rc::new(SymbolicValue::ConstantInt(BigInt::from(i))),
+                )),
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::BitAnd),
+                Rc::new(SymbolicValue::ConstantInt(BigInt::one())),
+            )),
+            true,
+            None,
+        ));
+        constraints.push(SymbolicValue::BinaryOp(
+            Rc::new(SymbolicValue::BinaryOp(
+                out_i.clone(),
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Mul),
+                Rc::new(SymbolicValue::BinaryOp(
+                    out_i.clone(),
+                    DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Sub),
+                    Rc::new(SymbolicValue::ConstantInt(BigInt::one())),
+                )),
+            )),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+            Rc::new(SymbolicValue::ConstantInt(BigInt::zero())),
+        ));
+        lc = Rc::new(SymbolicValue::BinaryOp(
+            lc,
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Add),
+            Rc::new(SymbolicValue::BinaryOp(
+                out_i,
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Mul),
+                Rc::new(SymbolicValue::ConstantInt(BigInt::one() << i)),
+            )),
+        ));
+    }
+    constraints.push(SymbolicValue::BinaryOp(
+        lc,
+        DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+        input.clone(),
+    ));
+    (assignments, constraints)
+}
+
 fn check_array_concrete(array: &Vec<SymbolicValueRef>) -> bool {
     for value_ref in array {
         match &**value_ref {
@@ -1235,6 +2236,86 @@ pub fn extract_variables_from_symbolic_value(
     }
 }
 
+/// Collects the divisors of `/`, `\`, and `%` in `value` that are not constants, i.e., the
+/// divisions that may divide by zero for some assignments. A constant zero divisor, which is left
+/// unevaluated when the dividend is not a constant, is collected as well.
+///
+/// # Parameters
+/// - `value`: The symbolic value to inspect.
+/// - `divisors`: A vector to which the non-constant and zero divisors are appended.
+pub fn extract_non_constant_divisors(value: &SymbolicValue, divisors: &mut Vec<SymbolicValue>) {
+    match value {
+        SymbolicValue::Assign(lhs, rhs, _, _)
+        | SymbolicValue::AssignEq(lhs, rhs)
+        | SymbolicValue::AssignTemplParam(lhs, rhs)
+        | SymbolicValue::AssignCall(lhs, rhs, _) => {
+            extract_non_constant_divisors(&lhs, divisors);
+            extract_non_constant_divisors(&rhs, divisors);
+        }
+        SymbolicValue::BinaryOp(lhs, op, rhs) | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+            let is_division = matches!(
+                op.0,
+                ExpressionInfixOpcode::Div
+                    | ExpressionInfixOpcode::IntDiv
+                    | ExpressionInfixOpcode::Mod
+            );
+            let is_constant_non_zero =
+                matches!(rhs.as_ref(), SymbolicValue::ConstantInt(v) if !v.is_zero());
+            if is_division && !is_constant_non_zero {
+                divisors.push((**rhs).clone());
+            }
+            extract_non_constant_divisors(&lhs, divisors);
+            extract_non_constant_divisors(&rhs, divisors);
+        }
+        SymbolicValue::UnaryOp(_, expr) => extract_non_constant_divisors(&expr, divisors),
+        SymbolicValue::Array(elements) => {
+            for elem in elements {
+                extract_non_constant_divisors(&elem, divisors);
+            }
+        }
+        SymbolicValue::UniformArray(value, size) => {
+            extract_non_constant_divisors(&value, divisors);
+            extract_non_constant_divisors(&size, divisors);
+        }
+        SymbolicValue::Call(_, args) => {
+            for arg in args {
+                extract_non_constant_divisors(&arg, divisors);
+            }
+        }
+        SymbolicValue::Conditional(cond, then_val, else_val) => {
+            extract_non_constant_divisors(&cond, divisors);
+            extract_non_constant_divisors(&then_val, divisors);
+            extract_non_constant_divisors(&else_val, divisors);
+        }
+        _ => {}
+    }
+}
+
+fn circom_infix_operator(op: &ExpressionInfixOpcode) -> &'static str {
+    match op {
+        ExpressionInfixOpcode::Mul => "*",
+        ExpressionInfixOpcode::Div => "/",
+        ExpressionInfixOpcode::Add => "+",
+        ExpressionInfixOpcode::Sub => "-",
+        ExpressionInfixOpcode::Pow => "**",
+        ExpressionInfixOpcode::IntDiv => "\\",
+        ExpressionInfixOpcode::Mod => "%",
+        ExpressionInfixOpcode::ShiftL => "<<",
+        ExpressionInfixOpcode::ShiftR => ">>",
+        ExpressionInfixOpcode::LesserEq => "<=",
+        ExpressionInfixOpcode::GreaterEq => ">=",
+        ExpressionInfixOpcode::Lesser => "<",
+        ExpressionInfixOpcode::Greater => ">",
+        ExpressionInfixOpcode::Eq => "==",
+        ExpressionInfixOpcode::NotEq => "!=",
+        ExpressionInfixOpcode::BoolOr => "||",
+        ExpressionInfixOpcode::BoolAnd => "&&",
+        ExpressionInfixOpcode::BitOr => "|",
+        ExpressionInfixOpcode::BitAnd => "&",
+        ExpressionInfixOpcode::BitXor => "^",
+    }
+}
+
 pub fn get_coefficient_of_polynomials(
     expr: &SymbolicValue,
     target_name: &SymbolicName,
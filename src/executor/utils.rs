@@ -1,3 +1,4 @@
+use colored::Colorize;
 use num_bigint_dig::BigInt;
 use num_traits::{One, Signed, Zero};
 use std::ops::{Div, Rem, Sub};
@@ -29,9 +30,24 @@ where
     (r0, s0, t0)
 }
 
+/// Computes `base^exp mod modulus` by square-and-multiply, so that the intermediates stay below
+/// `modulus^2` whatever the size of `exp`.
+///
+/// A zero exponent yields one, and a negative exponent raises the modular inverse of `base` to
+/// `-exp`, following `moddiv` for a zero base. The result lies in `[0, modulus)`.
+///
+/// Note that `evaluate_binary_op` normalizes a negative exponent into the field before calling
+/// this function, as circom does.
 pub fn modpow(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> BigInt {
-    let mut result = BigInt::from(1);
+    if exp.is_negative() {
+        return modpow(&moddiv(&BigInt::one(), base, modulus), &-exp, modulus);
+    }
+
+    let mut result = BigInt::one() % modulus;
     let mut base = base % modulus; // Reduce base mod modulus initially
+    if base.is_negative() {
+        base += modulus;
+    }
     let mut exp = exp.clone();
 
     while exp > BigInt::from(0) {
@@ -46,31 +62,54 @@ pub fn modpow(base: &BigInt, exp: &BigInt, modulus: &BigInt) -> BigInt {
     result
 }
 
+/// Divides `lv` by `rv` modulo `modulus`. Following circom, the result is zero when either
+/// operand is zero, and also when `rv` has no inverse, which only happens for a non-prime
+/// modulus.
 pub fn moddiv(lv: &BigInt, rv: &BigInt, modulus: &BigInt) -> BigInt {
     if lv.is_zero() || rv.is_zero() {
         return BigInt::zero();
     }
 
-    let mut r = modulus.clone();
-    let mut new_r = rv.clone();
-    if r.is_negative() {
-        r += modulus;
-    }
-    if new_r.is_negative() {
-        new_r += modulus;
+    match modinv(rv, modulus) {
+        Some(rv_inv) => {
+            let mut result = (lv * rv_inv) % modulus;
+            if result.is_negative() {
+                result += modulus;
+            }
+            result
+        }
+        None => BigInt::zero(),
     }
+}
 
-    let (_, _, mut rv_inv) = extended_euclidean(r, new_r);
-    rv_inv %= modulus;
-    if rv_inv.is_negative() {
-        rv_inv += modulus;
+/// Returns the inverse of `value` modulo `modulus`, in `[0, modulus)`, or `None` when it does
+/// not exist, i.e., when `value` shares a factor with `modulus`.
+///
+/// The inverse always exists for a non-zero element of a prime field, so `None` is only
+/// returned for zero or for a composite modulus.
+/// # Examples
+/// ```
+/// use num_bigint_dig::BigInt;
+/// use zkfuzz::executor::utils::modinv;
+///
+/// assert_eq!(modinv(&BigInt::from(3), &BigInt::from(7)), Some(BigInt::from(5)));
+/// assert_eq!(modinv(&BigInt::from(4), &BigInt::from(12)), None);
+/// ```
+pub fn modinv(value: &BigInt, modulus: &BigInt) -> Option<BigInt> {
+    let mut value = value % modulus;
+    if value.is_negative() {
+        value += modulus;
     }
 
-    let mut result = (lv * rv_inv) % modulus;
-    if result.is_negative() {
-        result += modulus;
+    let (gcd, _, mut inv) = extended_euclidean(modulus.clone(), value);
+    if !gcd.is_one() {
+        return None;
     }
-    result
+    inv %= modulus;
+    if inv.is_negative() {
+        inv += modulus;
+    }
+    Some(inv)
 }
 
 /// Returns Some(x) such that x² ≡ n (mod p), or None if no solution exists.
@@ -249,5 +288,5 @@ pub fn generate_cartesian_product_indices(dims: &[usize]) -> Vec<Vec<usize>> {
 }
 
 pub fn italic(text: &str) -> String {
-    format!("\x1b[3m{}\x1b[0m", text)
+    text.italic().to_string()
 }
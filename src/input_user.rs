@@ -1,4 +1,15 @@
+use std::ffi::OsString;
+use std::fs;
 use std::path::PathBuf;
+use std::str::FromStr;
+
+use colored::Colorize;
+use num_bigint_dig::BigInt;
+use num_traits::One;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::error::ZkFuzzError;
+use crate::executor::symbolic_setting::{ParamBound, WhitelistMode};
 
 pub struct Input {
     pub input_program: PathBuf,
@@ -40,14 +51,56 @@ pub struct Input {
     pub flag_printout_stats_csv: bool,
     pub flag_symbolic_template_params: bool,
     pub flag_save_output: bool,
+    pub flag_simplify_to_fixpoint: bool,
+    pub flag_dedup_constraints: bool,
+    pub flag_track_field_reductions: bool,
+    pub flag_analyze_all_templates: bool,
+    pub flag_no_color: bool,
+    pub flag_progress_json: bool,
+    pub flag_per_template_stats: bool,
+    pub flag_quiet: bool,
+    pub flag_dump_default_mutation_config: bool,
+    pub flag_list_signals: bool,
+    pub flag_boundary_values: bool,
+    pub flag_find_all: bool,
+    pub flag_check_functions: bool,
+    pub flag_mutate_template_params: bool,
+    pub flag_precise_primitives: bool,
+    pub flag_warn_as_error: bool,
     pub show_stats_of_ast: bool,
     pub lessthan_dissabled_flag: bool,
     pub prime: String,
     pub debug_prime: String,
+    pub explicit_debug_prime: bool,
     pub heuristics_range: String,
     pub search_mode: String,
     pub path_to_mutation_setting: String,
+    pub mutation_config_json: String,
     pub path_to_whitelist: String,
+    pub sample_safe_inputs: usize,
+    pub synthesize_main: String,
+    pub limit_depth: Option<usize>,
+    pub random_seed: Option<u64>,
+    pub max_findings: Option<usize>,
+    pub threads: Option<usize>,
+    pub output_format: String,
+    pub output_encoding: String,
+    pub input_constraints: String,
+    pub exec_timeout_secs: Option<u64>,
+    pub stats_format: String,
+    pub resume_population: String,
+    pub save_population: String,
+    pub emit_constraint_graph: String,
+    pub dump_symbolic_trace: String,
+    pub diff_against: String,
+    pub evaluate_input: String,
+    pub emit_repro: String,
+    pub report: String,
+    pub templates_filter: String,
+    pub whitelist_mode: String,
+    pub param_bounds: Vec<String>,
+    pub signal_range: Vec<String>,
+    pub assume: Vec<String>,
 }
 
 /*
@@ -62,18 +115,45 @@ const JSON: &'static str = "json";
 */
 
 impl Input {
+    /// Reads the options from the command line of the process.
+    ///
+    /// See `from_args` for the environment variables read as a fallback.
     pub fn new() -> Result<Input, ()> {
-        use ansi_term::Colour;
+        Input::from_args(std::env::args_os())
+    }
+
+    /// Reads the options from `args`, whose first element is the name of the program.
+    ///
+    /// The value of each option is taken, in order of precedence, from:
+    /// 1. the flag given in `args`,
+    /// 2. the environment variable of the option, e.g., `ZKFUZZ_SEARCH_MODE` for
+    ///    `--search_mode` (see `ENV_OPTIONS` for the supported options), which is convenient for
+    ///    containerized runs,
+    /// 3. the default value of the option.
+    pub fn from_args<I, T>(args: I) -> Result<Input, ()>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
         //use input_processing::SimplificationStyle;
-        let matches = input_processing::view();
-        let input = input_processing::get_input(&matches)?;
+        let matches = input_processing::view(args);
+        // The messages below are printed before `main` configures the colors of the output
+        if input_processing::get_no_color(&matches) {
+            colored::control::set_override(false);
+        }
+        // Dumping the default mutation setting does not read the circuit
+        let input = if input_processing::get_dump_default_mutation_config(&matches) {
+            PathBuf::from(matches.value_of("input").unwrap())
+        } else {
+            input_processing::get_input(&matches)?
+        };
         let file_name = input.file_stem().unwrap().to_str().unwrap().to_string();
         //let output_path = input_processing::get_output_path(&matches)?;
 
         let c_flag = input_processing::get_c(&matches);
 
         if c_flag && (file_name == "main" || file_name == "fr" || file_name == "calcwit"){
-            println!("{}", Colour::Yellow.paint(format!("The name {} is reserved in Circom when using de --c flag. The files generated for your circuit will use the name {}_c instead of {}.", file_name, file_name, file_name)));
+            println!("{}", format!("The name {} is reserved in Circom when using de --c flag. The files generated for your circuit will use the name {}_c instead of {}.", file_name, file_name, file_name).yellow());
             //file_name = format!("{}_c", file_name)
         };
         //let output_c_path = Input::build_folder(&output_path, &file_name, CPP);
@@ -127,14 +207,56 @@ impl Input {
             flag_printout_stats_csv: input_processing::get_stats_csv(&matches),
             flag_symbolic_template_params: input_processing::get_symbolic_template_params(&matches),
             flag_save_output: input_processing::get_save_output(&matches),
+            flag_simplify_to_fixpoint: input_processing::get_simplify_to_fixpoint(&matches),
+            flag_dedup_constraints: input_processing::get_dedup_constraints(&matches),
+            flag_track_field_reductions: input_processing::get_track_field_reductions(&matches),
+            flag_analyze_all_templates: input_processing::get_analyze_all_templates(&matches),
+            flag_no_color: input_processing::get_no_color(&matches),
+            flag_progress_json: input_processing::get_progress_json(&matches),
+            flag_per_template_stats: input_processing::get_per_template_stats(&matches),
+            flag_quiet: input_processing::get_quiet(&matches),
+            flag_dump_default_mutation_config: input_processing::get_dump_default_mutation_config(&matches),
+            flag_list_signals: input_processing::get_list_signals(&matches),
+            flag_boundary_values: input_processing::get_boundary_values(&matches),
+            flag_find_all: input_processing::get_find_all(&matches),
+            flag_check_functions: input_processing::get_check_functions(&matches),
+            flag_mutate_template_params: input_processing::get_mutate_template_params(&matches),
+            flag_precise_primitives: input_processing::get_precise_primitives(&matches),
+            flag_warn_as_error: input_processing::get_warn_as_error(&matches),
             show_stats_of_ast: input_processing::get_show_stats_of_ast(&matches),
             lessthan_dissabled_flag: input_processing::get_lessthan_dissabled_flag(&matches),
             prime: input_processing::get_prime(&matches)?,
             debug_prime: input_processing::get_debug_prime(&matches)?,
+            explicit_debug_prime: input_processing::get_explicit_debug_prime(&matches),
             heuristics_range: input_processing::get_heuristics_range(&matches)?,
             search_mode: input_processing::get_search_mode(&matches)?,
             path_to_mutation_setting: input_processing::get_path_to_mutation_setting(&matches)?,
+            mutation_config_json: input_processing::get_mutation_config_json(&matches)?,
             path_to_whitelist: input_processing::get_path_to_whitelist(&matches)?,
+            sample_safe_inputs: input_processing::get_sample_safe_inputs(&matches)?,
+            synthesize_main: input_processing::get_synthesize_main(&matches)?,
+            limit_depth: input_processing::get_limit_depth(&matches)?,
+            random_seed: input_processing::get_random_seed(&matches)?,
+            max_findings: input_processing::get_max_findings(&matches)?,
+            threads: input_processing::get_threads(&matches)?,
+            output_format: input_processing::get_output_format(&matches)?,
+            output_encoding: input_processing::get_output_encoding(&matches)?,
+            input_constraints: input_processing::get_input_constraints(&matches)?,
+            exec_timeout_secs: input_processing::get_exec_timeout_secs(&matches)?,
+            stats_format: input_processing::get_stats_format(&matches)?,
+            resume_population: input_processing::get_resume_population(&matches)?,
+            save_population: input_processing::get_save_population(&matches)?,
+            emit_constraint_graph: input_processing::get_emit_constraint_graph(&matches)?,
+            dump_symbolic_trace: input_processing::get_dump_symbolic_trace(&matches)?,
+            diff_against: input_processing::get_diff_against(&matches)?,
+            evaluate_input: input_processing::get_evaluate_input(&matches)?,
+            emit_repro: input_processing::get_emit_repro(&matches)?,
+            report: input_processing::get_report(&matches)?,
+            templates_filter: input_processing::get_templates_filter(&matches)?,
+            whitelist_mode: input_processing::get_whitelist_mode(&matches)?,
+            param_bounds: input_processing::get_param_bounds(&matches),
+            signal_range: input_processing::get_signal_range(&matches),
+            assume: input_processing::get_assume(&matches),
             link_libraries
         })
     }
@@ -265,15 +387,224 @@ impl Input {
     pub fn path_to_mutation_setting(&self) -> String{
         self.path_to_mutation_setting.clone()
     }
+    pub fn mutation_config_json(&self) -> String{
+        self.mutation_config_json.clone()
+    }
     pub fn path_to_whitelist(&self) -> String{
         self.path_to_whitelist.clone()
     }
+    pub fn sample_safe_inputs(&self) -> usize{
+        self.sample_safe_inputs
+    }
+    pub fn synthesize_main(&self) -> String{
+        self.synthesize_main.clone()
+    }
+    pub fn limit_depth(&self) -> Option<usize>{
+        self.limit_depth
+    }
+    pub fn random_seed(&self) -> Option<u64>{
+        self.random_seed
+    }
+    pub fn max_findings(&self) -> Option<usize>{
+        self.max_findings
+    }
+    pub fn threads(&self) -> Option<usize>{
+        self.threads
+    }
+    pub fn output_format(&self) -> String{
+        self.output_format.clone()
+    }
+    pub fn output_encoding(&self) -> String{
+        self.output_encoding.clone()
+    }
+    pub fn input_constraints(&self) -> String{
+        self.input_constraints.clone()
+    }
+    pub fn exec_timeout_secs(&self) -> Option<u64>{
+        self.exec_timeout_secs
+    }
+    pub fn stats_format(&self) -> String{
+        self.stats_format.clone()
+    }
+    pub fn resume_population(&self) -> String{
+        self.resume_population.clone()
+    }
+    pub fn save_population(&self) -> String{
+        self.save_population.clone()
+    }
+    pub fn emit_constraint_graph(&self) -> String{
+        self.emit_constraint_graph.clone()
+    }
+    pub fn dump_symbolic_trace(&self) -> String{
+        self.dump_symbolic_trace.clone()
+    }
+    pub fn diff_against(&self) -> String{
+        self.diff_against.clone()
+    }
+    pub fn evaluate_input(&self) -> String{
+        self.evaluate_input.clone()
+    }
+    pub fn emit_repro(&self) -> String{
+        self.emit_repro.clone()
+    }
+    pub fn report(&self) -> String{
+        self.report.clone()
+    }
+    pub fn templates_filter(&self) -> String{
+        self.templates_filter.clone()
+    }
+    pub fn whitelist_mode(&self) -> String{
+        self.whitelist_mode.clone()
+    }
+    pub fn param_bounds(&self) -> &Vec<String> {
+        &self.param_bounds
+    }
+    pub fn signal_range(&self) -> &Vec<String> {
+        &self.signal_range
+    }
+    pub fn assume(&self) -> &Vec<String> {
+        &self.assume
+    }
+}
+
+/// The options that fall back to an environment variable when they are not given on the
+/// command line, along with the name of the variable.
+pub const ENV_OPTIONS: [(&str, &str); 9] = [
+    ("debug_prime", "ZKFUZZ_PRIME"),
+    ("search_mode", "ZKFUZZ_SEARCH_MODE"),
+    ("heuristics_range", "ZKFUZZ_RANGE"),
+    ("path_to_mutation_setting", "ZKFUZZ_MUTATION_SETTING"),
+    ("random_seed", "ZKFUZZ_RANDOM_SEED"),
+    ("output_format", "ZKFUZZ_OUTPUT_FORMAT"),
+    ("exec_timeout_secs", "ZKFUZZ_EXEC_TIMEOUT_SECS"),
+    ("report", "ZKFUZZ_REPORT"),
+    ("threads", "ZKFUZZ_THREADS"),
+];
+
+/// Well-known primes that `--debug_prime` and `--heuristics_range` accept by name.
+const NAMED_PRIMES: [(&str, &str); 4] = [
+    ("bn128", "21888242871839275222246405745257275088548364400416034343698204186575808495617"),
+    ("bn254", "21888242871839275222246405745257275088548364400416034343698204186575808495617"),
+    ("bls12381", "52435875175126190479447740508185965837690552500527637822603658699938581184513"),
+    ("goldilocks", "18446744069414584321"),
+];
+
+/// Parses a decimal integer, a `0x`-prefixed hexadecimal integer, or one of `NAMED_PRIMES`.
+fn parse_big_int(value: &str) -> Option<BigInt> {
+    let value = value.trim();
+    if let Some((_, decimal)) = NAMED_PRIMES.iter().find(|(name, _)| name.eq_ignore_ascii_case(value)) {
+        return BigInt::from_str(decimal).ok();
+    }
+    match value.strip_prefix("0x").or_else(|| value.strip_prefix("0X")) {
+        Some(digits) => BigInt::parse_bytes(digits.as_bytes(), 16),
+        None => BigInt::from_str(value).ok(),
+    }
+}
+
+/// Parses the prime modulus given with `--debug_prime`.
+pub fn parse_prime(value: &str) -> Result<BigInt, ZkFuzzError> {
+    match parse_big_int(value) {
+        Some(prime) if prime > BigInt::one() => Ok(prime),
+        _ => Err(ZkFuzzError::InvalidPrime(value.to_string())),
+    }
+}
+
+/// Parses the range of the values tried by the brute-force search given with `--heuristics_range`.
+pub fn parse_range(value: &str) -> Result<BigInt, ZkFuzzError> {
+    match parse_big_int(value) {
+        Some(range) if range >= BigInt::one() => Ok(range),
+        _ => Err(ZkFuzzError::InvalidRange(value.to_string())),
+    }
+}
+
+/// Checks that the search mode given with `--search_mode` is supported by this build.
+pub fn check_search_mode(mode: &str) -> Result<(), ZkFuzzError> {
+    match mode {
+        "quick" | "full" | "heuristics" | "ga" | "off" => Ok(()),
+        #[cfg(feature = "z3")]
+        "smt" => Ok(()),
+        _ => Err(ZkFuzzError::UnsupportedSearchMode(mode.to_string())),
+    }
+}
+
+/// Parses the semantics of the whitelisted templates given with `--whitelist_mode`.
+pub fn parse_whitelist_mode(mode: &str) -> Result<WhitelistMode, ZkFuzzError> {
+    match mode {
+        "inline" => Ok(WhitelistMode::Inline),
+        "black_box" => Ok(WhitelistMode::TrustAsBlackBox),
+        _ => Err(ZkFuzzError::UnsupportedWhitelistMode(mode.to_string())),
+    }
+}
+
+/// Parses the bounds of symbolic template parameters given with `--param_bounds`, each of the
+/// form `name=lo:hi` where both ends are included.
+pub fn parse_param_bounds(entries: &[String]) -> Result<Vec<ParamBound>, ZkFuzzError> {
+    let mut bounds: Vec<ParamBound> = Vec::new();
+    for entry in entries {
+        let invalid = || {
+            ZkFuzzError::InvalidParamBounds(format!("`{}` is not of the form name=lo:hi", entry))
+        };
+        let (name, range) = entry.split_once('=').ok_or_else(invalid)?;
+        let (lower, upper) = range.split_once(':').ok_or_else(invalid)?;
+        let lower = parse_big_int(lower).ok_or_else(invalid)?;
+        let upper = parse_big_int(upper).ok_or_else(invalid)?;
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        if lower > upper {
+            return Err(ZkFuzzError::InvalidParamBounds(format!(
+                "the range of `{}` is empty",
+                name
+            )));
+        }
+        if bounds.iter().any(|bound| bound.name == name) {
+            return Err(ZkFuzzError::InvalidParamBounds(format!(
+                "`{}` is bounded twice",
+                name
+            )));
+        }
+        bounds.push(ParamBound { name, lower, upper });
+    }
+    Ok(bounds)
+}
+
+/// Parses the bit widths of input signals given with `--signal_range`, each of the form
+/// `name=bits` where `name` is the bare name of an input of the main component.
+pub fn parse_signal_ranges(entries: &[String]) -> Result<FxHashMap<String, usize>, ZkFuzzError> {
+    let mut ranges: FxHashMap<String, usize> = FxHashMap::default();
+    for entry in entries {
+        let invalid =
+            || ZkFuzzError::InvalidSignalRange(format!("`{}` is not of the form name=bits", entry));
+        let (name, bits) = entry.split_once('=').ok_or_else(invalid)?;
+        let bits = bits.trim().parse::<usize>().map_err(|_| invalid())?;
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return Err(invalid());
+        }
+        if ranges.insert(name.clone(), bits).is_some() {
+            return Err(ZkFuzzError::InvalidSignalRange(format!(
+                "`{}` is ranged twice",
+                name
+            )));
+        }
+    }
+    Ok(ranges)
+}
+
+/// Reads the whitelist given with `--path_to_whitelist`, one template name per line.
+pub fn load_whitelist(path: &str) -> Result<FxHashSet<String>, ZkFuzzError> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| ZkFuzzError::WhitelistReadError(path.to_string(), e.to_string()))?;
+    Ok(content.lines().map(|line| line.to_string()).collect())
 }
 mod input_processing {
-    use ansi_term::Colour;
     use clap::{App, Arg, ArgMatches};
+    use colored::Colorize;
+    use std::ffi::OsString;
     use std::path::{Path, PathBuf};
     use crate::VERSION;
+    use super::ENV_OPTIONS;
 
     pub fn get_input(matches: &ArgMatches) -> Result<PathBuf, ()> {
         let route = Path::new(matches.value_of("input").unwrap()).to_path_buf();
@@ -281,7 +612,7 @@ mod input_processing {
             Result::Ok(route)
         } else {
             let route = if route.to_str().is_some() { ": ".to_owned() + route.to_str().unwrap()} else { "".to_owned() };
-            Result::Err(eprintln!("{}", Colour::Red.paint("Input file does not exist".to_owned() + &route)))
+            Result::Err(eprintln!("{}", ("Input file does not exist".to_owned() + &route).red()))
         }
     }
 
@@ -291,7 +622,7 @@ mod input_processing {
         if route.is_dir() {
             Result::Ok(route)
         } else {
-            Result::Err(eprintln!("{}", Colour::Red.paint("invalid output path")))
+            Result::Err(eprintln!("{}", "invalid output path".red()))
         }
     }*/
 
@@ -313,7 +644,7 @@ mod input_processing {
                 if let Result::Ok(no_rounds) = rounds_r { 
                     if no_rounds == 0 { Ok(SimplificationStyle::O1) }
                     else {Ok(SimplificationStyle::O2(no_rounds))}} 
-                else { Result::Err(eprintln!("{}", Colour::Red.paint("invalid number of rounds"))) }
+                else { Result::Err(eprintln!("{}", "invalid number of rounds".red())) }
             },
             (false, false, false, true) => Ok(SimplificationStyle::O2(usize::MAX)),
             (false, false, false, false) => Ok(SimplificationStyle::O1),
@@ -369,6 +700,70 @@ mod input_processing {
         matches.is_present("save_output")
     }
 
+    pub fn get_simplify_to_fixpoint(matches: &ArgMatches) -> bool {
+        matches.is_present("simplify_to_fixpoint")
+    }
+
+    pub fn get_dedup_constraints(matches: &ArgMatches) -> bool {
+        matches.is_present("dedup_constraints")
+    }
+
+    pub fn get_track_field_reductions(matches: &ArgMatches) -> bool {
+        matches.is_present("track_field_reductions")
+    }
+
+    pub fn get_analyze_all_templates(matches: &ArgMatches) -> bool {
+        matches.is_present("analyze_all_templates")
+    }
+
+    pub fn get_no_color(matches: &ArgMatches) -> bool {
+        matches.is_present("no_color")
+    }
+
+    pub fn get_progress_json(matches: &ArgMatches) -> bool {
+        matches.is_present("progress_json")
+    }
+
+    pub fn get_per_template_stats(matches: &ArgMatches) -> bool {
+        matches.is_present("per_template_stats")
+    }
+
+    pub fn get_quiet(matches: &ArgMatches) -> bool {
+        matches.is_present("quiet")
+    }
+
+    pub fn get_dump_default_mutation_config(matches: &ArgMatches) -> bool {
+        matches.is_present("dump_default_mutation_config")
+    }
+
+    pub fn get_list_signals(matches: &ArgMatches) -> bool {
+        matches.is_present("list_signals")
+    }
+
+    pub fn get_boundary_values(matches: &ArgMatches) -> bool {
+        matches.is_present("boundary_values")
+    }
+
+    pub fn get_find_all(matches: &ArgMatches) -> bool {
+        matches.is_present("find_all")
+    }
+
+    pub fn get_check_functions(matches: &ArgMatches) -> bool {
+        matches.is_present("check_functions")
+    }
+
+    pub fn get_mutate_template_params(matches: &ArgMatches) -> bool {
+        matches.is_present("mutate_template_params")
+    }
+
+    pub fn get_precise_primitives(matches: &ArgMatches) -> bool {
+        matches.is_present("precise_primitives")
+    }
+
+    pub fn get_warn_as_error(matches: &ArgMatches) -> bool {
+        matches.is_present("warn_as_error")
+    }
+
     pub fn get_show_stats_of_ast(matches: &ArgMatches) -> bool {
         matches.is_present("show_stats_of_ast")
     }
@@ -422,7 +817,7 @@ mod input_processing {
                         Ok(String::from(matches.value_of("prime").unwrap()))
                     }
                     else{
-                        Result::Err(eprintln!("{}", Colour::Red.paint("invalid prime number")))
+                        Result::Err(eprintln!("{}", "invalid prime number".red()))
                     }
                }
                
@@ -437,6 +832,10 @@ mod input_processing {
         }
     }
 
+    pub fn get_explicit_debug_prime(matches: &ArgMatches) -> bool {
+        matches.occurrences_of("debug_prime") > 0
+    }
+
     pub fn get_heuristics_range(matches: &ArgMatches) -> Result<String, ()> {
         match matches.is_present("heuristics_range") {
             true => Ok(String::from(matches.value_of("heuristics_range").unwrap())),
@@ -458,6 +857,13 @@ mod input_processing {
         }
     }
 
+    pub fn get_mutation_config_json(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("mutation_config_json") {
+            true => Ok(String::from(matches.value_of("mutation_config_json").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
     pub fn get_path_to_whitelist(matches: &ArgMatches) -> Result<String, ()> {
         match matches.is_present("path_to_whitelist") {
             true => Ok(String::from(matches.value_of("path_to_whitelist").unwrap())),
@@ -465,7 +871,211 @@ mod input_processing {
         }
     }
 
-    pub fn view() -> ArgMatches<'static> {
+    pub fn get_synthesize_main(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("synthesize_main") {
+            true => Ok(String::from(matches.value_of("synthesize_main").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_sample_safe_inputs(matches: &ArgMatches) -> Result<usize, ()> {
+        match matches.is_present("sample_safe_inputs") {
+            true => matches.value_of("sample_safe_inputs").unwrap().parse::<usize>().map_err(|_| {
+                eprintln!("{}", "invalid number of safe inputs to sample".red())
+            }),
+            false => Ok(0)
+        }
+    }
+
+    pub fn get_limit_depth(matches: &ArgMatches) -> Result<Option<usize>, ()> {
+        match matches.is_present("limit_depth") {
+            true => matches.value_of("limit_depth").unwrap().parse::<usize>().map(Some).map_err(|_| {
+                eprintln!("{}", "invalid component depth limit".red())
+            }),
+            false => Ok(None)
+        }
+    }
+
+    pub fn get_random_seed(matches: &ArgMatches) -> Result<Option<u64>, ()> {
+        match matches.is_present("random_seed") {
+            true => matches.value_of("random_seed").unwrap().parse::<u64>().map(Some).map_err(|_| {
+                eprintln!("{}", "invalid random seed".red())
+            }),
+            false => Ok(None)
+        }
+    }
+
+    pub fn get_max_findings(matches: &ArgMatches) -> Result<Option<usize>, ()> {
+        match matches.is_present("max_findings") {
+            true => match matches.value_of("max_findings").unwrap().parse::<usize>() {
+                Ok(max_findings) if max_findings > 0 => Ok(Some(max_findings)),
+                _ => {
+                    eprintln!("{}", "max_findings should be a positive integer".red());
+                    Err(())
+                }
+            },
+            false => Ok(None)
+        }
+    }
+
+    pub fn get_threads(matches: &ArgMatches) -> Result<Option<usize>, ()> {
+        match matches.is_present("threads") {
+            true => match matches.value_of("threads").unwrap().parse::<usize>() {
+                Ok(threads) if threads > 0 => Ok(Some(threads)),
+                _ => {
+                    eprintln!("{}", "threads should be a positive integer".red());
+                    Err(())
+                }
+            },
+            false => Ok(None)
+        }
+    }
+
+    pub fn get_output_format(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.value_of("output_format").unwrap_or("zkfuzz") {
+            format @ ("zkfuzz" | "circom" | "sarif") => Ok(String::from(format)),
+            _ => {
+                eprintln!("{}", "output_format should be one of `zkfuzz`, `circom`, or `sarif`".red());
+                Err(())
+            }
+        }
+    }
+
+    pub fn get_output_encoding(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.value_of("output_encoding").unwrap_or("json") {
+            encoding @ ("json" | "cbor") => Ok(String::from(encoding)),
+            _ => {
+                eprintln!("{}", "output_encoding should be either `json` or `cbor`".red());
+                Err(())
+            }
+        }
+    }
+
+    pub fn get_resume_population(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("resume_population") {
+            true => Ok(String::from(matches.value_of("resume_population").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_save_population(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("save_population") {
+            true => Ok(String::from(matches.value_of("save_population").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_emit_constraint_graph(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("emit_constraint_graph") {
+            true => Ok(String::from(matches.value_of("emit_constraint_graph").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_dump_symbolic_trace(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("dump_symbolic_trace") {
+            true => Ok(String::from(matches.value_of("dump_symbolic_trace").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_diff_against(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("diff_against") {
+            true => Ok(String::from(matches.value_of("diff_against").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_evaluate_input(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("evaluate_input") {
+            true => Ok(String::from(matches.value_of("evaluate_input").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_emit_repro(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("emit_repro") {
+            true => Ok(String::from(matches.value_of("emit_repro").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_report(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("report") {
+            true => Ok(String::from(matches.value_of("report").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_templates_filter(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("templates_filter") {
+            true => Ok(String::from(matches.value_of("templates_filter").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_whitelist_mode(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("whitelist_mode") {
+            true => Ok(String::from(matches.value_of("whitelist_mode").unwrap())),
+            false => Ok(String::from("inline"))
+        }
+    }
+
+    pub fn get_stats_format(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.value_of("stats_format").unwrap_or("pretty") {
+            format @ ("pretty" | "csv" | "json") => Ok(String::from(format)),
+            _ => {
+                eprintln!("{}", "stats_format should be one of `pretty`, `csv`, or `json`".red());
+                Err(())
+            }
+        }
+    }
+
+    pub fn get_input_constraints(matches: &ArgMatches) -> Result<String, ()> {
+        match matches.is_present("input_constraints") {
+            true => Ok(String::from(matches.value_of("input_constraints").unwrap())),
+            false => Ok(String::from("none"))
+        }
+    }
+
+    pub fn get_exec_timeout_secs(matches: &ArgMatches) -> Result<Option<u64>, ()> {
+        match matches.is_present("exec_timeout_secs") {
+            true => matches.value_of("exec_timeout_secs").unwrap().parse::<u64>().map(Some).map_err(|_| {
+                eprintln!("{}", "invalid timeout of the symbolic execution".red())
+            }),
+            false => Ok(None)
+        }
+    }
+
+    /// Parses `args`, appending the value of the environment variable of each option of
+    /// `ENV_OPTIONS` that `args` does not give, so that the flags take precedence over the
+    /// environment and both are validated alike.
+    pub fn view<I, T>(args: I) -> ArgMatches<'static>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+        let matches = app().get_matches_from(args.clone());
+        let env_args: Vec<OsString> = ENV_OPTIONS
+            .iter()
+            .filter(|(name, _)| matches.occurrences_of(name) == 0)
+            .filter_map(|(name, var)| {
+                std::env::var_os(var).map(|value| {
+                    let mut arg = OsString::from(format!("--{}=", name));
+                    arg.push(value);
+                    arg
+                })
+            })
+            .collect();
+        if env_args.is_empty() {
+            matches
+        } else {
+            app().get_matches_from(args.into_iter().chain(env_args))
+        }
+    }
+
+    fn app() -> App<'static, 'static> {
         App::new("ZKP Circuit Fuzzer")
             .version(VERSION)
             .author("Anonymous Authors")
@@ -578,6 +1188,7 @@ mod input_processing {
             .arg(
                 Arg::with_name("link_libraries")
                 .short("l")
+                .long("library")
                 .takes_value(true)
                 .multiple(true)
                 .number_of_values(1)   
@@ -607,7 +1218,7 @@ mod input_processing {
                     .takes_value(true)
                     .default_value("21888242871839275222246405745257275088548364400416034343698204186575808495617")
                     .display_order(310)
-                    .help("(zkFuzz) Prime number for zkFuzz"),
+                    .help("(zkFuzz) Prime number for zkFuzz, in decimal, in hexadecimal with a 0x prefix, or by name (bn254, bls12381, goldilocks)"),
             )
             .arg (
                 Arg::with_name("search_mode")
@@ -623,7 +1234,7 @@ mod input_processing {
                     .takes_value(true)
                     .default_value("100")
                     .display_order(330)
-                    .help("(zkFuzz) Heuristics range for zkFuzz"),
+                    .help("(zkFuzz) Heuristics range for zkFuzz, in decimal or in hexadecimal with a 0x prefix"),
             )
             .arg (
                 Arg::with_name("path_to_mutation_setting")
@@ -633,6 +1244,14 @@ mod input_processing {
                     .display_order(340)
                     .help("(zkFuzz) Path to the setting file for Mutation Testing"),
             )
+            .arg (
+                Arg::with_name("mutation_config_json")
+                    .long("mutation_config_json")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(345)
+                    .help("(zkFuzz) Inline JSON setting for Mutation Testing (takes precedence over --path_to_mutation_setting)"),
+            )
             .arg (
                 Arg::with_name("path_to_whitelist")
                     .long("path_to_whitelist")
@@ -641,6 +1260,163 @@ mod input_processing {
                     .display_order(350)
                     .help("(zkFuzz) Path to the white-lists file"),
             )
+            .arg (
+                Arg::with_name("sample_safe_inputs")
+                    .long("sample_safe_inputs")
+                    .takes_value(true)
+                    .display_order(360)
+                    .help("(zkFuzz) Samples the given number of valid inputs satisfying all side constraints and prints them as circom input JSON"),
+            )
+            .arg (
+                Arg::with_name("synthesize_main")
+                    .long("synthesize_main")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(370)
+                    .help("(zkFuzz) Analyzes the given template (e.g. `IsZero` or `Num2Bits(8)`) as the main component of a synthesized harness, for files without `component main`"),
+            )
+            .arg (
+                Arg::with_name("limit_depth")
+                    .long("limit_depth")
+                    .takes_value(true)
+                    .display_order(380)
+                    .help("(zkFuzz) Treats components nested deeper than the given depth below main as black boxes with unconstrained outputs"),
+            )
+            .arg (
+                Arg::with_name("random_seed")
+                    .long("random_seed")
+                    .takes_value(true)
+                    .display_order(390)
+                    .help("(zkFuzz) Seeds the random number generator of the `ga` search mode, overriding `seed` in the mutation setting, to replay a run"),
+            )
+            .arg (
+                Arg::with_name("output_format")
+                    .long("output_format")
+                    .takes_value(true)
+                    .default_value("zkfuzz")
+                    .display_order(395)
+                    .help("(zkFuzz) Format of the counterexample: `zkfuzz` for the detailed report, `circom` for an input.json of the main component, or `sarif` for a SARIF log of all the findings"),
+            )
+            .arg (
+                Arg::with_name("output_encoding")
+                    .long("output_encoding")
+                    .takes_value(true)
+                    .default_value("json")
+                    .display_order(396)
+                    .help("(zkFuzz) Encoding of the counterexamples: `json`, or `cbor` to always save them to a compact binary file for large circuits"),
+            )
+            .arg (
+                Arg::with_name("input_constraints")
+                    .long("input_constraints")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(397)
+                    .help("(zkFuzz) Path to a JSON file, in the input.json format of circom, fixing some inputs of the main component during the search, which must include every input declared `public`"),
+            )
+            .arg (
+                Arg::with_name("exec_timeout_secs")
+                    .long("exec_timeout_secs")
+                    .takes_value(true)
+                    .display_order(398)
+                    .help("(zkFuzz) Stops gathering the trace/side constraints after the given number of seconds and reports the partial constraints without searching"),
+            )
+            .arg(
+                Arg::with_name("stats_format")
+                    .long("stats_format")
+                    .takes_value(true)
+                    .default_value("pretty")
+                    .display_order(399)
+                    .help("(zkFuzz) Format of the stats printed by --print_stats: `pretty`, `csv`, or `json`"),
+            )
+            .arg (
+                Arg::with_name("resume_population")
+                    .long("resume_population")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(400)
+                    .help("(zkFuzz) Path to a population saved by --save_population, from which the `ga` search mode starts"),
+            )
+            .arg (
+                Arg::with_name("save_population")
+                    .long("save_population")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(401)
+                    .help("(zkFuzz) Path to a JSON file where the `ga` search mode saves its final population"),
+            )
+            .arg (
+                Arg::with_name("emit_constraint_graph")
+                    .long("emit_constraint_graph")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(402)
+                    .help("(zkFuzz) Path to a Graphviz DOT file where the graph of the signals sharing a side constraint is written"),
+            )
+            .arg (
+                Arg::with_name("whitelist_mode")
+                    .long("whitelist_mode")
+                    .takes_value(true)
+                    .default_value("inline")
+                    .display_order(403)
+                    .help("(zkFuzz) Semantics of the whitelisted templates: `inline` (their body is executed, but their assignments are not mutated) or `black_box` (only their outputs are kept, as opaque functions of their inputs)"),
+            )
+            .arg (
+                Arg::with_name("param_bounds")
+                    .long("param_bounds")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(404)
+                    .help("(zkFuzz) Range `name=lo:hi` (both included) of a template parameter made symbolic by --symbolic_template_params or mutated by --mutate_template_params, whose values are explored one by one; can be repeated"),
+            )
+            .arg (
+                Arg::with_name("dump_symbolic_trace")
+                    .long("dump_symbolic_trace")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(405)
+                    .help("(zkFuzz) Path to a text file where the trace and side constraints are written, one per line"),
+            )
+            .arg(
+                Arg::with_name("diff_against")
+                    .long("diff_against")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(406)
+                    .help("(zkFuzz) Path to another version of the circuit whose side constraints are compared with those of the input circuit"),
+            )
+            .arg(
+                Arg::with_name("evaluate_input")
+                    .long("evaluate_input")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(407)
+                    .help("(zkFuzz) Path to an input JSON in the format of circom, on which the circuit is executed instead of the search"),
+            )
+            .arg(
+                Arg::with_name("emit_repro")
+                    .long("emit_repro")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(408)
+                    .help("(zkFuzz) Path to a directory where a circom harness and its input JSON reproducing the counterexample are written"),
+            )
+            .arg(
+                Arg::with_name("report")
+                    .long("report")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(410)
+                    .help("(zkFuzz) Path to which a JSON report of the run, with the execution summary, the statistics, the verdict, and the counterexamples, is written, overwriting any previous one"),
+            )
+            .arg(
+                Arg::with_name("templates_filter")
+                    .long("templates_filter")
+                    .takes_value(true)
+                    .default_value("none")
+                    .display_order(409)
+                    .help("(zkFuzz) Regular expression restricting --show_stats_of_ast and --print_ast to the templates whose names match it"),
+            )
             .arg(
                 Arg::with_name("lessthan_dissabled")
                     .long("lessthan_dissabled")
@@ -730,7 +1506,172 @@ mod input_processing {
                     .display_order(880)
                     .help("(zkFuzz) Save the output when the counterexample is found"),
             )
-            .get_matches()
+            .arg(
+                Arg::with_name("simplify_to_fixpoint")
+                    .long("simplify_to_fixpoint")
+                    .takes_value(false)
+                    .display_order(890)
+                    .help("(zkFuzz) Repeats the simplification of each constraint until it reaches a fixed point"),
+            )
+            .arg(
+                Arg::with_name("dedup_constraints")
+                    .long("dedup_constraints")
+                    .takes_value(false)
+                    .display_order(900)
+                    .help("(zkFuzz) Removes duplicated trace and side constraints before the statistics and the search"),
+            )
+            .arg(
+                Arg::with_name("track_field_reductions")
+                    .long("track_field_reductions")
+                    .takes_value(false)
+                    .display_order(910)
+                    .help("(zkFuzz) Replays the counterexample and reports every arithmetic result reduced modulo the prime"),
+            )
+            .arg(
+                Arg::with_name("analyze_all_templates")
+                    .long("analyze_all_templates")
+                    .takes_value(false)
+                    .display_order(920)
+                    .help("(zkFuzz) Analyzes every template as the main component with symbolic template parameters"),
+            )
+            .arg(
+                Arg::with_name("no_color")
+                    .long("no_color")
+                    .takes_value(false)
+                    .display_order(930)
+                    .help("(zkFuzz) Disables colored output, which is also disabled when stdout or stderr is not a terminal"),
+            )
+            .arg(
+                Arg::with_name("progress_json")
+                    .long("progress_json")
+                    .takes_value(false)
+                    .display_order(940)
+                    .help("(zkFuzz) Writes the progress of the genetic search to stderr as JSON lines"),
+            )
+            .arg(
+                Arg::with_name("per_template_stats")
+                    .long("per_template_stats")
+                    .takes_value(false)
+                    .display_order(950)
+                    .help("(zkFuzz) Prints the numbers of trace and side constraints and the compression rate of each template"),
+            )
+            .arg(
+                Arg::with_name("quiet")
+                    .long("quiet")
+                    .takes_value(false)
+                    .display_order(960)
+                    .help("(zkFuzz) Replaces the banner, the status lines, and the report with a single summary line, while still printing the warnings"),
+            )
+            .arg(
+                Arg::with_name("dump_default_mutation_config")
+                    .long("dump_default_mutation_config")
+                    .takes_value(false)
+                    .display_order(970)
+                    .help("(zkFuzz) Prints the default setting for Mutation Testing as JSON and exits"),
+            )
+            .arg(
+                Arg::with_name("list_signals")
+                    .long("list_signals")
+                    .takes_value(false)
+                    .display_order(980)
+                    .help("(zkFuzz) Prints the signals of the main component with their types and dimensions and exits"),
+            )
+            .arg(
+                Arg::with_name("boundary_values")
+                    .long("boundary_values")
+                    .takes_value(false)
+                    .display_order(990)
+                    .help("(zkFuzz) Makes the brute-force search modes try 0, 1, p-1, p-2, and (p-1)/2 for each variable first"),
+            )
+            .arg(
+                Arg::with_name("signal_range")
+                    .long("signal_range")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(1000)
+                    .help("(zkFuzz) Bit width `name=bits` of an input of the main component, whose values are sampled within [0, 2^bits) by the genetic search; can be repeated"),
+            )
+            .arg(
+                Arg::with_name("assume")
+                    .long("assume")
+                    .takes_value(true)
+                    .multiple(true)
+                    .number_of_values(1)
+                    .display_order(1010)
+                    .help("(zkFuzz) Circom expression over the inputs of the main component (e.g. `in < 2**32`) that the inputs of a counterexample must satisfy; can be repeated"),
+            )
+            .arg(
+                Arg::with_name("find_all")
+                    .long("find_all")
+                    .takes_value(false)
+                    .display_order(1020)
+                    .help("(zkFuzz) Keeps searching after a counterexample and reports every distinct one, the JSON output becoming an array"),
+            )
+            .arg(
+                Arg::with_name("max_findings")
+                    .long("max_findings")
+                    .takes_value(true)
+                    .requires("find_all")
+                    .display_order(1030)
+                    .help("(zkFuzz) Stops --find_all after the given number of distinct counterexamples"),
+            )
+            .arg(
+                Arg::with_name("check_functions")
+                    .long("check_functions")
+                    .takes_value(false)
+                    .display_order(1040)
+                    .help("(zkFuzz) Executes each function over symbolic arguments and warns about the ones whose return value depends on an unassigned variable"),
+            )
+            .arg(
+                Arg::with_name("threads")
+                    .long("threads")
+                    .takes_value(true)
+                    .display_order(1050)
+                    .help("(zkFuzz) Number of threads of the parallel search, 1 making it sequential [default: the number of logical CPUs]"),
+            )
+            .arg(
+                Arg::with_name("mutate_template_params")
+                    .long("mutate_template_params")
+                    .takes_value(false)
+                    .display_order(1060)
+                    .help("(zkFuzz) Replaces the arguments of the main template call with each combination of the values of --param_bounds, re-executing the circuit for each"),
+            )
+            .arg(
+                Arg::with_name("precise_primitives")
+                    .long("precise_primitives")
+                    .takes_value(false)
+                    .display_order(1070)
+                    .help("(zkFuzz) Replaces each whitelisted `Num2Bits` trusted as a black box with its exact constraints: its outputs are boolean and reconstruct its input"),
+            )
+            .arg(
+                Arg::with_name("warn_as_error")
+                    .long("warn_as_error")
+                    .takes_value(false)
+                    .display_order(1080)
+                    .help("(zkFuzz) Exits with a non-zero status when a counterexample or a warning (e.g., dangling signals or potential divisions by zero) is reported, for CI gates"),
+            )
+    }
+
+    pub fn get_param_bounds(matches: &ArgMatches) -> Vec<String> {
+        match matches.values_of("param_bounds") {
+            Some(entries) => entries.map(|entry| entry.to_string()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_signal_range(matches: &ArgMatches) -> Vec<String> {
+        match matches.values_of("signal_range") {
+            Some(entries) => entries.map(|entry| entry.to_string()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    pub fn get_assume(matches: &ArgMatches) -> Vec<String> {
+        match matches.values_of("assume") {
+            Some(entries) => entries.map(|entry| entry.to_string()).collect(),
+            None => Vec::new(),
+        }
     }
 
     pub fn get_link_libraries(matches: &ArgMatches) -> Vec<PathBuf> {
@@ -1,5 +1,8 @@
+pub mod api;
+pub mod error;
 pub mod executor;
 pub mod mutator;
+pub mod stats;
 
 pub mod input_user;
 pub mod parser_user;
@@ -1,3 +1,5 @@
+mod api;
+mod error;
 mod executor;
 mod mutator;
 mod stats;
@@ -8,64 +10,138 @@ mod type_analysis_user;
 
 use std::env;
 use std::fs::File;
-use std::io::{self, BufRead, Write};
-use std::path::Path;
-use std::str::FromStr;
+use std::io::{self, IsTerminal, Write};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time;
 
 use colored::Colorize;
 use env_logger;
-use input_user::Input;
+use input_user::{
+    check_search_mode, load_whitelist, parse_param_bounds, parse_prime, parse_range,
+    parse_signal_ranges, parse_whitelist_mode, Input,
+};
 use log::{debug, info, warn};
 use num_bigint_dig::BigInt;
 use rand::distributions::Alphanumeric;
 use rand::{thread_rng, Rng};
+use regex::Regex;
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde_json::json;
 
-use program_structure::ast::Expression;
+use program_structure::ast::{Expression, Meta};
 use program_structure::program_archive::ProgramArchive;
 
+use api::{analyze_all_templates, gather_side_constraints, AnalysisConfig};
+use error::ZkFuzzError;
 use executor::symbolic_execution::SymbolicExecutor;
 use executor::symbolic_setting::{
-    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+    count_param_bindings, enumerate_param_bindings, get_default_setting_for_concrete_execution,
+    get_default_setting_for_symbolic_execution, SymbolicExecutorSetting, WhitelistMode,
+    MAX_PARAM_BINDINGS,
+};
+use executor::symbolic_value::{
+    extract_variables, OwnerName, SymbolicLibrary, SymbolicName, SymbolicNameInterner,
+    SymbolicValue, SymbolicValueRef,
 };
-use executor::symbolic_value::{OwnerName, SymbolicLibrary};
 
-use mutator::mutation_config::load_config_from_json;
-use mutator::mutation_test_crossover_fn::random_crossover;
+#[cfg(not(feature = "parallel"))]
+use mutator::brute_force::brute_force_search_all;
+#[cfg(feature = "parallel")]
+use mutator::brute_force::parallel_brute_force_search_all as brute_force_search_all;
+use mutator::mutation_config::{load_config_from_json, load_config_from_json_str, MutationConfig};
 use mutator::mutation_test_evolution_fn::simple_evolution;
-use mutator::mutation_test_trace_fitness_fn::evaluate_trace_fitness_by_error;
-use mutator::mutation_test_trace_initialization_fn::{
-    initialize_population_with_constant_replacement,
-    initialize_population_with_operator_or_const_replacement,
-    initialize_population_with_operator_or_const_replacement_or_addition,
-    initialize_population_with_operator_or_const_replacement_or_deletion,
-};
-use mutator::mutation_test_trace_mutation_fn::{
-    mutate_trace_with_constant_replacement, mutate_trace_with_operator_or_const_replacement,
-    mutate_trace_with_operator_or_const_replacement_or_addition,
-    mutate_trace_with_operator_or_const_replacement_or_deletion,
+use mutator::mutation_test_operators::OperatorRegistry;
+use mutator::mutation_test_trace_fitness_fn::{
+    evaluate_trace_fitness_by_distance, evaluate_trace_fitness_by_error,
 };
-use mutator::mutation_test_trace_selection_fn::roulette_selection;
 use mutator::mutation_test_update_input_fn::{
     update_input_population_with_coverage_maximization, update_input_population_with_fitness_score,
     update_input_population_with_random_sampling,
 };
+use mutator::safe_inputs::{from_circom_input_json, sample_safe_inputs, to_circom_input_json};
+#[cfg(feature = "z3")]
+use mutator::smt_search::smt_search;
 use mutator::{
-    brute_force::brute_force_search, mutation_test::mutation_test_search,
-    unused_outputs::check_unused_outputs, utils::BaseVerificationConfig,
+    contradictions::detect_unsatisfiable_constraints,
+    free_witnesses::{find_free_witnesses, find_unconstrained_assignments},
+    mutation_test::{
+        load_population_from_json, mutation_test_search_with_population,
+        report_progress_as_json_line, save_population_to_json,
+    },
+    shrink::shrink_counterexample,
+    undetermined_functions::find_undetermined_functions,
+    unused_outputs::{check_dangling_intermediates, check_unused_outputs},
+    utils::{
+        evaluate_input, push_distinct_finding, replay_field_reductions,
+        save_counter_examples_to_cbor, BaseVerificationConfig, SearchCompleteness,
+    },
 };
 
 use stats::ast_stats::ASTStats;
+use stats::constraint_diff::{diff_constraints, normalize_constraints};
+use stats::constraint_graph::constraint_graph_to_dot;
+use stats::execution_metrics::{print_execution_metrics_pretty, ExecutionMetrics};
+use stats::sarif::{to_sarif_log, SarifFinding};
+use stats::signals::describe_signals;
 use stats::symbolic_stats::{
     print_constraint_summary_statistics_csv, print_constraint_summary_statistics_pretty,
-    ConstraintStatistics,
+    ConstraintStatistics, CONSTRAINT_STATISTICS_CSV_HEADERS,
 };
+use stats::template_stats::count_constraints_per_template;
+use stats::trace_dump::dump_symbolic_trace;
 
 const VERSION: &'static str = env!("CARGO_PKG_VERSION");
-const RESET: &str = "\x1b[0m";
-const BACK_GRAY_SCRIPT_BLACK: &str = "\x1b[30;100m"; //94
+
+/// Whether `--quiet` is given, in which case the status lines, but not the warnings, are not
+/// printed.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Prints a status line to stderr unless `--quiet` is given.
+macro_rules! status {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Disables the colors of the console output when `--no_color` is given or when stdout or
+/// stderr is not a terminal, so that piped logs do not contain ANSI escape codes.
+fn configure_color_output(no_color: bool) {
+    if no_color || !io::stdout().is_terminal() || !io::stderr().is_terminal() {
+        colored::control::set_override(false);
+    }
+}
+
+/// Installs a global rayon pool of `threads` threads, if given, before any search runs, so that
+/// the parallel search uses a fixed number of workers. Without the `parallel` feature, every
+/// search is sequential and the number of threads is ignored.
+#[cfg(feature = "parallel")]
+fn configure_thread_pool(threads: Option<usize>) -> Result<(), ZkFuzzError> {
+    match threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build_global()
+            .map_err(|e| ZkFuzzError::InvalidConfig(e.to_string())),
+        None => Ok(()),
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn configure_thread_pool(_threads: Option<usize>) -> Result<(), ZkFuzzError> {
+    Ok(())
+}
+
+/// Returns the path of an output file next to the circuit, made unique by a random string.
+fn output_file_path(input_file: &str, suffix: &str) -> String {
+    let random_string: String = thread_rng()
+        .sample_iter(&Alphanumeric)
+        .take(10)
+        .map(char::from)
+        .collect();
+    format!("{}_{}{}", input_file, random_string, suffix)
+}
 
 fn display_zkfuzz_header() {
     let logo = r#"
@@ -73,78 +149,1443 @@ fn display_zkfuzz_header() {
      ZKP Circuit Fuzzer
     "#;
 
-    eprintln!("{}", logo.bright_cyan().bold());
-    eprintln!("{}", "Welcome to the zkFuzz".green().bold());
-    eprintln!("{}", "══════════════════════════════════".green());
+    status!("{}", logo.bright_cyan().bold());
+    status!("{}", "Welcome to the zkFuzz".green().bold());
+    status!("{}", "══════════════════════════════════".green());
 }
 
-fn read_file_to_lines(file_path: &str) -> io::Result<Vec<String>> {
-    let path = Path::new(file_path);
-    let file = File::open(path)?;
-    let reader = io::BufReader::new(file);
+fn load_input_assignment(
+    file_path: &str,
+    symbolic_library: &SymbolicLibrary,
+    main_template_name: &str,
+) -> Result<FxHashMap<SymbolicName, BigInt>, String> {
+    let content = std::fs::read_to_string(file_path).map_err(|e| e.to_string())?;
+    let json: serde_json::Value = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+    let main_owner = Rc::new(vec![OwnerName {
+        id: symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    }]);
+    from_circom_input_json(
+        &json,
+        &symbolic_library.name2id,
+        &main_owner,
+        &symbolic_library.template_library[&symbolic_library.name2id[main_template_name]].input_ids,
+    )
+}
 
-    let lines: Vec<String> = reader.lines().collect::<Result<_, _>>()?;
+fn load_mutation_config(user_input: &Input) -> Result<MutationConfig, ZkFuzzError> {
+    let mut mutation_config = if user_input.mutation_config_json() != "none" {
+        if user_input.path_to_mutation_setting() != "none" {
+            warn!("Both --mutation_config_json and --path_to_mutation_setting are given; the inline JSON is used");
+        }
+        load_config_from_json_str(&user_input.mutation_config_json())
+    } else {
+        load_config_from_json(&user_input.path_to_mutation_setting())
+    }
+    .map_err(|e| ZkFuzzError::InvalidConfig(e.to_string()))?;
+    if let Some(seed) = user_input.random_seed() {
+        mutation_config.seed = Some(seed);
+    }
+    if user_input.flag_quiet {
+        mutation_config.quiet = true;
+    }
+    mutation_config
+        .input_bit_hints
+        .extend(parse_signal_ranges(user_input.signal_range())?);
+    Ok(mutation_config)
+}
+
+/// Parses the assumptions given with `--assume` and evaluates them in the scope of the main
+/// component, which `sym_executor` must have executed.
+fn load_assumptions(
+    user_input: &Input,
+    sym_executor: &mut SymbolicExecutor,
+    main_template_name: &str,
+    prime: &BigInt,
+) -> Result<Vec<SymbolicValueRef>, ZkFuzzError> {
+    if user_input.assume().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let input_ids = sym_executor.symbolic_library.template_library
+        [&sym_executor.symbolic_library.name2id[main_template_name]]
+        .input_ids
+        .clone();
+    let mut input_names = input_ids
+        .iter()
+        .map(|id| sym_executor.symbolic_library.id2name[id].clone())
+        .collect::<Vec<_>>();
+    input_names.sort();
 
-    Ok(lines)
+    let expressions = parser_user::parse_assumptions(user_input.assume(), &input_names, prime)?;
+    user_input
+        .assume()
+        .iter()
+        .zip(expressions.iter())
+        .map(|(assumption, expr)| {
+            let condition = Rc::new(sym_executor.evaluate_standalone_expression(expr));
+            let mut variables = extract_variables(&[condition.clone()]);
+            variables.sort();
+            if let Some(name) = variables
+                .iter()
+                .find(|name| name.owner.len() != 1 || !input_ids.contains(&name.id))
+            {
+                return Err(ZkFuzzError::InvalidAssumption(
+                    assumption.clone(),
+                    format!(
+                        "`{}` is not an input of the main component",
+                        name.lookup_fmt(&sym_executor.symbolic_library.id2name)
+                    ),
+                ));
+            }
+            Ok(condition)
+        })
+        .collect()
 }
 
-fn main() {
-    display_zkfuzz_header();
+fn analyze_all_templates_and_report(
+    user_input: &Input,
+    whitelist: FxHashSet<String>,
+    whitelist_mode: WhitelistMode,
+    prime: BigInt,
+) -> Result<(), ZkFuzzError> {
+    let mutation_config = load_mutation_config(user_input)?;
+    let config = AnalysisConfig {
+        prime: prime,
+        link_libraries: user_input.get_link_libraries().clone(),
+        whitelist: whitelist,
+        whitelist_mode: whitelist_mode,
+        search_mode: user_input.search_mode(),
+        mutation_config: mutation_config,
+        constraint_assert_dissabled: user_input.constraint_assert_dissabled_flag(),
+        lessthan_dissabled: user_input.lessthan_dissabled_flag,
+    };
+
+    status!("{}", "🗂️ Analyzing All Templates...".green());
+    let reports = analyze_all_templates(user_input.input_file(), config)?;
+    for (template_name, report) in &reports {
+        if user_input.flag_quiet {
+            eprintln!(
+                "template={} result={} compression={:.1}%",
+                template_name,
+                if report.counter_example.is_none() {
+                    "safe"
+                } else {
+                    "unsafe"
+                },
+                report.compression_rate * 100 as f64
+            );
+        }
+        status!("{}", "══════════════════════════════════".green());
+        status!("{} {}", "🧩 Template:".cyan().bold(), template_name);
+        status!(
+            " ├─ Compression Rate  : {:.2}% ({}/{})",
+            report.compression_rate * 100 as f64,
+            report.side_constraint_statistics.total_constraints,
+            report.trace_statistics.total_constraints
+        );
+        status!(
+            " ├─ Div-by-Zero Risks : {}",
+            report.potential_div_by_zeros.len()
+        );
+        status!(
+            " └─ Verification      : {}",
+            if report.counter_example.is_none() {
+                format!("🆗 {}", report.search_completeness.verdict())
+                    .yellow()
+                    .bold()
+            } else {
+                "💥 NOT SAFE 💥".red().bold()
+            }
+        );
+        if let Some(ce) = &report.counter_example {
+            status!("{}", ce.lookup_fmt(&report.id2name));
+        }
+    }
+    Result::Ok(())
+}
 
+fn main() {
     let result = start();
-    if result.is_err() {
-        eprintln!("{}", "previous errors were found".red());
+    if let Err(e) = result {
+        eprintln!("{} {}", "❌ Error:".red().bold(), e);
         std::process::exit(1);
     } else {
-        eprintln!("{}", "Everything went okay".green());
+        status!("{}", "Everything went okay".green());
         //std::process::exit(0);
     }
 }
 
-fn show_stats(program_archive: &ProgramArchive) {
+/// Replaces the arguments of the main template call that are bound by `--param_bounds` with
+/// their values.
+fn instantiate_param_values(
+    names: &Vec<String>,
+    args: &Vec<Expression>,
+    param_binding: &[(String, BigInt)],
+) -> Vec<Expression> {
+    names
+        .iter()
+        .zip(args.iter())
+        .map(
+            |(name, arg)| match param_binding.iter().find(|(n, _)| n == name) {
+                Some((_, value)) => Expression::Number(Meta::new(0, 0), value.clone()),
+                None => arg.clone(),
+            },
+        )
+        .collect()
+}
+
+/// Formats the names of the registered operators for an error message, e.g., [`a`, `b`].
+fn format_operator_names(names: &[String]) -> String {
+    format!(
+        "[{}]",
+        names
+            .iter()
+            .map(|name| format!("`{}`", name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    )
+}
+
+/// Renders a value bound to a template parameter as a circom expression.
+fn to_circom_literal(value: &SymbolicValue, lookup: &FxHashMap<usize, String>) -> String {
+    match value {
+        SymbolicValue::ConstantInt(v) => v.to_string(),
+        SymbolicValue::Array(elems) => format!(
+            "[{}]",
+            elems
+                .iter()
+                .map(|elem| to_circom_literal(elem, lookup))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        _ => value.lookup_fmt(lookup),
+    }
+}
+
+/// Renders the values bound to the parameters of the main template as circom expressions, or
+/// their names when they are not bound.
+fn render_template_args(sym_executor: &SymbolicExecutor, names: &Vec<String>) -> Vec<String> {
+    names
+        .iter()
+        .map(|name| {
+            let sym_name = SymbolicName::new(
+                sym_executor.symbolic_library.name2id[name],
+                sym_executor.cur_state.owner_name.clone(),
+                None,
+            );
+            match sym_executor.cur_state.get_sym_val(&sym_name) {
+                Some(value) => to_circom_literal(value, &sym_executor.symbolic_library.id2name),
+                None => name.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Writes the circuit, the harness and the input of `--emit_repro` to `dir`, creating it if
+/// needed. The harness includes the circuit, which is written without its main component.
+fn emit_repro(
+    dir: &str,
+    circuit: &str,
+    harness: &str,
+    input_json: &serde_json::Value,
+) -> io::Result<()> {
+    let dir = std::path::Path::new(dir);
+    std::fs::create_dir_all(dir)?;
+    std::fs::write(dir.join("circuit.circom"), circuit)?;
+    std::fs::write(dir.join("repro.circom"), harness)?;
+    std::fs::write(
+        dir.join("input.json"),
+        serde_json::to_string_pretty(input_json).unwrap(),
+    )
+}
+
+/// Compiles the regular expression of `--templates_filter`, if any.
+fn parse_templates_filter(filter: &str) -> Result<Option<Regex>, ZkFuzzError> {
+    if filter == "none" {
+        return Ok(None);
+    }
+    Regex::new(filter)
+        .map(Some)
+        .map_err(|e| ZkFuzzError::InvalidTemplatesFilter(filter.to_string(), e.to_string()))
+}
+
+fn is_selected_template(templates_filter: &Option<Regex>, name: &str) -> bool {
+    templates_filter
+        .as_ref()
+        .map_or(true, |filter| filter.is_match(name))
+}
+
+fn show_stats(program_archive: &ProgramArchive, templates_filter: &Option<Regex>) {
     println!("template_name,num_statements,num_variables,num_if_then_else,num_while,num_constraint_equality,num_assign_var,num_assign_constraint_signal,num_assign_signal,avg_loc_constraint_equality,avg_loc_assign_constraint_signal,avg_loc_assign_signal");
     for (k, v) in program_archive.templates.clone().into_iter() {
+        if !is_selected_template(templates_filter, &k) {
+            continue;
+        }
         let mut ass = ASTStats::default();
         ass.collect_stats(v.get_body());
         println!("{},{}", k, ass.get_csv());
     }
 }
 
-fn start() -> Result<(), ()> {
+/// Returns the prime of `--debug_prime`, defaulting to the one declared in the source, if any.
+fn resolve_prime(user_input: &Input) -> Result<BigInt, ZkFuzzError> {
+    let declared = match parser_user::parse_declared_prime(user_input.input_file()) {
+        Some(declared) => parse_prime(&declared)?,
+        None => return parse_prime(&user_input.debug_prime()),
+    };
+    if !user_input.explicit_debug_prime {
+        return Ok(declared);
+    }
+    let prime = parse_prime(&user_input.debug_prime())?;
+    if prime != declared {
+        // Not gated by --quiet, since the analysis runs over another field than the declared one
+        eprintln!(
+            "{} --debug_prime {} overrides the prime {} declared in the source",
+            "⚠️ Prime Mismatch:".yellow().bold(),
+            prime,
+            declared
+        );
+    }
+    Ok(prime)
+}
+
+/// What `start` shares between the instances of the main template.
+struct InstanceContext<'a> {
+    user_input: &'a Input,
+    program_archive: &'a ProgramArchive,
+    /// The library of the parsed templates, which each instance works on a copy of.
+    symbolic_library: &'a SymbolicLibrary,
+    base_config: &'a SymbolicExecutorSetting,
+    /// The side constraints of the circuit given with `--diff_against`, if any.
+    diff_base: &'a Option<Vec<String>>,
+    prime: &'a BigInt,
+    range: &'a BigInt,
+    start_time: time::Instant,
+}
+
+/// Result of the analysis of an instance of the main template.
+#[derive(Default)]
+struct InstanceReport {
+    /// The entry of the instance in the report of `--report`, if the report is requested.
+    report: Option<serde_json::Value>,
+    /// The number of counterexamples and warnings, which fail the run with `--warn_as_error`.
+    num_findings: usize,
+}
+
+/// Analyzes the instance of the main template whose parameters are bound by `param_binding`.
+///
+/// The trace and the side constraints of the instance are gathered and searched for
+/// counterexamples, and the summary of the instance is printed. Modes that only inspect the
+/// circuit, such as `--list_signals`, print their output and return an empty report.
+fn analyze_instance(
+    context: &InstanceContext,
+    param_binding: &[(String, BigInt)],
+) -> Result<InstanceReport, ZkFuzzError> {
+    let InstanceContext {
+        user_input,
+        program_archive,
+        symbolic_library,
+        base_config,
+        diff_base,
+        prime,
+        range,
+        start_time,
+    } = *context;
+
+    let mut instance_library = symbolic_library.clone();
+    let mut sym_executor = SymbolicExecutor::new(&mut instance_library, base_config);
+    sym_executor.register_public_inputs(&program_archive.public_inputs);
+
+    let (id, args) = match &program_archive.initial_template_call {
+        Expression::Call { id, args, .. } => (id, args),
+        _ => return Result::Err(ZkFuzzError::NoMainComponent),
+    };
+    let template = program_archive.templates[id].clone();
+    if let Some((name, _)) = param_binding
+        .iter()
+        .find(|(name, _)| !template.get_name_of_params().contains(name))
+    {
+        return Result::Err(ZkFuzzError::InvalidParamBounds(format!(
+            "`{}` is not a template parameter of `{}`",
+            name, id
+        )));
+    }
+    let args = &instantiate_param_values(template.get_name_of_params(), args, param_binding);
+    if !param_binding.is_empty() {
+        status!(
+            "{} {}",
+            "🧮 Instantiating the Main Template with".green(),
+            param_binding
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    status!("{}", "🛒 Gathering Trace/Side Constraints...".green());
+
+    sym_executor.symbolic_library.name2id.insert(
+        "main".to_string(),
+        sym_executor.symbolic_library.name2id.len(),
+    );
+    sym_executor.symbolic_library.id2name.insert(
+        sym_executor.symbolic_library.name2id["main"],
+        "main".to_string(),
+    );
+
+    sym_executor.cur_state.add_owner(&OwnerName {
+        id: sym_executor.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    });
+    sym_executor
+        .cur_state
+        .set_template_id(sym_executor.symbolic_library.name2id[id]);
+
+    if !user_input.flag_symbolic_template_params {
+        sym_executor
+            .feed_arguments(template.get_name_of_params(), args)
+            .map_err(|e| ZkFuzzError::InvalidTemplateArguments(id.clone(), e))?;
+    } else {
+        sym_executor.feed_param_values(param_binding);
+    }
+    // The search may clear the state, so the arguments are kept for `--emit_repro`
+    let template_args = render_template_args(&sym_executor, template.get_name_of_params());
+
+    if user_input.flag_list_signals {
+        // The dimensions of the signals are resolved by their declarations alone
+        let mut init_config = base_config.clone();
+        init_config.only_initialization_blocks = true;
+        init_config.off_trace = true;
+        sym_executor.setting = &init_config;
+        let body = sym_executor.symbolic_library.template_library
+            [&sym_executor.symbolic_library.name2id[id]]
+            .body
+            .clone();
+        sym_executor.execute(&body, 0);
+
+        let signals = describe_signals(&sym_executor, id);
+        if user_input.stats_format() == "json" {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json!({
+                    "template": id,
+                    "signals": signals
+                        .iter()
+                        .map(|signal| signal.to_json())
+                        .collect::<Vec<_>>(),
+                }))
+                .unwrap()
+            );
+        } else {
+            for signal in &signals {
+                println!("{}", signal);
+            }
+        }
+        return Result::Ok(InstanceReport::default());
+    }
+
+    let body = sym_executor.symbolic_library.template_library
+        [&sym_executor.symbolic_library.name2id[id]]
+        .body
+        .clone();
+    sym_executor.execute(&body, 0);
+    // The warnings are not gated by --quiet, unlike the status lines
+    for warning in &sym_executor.bit_width_warnings {
+        eprintln!("{} {}", "⚠️ Bit Width Overflow:".yellow().bold(), warning);
+    }
+    // The search may clear the state, so the divisions are kept for the report
+    let potential_div_by_zeros = sym_executor.cur_state.potential_div_by_zeros.clone();
+    for potential_div_by_zero in &potential_div_by_zeros {
+        eprintln!(
+            "{} {}",
+            "⚠️ Potential Division by Zero:".yellow().bold(),
+            potential_div_by_zero.lookup_fmt(&sym_executor.symbolic_library.id2name)
+        );
+    }
+    let unconstrained_assignments = find_unconstrained_assignments(
+        &sym_executor.cur_state.symbolic_trace,
+        &sym_executor.cur_state.side_constraints,
+    );
+    for unconstrained in &unconstrained_assignments {
+        eprintln!(
+            "{} {} is assigned with `<--` but appears in no constraint",
+            "⚠️ Unconstrained Assignment:".yellow().bold(),
+            unconstrained.lookup_fmt(&sym_executor.symbolic_library.id2name)
+        );
+    }
+    let timed_out = sym_executor.cur_state.timed_out;
+    if timed_out {
+        eprintln!(
+            "{} the trace/side constraints are partial, and the search is skipped",
+            "⏱️ Symbolic Execution Timed Out:".yellow().bold()
+        );
+    }
+    if let Some(max_depth) = base_config.max_component_depth {
+        status!(
+            "{} {} (depth > {})",
+            "⬛ Blackboxed Components:".yellow(),
+            sym_executor.num_blackboxed_components,
+            max_depth
+        );
+    }
+
+    if user_input.flag_dedup_constraints {
+        // The duplicates are dropped as they are gathered, and the remaining pass
+        // catches the ones that only became equal when rewritten afterwards
+        let (num_trace_duplicates, num_side_duplicates) =
+            sym_executor.cur_state.dedup_constraints();
+        status!(
+            "{} {} trace / {} side constraints",
+            "🧹 Removed Duplicates:",
+            sym_executor.cur_state.num_trace_duplicates + num_trace_duplicates,
+            sym_executor.cur_state.num_side_constraint_duplicates + num_side_duplicates
+        );
+    }
+
+    status!("{}", "══════════════════════════════════".green());
+    let mut ts = ConstraintStatistics::new();
+    let mut ss = ConstraintStatistics::new();
+    for c in &sym_executor.cur_state.symbolic_trace {
+        ts.update(c);
+    }
+    for c in &sym_executor.cur_state.side_constraints {
+        ss.update(c);
+    }
+    // Gathered before any concrete execution updates the function counters
+    let execution_metrics = ExecutionMetrics::from_executor(&sym_executor);
+    let free_witnesses = find_free_witnesses(
+        &sym_executor.cur_state.symbolic_trace,
+        &sym_executor.cur_state.side_constraints,
+        prime,
+    );
+    let dangling_intermediates = check_dangling_intermediates(&sym_executor, id);
+    let contradictions =
+        detect_unsatisfiable_constraints(&sym_executor.cur_state.side_constraints, prime);
+    for contradiction in &contradictions {
+        eprintln!(
+            "{} `{}` contradicts `{}`",
+            "⛔ Unsatisfiable Constraints:".red().bold(),
+            contradiction
+                .second
+                .lookup_fmt(&sym_executor.symbolic_library.id2name),
+            contradiction
+                .first
+                .lookup_fmt(&sym_executor.symbolic_library.id2name)
+        );
+    }
+    if user_input.flag_check_functions {
+        for function in find_undetermined_functions(&mut sym_executor.symbolic_library, base_config)
+        {
+            eprintln!(
+                "{} `{}` returns the unassigned {}",
+                "❓ Undetermined Function:".yellow().bold(),
+                function.name,
+                function
+                    .free_variables
+                    .iter()
+                    .map(|name| name.lookup_fmt(&sym_executor.symbolic_library.id2name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+    if user_input.emit_constraint_graph() != "none" {
+        status!(
+            "{} {}",
+            "💾 Saving the constraint graph to:",
+            user_input.emit_constraint_graph().cyan()
+        );
+        let dot = constraint_graph_to_dot(
+            &sym_executor.cur_state.side_constraints,
+            &sym_executor.symbolic_library,
+            id,
+        );
+        std::fs::write(user_input.emit_constraint_graph(), dot)
+            .map_err(|e| ZkFuzzError::Io(user_input.emit_constraint_graph(), e.to_string()))?;
+    }
+    if user_input.dump_symbolic_trace() != "none" {
+        status!(
+            "{} {}",
+            "💾 Saving the symbolic trace to:",
+            user_input.dump_symbolic_trace().cyan()
+        );
+        dump_symbolic_trace(
+            &user_input.dump_symbolic_trace(),
+            &sym_executor.cur_state.symbolic_trace,
+            &sym_executor.cur_state.side_constraints,
+            &sym_executor.symbolic_library.id2name,
+            user_input.output_format() == "zkfuzz",
+        )
+        .map_err(|e| ZkFuzzError::Io(user_input.dump_symbolic_trace(), e.to_string()))?;
+    }
+    if let Some(diff_base) = diff_base {
+        let diff = diff_constraints(
+            diff_base,
+            &normalize_constraints(
+                &sym_executor.cur_state.side_constraints,
+                &sym_executor.symbolic_library.id2name,
+            ),
+        );
+        status!("{}", "══════════════════════════════════".green());
+        status!(
+            "{} {} added, {} removed (against {})",
+            "🔀 Constraint Diff:".cyan().bold(),
+            diff.added.len(),
+            diff.removed.len(),
+            user_input.diff_against()
+        );
+        for c in &diff.added {
+            status!("{} {}", "➕ Added Constraint:".green(), c);
+        }
+        for c in &diff.removed {
+            status!("{} {}", "➖ Removed Constraint:".red(), c);
+        }
+    }
+    debug!(
+        "Final State: {}",
+        sym_executor
+            .cur_state
+            .lookup_fmt(&sym_executor.symbolic_library.id2name)
+    );
+
+    if user_input.sample_safe_inputs() > 0 {
+        status!("{}", "🌱 Sampling Safe Inputs...".green());
+        let verification_base_config = BaseVerificationConfig {
+            target_template_name: id.to_string(),
+            prime: prime.clone(),
+            range: range.clone(),
+            quick_mode: &*user_input.search_mode == "quick",
+            heuristics_mode: true,
+            template_param_names: template.get_name_of_params().clone(),
+            template_param_values: args.clone(),
+            ..Default::default()
+        };
+        let symbolic_trace = sym_executor.cur_state.symbolic_trace.clone();
+        let side_constraints = sym_executor.cur_state.side_constraints.clone();
+        let samples = sample_safe_inputs(
+            &mut sym_executor,
+            &symbolic_trace,
+            &side_constraints,
+            &verification_base_config,
+            user_input.sample_safe_inputs(),
+        );
+        status!(
+            "{} {}/{}",
+            "🌱 Found Safe Inputs:",
+            samples.len(),
+            user_input.sample_safe_inputs()
+        );
+        let json_output = serde_json::Value::Array(
+            samples
+                .iter()
+                .map(|s| to_circom_input_json(s, &sym_executor.symbolic_library.id2name))
+                .collect(),
+        );
+        println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
+        return Result::Ok(InstanceReport::default());
+    }
+
+    if user_input.evaluate_input() != "none" {
+        status!("{}", "🧮 Evaluating Input...".green());
+        let assignment = load_input_assignment(
+            &user_input.evaluate_input(),
+            &sym_executor.symbolic_library,
+            id,
+        )
+        .map_err(ZkFuzzError::InvalidInput)?;
+        let verification_base_config = BaseVerificationConfig {
+            target_template_name: id.to_string(),
+            prime: prime.clone(),
+            range: range.clone(),
+            template_param_names: template.get_name_of_params().clone(),
+            template_param_values: args.clone(),
+            ..Default::default()
+        };
+        let conc_setting = get_default_setting_for_concrete_execution(
+            prime.clone(),
+            user_input.constraint_assert_dissabled_flag(),
+        );
+        let mut conc_executor =
+            SymbolicExecutor::new(&mut sym_executor.symbolic_library, &conc_setting);
+        let evaluation = evaluate_input(&mut conc_executor, &assignment, &verification_base_config);
+        for (name, value) in &evaluation.outputs {
+            println!(
+                "{} = {}",
+                name.lookup_fmt(&conc_executor.symbolic_library.id2name),
+                value
+            );
+        }
+        match &evaluation.violated_condition {
+            None => eprintln!(
+                "{}",
+                "🆗 The input satisfies every constraint".green().bold()
+            ),
+            Some((_, condition, location)) => eprintln!(
+                "{} {}{}",
+                "💥 Violated Condition:".red().bold(),
+                condition,
+                match location {
+                    Some(location) => format!(" at {}", location),
+                    None => String::new(),
+                }
+            ),
+        }
+        return Result::Ok(InstanceReport::default());
+    }
+
+    let mut is_safe = true;
+    let mut num_findings = 0;
+    let mut search_completeness = SearchCompleteness::Incomplete;
+    // The search may clear the state, so the abstractions are checked beforehand
+    let has_abstractions = sym_executor.has_abstractions();
+    let mut sarif_findings = Vec::new();
+    let mut reported_counter_examples = Vec::new();
+    if user_input.search_mode != "off" && !timed_out {
+        status!("{}", "══════════════════════════════════".green());
+        status!("{}", "🩺 Scanning TCCT Instances...".green());
+
+        let (main_template_name, template_param_names, template_param_values) =
+            (id, template.get_name_of_params().clone(), args.clone());
+
+        let input_constraints = if user_input.input_constraints() == "none" {
+            FxHashMap::default()
+        } else {
+            match load_input_assignment(
+                &user_input.input_constraints(),
+                &sym_executor.symbolic_library,
+                main_template_name,
+            ) {
+                Ok(input_constraints) => input_constraints,
+                Err(message) => {
+                    return Result::Err(ZkFuzzError::InvalidInputConstraints(message));
+                }
+            }
+        };
+
+        let assumptions =
+            load_assumptions(user_input, &mut sym_executor, main_template_name, prime)?;
+
+        let mut public_inputs = sym_executor
+            .public_input_ids
+            .iter()
+            .map(|id| sym_executor.symbolic_library.id2name[id].clone())
+            .collect::<Vec<_>>();
+        public_inputs.sort();
+        // The public inputs only matter to validate --input_constraints, whose values are what
+        // holds them fixed during the search
+        if user_input.input_constraints() != "none" {
+            if let Some(missing) = public_inputs.iter().find(|name| {
+                let id = sym_executor.symbolic_library.name2id[*name];
+                !input_constraints.keys().any(|n| n.id == id)
+            }) {
+                return Result::Err(ZkFuzzError::InvalidInputConstraints(format!(
+                    "no value for the public input `{}`",
+                    missing
+                )));
+            }
+            if !public_inputs.is_empty() {
+                status!(
+                    "{} {}",
+                    "🔒 Public Inputs Held:".green(),
+                    public_inputs.join(", ")
+                );
+            }
+        } else if !public_inputs.is_empty() {
+            status!(
+                "{} {} (searched, since --input_constraints gives no value)",
+                "🔓 Public Inputs:".yellow(),
+                public_inputs.join(", ")
+            );
+        }
+
+        let verification_base_config = BaseVerificationConfig {
+            target_template_name: main_template_name.to_string(),
+            prime: prime.clone(),
+            range: range.clone(),
+            quick_mode: &*user_input.search_mode == "quick",
+            heuristics_mode: &*user_input.search_mode == "heuristics",
+            template_param_names: template_param_names,
+            template_param_values: template_param_values,
+            input_constraints: input_constraints,
+            boundary_values: user_input.flag_boundary_values,
+            assumptions: assumptions,
+            max_findings: if user_input.flag_find_all {
+                user_input.max_findings().unwrap_or(usize::MAX)
+            } else {
+                1
+            },
+            ..Default::default()
+        };
+
+        let mut new_base_config = base_config.clone();
+        new_base_config.off_trace = true;
+        // The timeout only applies to gathering the constraints
+        new_base_config.deadline = None;
+        sym_executor.setting = &new_base_config;
+
+        let mut counter_examples =
+            check_unused_outputs(&mut sym_executor, &verification_base_config);
+        let mut auxiliary_result = json!({});
+        // With --find_all, the search goes on after unused outputs
+        if counter_examples.len() < verification_base_config.max_findings {
+            let subse_base_config = get_default_setting_for_concrete_execution(
+                prime.clone(),
+                user_input.constraint_assert_dissabled_flag(),
+            );
+            let mut conc_executor =
+                SymbolicExecutor::new(&mut sym_executor.symbolic_library, &subse_base_config);
+            conc_executor
+                .feed_arguments(
+                    &verification_base_config.template_param_names,
+                    &verification_base_config.template_param_values,
+                )
+                .map_err(|e| ZkFuzzError::InvalidTemplateArguments(id.clone(), e))?;
+
+            let found = match &*user_input.search_mode() {
+                "quick" => brute_force_search_all(
+                    &mut conc_executor,
+                    &sym_executor.cur_state.symbolic_trace.clone(),
+                    &sym_executor.cur_state.side_constraints.clone(),
+                    &verification_base_config,
+                ),
+                "full" => brute_force_search_all(
+                    &mut conc_executor,
+                    &sym_executor.cur_state.symbolic_trace.clone(),
+                    &sym_executor.cur_state.side_constraints.clone(),
+                    &verification_base_config,
+                ),
+                "heuristics" => brute_force_search_all(
+                    &mut conc_executor,
+                    &sym_executor.cur_state.symbolic_trace.clone(),
+                    &sym_executor.cur_state.side_constraints.clone(),
+                    &verification_base_config,
+                ),
+                #[cfg(feature = "z3")]
+                "smt" => smt_search(
+                    &mut conc_executor,
+                    &sym_executor.cur_state.symbolic_trace.clone(),
+                    &sym_executor.cur_state.side_constraints.clone(),
+                    &verification_base_config,
+                ),
+                "ga" => {
+                    let mut mutation_config = load_mutation_config(user_input)?;
+                    if mutation_config.infer_input_bit_hints {
+                        // The explicit hints take precedence over the inferred ones
+                        let mut inferred_hints: FxHashMap<String, usize> = FxHashMap::default();
+                        for (name, bits) in &sym_executor.bit_width_hints {
+                            if name.owner.len() == 1 {
+                                let hint = inferred_hints
+                                    .entry(conc_executor.symbolic_library.id2name[&name.id].clone())
+                                    .or_insert(*bits);
+                                *hint = (*hint).max(*bits);
+                            }
+                        }
+                        for (name, bits) in inferred_hints {
+                            mutation_config.input_bit_hints.entry(name).or_insert(bits);
+                        }
+                    }
+                    info!("\n{}", mutation_config);
+
+                    let registry = OperatorRegistry::default();
+                    let trace_mutation = registry
+                        .trace_mutation(&mutation_config.trace_mutation_method)
+                        .ok_or_else(|| {
+                            ZkFuzzError::InvalidConfig(format!(
+                                "`trace_mutation_method` should be one of {}",
+                                format_operator_names(&registry.trace_mutation_names())
+                            ))
+                        })?;
+                    let trace_crossover = registry
+                        .crossover(&mutation_config.crossover_method)
+                        .ok_or_else(|| {
+                            ZkFuzzError::InvalidConfig(format!(
+                                "`crossover_method` should be one of {}",
+                                format_operator_names(&registry.crossover_names())
+                            ))
+                        })?;
+                    let trace_selection = registry
+                        .selection(&mutation_config.selection_method)
+                        .ok_or_else(|| {
+                            ZkFuzzError::InvalidConfig(format!(
+                                "`selection_method` should be one of {}",
+                                format_operator_names(&registry.selection_names())
+                            ))
+                        })?;
+
+                    let update_input_fn = match mutation_config
+                        .input_initialization_method
+                        .as_str()
+                    {
+                        "random" => update_input_population_with_random_sampling,
+                        "fitness" => update_input_population_with_fitness_score,
+                        "coverage" => update_input_population_with_coverage_maximization,
+                        _ => return Result::Err(ZkFuzzError::InvalidConfig("`input_initialization_method` should be one of [`random`, `fitness`, `coverage`]".to_string()))
+                    };
+
+                    let trace_fitness_fn = match mutation_config.fitness_method.as_str() {
+                        "error" => evaluate_trace_fitness_by_error,
+                        "distance" => evaluate_trace_fitness_by_distance,
+                        _ => {
+                            return Result::Err(ZkFuzzError::InvalidConfig(
+                                "`fitness_method` should be one of [`error`, `distance`]"
+                                    .to_string(),
+                            ))
+                        }
+                    };
+
+                    let initial_population = if user_input.resume_population() == "none" {
+                        None
+                    } else {
+                        Some(
+                            load_population_from_json(&user_input.resume_population()).map_err(
+                                |e| ZkFuzzError::Io(user_input.resume_population(), e.to_string()),
+                            )?,
+                        )
+                    };
+
+                    let result = mutation_test_search_with_population(
+                        &mut conc_executor,
+                        &sym_executor.cur_state.symbolic_trace.clone(),
+                        &sym_executor.cur_state.side_constraints.clone(),
+                        &verification_base_config,
+                        &mutation_config,
+                        update_input_fn,
+                        trace_fitness_fn,
+                        simple_evolution,
+                        trace_mutation,
+                        trace_crossover,
+                        trace_selection,
+                        initial_population,
+                        &mut |event| {
+                            if user_input.flag_progress_json {
+                                report_progress_as_json_line(&event);
+                            }
+                        },
+                    );
+                    if user_input.save_population() != "none" {
+                        status!(
+                            "{} {}",
+                            "💾 Saving the population to:",
+                            user_input.save_population().cyan()
+                        );
+                        save_population_to_json(
+                            &result.final_population,
+                            &user_input.save_population(),
+                        )
+                        .map_err(|e| {
+                            ZkFuzzError::Io(user_input.save_population(), e.to_string())
+                        })?;
+                    }
+                    auxiliary_result["mutation_test_config"] =
+                        serde_json::to_value(result.mutation_config)
+                            .expect("Failed to serialize to JSON");
+                    let top_candidates: Vec<serde_json::Value> = result
+                        .top_candidates
+                        .iter()
+                        .map(|candidate| candidate.to_json(&conc_executor.symbolic_library.id2name))
+                        .collect();
+                    if result.counter_example.is_none() {
+                        for candidate in &result.top_candidates {
+                            status!(
+                                "{} fitness={} generation={} {}",
+                                "🥈 Closest Candidate:".yellow(),
+                                candidate.fitness,
+                                candidate.generation,
+                                candidate.trace_mutation
+                            );
+                        }
+                    }
+                    auxiliary_result["mutation_test_log"] = json!({"random_seed":result.random_seed,"generation":result.generation, "fitness_score_log":result.fitness_score_log, "top_candidates":top_candidates});
+                    result.search_outcome()
+                }
+                _ => {
+                    return Result::Err(ZkFuzzError::UnsupportedSearchMode(
+                        user_input.search_mode(),
+                    ))
+                }
+            };
+
+            // A search over an abstraction of the circuit proves nothing about it
+            search_completeness = if has_abstractions {
+                SearchCompleteness::Incomplete
+            } else {
+                found.completeness
+            };
+            let found: Vec<_> = found
+                .counter_examples
+                .iter()
+                .map(|ce| {
+                    shrink_counterexample(
+                        &mut conc_executor,
+                        &sym_executor.cur_state.symbolic_trace,
+                        &sym_executor.cur_state.side_constraints,
+                        ce,
+                        &verification_base_config,
+                    )
+                })
+                .collect();
+
+            for mut ce in found {
+                ce.explain(&sym_executor.cur_state);
+                if user_input.flag_track_field_reductions {
+                    let mut tracking_config = subse_base_config.clone();
+                    tracking_config.track_field_reductions = true;
+                    let mut tracking_executor =
+                        SymbolicExecutor::new(&mut sym_executor.symbolic_library, &tracking_config);
+                    ce.field_reductions = replay_field_reductions(
+                        &mut tracking_executor,
+                        &ce.assignment,
+                        &verification_base_config,
+                    );
+                }
+                // Shrinking may turn two findings into the same one
+                push_distinct_finding(&mut counter_examples, ce);
+            }
+        }
+        let mut json_outputs = Vec::new();
+        for (i, ce) in counter_examples.iter().enumerate() {
+            is_safe = false;
+            num_findings += 1;
+            sarif_findings.push(SarifFinding::from_counter_example(
+                ce,
+                &sym_executor.symbolic_library,
+                id,
+            ));
+            if user_input.report() != "none" {
+                reported_counter_examples.push(ce.to_json_with_meta(
+                    &sym_executor.symbolic_library.id2name,
+                    &FxHashMap::default(),
+                ));
+            }
+            // The reproduction is emitted for the first counterexample only
+            if i == 0 && user_input.emit_repro() != "none" {
+                status!(
+                    "{} {}",
+                    "💾 Saving the reproduction to:",
+                    user_input.emit_repro().cyan()
+                );
+                let input_ids = &sym_executor.symbolic_library.template_library
+                    [&sym_executor.symbolic_library.name2id[id]]
+                    .input_ids;
+                let circuit =
+                    parser_user::strip_main_component(user_input.input_file()).map_err(|e| {
+                        ZkFuzzError::Io(user_input.input_file().to_string(), e.to_string())
+                    })?;
+                emit_repro(
+                    &user_input.emit_repro(),
+                    &circuit,
+                    &ce.to_circom_test_harness(
+                        &sym_executor.symbolic_library.id2name,
+                        "circuit.circom",
+                        id,
+                        &template_args,
+                    ),
+                    &ce.to_circom_input_json(&sym_executor.symbolic_library.id2name, input_ids),
+                )
+                .map_err(|e| ZkFuzzError::Io(user_input.emit_repro(), e.to_string()))?;
+            }
+            let json_output = if user_input.output_format() == "circom" {
+                let input_ids = &sym_executor.symbolic_library.template_library
+                    [&sym_executor.symbolic_library.name2id[id]]
+                    .input_ids;
+                Some(ce.to_circom_input_json(&sym_executor.symbolic_library.id2name, input_ids))
+            } else if user_input.flag_save_output {
+                let ce_meta = FxHashMap::from_iter([
+                    (
+                        "0_target_path".to_string(),
+                        user_input.input_file().to_string(),
+                    ),
+                    ("1_main_template".to_string(), id.to_string()),
+                    ("2_search_mode".to_string(), user_input.search_mode()),
+                    (
+                        "3_execution_time".to_string(),
+                        format!("{:?}", start_time.elapsed()),
+                    ),
+                    (
+                        "4_git_hash_of_zkfuzz".to_string(),
+                        format!("{}", option_env!("GIT_HASH").unwrap_or("unknown")),
+                    ),
+                ]);
+
+                let mut json_output =
+                    ce.to_json_with_meta(&sym_executor.symbolic_library.id2name, &ce_meta);
+                json_output["8_auxiliary_result"] = auxiliary_result.clone();
+                Some(json_output)
+            } else {
+                None
+            };
+
+            match json_output {
+                Some(json_output) => json_outputs.push(json_output),
+                None => {
+                    status!("{}", ce.lookup_fmt(&sym_executor.symbolic_library.id2name));
+                }
+            }
+        }
+        if user_input.output_encoding() == "cbor" {
+            if !counter_examples.is_empty() {
+                let file_path = output_file_path(user_input.input_file(), "_counterexample.cbor");
+                status!("{} {}", "💾 Saving the output to:", file_path.cyan());
+                save_counter_examples_to_cbor(&counter_examples, &file_path)
+                    .map_err(|e| ZkFuzzError::Io(file_path.clone(), e))?;
+            }
+        } else if !json_outputs.is_empty() {
+            let json_output = if user_input.flag_find_all {
+                serde_json::Value::Array(json_outputs)
+            } else {
+                json_outputs.remove(0)
+            };
+            if user_input.flag_save_output {
+                // Save the output as JSON
+                let file_path = output_file_path(
+                    user_input.input_file(),
+                    if user_input.output_format() == "circom" {
+                        "_input.json"
+                    } else {
+                        "_counterexample.json"
+                    },
+                );
+                status!("{} {}", "💾 Saving the output to:", file_path.cyan(),);
+
+                let json_string = serde_json::to_string_pretty(&json_output).unwrap();
+                File::create(&file_path)
+                    .and_then(|mut file| file.write_all(json_string.as_bytes()))
+                    .map_err(|e| ZkFuzzError::Io(file_path.clone(), e.to_string()))?;
+            } else {
+                println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
+            }
+        }
+    }
+
+    let result = if timed_out {
+        "timeout"
+    } else if !is_safe {
+        "unsafe"
+    } else if !contradictions.is_empty() {
+        "unsatisfiable"
+    } else if user_input.search_mode == "off" {
+        "unchecked"
+    } else {
+        "safe"
+    };
+    // The warnings only fail the run with --warn_as_error
+    num_findings += contradictions.len()
+        + free_witnesses.len()
+        + dangling_intermediates.len()
+        + potential_div_by_zeros.len()
+        + unconstrained_assignments.len()
+        + sym_executor.bit_width_warnings.len();
+    if user_input.flag_quiet {
+        eprintln!(
+            "result={} compression={:.1}% time={:.1}s",
+            result,
+            (ss.total_constraints as f64 / ts.total_constraints as f64) * 100 as f64,
+            start_time.elapsed().as_secs_f64()
+        );
+    }
+    status!(
+        "{}",
+        "╔═══════════════════════════════════════════════════════════════╗".green()
+    );
+    status!(
+        "{}",
+        "║                        zkFuzz Report                        ║".green()
+    );
+    status!(
+        "{}",
+        "╚═══════════════════════════════════════════════════════════════╝".green()
+    );
+    status!("{}", "📊 Execution Summary:".cyan().bold());
+    status!(" ├─ Prime Number      : {}", prime);
+    status!(" ├─ Field Bit Width   : {}", prime.bits());
+    status!(
+        " ├─ Compression Rate  : {:.2}% ({}/{})",
+        (ss.total_constraints as f64 / ts.total_constraints as f64) * 100 as f64,
+        ss.total_constraints,
+        ts.total_constraints
+    );
+    status!(
+        " ├─ Verification      : {}",
+        if timed_out {
+            "⏱️ Skipped (Timed Out)".yellow().bold()
+        } else if !is_safe {
+            "💥 NOT SAFE 💥".red().bold()
+        } else if !contradictions.is_empty() {
+            "⛔ UNSATISFIABLE (No Witness Exists) ⛔".red().bold()
+        } else if user_input.search_mode == "off" {
+            "⏭️ Skipped (search disabled)".yellow().bold()
+        } else if search_completeness == SearchCompleteness::Exhaustive {
+            format!("🆗 {}", search_completeness.verdict())
+                .green()
+                .bold()
+        } else {
+            format!("🆗 {}", search_completeness.verdict())
+                .yellow()
+                .bold()
+        }
+    );
+    status!(
+        " ├─ Free Witnesses    : {}",
+        if free_witnesses.is_empty() {
+            "-".to_string()
+        } else {
+            free_witnesses
+                .iter()
+                .map(|n| n.lookup_fmt(&sym_executor.symbolic_library.id2name))
+                .collect::<Vec<_>>()
+                .join(", ")
+                .yellow()
+                .to_string()
+        }
+    );
+    status!(
+        " ├─ Dangling Signals  : {}",
+        if dangling_intermediates.is_empty() {
+            "-".to_string()
+        } else {
+            dangling_intermediates
+                .iter()
+                .map(|n| n.lookup_fmt(&sym_executor.symbolic_library.id2name))
+                .collect::<Vec<_>>()
+                .join(", ")
+                .yellow()
+                .to_string()
+        }
+    );
+    status!(
+        " ├─ Div-by-Zero Risks : {}",
+        if potential_div_by_zeros.is_empty() {
+            "-".to_string()
+        } else {
+            potential_div_by_zeros
+                .iter()
+                .map(|d| d.lookup_fmt(&sym_executor.symbolic_library.id2name))
+                .collect::<Vec<_>>()
+                .join(", ")
+                .yellow()
+                .to_string()
+        }
+    );
+    status!(" └─ Execution Time    : {:?}", start_time.elapsed());
+
+    let report = if user_input.report() != "none" {
+        let lookup = &sym_executor.symbolic_library.id2name;
+        Some(json!({
+            "target_path": user_input.input_file(),
+            "main_template": id,
+            "template_params": param_binding
+                .iter()
+                .map(|(name, value)| (name.clone(), json!(value.to_string())))
+                .collect::<serde_json::Map<_, _>>(),
+            "search_mode": user_input.search_mode(),
+            "git_hash_of_zkfuzz": option_env!("GIT_HASH").unwrap_or("unknown"),
+            "prime": prime.to_string(),
+            "result": result,
+            "exhaustive": result == "safe"
+                && search_completeness == SearchCompleteness::Exhaustive,
+            "compression_rate": ss.total_constraints as f64
+                / ts.total_constraints as f64,
+            "free_witnesses": free_witnesses
+                .iter()
+                .map(|n| n.lookup_fmt(lookup))
+                .collect::<Vec<_>>(),
+            "dangling_signals": dangling_intermediates
+                .iter()
+                .map(|n| n.lookup_fmt(lookup))
+                .collect::<Vec<_>>(),
+            "potential_div_by_zeros": potential_div_by_zeros
+                .iter()
+                .map(|d| d.lookup_fmt(lookup))
+                .collect::<Vec<_>>(),
+            "stats": {
+                "trace": ts.to_json(),
+                "side_constraints": ss.to_json(),
+                "execution": execution_metrics.to_json(),
+            },
+            "execution_time_secs": start_time.elapsed().as_secs_f64(),
+            "counter_examples": reported_counter_examples,
+        }))
+    } else {
+        None
+    };
+
+    if user_input.output_format() == "sarif" {
+        sarif_findings.extend(dangling_intermediates.iter().map(|name| {
+            SarifFinding::from_dangling_intermediate(name, &sym_executor.symbolic_library, id)
+        }));
+        sarif_findings.extend(
+            potential_div_by_zeros.iter().map(|d| {
+                SarifFinding::from_potential_div_by_zero(d, &sym_executor.symbolic_library)
+            }),
+        );
+        let run_properties = FxHashMap::from_iter([
+            (
+                "target_path".to_string(),
+                user_input.input_file().to_string(),
+            ),
+            ("main_template".to_string(), id.to_string()),
+            ("search_mode".to_string(), user_input.search_mode()),
+            (
+                "git_hash_of_zkfuzz".to_string(),
+                format!("{}", option_env!("GIT_HASH").unwrap_or("unknown")),
+            ),
+        ]);
+        let sarif_log = to_sarif_log(&sarif_findings, user_input.input_file(), &run_properties);
+        println!("{}", serde_json::to_string_pretty(&sarif_log).unwrap());
+    }
+
+    if user_input.flag_printout_stats && user_input.stats_format() == "json" {
+        let json_output = json!({
+            "trace": ts.to_json(),
+            "side_constraints": ss.to_json(),
+            "execution": execution_metrics.to_json(),
+        });
+        println!("{}", serde_json::to_string_pretty(&json_output).unwrap());
+    } else if user_input.flag_printout_stats && user_input.stats_format() == "pretty" {
+        println!(
+            "\n{}",
+            "🪶 Stats of Symbolic Trace  ══════════════════════"
+                .yellow()
+                .bold()
+        );
+        print_constraint_summary_statistics_pretty(&ts);
+        println!(
+            "\n{}",
+            "⛓️ Stats of Side Constraint ══════════════════════"
+                .yellow()
+                .bold()
+        );
+        print_constraint_summary_statistics_pretty(&ss);
+        println!(
+            "\n{}",
+            "🧮 Stats of Execution ══════════════════════"
+                .yellow()
+                .bold()
+        );
+        print_execution_metrics_pretty(&execution_metrics);
+    } else if user_input.flag_printout_stats || user_input.flag_printout_stats_csv {
+        println!("{}", CONSTRAINT_STATISTICS_CSV_HEADERS.join(","));
+        print_constraint_summary_statistics_csv(&ts);
+        print_constraint_summary_statistics_csv(&ss);
+    }
+    if user_input.flag_per_template_stats {
+        println!(
+            "\n{}",
+            "🧩 Stats per Template ══════════════════════"
+                .yellow()
+                .bold()
+        );
+        let counts = count_constraints_per_template(
+            &sym_executor.cur_state.symbolic_trace,
+            &sym_executor.cur_state.side_constraints,
+            &sym_executor.component_templates,
+            &sym_executor.symbolic_library,
+            id,
+        );
+        for (template_name, c) in counts {
+            println!(
+                " • {:<20}: {:.2}% ({}/{})",
+                template_name,
+                c.compression_rate() * 100 as f64,
+                c.side_constraints,
+                c.trace_constraints
+            );
+        }
+    }
+    status!(
+        "{}",
+        "════════════════════════════════════════════════════════════════".green()
+    );
+
+    Result::Ok(InstanceReport {
+        report,
+        num_findings,
+    })
+}
+
+fn start() -> Result<(), ZkFuzzError> {
     let start_time = time::Instant::now();
     //use compilation_user::CompilerConfig;
 
-    let user_input = Input::new()?;
-    let mut program_archive = parser_user::parse_project(&user_input)?;
-    type_analysis_user::analyse_project(&mut program_archive)?;
+    let user_input = Input::new().map_err(|_| ZkFuzzError::InvalidArguments)?;
+    if user_input.flag_dump_default_mutation_config {
+        println!("{}", MutationConfig::default_json());
+        return Result::Ok(());
+    }
+    configure_color_output(user_input.flag_no_color);
+    QUIET.store(user_input.flag_quiet, Ordering::Relaxed);
+    display_zkfuzz_header();
+
+    let prime = resolve_prime(&user_input)?;
+    let range = parse_range(&user_input.heuristics_range())?;
+    check_search_mode(&user_input.search_mode())?;
+    configure_thread_pool(user_input.threads())?;
+    let whitelist_mode = parse_whitelist_mode(&user_input.whitelist_mode())?;
+    let param_bounds = parse_param_bounds(user_input.param_bounds())?;
+    let templates_filter = parse_templates_filter(&user_input.templates_filter())?;
+    if !param_bounds.is_empty()
+        && !user_input.flag_symbolic_template_params
+        && !user_input.flag_mutate_template_params
+    {
+        return Result::Err(ZkFuzzError::InvalidParamBounds(
+            "--param_bounds requires --symbolic_template_params or --mutate_template_params"
+                .to_string(),
+        ));
+    }
+    if user_input.flag_mutate_template_params && param_bounds.is_empty() {
+        return Result::Err(ZkFuzzError::InvalidParamBounds(
+            "--mutate_template_params requires --param_bounds".to_string(),
+        ));
+    }
+    // The circuit is executed again for each combination, so only small grids are allowed
+    let num_bindings = count_param_bindings(&param_bounds);
+    if num_bindings > BigInt::from(MAX_PARAM_BINDINGS) {
+        return Result::Err(ZkFuzzError::InvalidParamBounds(format!(
+            "at most {} combinations are explored, but --param_bounds gives {}",
+            MAX_PARAM_BINDINGS, num_bindings
+        )));
+    }
+
+    let mut program_archive =
+        parser_user::parse_project(&user_input).map_err(|_| ZkFuzzError::Parse(Vec::new()))?;
+    type_analysis_user::analyse_project(&mut program_archive)
+        .map_err(|_| ZkFuzzError::TypeCheck(Vec::new()))?;
 
     if user_input.show_stats_of_ast {
-        show_stats(&program_archive);
+        show_stats(&program_archive, &templates_filter);
         return Result::Ok(());
     }
 
     env_logger::init();
 
-    eprintln!("{}", "🧾 Loading Whitelists...".green());
+    status!("{}", "🧾 Loading Whitelists...".green());
     let whitelist = if user_input.path_to_whitelist() == "none" {
         FxHashSet::from_iter(["IsZero".to_string(), "Num2Bits".to_string()])
     } else {
-        FxHashSet::from_iter(
-            read_file_to_lines(&&&user_input.path_to_whitelist())
-                .unwrap()
-                .into_iter(),
-        )
+        load_whitelist(&user_input.path_to_whitelist())?
     };
 
+    if user_input.flag_analyze_all_templates {
+        return analyze_all_templates_and_report(&user_input, whitelist, whitelist_mode, prime);
+    }
+
     let mut symbolic_library = SymbolicLibrary {
         template_library: FxHashMap::default(),
         name2id: FxHashMap::default(),
         id2name: FxHashMap::default(),
         function_library: FxHashMap::default(),
+        bus_library: FxHashMap::default(),
         function_counter: FxHashMap::default(),
+        source_files: FxHashMap::default(),
+        name_interner: SymbolicNameInterner::default(),
     };
+    symbolic_library.register_source_files(&program_archive.file_library);
 
-    eprintln!("{}", "🧩 Parsing Templates...".green());
+    status!("{}", "🧩 Parsing Templates...".green());
     let mut templates_names = program_archive
         .templates
         .keys()
@@ -162,12 +1603,12 @@ fn start() -> Result<(), ()> {
             user_input.lessthan_dissabled_flag,
         );
 
-        if user_input.flag_printout_ast {
-            eprintln!(
-                "{}{} {}{}",
-                BACK_GRAY_SCRIPT_BLACK, "🌳 AST Tree for", k, RESET
+        if user_input.flag_printout_ast && is_selected_template(&templates_filter, &k) {
+            status!(
+                "{}",
+                format!("🌳 AST Tree for {}", k).black().on_bright_black()
             );
-            eprintln!(
+            status!(
                 "{}",
                 symbolic_library.template_library[&symbolic_library.name2id[&k]]
                     .body
@@ -179,7 +1620,7 @@ fn start() -> Result<(), ()> {
         }
     }
 
-    eprintln!("{}", "⚙️ Parsing Function...".green());
+    status!("{}", "⚙️ Parsing Function...".green());
     let mut function_names = program_archive
         .functions
         .keys()
@@ -192,11 +1633,11 @@ fn start() -> Result<(), ()> {
         symbolic_library.register_function(k.clone(), body.clone(), v.get_name_of_params());
 
         if user_input.flag_printout_ast {
-            eprintln!(
-                "{}{} {}{}",
-                BACK_GRAY_SCRIPT_BLACK, "🌴 AST Tree for", k, RESET
+            status!(
+                "{}",
+                format!("🌴 AST Tree for {}", k).black().on_bright_black()
             );
-            eprintln!(
+            status!(
                 "{}",
                 symbolic_library.function_library[&symbolic_library.name2id[&k]]
                     .body
@@ -208,330 +1649,86 @@ fn start() -> Result<(), ()> {
         }
     }
 
-    let base_config = get_default_setting_for_symbolic_execution(
-        BigInt::from_str(&user_input.debug_prime()).unwrap(),
+    let mut bus_names = program_archive.buses.keys().cloned().collect::<Vec<_>>();
+    bus_names.sort();
+    for k in bus_names {
+        let v = program_archive.buses.get(&k).unwrap();
+        symbolic_library.register_bus(k.clone(), v.get_body().clone(), v.get_name_of_params());
+    }
+
+    let mut base_config = get_default_setting_for_symbolic_execution(
+        prime.clone(),
         user_input.constraint_assert_dissabled_flag(),
     );
-    let mut sym_executor = SymbolicExecutor::new(&mut symbolic_library, &base_config);
-
-    match &program_archive.initial_template_call {
-        Expression::Call { id, args, .. } => {
-            let template = program_archive.templates[id].clone();
-
-            eprintln!("{}", "🛒 Gathering Trace/Side Constraints...".green());
-
-            sym_executor.symbolic_library.name2id.insert(
-                "main".to_string(),
-                sym_executor.symbolic_library.name2id.len(),
-            );
-            sym_executor.symbolic_library.id2name.insert(
-                sym_executor.symbolic_library.name2id["main"],
-                "main".to_string(),
-            );
-
-            sym_executor.cur_state.add_owner(&OwnerName {
-                id: sym_executor.symbolic_library.name2id["main"],
-                counter: 0,
-                access: None,
-            });
-            sym_executor
-                .cur_state
-                .set_template_id(sym_executor.symbolic_library.name2id[id]);
-
-            if !user_input.flag_symbolic_template_params {
-                sym_executor.feed_arguments(template.get_name_of_params(), args);
-            }
-
-            let body = sym_executor.symbolic_library.template_library
-                [&sym_executor.symbolic_library.name2id[id]]
-                .body
-                .clone();
-            sym_executor.execute(&body, 0);
-
-            eprintln!("{}", "══════════════════════════════════".green());
-            let mut ts = ConstraintStatistics::new();
-            let mut ss = ConstraintStatistics::new();
-            for c in &sym_executor.cur_state.symbolic_trace {
-                ts.update(c);
-            }
-            for c in &sym_executor.cur_state.side_constraints {
-                ss.update(c);
-            }
-            debug!(
-                "Final State: {}",
-                sym_executor
-                    .cur_state
-                    .lookup_fmt(&sym_executor.symbolic_library.id2name)
-            );
-
-            let mut is_safe = true;
-            if user_input.search_mode != "off" {
-                eprintln!("{}", "══════════════════════════════════".green());
-                eprintln!("{}", "🩺 Scanning TCCT Instances...".green());
-
-                let (main_template_name, template_param_names, template_param_values) =
-                    match &program_archive.initial_template_call {
-                        Expression::Call { id, args, .. } => {
-                            let template = &program_archive.templates[id];
-                            (id, template.get_name_of_params().clone(), args.clone())
-                        }
-                        _ => unimplemented!(),
-                    };
+    base_config.simplify_to_fixpoint = user_input.flag_simplify_to_fixpoint;
+    base_config.max_component_depth = user_input.limit_depth();
+    base_config.whitelist_mode = whitelist_mode;
+    base_config.precise_primitives = user_input.flag_precise_primitives;
+    base_config.dedup_constraints = user_input.flag_dedup_constraints;
+    base_config.deadline = user_input
+        .exec_timeout_secs()
+        .map(|secs| time::Instant::now() + time::Duration::from_secs(secs));
+    let diff_base = if user_input.diff_against() == "none" {
+        None
+    } else {
+        status!(
+            "{} {}",
+            "🔀 Gathering Side Constraints of:".green(),
+            user_input.diff_against().cyan()
+        );
+        let config = AnalysisConfig {
+            prime: prime.clone(),
+            link_libraries: user_input.get_link_libraries().clone(),
+            whitelist: whitelist.clone(),
+            whitelist_mode: whitelist_mode,
+            constraint_assert_dissabled: user_input.constraint_assert_dissabled_flag(),
+            lessthan_dissabled: user_input.lessthan_dissabled_flag,
+            ..AnalysisConfig::default()
+        };
+        Some(gather_side_constraints(
+            &user_input.diff_against(),
+            &config,
+        )?)
+    };
 
-                let verification_base_config = BaseVerificationConfig {
-                    target_template_name: main_template_name.to_string(),
-                    prime: BigInt::from_str(&user_input.debug_prime()).unwrap(),
-                    range: BigInt::from_str(&user_input.heuristics_range()).unwrap(),
-                    quick_mode: &*user_input.search_mode == "quick",
-                    heuristics_mode: &*user_input.search_mode == "heuristics",
-                    progress_interval: 10000,
-                    template_param_names: template_param_names,
-                    template_param_values: template_param_values,
-                };
-
-                let mut new_base_config = base_config.clone();
-                new_base_config.off_trace = true;
-                sym_executor.setting = &new_base_config;
-
-                let mut counter_example =
-                    check_unused_outputs(&mut sym_executor, &verification_base_config);
-                let mut auxiliary_result = json!({});
-                if let Some(_) = &counter_example {
-                    is_safe = false;
-                } else {
-                    let subse_base_config = get_default_setting_for_concrete_execution(
-                        BigInt::from_str(&user_input.debug_prime()).unwrap(),
-                        user_input.constraint_assert_dissabled_flag(),
-                    );
-                    let mut conc_executor = SymbolicExecutor::new(
-                        &mut sym_executor.symbolic_library,
-                        &subse_base_config,
-                    );
-                    conc_executor.feed_arguments(
-                        &verification_base_config.template_param_names,
-                        &verification_base_config.template_param_values,
-                    );
+    let context = InstanceContext {
+        user_input: &user_input,
+        program_archive: &program_archive,
+        symbolic_library: &symbolic_library,
+        base_config: &base_config,
+        diff_base: &diff_base,
+        prime: &prime,
+        range: &range,
+        start_time,
+    };
+    let param_bindings = enumerate_param_bindings(&param_bounds);
+    // One report per instance of the main template, written once every instance is analyzed
+    let mut reports = Vec::new();
+    let mut num_findings = 0;
+    for param_binding in &param_bindings {
+        let instance_report = analyze_instance(&context, param_binding)?;
+        reports.extend(instance_report.report);
+        num_findings += instance_report.num_findings;
+    }
 
-                    counter_example = match &*user_input.search_mode() {
-                        "quick" => brute_force_search(
-                            &mut conc_executor,
-                            &sym_executor.cur_state.symbolic_trace.clone(),
-                            &sym_executor.cur_state.side_constraints.clone(),
-                            &verification_base_config,
-                        ),
-                        "full" => brute_force_search(
-                            &mut conc_executor,
-                            &sym_executor.cur_state.symbolic_trace.clone(),
-                            &sym_executor.cur_state.side_constraints.clone(),
-                            &verification_base_config,
-                        ),
-                        "heuristics" => brute_force_search(
-                            &mut conc_executor,
-                            &sym_executor.cur_state.symbolic_trace.clone(),
-                            &sym_executor.cur_state.side_constraints.clone(),
-                            &verification_base_config,
-                        ),
-                        "ga" => {
-                            let mutation_config =
-                                load_config_from_json(&&user_input.path_to_mutation_setting())
-                                    .unwrap();
-                            info!("\n{}", mutation_config);
-
-                            let trace_initialization_fn = match mutation_config.trace_mutation_method.as_str() {
-                                "naive" => initialize_population_with_constant_replacement,
-                                "constant" => initialize_population_with_constant_replacement,
-                                "constant_operator" => initialize_population_with_operator_or_const_replacement,
-                                "constant_operator_add" => initialize_population_with_operator_or_const_replacement_or_addition,
-                                "constant_operator_delete" => initialize_population_with_operator_or_const_replacement_or_deletion,
-                                _ => panic!("`trace_mutation_method` should be one of [`constant`, `constant_operator`, `constant_operator_add`, `constant_operator_delete`]")
-                            };
-
-                            let trace_mutation_fn = match mutation_config.trace_mutation_method.as_str() {
-                                "naive" => mutate_trace_with_constant_replacement,
-                                "constant" => mutate_trace_with_constant_replacement,
-                                "constant_operator" => mutate_trace_with_operator_or_const_replacement,
-                                "constant_operator_add" => mutate_trace_with_operator_or_const_replacement_or_addition,
-                                "constant_operator_delete" => mutate_trace_with_operator_or_const_replacement_or_deletion,
-                                _ => panic!("`trace_mutation_method` should be one of [`constant`, `constant_operator`, `constant_operator_add`, `constant_operator_delete`]")
-                            };
-
-                            let update_input_fn = match mutation_config
-                                .input_initialization_method
-                                .as_str()
-                            {
-                                "random" => update_input_population_with_random_sampling,
-                                "fitness" => update_input_population_with_fitness_score,
-                                "coverage" => update_input_population_with_coverage_maximization,
-                                _ => panic!("`input_initialization_method` should be one of [`random`, `fitness`, `coverage`]")
-                            };
-
-                            let result = mutation_test_search(
-                                &mut conc_executor,
-                                &sym_executor.cur_state.symbolic_trace.clone(),
-                                &sym_executor.cur_state.side_constraints.clone(),
-                                &verification_base_config,
-                                &mutation_config,
-                                trace_initialization_fn,
-                                update_input_fn,
-                                evaluate_trace_fitness_by_error,
-                                simple_evolution,
-                                trace_mutation_fn,
-                                random_crossover,
-                                roulette_selection,
-                            );
-                            auxiliary_result["mutation_test_config"] =
-                                serde_json::to_value(result.mutation_config)
-                                    .expect("Failed to serialize to JSON");
-                            auxiliary_result["mutation_test_log"] = json!({"random_seed":result.random_seed,"generation":result.generation, "fitness_score_log":result.fitness_score_log});
-                            result.counter_example
-                        }
-                        _ => panic!(
-                            "search_mode={} is not supported",
-                            user_input.search_mode.to_string()
-                        ),
-                    };
-                }
-                if let Some(ce) = &counter_example {
-                    is_safe = false;
-                    if user_input.flag_save_output {
-                        // Save the output as JSON
-                        let ce_meta = FxHashMap::from_iter([
-                            (
-                                "0_target_path".to_string(),
-                                user_input.input_file().to_string(),
-                            ),
-                            ("1_main_template".to_string(), id.to_string()),
-                            ("2_search_mode".to_string(), user_input.search_mode()),
-                            (
-                                "3_execution_time".to_string(),
-                                format!("{:?}", start_time.elapsed()),
-                            ),
-                            (
-                                "4_git_hash_of_zkfuzz".to_string(),
-                                format!("{}", option_env!("GIT_HASH").unwrap_or("unknown")),
-                            ),
-                        ]);
-
-                        let mut json_output =
-                            ce.to_json_with_meta(&sym_executor.symbolic_library.id2name, &ce_meta);
-                        json_output["8_auxiliary_result"] = auxiliary_result;
-
-                        let mut file_path = user_input.input_file().to_string();
-                        file_path.push('_');
-                        let random_string: String = thread_rng()
-                            .sample_iter(&Alphanumeric)
-                            .take(10)
-                            .map(char::from)
-                            .collect();
-                        file_path.push_str(&random_string);
-                        file_path.push_str("_counterexample.json");
-                        eprintln!("{} {}", "💾 Saving the output to:", file_path.cyan(),);
-
-                        let mut file = File::create(file_path).expect("Unable to create file");
-                        let json_string = serde_json::to_string_pretty(&json_output).unwrap();
-                        file.write_all(json_string.as_bytes())
-                            .expect("Unable to write data");
-                    } else {
-                        eprintln!("{}", ce.lookup_fmt(&sym_executor.symbolic_library.id2name));
-                    }
-                }
-            }
+    if user_input.report() != "none" && !reports.is_empty() {
+        status!(
+            "{} {}",
+            "💾 Saving the report to:",
+            user_input.report().cyan()
+        );
+        // One entry per instance of the main template, so that the shape does not depend on
+        // `--param_bounds`
+        let report = json!({ "instances": reports });
+        std::fs::write(
+            user_input.report(),
+            serde_json::to_string_pretty(&report).unwrap(),
+        )
+        .map_err(|e| ZkFuzzError::Io(user_input.report(), e.to_string()))?;
+    }
 
-            eprintln!(
-                "{}",
-                "╔═══════════════════════════════════════════════════════════════╗".green()
-            );
-            eprintln!(
-                "{}",
-                "║                        zkFuzz Report                        ║".green()
-            );
-            eprintln!(
-                "{}",
-                "╚═══════════════════════════════════════════════════════════════╝".green()
-            );
-            eprintln!("{}", "📊 Execution Summary:".cyan().bold());
-            eprintln!(" ├─ Prime Number      : {}", user_input.debug_prime());
-            eprintln!(
-                " ├─ Compression Rate  : {:.2}% ({}/{})",
-                (ss.total_constraints as f64 / ts.total_constraints as f64) * 100 as f64,
-                ss.total_constraints,
-                ts.total_constraints
-            );
-            eprintln!(
-                " ├─ Verification      : {}",
-                if is_safe {
-                    "🆗 No Counter Example Found".green().bold()
-                } else {
-                    "💥 NOT SAFE 💥".red().bold()
-                }
-            );
-            eprintln!(" └─ Execution Time    : {:?}", start_time.elapsed());
-
-            if user_input.flag_printout_stats {
-                println!(
-                    "\n{}",
-                    "🪶 Stats of Symbolic Trace  ══════════════════════"
-                        .yellow()
-                        .bold()
-                );
-                print_constraint_summary_statistics_pretty(&ts);
-                println!(
-                    "\n{}",
-                    "⛓️ Stats of Side Constraint ══════════════════════"
-                        .yellow()
-                        .bold()
-                );
-                print_constraint_summary_statistics_pretty(&ss);
-            } else if user_input.flag_printout_stats_csv {
-                let headers = vec![
-                    "Total_Constraints",
-                    "Constant_Counts",
-                    "Conditional_Counts",
-                    "Array_Counts",
-                    "Avg_Depth",
-                    "Max_Depth",
-                    "Count_Assign",
-                    "Count_AssignEq",
-                    "Count_AssignCall",
-                    "Count_QuadZeroDiv",
-                    "Count_Mul",
-                    "Count_Div",
-                    "Count_Add",
-                    "Count_Sub",
-                    "Count_Pow",
-                    "Count_IntDiv",
-                    "Count_Mod",
-                    "Count_ShiftL",
-                    "Count_ShiftR",
-                    "Count_LesserEq",
-                    "Count_GreaterEq",
-                    "Count_Lesser",
-                    "Count_Greater",
-                    "Count_Eq",
-                    "Count_NotEq",
-                    "Count_BoolOr",
-                    "Count_BoolAnd",
-                    "Count_BitOr",
-                    "Count_BitAnd",
-                    "Count_BitXor",
-                    "Number_of_Variable",
-                    "Variable_Avg_Count",
-                    "Variable_Max_Count",
-                    "Function_Avg_Count",
-                    "Function_Max_Count",
-                ];
-                println!("{}", headers.join(","));
-                print_constraint_summary_statistics_csv(&ts);
-                print_constraint_summary_statistics_csv(&ss);
-            }
-            eprintln!(
-                "{}",
-                "════════════════════════════════════════════════════════════════".green()
-            );
-        }
-        _ => {
-            warn!("Cannot Find Main Call");
-        }
+    if user_input.flag_warn_as_error && num_findings > 0 {
+        return Result::Err(ZkFuzzError::FindingsAsErrors(num_findings));
     }
 
     Result::Ok(())
@@ -1,17 +1,27 @@
 use std::collections::HashSet;
 use std::io;
 use std::io::Write;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use num_bigint_dig::BigInt;
 use num_traits::{One, Zero};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use rustc_hash::FxHashMap;
 
 use crate::executor::symbolic_execution::SymbolicExecutor;
+#[cfg(feature = "parallel")]
+use crate::executor::symbolic_setting::SymbolicExecutorSetting;
 use crate::executor::symbolic_value::{extract_variables, SymbolicName, SymbolicValueRef};
+#[cfg(feature = "parallel")]
+use crate::executor::symbolic_value::{SymbolicLibrary, SymbolicNameInterner};
+use crate::mutator::compiled_trace::CompiledConstraints;
+#[cfg(feature = "parallel")]
+use crate::mutator::utils::UnderConstrainedType;
 use crate::mutator::utils::{
-    is_vulnerable, verify_assignment, BaseVerificationConfig, CounterExample, VerificationResult,
+    is_vulnerable, push_distinct_finding, verify_compiled_assignment, BaseVerificationConfig,
+    CounterExample, SearchCompleteness, SearchOutcome, VerificationResult,
 };
 
 /// Performs a brute-force search over variable assignments to evaluate constraints.
@@ -30,6 +40,90 @@ pub fn brute_force_search(
     side_constraints: &Vec<SymbolicValueRef>,
     base_config: &BaseVerificationConfig,
 ) -> Option<CounterExample> {
+    collect_counter_examples(sexe, symbolic_trace, side_constraints, base_config, 1)
+        .counter_examples
+        .pop()
+}
+
+/// Variant of `brute_force_search` that keeps enumerating after a counterexample, until
+/// `max_findings` of `base_config` distinct ones are found or every assignment is tried.
+///
+/// # Returns
+/// The distinct counterexamples, in the order in which they were found, as decided by
+/// `CounterExample::is_same_finding`. The search is exhaustive only when neither `quick_mode`
+/// nor `heuristics_mode` is set, i.e., when every value of the field was tried for every
+/// variable.
+pub fn brute_force_search_all(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+) -> SearchOutcome {
+    collect_counter_examples(
+        sexe,
+        symbolic_trace,
+        side_constraints,
+        base_config,
+        base_config.max_findings,
+    )
+}
+
+fn collect_counter_examples(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+    max_findings: usize,
+) -> SearchOutcome {
+    let variables = gather_variables(symbolic_trace, side_constraints, base_config);
+
+    let mut assignment = base_config.input_constraints.clone();
+    let current_iteration = Arc::new(AtomicUsize::new(0));
+    let stop = AtomicBool::new(false);
+    let mut findings = Vec::new();
+
+    let symbolic_trace = CompiledConstraints::new(symbolic_trace);
+    let side_constraints = CompiledConstraints::new(side_constraints);
+    let context = SearchContext {
+        symbolic_trace: &symbolic_trace,
+        side_constraints: &side_constraints,
+        base_config,
+        variables: &variables,
+        current_iteration: &current_iteration,
+        stop: &stop,
+        max_findings,
+    };
+    let is_interrupted = search(sexe, &context, 0, &mut assignment, &mut findings);
+
+    print_search_summary(&current_iteration, base_config, variables.len(), &findings);
+    SearchOutcome {
+        counter_examples: findings,
+        completeness: completeness_of(base_config, is_interrupted),
+    }
+}
+
+/// Returns whether a search covered every assignment, given whether it was interrupted after
+/// finding enough counterexamples.
+///
+/// Only the sweep of the full mode covers the whole field, the boundary values being among its
+/// values anyway.
+fn completeness_of(
+    base_config: &BaseVerificationConfig,
+    is_interrupted: bool,
+) -> SearchCompleteness {
+    if base_config.quick_mode || base_config.heuristics_mode || is_interrupted {
+        SearchCompleteness::Incomplete
+    } else {
+        SearchCompleteness::Exhaustive
+    }
+}
+
+/// Collects the variables to enumerate, leaving out the inputs fixed by `input_constraints`.
+fn gather_variables(
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+) -> Vec<SymbolicName> {
     let mut trace_variables = extract_variables(symbolic_trace);
     let mut side_variables = extract_variables(side_constraints);
 
@@ -37,146 +131,159 @@ pub fn brute_force_search(
     variables.append(&mut trace_variables);
     variables.append(&mut side_variables);
     let variables_set: HashSet<SymbolicName> = variables.iter().cloned().collect();
-    variables = variables_set.into_iter().collect();
+    variables_set
+        .into_iter()
+        .filter(|v| !base_config.input_constraints.contains_key(v))
+        .collect()
+}
 
-    let mut assignment = FxHashMap::default();
-    let current_iteration = Arc::new(AtomicUsize::new(0));
+/// Enumerates the values tried for each variable, depending on the search mode.
+///
+/// With `boundary_values`, the values returned by `boundary_values` are tried first, and then the
+/// values of the search mode that are not among them.
+fn candidate_values(base_config: &BaseVerificationConfig) -> Box<dyn Iterator<Item = BigInt>> {
+    if base_config.boundary_values {
+        let boundaries = boundary_values(&base_config.prime);
+        Box::new(
+            boundaries
+                .clone()
+                .into_iter()
+                .chain(sweep_values(base_config).filter(move |value| !boundaries.contains(value))),
+        )
+    } else {
+        sweep_values(base_config)
+    }
+}
 
-    fn search(
-        sexe: &mut SymbolicExecutor,
-        symbolic_trace: &[SymbolicValueRef],
-        side_constraints: &[SymbolicValueRef],
-        base_config: &BaseVerificationConfig,
-        index: usize,
-        variables: &[SymbolicName],
-        assignment: &mut FxHashMap<SymbolicName, BigInt>,
-        current_iteration: &Arc<AtomicUsize>,
-    ) -> VerificationResult {
-        if index == variables.len() {
-            let iter = current_iteration.fetch_add(1, Ordering::SeqCst);
-            if iter % base_config.progress_interval == 0 {
-                print!(
-                    "\rProgress: {} / {}^{}",
-                    iter,
-                    &base_config.prime,
-                    variables.len()
-                );
-                io::stdout().flush().unwrap();
-            }
+/// Returns the values at which field arithmetic typically breaks: `0`, `1`, `prime - 1` (i.e.,
+/// `-1`), `prime - 2`, and `(prime - 1) / 2`, the largest value whose double does not wrap around.
+pub fn boundary_values(prime: &BigInt) -> Vec<BigInt> {
+    let mut values: Vec<BigInt> = Vec::new();
+    for value in [
+        BigInt::zero(),
+        BigInt::one(),
+        prime - BigInt::one(),
+        prime - BigInt::from(2),
+        (prime - BigInt::one()) / BigInt::from(2),
+    ] {
+        // The values coincide for tiny primes
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    values
+}
+
+/// Enumerates the values of the search mode itself.
+fn sweep_values(base_config: &BaseVerificationConfig) -> Box<dyn Iterator<Item = BigInt>> {
+    let step = |v: &BigInt| Some(v + BigInt::one());
+    if base_config.quick_mode {
+        Box::new(vec![BigInt::zero(), BigInt::one(), -1 * BigInt::one()].into_iter())
+    } else if base_config.heuristics_mode {
+        let range = base_config.range.clone();
+        let prime = base_config.prime.clone();
+        Box::new(
+            std::iter::successors(Some(-&base_config.range), step)
+                .take_while(move |v| *v <= range)
+                .chain(
+                    std::iter::successors(Some(&base_config.prime - &base_config.range), step)
+                        .take_while(move |v| *v < prime),
+                ),
+        )
+    } else {
+        let prime = base_config.prime.clone();
+        Box::new(std::iter::successors(Some(BigInt::zero()), step).take_while(move |v| *v < prime))
+    }
+}
 
-            return verify_assignment(
-                sexe,
-                symbolic_trace,
-                side_constraints,
-                assignment,
-                base_config,
+/// The arguments of `search` that stay the same across its recursive calls.
+struct SearchContext<'a> {
+    symbolic_trace: &'a CompiledConstraints,
+    side_constraints: &'a CompiledConstraints,
+    base_config: &'a BaseVerificationConfig,
+    /// The variables to enumerate, in order.
+    variables: &'a [SymbolicName],
+    /// The number of assignments tried so far, shared by the workers of a parallel search.
+    current_iteration: &'a Arc<AtomicUsize>,
+    /// Set to stop every worker of a parallel search.
+    stop: &'a AtomicBool,
+    max_findings: usize,
+}
+
+/// Enumerates the values of `context.variables[index..]`, appending the distinct
+/// counterexamples to `findings`.
+///
+/// # Returns
+/// Whether the search is over, because `findings` holds `max_findings` counterexamples or
+/// `stop` is set.
+fn search(
+    sexe: &mut SymbolicExecutor,
+    context: &SearchContext,
+    index: usize,
+    assignment: &mut FxHashMap<SymbolicName, BigInt>,
+    findings: &mut Vec<CounterExample>,
+) -> bool {
+    if context.stop.load(Ordering::Relaxed) {
+        return true;
+    }
+
+    let base_config = context.base_config;
+    if index == context.variables.len() {
+        let iter = context.current_iteration.fetch_add(1, Ordering::SeqCst);
+        if iter % base_config.progress_interval == 0 {
+            print!(
+                "\rProgress: {} / {}^{}",
+                iter,
+                &base_config.prime,
+                context.variables.len()
             );
+            io::stdout().flush().unwrap();
         }
 
-        let var = &variables[index];
-        if base_config.quick_mode {
-            let candidates = vec![BigInt::zero(), BigInt::one(), -1 * BigInt::one()];
-            for c in candidates.into_iter() {
-                assignment.insert(var.clone(), c.clone());
-                let result = search(
-                    sexe,
-                    symbolic_trace,
-                    side_constraints,
-                    base_config,
-                    index + 1,
-                    variables,
-                    assignment,
-                    current_iteration,
-                );
-                if is_vulnerable(&result) {
-                    return result;
-                }
-                assignment.remove(var);
-            }
-        } else if base_config.heuristics_mode {
-            let mut value = -&base_config.range;
-            while value <= base_config.range {
-                assignment.insert(var.clone(), value.clone());
-
-                let result = search(
-                    sexe,
-                    symbolic_trace,
-                    side_constraints,
-                    base_config,
-                    index + 1,
-                    variables,
-                    assignment,
-                    current_iteration,
-                );
-
-                if is_vulnerable(&result) {
-                    return result;
-                }
-                assignment.remove(&var);
-                value += BigInt::one();
-            }
-            let mut value = &base_config.prime - &base_config.range;
-
-            while value < base_config.prime {
-                assignment.insert(var.clone(), value.clone());
-
-                let result = search(
-                    sexe,
-                    symbolic_trace,
-                    side_constraints,
-                    base_config,
-                    index + 1,
-                    variables,
-                    assignment,
-                    current_iteration,
-                );
-
-                if is_vulnerable(&result) {
-                    return result;
-                }
-                assignment.remove(&var);
-                value += BigInt::one();
-            }
-        } else {
-            let mut value = BigInt::zero();
-            while value < base_config.prime {
-                assignment.insert(var.clone(), value.clone());
-                let result = search(
-                    sexe,
-                    symbolic_trace,
-                    side_constraints,
-                    base_config,
-                    index + 1,
-                    variables,
-                    assignment,
-                    current_iteration,
-                );
-                if is_vulnerable(&result) {
-                    return result;
-                }
-                assignment.remove(var);
-                value += BigInt::one();
-            }
+        let flag = verify_compiled_assignment(
+            sexe,
+            context.symbolic_trace,
+            context.side_constraints,
+            assignment,
+            base_config,
+        );
+        if is_vulnerable(&flag) {
+            push_distinct_finding(
+                findings,
+                CounterExample {
+                    flag: flag,
+                    target_output: None,
+                    assignment: assignment.clone(),
+                    field_reductions: Vec::new(),
+                    provenance: None,
+                },
+            );
         }
-        VerificationResult::WellConstrained
+        return findings.len() >= context.max_findings;
     }
 
-    let flag = search(
-        sexe,
-        &symbolic_trace,
-        &side_constraints,
-        base_config,
-        0,
-        &variables,
-        &mut assignment,
-        &current_iteration,
-    );
+    let var = &context.variables[index];
+    for value in candidate_values(base_config) {
+        assignment.insert(var.clone(), value);
+        if search(sexe, context, index + 1, assignment, findings) {
+            return true;
+        }
+        assignment.remove(var);
+    }
+    false
+}
 
+fn print_search_summary(
+    current_iteration: &Arc<AtomicUsize>,
+    base_config: &BaseVerificationConfig,
+    num_variables: usize,
+    findings: &[CounterExample],
+) {
     print!(
         "\rProgress: {} / {}^{}",
         current_iteration.load(Ordering::SeqCst),
         base_config.prime,
-        variables.len()
+        num_variables
     );
     io::stdout().flush().unwrap();
 
@@ -185,15 +292,245 @@ pub fn brute_force_search(
         "     ├─ Total iterations: {}",
         current_iteration.load(Ordering::SeqCst)
     );
-    println!("     └─ Verification result: {}", flag);
+    if findings.len() > 1 {
+        println!("     ├─ Distinct findings: {}", findings.len());
+    }
+    match findings.first() {
+        Some(counter_example) => {
+            println!("     └─ Verification result: {}", counter_example.flag)
+        }
+        None => println!(
+            "     └─ Verification result: {}",
+            VerificationResult::WellConstrained
+        ),
+    }
+}
+
+/// Everything a worker of `parallel_brute_force_search` needs to verify assignments on its own.
+#[cfg(feature = "parallel")]
+struct WorkerInput {
+    symbolic_library: SymbolicLibrary,
+    setting: SymbolicExecutorSetting,
+    base_config: BaseVerificationConfig,
+    symbolic_trace: Vec<SymbolicValueRef>,
+    side_constraints: Vec<SymbolicValueRef>,
+    variables: Vec<SymbolicName>,
+}
+
+#[cfg(feature = "parallel")]
+struct WorkerOutput {
+    flag: VerificationResult,
+    assignment: FxHashMap<SymbolicName, BigInt>,
+}
+
+// SAFETY: every `Rc` and `RefCell` reachable from a `WorkerInput` or a `WorkerOutput` is created
+// by `deep_clone` for that value alone, so moving it to another thread cannot race on reference
+// counts or cached hashes. The definitions shared by the cloned `SymbolicLibrary` are behind an
+// `Arc`, contain no `Rc`, and are never mutated. Its `name_interner`, the only part of the
+// library holding `Rc`s, is replaced with an empty one rather than cloned.
+#[cfg(feature = "parallel")]
+unsafe impl Send for WorkerInput {}
+#[cfg(feature = "parallel")]
+unsafe impl Send for WorkerOutput {}
 
-    if is_vulnerable(&flag) {
-        Some(CounterExample {
+#[cfg(feature = "parallel")]
+impl WorkerOutput {
+    fn new(flag: &VerificationResult, assignment: &FxHashMap<SymbolicName, BigInt>) -> Self {
+        let flag = match flag {
+            VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                sym_name,
+                name,
+                value,
+            )) => VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                sym_name.deep_clone(),
+                name.clone(),
+                value.clone(),
+            )),
+            _ => flag.clone(),
+        };
+        WorkerOutput {
             flag: flag,
-            target_output: None,
-            assignment: assignment,
+            assignment: assignment
+                .iter()
+                .map(|(k, v)| (k.deep_clone(), v.clone()))
+                .collect(),
+        }
+    }
+}
+
+/// Multi-threaded variant of `brute_force_search`.
+///
+/// The candidate values of the first variable are distributed in a round-robin fashion over
+/// the threads of the rayon pool, and each thread enumerates the remaining variables with its
+/// own copy of the symbolic library and of the constraints. The first counterexample found by
+/// any thread stops the others. A pool of a single thread runs `brute_force_search` instead, so
+/// that the result is the one of the sequential search.
+///
+/// # Parameters
+/// - `sexe`: A mutable reference to the symbolic executor.
+/// - `symbolic_trace`: A vector of constraints representing the program trace.
+/// - `side_constraints`: A vector of additional constraints for validation.
+/// - `base_config`: The verification base_configs.
+///
+/// # Returns
+/// An `Option<CounterExample>` containing a counterexample if constraints are invalid, or `None` otherwise.
+#[cfg(feature = "parallel")]
+pub fn parallel_brute_force_search(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+) -> Option<CounterExample> {
+    parallel_collect_counter_examples(sexe, symbolic_trace, side_constraints, base_config, 1)
+        .counter_examples
+        .pop()
+}
+
+/// Multi-threaded variant of `brute_force_search_all`.
+///
+/// The threads stop once they found `max_findings` of `base_config` counterexamples in total.
+/// Since two threads may find the same bug, fewer distinct counterexamples may be returned than
+/// a sequential search would.
+#[cfg(feature = "parallel")]
+pub fn parallel_brute_force_search_all(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+) -> SearchOutcome {
+    parallel_collect_counter_examples(
+        sexe,
+        symbolic_trace,
+        side_constraints,
+        base_config,
+        base_config.max_findings,
+    )
+}
+
+#[cfg(feature = "parallel")]
+fn parallel_collect_counter_examples(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+    max_findings: usize,
+) -> SearchOutcome {
+    let variables = gather_variables(symbolic_trace, side_constraints, base_config);
+    let num_workers = rayon::current_num_threads();
+    if variables.is_empty() || num_workers == 1 {
+        return collect_counter_examples(
+            sexe,
+            symbolic_trace,
+            side_constraints,
+            base_config,
+            max_findings,
+        );
+    }
+
+    let current_iteration = Arc::new(AtomicUsize::new(0));
+    let stop = AtomicBool::new(false);
+    let num_found = AtomicUsize::new(0);
+    let inputs: Vec<WorkerInput> = (0..num_workers)
+        .map(|_| WorkerInput {
+            // The interned variables are `Rc`s shared with the constraints of `sexe`, so each
+            // worker starts with an empty interner instead of a copy
+            symbolic_library: SymbolicLibrary {
+                name_interner: SymbolicNameInterner::default(),
+                ..sexe.symbolic_library.clone()
+            },
+            setting: sexe.setting.clone(),
+            base_config: BaseVerificationConfig {
+                input_constraints: base_config
+                    .input_constraints
+                    .iter()
+                    .map(|(k, v)| (k.deep_clone(), v.clone()))
+                    .collect(),
+                assumptions: base_config
+                    .assumptions
+                    .iter()
+                    .map(|c| SymbolicValueRef::new(c.deep_clone()))
+                    .collect(),
+                ..base_config.clone()
+            },
+            symbolic_trace: symbolic_trace
+                .iter()
+                .map(|c| SymbolicValueRef::new(c.deep_clone()))
+                .collect(),
+            side_constraints: side_constraints
+                .iter()
+                .map(|c| SymbolicValueRef::new(c.deep_clone()))
+                .collect(),
+            variables: variables.iter().map(|v| v.deep_clone()).collect(),
         })
-    } else {
-        None
+        .collect();
+
+    let outputs: Vec<Vec<WorkerOutput>> = inputs
+        .into_par_iter()
+        .enumerate()
+        .map(|(worker_id, mut input)| {
+            let mut worker = SymbolicExecutor::new(&mut input.symbolic_library, &input.setting);
+            worker
+                .feed_arguments(
+                    &input.base_config.template_param_names,
+                    &input.base_config.template_param_values,
+                )
+                .expect("Template arguments are validated when the main component is instantiated");
+
+            let symbolic_trace = CompiledConstraints::new(&input.symbolic_trace);
+            let side_constraints = CompiledConstraints::new(&input.side_constraints);
+            let context = SearchContext {
+                symbolic_trace: &symbolic_trace,
+                side_constraints: &side_constraints,
+                base_config: &input.base_config,
+                variables: &input.variables,
+                current_iteration: &current_iteration,
+                stop: &stop,
+                max_findings,
+            };
+            let mut assignment = input.base_config.input_constraints.clone();
+            let mut findings = Vec::new();
+            for value in candidate_values(&input.base_config)
+                .skip(worker_id)
+                .step_by(num_workers)
+            {
+                if stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                assignment.insert(input.variables[0].clone(), value);
+                let num_before = findings.len();
+                search(&mut worker, &context, 1, &mut assignment, &mut findings);
+                let num_new = findings.len() - num_before;
+                if num_new > 0
+                    && num_found.fetch_add(num_new, Ordering::SeqCst) + num_new >= max_findings
+                {
+                    stop.store(true, Ordering::Relaxed);
+                    break;
+                }
+            }
+            findings
+                .iter()
+                .map(|ce| WorkerOutput::new(&ce.flag, &ce.assignment))
+                .collect()
+        })
+        .collect();
+
+    let mut findings = Vec::new();
+    for output in outputs.into_iter().flatten() {
+        push_distinct_finding(
+            &mut findings,
+            CounterExample {
+                flag: output.flag,
+                target_output: None,
+                assignment: output.assignment,
+                field_reductions: Vec::new(),
+                provenance: None,
+            },
+        );
+    }
+    findings.truncate(max_findings);
+    print_search_summary(&current_iteration, base_config, variables.len(), &findings);
+    SearchOutcome {
+        counter_examples: findings,
+        completeness: completeness_of(base_config, stop.load(Ordering::Relaxed)),
     }
 }
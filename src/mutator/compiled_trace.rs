@@ -0,0 +1,379 @@
+use num_bigint_dig::BigInt;
+use num_traits::{One, Signed, Zero};
+use rustc_hash::FxHashMap;
+
+use program_structure::ast::ExpressionPrefixOpcode;
+
+use crate::executor::debug_ast::{
+    DebuggableExpressionInfixOpcode, DebuggableExpressionPrefixOpcode,
+};
+use crate::executor::symbolic_value::{
+    evaluate_binary_op, evaluate_binary_op_integer_mode, SymbolicLibrary, SymbolicName,
+    SymbolicValue, SymbolicValueRef,
+};
+use crate::mutator::utils::{
+    assign_evaluated_value, emulate_trace_entry, evaluate_symbolic_value, holds_binary_constraint,
+    is_satisfied_constraint, Direction,
+};
+
+/// An expression of a compiled trace, whose variables are resolved to slots.
+enum Node {
+    Constant(SymbolicValue),
+    Slot(usize),
+    BinaryOp(Box<Node>, DebuggableExpressionInfixOpcode, Box<Node>),
+    AuxBinaryOp(Box<Node>, DebuggableExpressionInfixOpcode, Box<Node>),
+    UnaryOp(
+        DebuggableExpressionPrefixOpcode,
+        Box<Node>,
+        SymbolicValueRef,
+    ),
+    Conditional(Box<Node>, Box<Node>, Box<Node>),
+    /// An expression left to `evaluate_symbolic_value`, such as a call or an array.
+    Opaque(SymbolicValueRef),
+}
+
+enum Step {
+    Assign {
+        name: SymbolicName,
+        slot: usize,
+        rhs: Node,
+    },
+    Check {
+        lhs: Node,
+        op: DebuggableExpressionInfixOpcode,
+        rhs: Node,
+    },
+    /// An entry left to `emulate_trace_entry`.
+    Entry,
+}
+
+/// A symbolic trace compiled once into an evaluation DAG over its variables.
+///
+/// `emulate_symbolic_trace` looks every variable up in the assignment and walks the symbolic
+/// values of the trace anew for each input, although only the input assignment changes between
+/// the candidates of the search. A `CompiledTrace` resolves each variable of the trace to a slot
+/// up front, so that emulating it for another input only re-evaluates the compiled expressions
+/// over a vector of values.
+///
+/// The emulation is equivalent to `emulate_symbolic_trace`: the assignment is updated in the same
+/// way, and the entries that are not compiled, such as the constraints at runtime mutable
+/// positions, are delegated to `emulate_trace_entry`.
+pub struct CompiledTrace {
+    entries: Vec<(SymbolicValueRef, Step)>,
+    names: Vec<SymbolicName>,
+}
+
+impl CompiledTrace {
+    /// Compiles the given symbolic trace.
+    pub fn new(trace: &[SymbolicValueRef]) -> Self {
+        let mut slots = FxHashMap::default();
+        let mut names = Vec::new();
+        let entries = trace
+            .iter()
+            .map(|inst| {
+                let step = match inst.as_ref() {
+                    SymbolicValue::Assign(lhs, rhs, _, _)
+                    | SymbolicValue::AssignEq(lhs, rhs)
+                    | SymbolicValue::AssignTemplParam(lhs, rhs)
+                    | SymbolicValue::AssignCall(lhs, rhs, _) => match lhs.as_ref() {
+                        SymbolicValue::Variable(name) => {
+                            let rhs = compile_node(rhs, &mut slots, &mut names);
+                            Step::Assign {
+                                name: name.clone(),
+                                slot: slot_of(name, &mut slots, &mut names),
+                                rhs,
+                            }
+                        }
+                        _ => Step::Entry,
+                    },
+                    SymbolicValue::BinaryOp(lhs, op, rhs)
+                    | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => Step::Check {
+                        lhs: compile_node(lhs, &mut slots, &mut names),
+                        op: op.clone(),
+                        rhs: compile_node(rhs, &mut slots, &mut names),
+                    },
+                    _ => Step::Entry,
+                };
+                (inst.clone(), step)
+            })
+            .collect();
+        CompiledTrace { entries, names }
+    }
+
+    /// Simulates the execution of the compiled trace with the given input assignment.
+    ///
+    /// The parameters and the result are the same as those of `emulate_symbolic_trace`.
+    pub fn emulate(
+        &self,
+        prime: &BigInt,
+        runtime_mutable_positions: &FxHashMap<usize, Direction>,
+        assignment: &mut FxHashMap<SymbolicName, BigInt>,
+        symbolic_library: &mut SymbolicLibrary,
+    ) -> Option<(bool, usize)> {
+        let mut values: Vec<Option<BigInt>> = self
+            .names
+            .iter()
+            .map(|name| assignment.get(name).cloned())
+            .collect();
+        let mut success = true;
+        let mut failure_pos = 0;
+        for (i, (inst, step)) in self.entries.iter().enumerate() {
+            let flag = match step {
+                Step::Assign { name, slot, rhs } => {
+                    let rhs_val = evaluate_node(prime, rhs, &values, assignment, symbolic_library)?;
+                    let is_array = matches!(rhs_val, SymbolicValue::Array(_));
+                    let flag = assign_evaluated_value(name, rhs_val, assignment);
+                    if is_array {
+                        // The elements are stored under names of their own.
+                        self.reload(&mut values, assignment);
+                    } else {
+                        values[*slot] = assignment.get(name).cloned();
+                    }
+                    flag
+                }
+                Step::Check { lhs, op, rhs } if !runtime_mutable_positions.contains_key(&i) => {
+                    let lhs_val = evaluate_node(prime, lhs, &values, assignment, symbolic_library);
+                    let rhs_val = evaluate_node(prime, rhs, &values, assignment, symbolic_library);
+                    holds_binary_constraint(prime, inst, op, lhs_val, rhs_val, symbolic_library)
+                }
+                Step::Check { lhs, rhs, .. } => {
+                    let flag = emulate_trace_entry(
+                        prime,
+                        i,
+                        inst,
+                        runtime_mutable_positions,
+                        assignment,
+                        symbolic_library,
+                    )?;
+                    // The runtime mutation may have overwritten a variable operand.
+                    for operand in [lhs, rhs] {
+                        if let Node::Slot(slot) = operand {
+                            values[*slot] = assignment.get(&self.names[*slot]).cloned();
+                        }
+                    }
+                    flag
+                }
+                Step::Entry => emulate_trace_entry(
+                    prime,
+                    i,
+                    inst,
+                    runtime_mutable_positions,
+                    assignment,
+                    symbolic_library,
+                )?,
+            };
+            if !flag {
+                success = false;
+                failure_pos = i;
+            }
+        }
+
+        Some((success, failure_pos))
+    }
+
+    fn reload(&self, values: &mut [Option<BigInt>], assignment: &FxHashMap<SymbolicName, BigInt>) {
+        for (value, name) in values.iter_mut().zip(self.names.iter()) {
+            *value = assignment.get(name).cloned();
+        }
+    }
+}
+
+/// A list of constraints compiled once into evaluation DAGs over their variables.
+///
+/// This is the counterpart of `CompiledTrace` for `evaluate_constraints`, which the brute-force
+/// search calls for every candidate assignment.
+pub struct CompiledConstraints {
+    constraints: Vec<(SymbolicValueRef, Check)>,
+    names: Vec<SymbolicName>,
+}
+
+enum Check {
+    /// The equality of both sides of an assignment.
+    Equality(Node, Node),
+    Value(Node),
+}
+
+impl CompiledConstraints {
+    /// Compiles the given constraints.
+    pub fn new(constraints: &[SymbolicValueRef]) -> Self {
+        let mut slots = FxHashMap::default();
+        let mut names = Vec::new();
+        let constraints = constraints
+            .iter()
+            .map(|constraint| {
+                let check = match constraint.as_ref() {
+                    SymbolicValue::Assign(lhs, rhs, _, _)
+                    | SymbolicValue::AssignEq(lhs, rhs)
+                    | SymbolicValue::AssignCall(lhs, rhs, _) => Check::Equality(
+                        compile_node(lhs, &mut slots, &mut names),
+                        compile_node(rhs, &mut slots, &mut names),
+                    ),
+                    _ => Check::Value(compile_node(constraint, &mut slots, &mut names)),
+                };
+                (constraint.clone(), check)
+            })
+            .collect();
+        CompiledConstraints { constraints, names }
+    }
+
+    /// Returns whether the given assignment satisfies all the compiled constraints.
+    ///
+    /// The parameters, the result, and the panics are the same as those of
+    /// `evaluate_constraints`.
+    pub fn evaluate(
+        &self,
+        prime: &BigInt,
+        assignment: &FxHashMap<SymbolicName, BigInt>,
+        symbolic_library: &mut SymbolicLibrary,
+    ) -> bool {
+        let values: Vec<Option<BigInt>> = self
+            .names
+            .iter()
+            .map(|name| assignment.get(name).cloned())
+            .collect();
+        self.constraints.iter().all(|(constraint, check)| {
+            let sv = match check {
+                Check::Equality(lhs, rhs) => {
+                    let lhs_val = evaluate_node(prime, lhs, &values, assignment, symbolic_library);
+                    let rhs_val = evaluate_node(prime, rhs, &values, assignment, symbolic_library);
+                    match (lhs_val, rhs_val) {
+                        (
+                            Some(SymbolicValue::ConstantInt(lv)),
+                            Some(SymbolicValue::ConstantInt(rv)),
+                        ) => Some(SymbolicValue::ConstantBool(lv % prime == rv % prime)),
+                        (
+                            Some(SymbolicValue::ConstantInt(lv)),
+                            Some(SymbolicValue::ConstantBool(rv)),
+                        ) => {
+                            let rv_to_int = if rv { BigInt::one() } else { BigInt::zero() };
+                            Some(SymbolicValue::ConstantBool(lv % prime == rv_to_int))
+                        }
+                        (Some(_), Some(_)) => {
+                            // Reports the unassigned variables as `evaluate_symbolic_value` does.
+                            evaluate_symbolic_value(prime, constraint, assignment, symbolic_library)
+                        }
+                        _ => None,
+                    }
+                }
+                Check::Value(node) => {
+                    evaluate_node(prime, node, &values, assignment, symbolic_library)
+                }
+            };
+            is_satisfied_constraint(sv, symbolic_library)
+        })
+    }
+}
+
+fn slot_of(
+    name: &SymbolicName,
+    slots: &mut FxHashMap<SymbolicName, usize>,
+    names: &mut Vec<SymbolicName>,
+) -> usize {
+    *slots.entry(name.clone()).or_insert_with(|| {
+        names.push(name.clone());
+        names.len() - 1
+    })
+}
+
+fn compile_node(
+    value: &SymbolicValueRef,
+    slots: &mut FxHashMap<SymbolicName, usize>,
+    names: &mut Vec<SymbolicName>,
+) -> Node {
+    match value.as_ref() {
+        SymbolicValue::NOP | SymbolicValue::ConstantBool(_) | SymbolicValue::ConstantInt(_) => {
+            Node::Constant(value.as_ref().clone())
+        }
+        SymbolicValue::Variable(name) => Node::Slot(slot_of(name, slots, names)),
+        SymbolicValue::BinaryOp(lhs, op, rhs) => Node::BinaryOp(
+            Box::new(compile_node(lhs, slots, names)),
+            op.clone(),
+            Box::new(compile_node(rhs, slots, names)),
+        ),
+        SymbolicValue::AuxBinaryOp(lhs, op, rhs) => Node::AuxBinaryOp(
+            Box::new(compile_node(lhs, slots, names)),
+            op.clone(),
+            Box::new(compile_node(rhs, slots, names)),
+        ),
+        SymbolicValue::UnaryOp(op, expr) => Node::UnaryOp(
+            op.clone(),
+            Box::new(compile_node(expr, slots, names)),
+            value.clone(),
+        ),
+        SymbolicValue::Conditional(cond, then_branch, else_branch) => Node::Conditional(
+            Box::new(compile_node(cond, slots, names)),
+            Box::new(compile_node(then_branch, slots, names)),
+            Box::new(compile_node(else_branch, slots, names)),
+        ),
+        _ => Node::Opaque(value.clone()),
+    }
+}
+
+/// Evaluates a compiled expression as `evaluate_symbolic_value` evaluates the original one.
+///
+/// `values` holds the value of each slot, and `assignment` the same values by name, which the
+/// opaque expressions are evaluated with.
+fn evaluate_node(
+    prime: &BigInt,
+    node: &Node,
+    values: &[Option<BigInt>],
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    symbolic_library: &mut SymbolicLibrary,
+) -> Option<SymbolicValue> {
+    match node {
+        Node::Constant(value) => Some(value.clone()),
+        Node::Slot(slot) => values[*slot].clone().map(SymbolicValue::ConstantInt),
+        Node::BinaryOp(lhs, op, rhs) => {
+            let lhs_val = evaluate_node(prime, lhs, values, assignment, symbolic_library);
+            let rhs_val = evaluate_node(prime, rhs, values, assignment, symbolic_library);
+            match evaluate_binary_op(&lhs_val?, &rhs_val?, prime, op) {
+                SymbolicValue::Undefined => None,
+                value => Some(value),
+            }
+        }
+        Node::AuxBinaryOp(lhs, op, rhs) => {
+            let lhs_val = evaluate_node(prime, lhs, values, assignment, symbolic_library);
+            let rhs_val = evaluate_node(prime, rhs, values, assignment, symbolic_library);
+            match evaluate_binary_op_integer_mode(&lhs_val?, &rhs_val?, prime, op) {
+                SymbolicValue::Undefined => None,
+                value => Some(value),
+            }
+        }
+        Node::UnaryOp(op, expr, original) => {
+            match (
+                &op.0,
+                evaluate_node(prime, expr, values, assignment, symbolic_library)?,
+            ) {
+                (ExpressionPrefixOpcode::Sub, SymbolicValue::ConstantInt(rv)) => {
+                    Some(SymbolicValue::ConstantInt(-rv))
+                }
+                (ExpressionPrefixOpcode::BoolNot, SymbolicValue::ConstantBool(rv)) => {
+                    Some(SymbolicValue::ConstantBool(!rv))
+                }
+                // Reports the unsupported operand as `evaluate_symbolic_value` does.
+                _ => evaluate_symbolic_value(prime, original, assignment, symbolic_library),
+            }
+        }
+        Node::Conditional(cond, then_branch, else_branch) => {
+            let cond_val = evaluate_node(prime, cond, values, assignment, symbolic_library);
+            let then_val = evaluate_node(prime, then_branch, values, assignment, symbolic_library);
+            let else_val = evaluate_node(prime, else_branch, values, assignment, symbolic_library);
+            match cond_val? {
+                SymbolicValue::ConstantBool(true) => then_val,
+                SymbolicValue::ConstantBool(false) => else_val,
+                SymbolicValue::ConstantInt(num) => {
+                    if num.is_positive() {
+                        then_val
+                    } else {
+                        else_val
+                    }
+                }
+                cond_val => panic!(
+                    "Unassigned variables exist: {}",
+                    cond_val.lookup_fmt(&symbolic_library.id2name)
+                ),
+            }
+        }
+        Node::Opaque(value) => evaluate_symbolic_value(prime, value, assignment, symbolic_library),
+    }
+}
@@ -0,0 +1,84 @@
+use num_bigint_dig::BigInt;
+use rustc_hash::FxHashMap;
+
+use program_structure::ast::ExpressionInfixOpcode;
+
+use crate::executor::debug_ast::DebuggableExpressionInfixOpcode;
+use crate::executor::symbolic_state::SymbolicConstraints;
+use crate::executor::symbolic_value::{SymbolicName, SymbolicValue, SymbolicValueRef};
+
+/// Two side constraints pinning the same signal to distinct constants.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Contradiction {
+    pub name: SymbolicName,
+    pub first: SymbolicValueRef,
+    pub second: SymbolicValueRef,
+}
+
+/// Detects side constraints that no witness can satisfy together.
+///
+/// Only the direct contradictions between constant equalities are detected, i.e., a signal
+/// constrained to be equal to two constants that differ modulo `prime`, such as `x === 0` and
+/// `x === 1`. A circuit with such constraints accepts no witness at all, which is a distinct bug
+/// from an under-constrained circuit, and which the search cannot reveal since it only looks for
+/// witnesses satisfying the side constraints.
+///
+/// # Parameters
+/// - `side_constraints`: The side constraints of the circuit.
+/// - `prime`: The prime modulus of the finite field.
+///
+/// # Returns
+/// The contradictions, each made of the first constant equality of a signal and a later one
+/// disagreeing with it, in the order of the side constraints.
+///
+/// # Notes
+/// The check is not a solver: contradictions that only follow from several non-constant
+/// constraints, such as `x === y`, `x === 0`, and `y === 1`, are not detected.
+pub fn detect_unsatisfiable_constraints(
+    side_constraints: &SymbolicConstraints,
+    prime: &BigInt,
+) -> Vec<Contradiction> {
+    let mut pinned: FxHashMap<&SymbolicName, (BigInt, &SymbolicValueRef)> = FxHashMap::default();
+    let mut contradictions = Vec::new();
+    for constraint in side_constraints {
+        if let Some((name, value)) = as_constant_equality(constraint, prime) {
+            match pinned.get(name) {
+                Some((pinned_value, first)) if *pinned_value != value => {
+                    contradictions.push(Contradiction {
+                        name: name.clone(),
+                        first: (*first).clone(),
+                        second: constraint.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    pinned.insert(name, (value, constraint));
+                }
+            }
+        }
+    }
+    contradictions
+}
+
+/// Returns the signal and the normalized constant of a constraint `x == c` or `c == x`.
+fn as_constant_equality<'a>(
+    constraint: &'a SymbolicValue,
+    prime: &BigInt,
+) -> Option<(&'a SymbolicName, BigInt)> {
+    let (lhs, rhs) = match constraint {
+        SymbolicValue::AssignEq(lhs, rhs) => (lhs, rhs),
+        SymbolicValue::BinaryOp(
+            lhs,
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+            rhs,
+        ) => (lhs, rhs),
+        _ => return None,
+    };
+    match (lhs.as_ref(), rhs.as_ref()) {
+        (SymbolicValue::Variable(name), SymbolicValue::ConstantInt(c))
+        | (SymbolicValue::ConstantInt(c), SymbolicValue::Variable(name)) => {
+            Some((name, ((c % prime) + prime) % prime))
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,122 @@
+use std::rc::Rc;
+
+use num_bigint_dig::BigInt;
+use num_traits::Zero;
+use rustc_hash::FxHashSet;
+
+use program_structure::ast::ExpressionInfixOpcode;
+
+use crate::executor::debug_ast::DebuggableExpressionInfixOpcode;
+use crate::executor::symbolic_state::{SymbolicConstraints, SymbolicTrace};
+use crate::executor::symbolic_value::{
+    extract_variables, get_coefficient_of_polynomials, get_degree_polynomial, SymbolicName,
+    SymbolicValue,
+};
+
+/// Collects the signals assigned with `<--` whose value is not uniquely determined by the side
+/// constraints.
+///
+/// A signal is considered determined when at least one side constraint is linear in it with a
+/// non-zero constant coefficient, since such a constraint fixes the signal once every other
+/// variable is known. Every other `<--`-assigned signal is a free witness: a malicious prover can
+/// choose its value without violating any constraint solely because of that choice.
+///
+/// # Parameters
+/// - `symbolic_trace`: The symbolic trace containing the `Assign` instructions.
+/// - `side_constraints`: The side constraints of the circuit.
+/// - `prime`: The prime modulus of the finite field.
+///
+/// # Returns
+/// The sorted list of free witnesses.
+///
+/// # Notes
+/// The check is conservative: a signal that only appears in non-polynomial or higher-degree
+/// constraints is reported as free even if those constraints happen to pin it down.
+pub fn find_free_witnesses(
+    symbolic_trace: &SymbolicTrace,
+    side_constraints: &SymbolicConstraints,
+    prime: &BigInt,
+) -> Vec<SymbolicName> {
+    let mut assigned = FxHashSet::default();
+    for inst in symbolic_trace {
+        if let SymbolicValue::Assign(lhs, _, _, _) = inst.as_ref() {
+            if let SymbolicValue::Variable(name) = lhs.as_ref() {
+                assigned.insert(name.clone());
+            }
+        }
+    }
+
+    let equations: Vec<SymbolicValue> = side_constraints
+        .iter()
+        .filter_map(|c| match c.as_ref() {
+            SymbolicValue::AssignEq(lhs, rhs) => Some((lhs.clone(), rhs.clone())),
+            SymbolicValue::BinaryOp(
+                lhs,
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+                rhs,
+            ) => Some((lhs.clone(), rhs.clone())),
+            _ => None,
+        })
+        .map(|(lhs, rhs)| {
+            SymbolicValue::BinaryOp(
+                lhs,
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Sub),
+                rhs,
+            )
+        })
+        .collect();
+
+    let mut free_witnesses: Vec<SymbolicName> = assigned
+        .into_iter()
+        .filter(|name| !equations.iter().any(|eq| is_determined_by(eq, name, prime)))
+        .collect();
+    free_witnesses.sort();
+    free_witnesses
+}
+
+/// Collects the signals assigned with `<--` that do not appear in any side constraint.
+///
+/// This is the most blatant form of a free witness: nothing in the circuit even mentions the
+/// signal, so a malicious prover can set it to any value. Unlike `find_free_witnesses`, the check
+/// is purely syntactic and cheap, so it can be reported before any search. Assignments of
+/// whitelisted templates are trusted and skipped.
+///
+/// # Parameters
+/// - `symbolic_trace`: The symbolic trace containing the `Assign` instructions.
+/// - `side_constraints`: The side constraints of the circuit.
+///
+/// # Returns
+/// The sorted list of unconstrained signals.
+pub fn find_unconstrained_assignments(
+    symbolic_trace: &SymbolicTrace,
+    side_constraints: &SymbolicConstraints,
+) -> Vec<SymbolicName> {
+    let constrained: FxHashSet<SymbolicName> =
+        extract_variables(side_constraints).into_iter().collect();
+
+    let mut unconstrained = FxHashSet::default();
+    for inst in symbolic_trace {
+        if let SymbolicValue::Assign(lhs, _, false, _) = inst.as_ref() {
+            if let SymbolicValue::Variable(name) = lhs.as_ref() {
+                if !constrained.contains(name) {
+                    unconstrained.insert(name.clone());
+                }
+            }
+        }
+    }
+
+    let mut unconstrained: Vec<SymbolicName> = unconstrained.into_iter().collect();
+    unconstrained.sort();
+    unconstrained
+}
+
+fn is_determined_by(equation: &SymbolicValue, name: &SymbolicName, prime: &BigInt) -> bool {
+    if get_degree_polynomial(equation, name) != 1 {
+        return false;
+    }
+    let coefficients = get_coefficient_of_polynomials(equation, name, prime);
+    match Rc::as_ref(&coefficients[1]) {
+        SymbolicValue::ConstantInt(c) => !(c % prime).is_zero(),
+        _ => false,
+    }
+}
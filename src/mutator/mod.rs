@@ -1,13 +1,22 @@
 pub mod brute_force;
+pub mod compiled_trace;
+pub mod contradictions;
+pub mod free_witnesses;
 pub mod mutation_config;
 pub mod mutation_test;
 pub mod mutation_test_crossover_fn;
 pub mod mutation_test_evolution_fn;
+pub mod mutation_test_operators;
 pub mod mutation_test_trace_fitness_fn;
 pub mod mutation_test_trace_initialization_fn;
 pub mod mutation_test_trace_mutation_fn;
 pub mod mutation_test_trace_selection_fn;
 pub mod mutation_test_update_input_fn;
 pub mod mutation_utils;
+pub mod safe_inputs;
+pub mod shrink;
+#[cfg(feature = "z3")]
+pub mod smt_search;
+pub mod undetermined_functions;
 pub mod unused_outputs;
 pub mod utils;
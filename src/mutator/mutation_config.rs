@@ -1,25 +1,63 @@
 use std::fmt;
 use std::fs::File;
+use std::io::Read;
 use std::str::FromStr;
 
 use colored::Colorize;
 use log::info;
 use num_bigint_dig::BigInt;
+use rustc_hash::FxHashMap;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DisplayFromStr};
 
+/// The interpolation of a `MutationRateSchedule` between its start and end rates.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScheduleType {
+    /// `start_rate + (end_rate - start_rate) * t`.
+    Linear,
+    /// `start_rate * (end_rate / start_rate)^t`, which requires positive rates.
+    Exponential,
+}
+
+/// A mutation rate annealed from `start_rate` in the first generation to `end_rate` in the last
+/// one, so that the search explores early and exploits late.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MutationRateSchedule {
+    pub start_rate: f64,
+    pub end_rate: f64,
+    pub schedule_type: ScheduleType,
+}
+
+impl MutationRateSchedule {
+    /// Returns the rate at `progress`, the fraction of the generations elapsed, clamped to
+    /// `[0, 1]`. The rate is constant when `start_rate` equals `end_rate`.
+    pub fn rate_at(&self, progress: f64) -> f64 {
+        let t = progress.max(0.0).min(1.0);
+        match self.schedule_type {
+            ScheduleType::Linear => self.start_rate + (self.end_rate - self.start_rate) * t,
+            ScheduleType::Exponential => {
+                self.start_rate * (self.end_rate / self.start_rate).powf(t)
+            }
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Clone, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct MutationConfig {
-    pub seed: u64,
+    pub seed: Option<u64>,
     pub program_population_size: usize,
     pub input_population_size: usize,
     pub max_generations: usize,
     pub input_initialization_method: String,
     pub trace_mutation_method: String,
     pub fitness_function: String,
+    pub fitness_method: String,
     pub mutation_rate: f64,
+    pub mutation_rate_schedule: Option<MutationRateSchedule>,
     pub crossover_rate: f64,
     pub operator_mutation_rate: f64,
     pub runtime_mutation_rate: f64,
@@ -30,6 +68,7 @@ pub struct MutationConfig {
     pub input_generation_crossover_rate: f64,
     pub input_generation_mutation_rate: f64,
     pub input_generation_singlepoint_mutation_rate: f64,
+    pub input_uniform_crossover: bool,
     #[serde_as(as = "Vec<(DisplayFromStr, DisplayFromStr)>")]
     pub random_value_ranges: Vec<(BigInt, BigInt)>,
     pub random_value_probs: Vec<f64>,
@@ -42,19 +81,33 @@ pub struct MutationConfig {
     pub dissable_runtime_mutation_for_hash_check: bool,
     pub dissable_heuristic_for_invalid_array_subscript: bool,
     pub save_fitness_scores: bool,
+    pub log_best_genome: bool,
+    pub save_coverage_log: bool,
+    pub progress_interval: usize,
+    pub quiet: bool,
+    pub input_mutation_weights: FxHashMap<String, f64>,
+    pub input_bit_hints: FxHashMap<String, usize>,
+    pub infer_input_bit_hints: bool,
+    pub prefilter_samples: usize,
+    pub selection_method: String,
+    pub tournament_size: usize,
+    pub crossover_method: String,
+    pub num_top_candidates: usize,
 }
 
 impl Default for MutationConfig {
     fn default() -> Self {
         MutationConfig {
-            seed: 0,
+            seed: None,
             program_population_size: 30,
             input_population_size: 30,
             max_generations: 500,
             input_initialization_method: "random".to_string(),
             trace_mutation_method: "constant_operator".to_string(),
             fitness_function: "error".to_string(),
+            fitness_method: "error".to_string(),
             mutation_rate: 0.3,
+            mutation_rate_schedule: None,
             crossover_rate: 0.5,
             operator_mutation_rate:0.1,
             runtime_mutation_rate:0.3,
@@ -65,6 +118,7 @@ impl Default for MutationConfig {
             input_generation_crossover_rate: 0.66,
             input_generation_mutation_rate: 0.5,
             input_generation_singlepoint_mutation_rate: 0.5,
+            input_uniform_crossover: false,
             random_value_ranges: vec![
                 (BigInt::from(0), BigInt::from(2)),
                 (BigInt::from(2), BigInt::from(11)),
@@ -83,6 +137,18 @@ impl Default for MutationConfig {
             dissable_runtime_mutation_for_hash_check:false,
             dissable_heuristic_for_invalid_array_subscript:false,
             save_fitness_scores: false,
+            log_best_genome: false,
+            save_coverage_log: false,
+            progress_interval: 1,
+            quiet: false,
+            input_mutation_weights: FxHashMap::default(),
+            input_bit_hints: FxHashMap::default(),
+            infer_input_bit_hints: false,
+            prefilter_samples: 0,
+            selection_method: "roulette".to_string(),
+            tournament_size: 3,
+            crossover_method: "random".to_string(),
+            num_top_candidates: 5,
         }
     }
 }
@@ -97,6 +163,9 @@ impl fmt::Display for MutationConfig {
     ├─ Maximum Number of Generations              : {}
     ├─ Input Initialization Method                : {} 
     ├─ Fitness Function                           : {} 
+    ├─ Fitness Method                             : {}
+    ├─ Selection Method                           : {}
+    ├─ Crossover Method                           : {}
     ├─ Trace Mutation Rate                        : {}
     ├─ Trace Crossover Rate                       : {}
     ├─ Operator Mutation Rate                     : {}
@@ -112,6 +181,9 @@ impl fmt::Display for MutationConfig {
             self.max_generations.to_string().bright_yellow(),
             self.input_initialization_method.bright_yellow(),
             self.fitness_function.bright_yellow(),
+            self.fitness_method.bright_yellow(),
+            self.selection_method.bright_yellow(),
+            self.crossover_method.bright_yellow(),
             self.mutation_rate.to_string().bright_yellow(),
             self.crossover_rate.to_string().bright_yellow(),
             self.operator_mutation_rate.to_string().bright_yellow(),
@@ -134,13 +206,77 @@ impl fmt::Display for MutationConfig {
     }
 }
 
+impl MutationConfig {
+    /// Returns the mutation weight of an input signal, looked up by its bare name.
+    ///
+    /// All elements of an input array share the weight of the array. Inputs without an entry in
+    /// `input_mutation_weights` get the uniform baseline of `1.0`.
+    pub fn input_mutation_weight(&self, name: &str) -> f64 {
+        *self.input_mutation_weights.get(name).unwrap_or(&1.0)
+    }
+
+    /// Returns the number of bits an input signal is sampled within, looked up by its bare name.
+    ///
+    /// As with `input_mutation_weights`, all elements of an input array share the hint of the
+    /// array. Inputs without an entry in `input_bit_hints` are sampled from the whole
+    /// `random_value_ranges`.
+    pub fn input_bit_hint(&self, name: &str) -> Option<usize> {
+        self.input_bit_hints.get(name).copied()
+    }
+
+    /// Returns the mutation rate of `generation`.
+    ///
+    /// Without `mutation_rate_schedule`, this is the constant `mutation_rate`. Otherwise, the
+    /// schedule is evaluated at `generation / max_generations`.
+    pub fn mutation_rate_at(&self, generation: usize) -> f64 {
+        match &self.mutation_rate_schedule {
+            Some(schedule) if self.max_generations > 0 => {
+                schedule.rate_at(generation as f64 / self.max_generations as f64)
+            }
+            Some(schedule) => schedule.start_rate,
+            None => self.mutation_rate,
+        }
+    }
+
+    /// Returns the default configuration as pretty-printed JSON, which can be edited and passed
+    /// back with `--path_to_mutation_setting`.
+    pub fn default_json() -> String {
+        serde_json::to_string_pretty(&MutationConfig::default())
+            .expect("Failed to serialize to JSON")
+    }
+}
+
 pub fn load_config_from_json(file_path: &str) -> Result<MutationConfig, serde_json::Error> {
     let file = File::open(file_path);
     if file.is_ok() {
-        let settings: MutationConfig = serde_json::from_reader(file.unwrap())?;
-        Ok(settings)
+        let mut content = String::new();
+        file.unwrap()
+            .read_to_string(&mut content)
+            .map_err(serde_json::Error::io)?;
+        load_config_from_json_str(&content)
     } else {
         info!("Use the default setting for mutation testing");
         Ok(MutationConfig::default())
     }
 }
+
+/// Parses a mutation configuration given as a JSON string.
+///
+/// Omitted keys fall back to their default values, exactly as with `load_config_from_json`, while
+/// unknown keys, e.g., misspelled ones, are rejected with an error naming them instead of being
+/// silently ignored. An exponential `mutation_rate_schedule` whose rates are not positive is
+/// rejected too, since its rates would be NaN or infinite.
+pub fn load_config_from_json_str(json: &str) -> Result<MutationConfig, serde_json::Error> {
+    let settings: MutationConfig = serde_json::from_str(json)?;
+    if let Some(schedule) = &settings.mutation_rate_schedule {
+        if schedule.schedule_type == ScheduleType::Exponential
+            && !(schedule.start_rate > 0.0 && schedule.end_rate > 0.0)
+        {
+            return Err(serde::de::Error::custom(format!(
+                "the exponential `mutation_rate_schedule` requires positive rates, got `start_rate` {} and `end_rate` {}",
+                schedule.start_rate, schedule.end_rate
+            )));
+        }
+    }
+    Ok(settings)
+}
@@ -1,39 +1,260 @@
-use std::collections::HashSet;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashSet};
+use std::fs::File;
 use std::io;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::time::Instant;
 
 use colored::Colorize;
-use log::info;
+use log::{debug, info};
 use num_bigint_dig::BigInt;
 use num_traits::Zero;
 use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use serde_with::{serde_as, DisplayFromStr};
 
+use crate::executor::coverage::CoverageTracker;
 use crate::executor::symbolic_execution::SymbolicExecutor;
 use crate::executor::symbolic_state::{SymbolicConstraints, SymbolicTrace};
 use crate::executor::symbolic_value::{
-    extract_variables, QuadraticPoly, SymbolicName, SymbolicValue,
+    extract_variables, OwnerName, QuadraticPoly, SymbolicName, SymbolicValue,
 };
 
 use crate::executor::utils::solve_quadratic_modulus_equation;
 use crate::mutator::mutation_config::MutationConfig;
+use crate::mutator::mutation_test_operators::{Crossover, Selection, TraceMutation};
+use crate::mutator::mutation_test_update_input_fn::evaluate_coverage;
+use crate::mutator::mutation_utils::{draw_bigint_with_probabilities, render_trace_mutation};
 use crate::mutator::utils::{
-    evaluate_symbolic_value, gather_potential_zero_division, gather_runtime_mutable_inputs,
-    is_containing_binary_check, BaseVerificationConfig, CounterExample, Direction,
+    evaluate_constraints, evaluate_symbolic_value, gather_potential_zero_division,
+    gather_runtime_mutable_inputs, is_containing_binary_check, push_distinct_finding,
+    BaseVerificationConfig, CounterExample, Direction, SearchCompleteness, SearchOutcome,
 };
 
 pub struct MutationTestResult {
     pub random_seed: u64,
     pub mutation_config: MutationConfig,
     pub counter_example: Option<CounterExample>,
+    /// The distinct counterexamples found, up to `max_findings` of the base configuration, the
+    /// first one being `counter_example`.
+    pub counter_examples: Vec<CounterExample>,
     pub generation: usize,
     pub fitness_score_log: Vec<BigInt>,
+    pub best_genome_log: Vec<String>,
+    pub coverage_log: Vec<usize>,
+    pub prefilter_verdict: PrefilterVerdict,
+    /// The traces and inputs of the last generation, to resume the search in a later run.
+    pub final_population: Population,
+    /// The candidates with the highest fitness scores over the whole search, from the best.
+    pub top_candidates: Vec<Candidate>,
+}
+
+impl MutationTestResult {
+    /// Returns the counterexamples as a `SearchOutcome`, which is never exhaustive since the
+    /// genetic search only samples the assignments.
+    pub fn search_outcome(&self) -> SearchOutcome {
+        SearchOutcome {
+            counter_examples: self.counter_examples.clone(),
+            completeness: SearchCompleteness::Incomplete,
+        }
+    }
+}
+
+/// Outcome of the random pre-filter run before the genetic search.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrefilterVerdict {
+    /// The pre-filter is disabled or could not conclude; the search is run.
+    Undecided,
+    /// No random input passes the trace, which hints at a bug in the circuit or in the trace.
+    /// The search is still run, since the inputs may merely be outside of the accepted domain.
+    InfeasibleTrace,
+    /// Every random input satisfies the side constraints and the trace has no mutable assignment.
+    LooksWellConstrained,
+}
+
+/// Snapshot of a running search, reported every `progress_interval` generations.
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ProgressEvent {
+    /// The generation that was just evaluated.
+    pub iteration: usize,
+    /// The best fitness score of the generation; zero means a counterexample was found.
+    #[serde_as(as = "DisplayFromStr")]
+    pub best_fitness: BigInt,
+    /// The number of unique execution paths covered so far. It is cumulative over the generations
+    /// when `save_coverage_log` is enabled, and otherwise as tracked by the executor.
+    pub coverage_count: usize,
+    /// The time elapsed since the search started.
+    pub elapsed_ms: u64,
+    /// The number of mutated traces evaluated so far, each against the whole input population.
+    pub candidates_tried: usize,
+}
+
+/// Writes a progress event to stderr as a single line of JSON, e.g., for monitoring dashboards.
+pub fn report_progress_as_json_line(event: &ProgressEvent) {
+    eprintln!(
+        "{}",
+        serde_json::to_string(event).expect("Failed to serialize to JSON")
+    );
 }
 
 pub type Gene = FxHashMap<usize, SymbolicValue>;
 
+/// The population of the genetic search: the mutated traces and the input assignments.
+///
+/// A population is only meaningful for the circuit it was gathered on, since the genes refer to
+/// positions in the symbolic trace and the inputs refer to the ids of the names.
+#[serde_as]
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Population {
+    pub traces: Vec<Gene>,
+    #[serde_as(as = "Vec<Vec<(_, DisplayFromStr)>>")]
+    pub inputs: Vec<FxHashMap<SymbolicName, BigInt>>,
+}
+
+/// Saves a population as a JSON file.
+pub fn save_population_to_json(
+    population: &Population,
+    file_path: &str,
+) -> Result<(), serde_json::Error> {
+    let mut file = File::create(file_path).map_err(serde_json::Error::io)?;
+    let json_string = serde_json::to_string(population)?;
+    file.write_all(json_string.as_bytes())
+        .map_err(serde_json::Error::io)
+}
+
+/// Loads a population saved by `save_population_to_json`.
+pub fn load_population_from_json(file_path: &str) -> Result<Population, serde_json::Error> {
+    let mut content = String::new();
+    File::open(file_path)
+        .and_then(|mut file| file.read_to_string(&mut content))
+        .map_err(serde_json::Error::io)?;
+    serde_json::from_str(&content)
+}
+
+/// A mutated trace of the search with the input for which it got its fitness score.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    /// The fitness score; zero means a counterexample.
+    pub fitness: BigInt,
+    /// The generation in which the candidate was evaluated.
+    pub generation: usize,
+    /// The mutation of the trace, rendered by `render_trace_mutation`.
+    pub trace_mutation: String,
+    pub assignment: FxHashMap<SymbolicName, BigInt>,
+}
+
+impl Candidate {
+    /// Renders the candidate as JSON, with the inputs keyed by their names.
+    pub fn to_json(&self, lookup: &FxHashMap<usize, String>) -> Value {
+        let assignment: serde_json::Map<String, Value> = self
+            .assignment
+            .iter()
+            .map(|(name, value)| (name.lookup_fmt(lookup), json!(value.to_string())))
+            .collect();
+        json!({
+            "fitness": self.fitness.to_string(),
+            "generation": self.generation,
+            "trace_mutation": self.trace_mutation,
+            "assignment": assignment,
+        })
+    }
+}
+
+struct RankedCandidate {
+    /// The number of candidates offered before this one, which breaks ties in favor of the
+    /// earliest candidate.
+    order: usize,
+    candidate: Candidate,
+}
+
+impl Ord for RankedCandidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.candidate
+            .fitness
+            .cmp(&other.candidate.fitness)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for RankedCandidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for RankedCandidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RankedCandidate {}
+
+/// Keeps the `capacity` candidates with the highest fitness scores offered over a search.
+///
+/// The candidates are kept in a min-heap, so that the worst of them is the one replaced by a
+/// better candidate. Among equal fitness scores, the earliest candidate ranks first, and a
+/// candidate already kept with the same mutation and input is not kept twice.
+pub struct TopCandidates {
+    capacity: usize,
+    heap: BinaryHeap<Reverse<RankedCandidate>>,
+    num_offered: usize,
+}
+
+impl TopCandidates {
+    pub fn new(capacity: usize) -> Self {
+        TopCandidates {
+            capacity: capacity,
+            heap: BinaryHeap::with_capacity(capacity + 1),
+            num_offered: 0,
+        }
+    }
+
+    /// Whether a candidate with the given fitness score would be kept, which spares rendering
+    /// the candidates that would not.
+    pub fn admits(&self, fitness: &BigInt) -> bool {
+        self.capacity > 0
+            && (self.heap.len() < self.capacity
+                || self
+                    .heap
+                    .peek()
+                    .map_or(true, |Reverse(worst)| *fitness > worst.candidate.fitness))
+    }
+
+    pub fn offer(&mut self, candidate: Candidate) {
+        let is_kept = self.heap.iter().any(|Reverse(kept)| {
+            kept.candidate.trace_mutation == candidate.trace_mutation
+                && kept.candidate.assignment == candidate.assignment
+        });
+        if !self.admits(&candidate.fitness) || is_kept {
+            return;
+        }
+        if self.heap.len() == self.capacity {
+            self.heap.pop();
+        }
+        self.heap.push(Reverse(RankedCandidate {
+            order: self.num_offered,
+            candidate: candidate,
+        }));
+        self.num_offered += 1;
+    }
+
+    /// Returns the kept candidates, from the best to the worst.
+    pub fn into_sorted_vec(self) -> Vec<Candidate> {
+        // `Reverse` sorts the heap from the highest rank
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(ranked)| ranked.candidate)
+            .collect()
+    }
+}
+
 /// Conducts a mutation-based search to find counterexamples for symbolic trace verification.
 ///
 /// This function applies a genetic algorithm-like approach to search for counterexamples that
@@ -48,83 +269,150 @@ pub type Gene = FxHashMap<usize, SymbolicValue>;
 /// - `base_config`: The base configuration containing general verification settings.
 /// - `mutation_config`: The mutation-specific configuration, including parameters such as
 ///   population size, mutation rate, and maximum number of generations.
-/// - `trace_initialization_fn`: A function that initializes the population of symbolic traces.
 /// - `update_input_fn`: A function that updates the input population at regular intervals.
 /// - `trace_fitness_fn`: A function that evaluates the fitness of a given trace and determines if it violates constraints.
 /// - `trace_evolution_fn`: A function that handles the evolution of the trace population by applying
 ///   mutation, crossover, and selection.
-/// - `trace_mutation_fn`: A function that applies mutation to a trace.
-/// - `trace_crossover_fn`: A function that combines two parent traces to produce an offspring trace.
-/// - `trace_selection_fn`: A function that selects traces from the population based on their fitness scores.
+/// - `trace_mutation`: The operator that initializes the population of symbolic traces and
+///   applies mutation to a trace.
+/// - `trace_crossover`: The operator that combines two parent traces to produce an offspring trace.
+/// - `trace_selection`: The operator that selects traces from the population based on their fitness scores.
+/// - `progress_fn`: A callback receiving a `ProgressEvent` every `progress_interval` generations
+///   and when a counterexample is found, independently of the human-readable progress on stdout.
 ///
 /// # Returns
 /// A `MutationTestResult` containing:
 /// - `random_seed`: The seed used for the random number generator.
 /// - `mutation_config`: A copy of the mutation configuration.
 /// - `counter_example`: An optional counterexample found during the search.
+/// - `counter_examples`: The distinct counterexamples found during the search. Unless
+///   `max_findings` of `base_config` is greater than one, the search stops at the first one.
 /// - `generation`: The generation in which the counterexample was found, or the maximum number of generations if no solution was found.
 /// - `fitness_score_log`: A log of the best fitness scores across generations.
+/// - `best_genome_log`: When `log_best_genome` is enabled, the rendered best genome of each generation.
+/// - `coverage_log`: When `save_coverage_log` is enabled, the cumulative number of unique execution
+///   paths covered by the input population after each generation.
+/// - `prefilter_verdict`: The verdict of the random pre-filter. When it is `LooksWellConstrained`,
+///   the search is skipped and `generation` is zero.
+/// - `top_candidates`: The `num_top_candidates` evaluated traces with the highest fitness scores,
+///   which show how close a seemingly well-constrained circuit came to a counterexample.
 ///
 /// # Type Parameters
-/// - `TraceInitializationFn`: A closure or function that initializes the population of traces.
 /// - `UpdateInputFn`: A closure or function that updates the input population.
 /// - `TraceFitnessFn`: A closure or function that evaluates the fitness of a symbolic trace.
 /// - `TraceEvolutionFn`: A closure or function that handles trace population evolution.
-/// - `TraceMutationFn`: A closure or function that mutates a trace.
-/// - `TraceCrossoverFn`: A closure or function that performs crossover between two traces.
-/// - `TraceSelectionFn`: A closure or function that selects traces from the population.
 ///
 /// # Algorithm
 /// 1. **Initialization**:
 ///    - Set the random seed.
 ///    - Identify mutable locations in the symbolic trace.
 ///    - Extract input variables and constraints.
+///    - Concretely execute `prefilter_samples` random inputs, warn if none of them passes the
+///      trace, and stop early if the circuit trivially looks well-constrained.
 ///    - Initialize the population of symbolic traces.
 ///
 /// 2. **Iterative Search**:
 ///    - Update the input population at regular intervals.
 ///    - Evolve the trace population using mutation, crossover, and selection.
 ///    - Evaluate the fitness of the population.
-///    - If a counterexample is found, return it immediately.
+///    - If a counterexample is found, return it immediately, or, when `max_findings` is greater
+///      than one, once that many distinct counterexamples are found.
 ///
 /// 3. **Termination**:
 ///    - Stop after reaching the maximum number of generations.
 ///    - If no solution is found, return a result indicating failure.
 ///
 /// # Notes
-/// - This function assumes that all closures and functions provided as parameters are consistent with the structure of the symbolic execution process.
+/// - This function assumes that all closures, functions, and operators provided as parameters are consistent with the structure of the symbolic execution process.
+/// - The operators are typically looked up by name in an `OperatorRegistry`.
 /// - The fitness function must be designed such that a fitness score of zero indicates a counterexample.
-pub fn mutation_test_search<
-    TraceInitializationFn,
-    UpdateInputFn,
-    TraceFitnessFn,
-    TraceEvolutionFn,
-    TraceMutationFn,
-    TraceCrossoverFn,
-    TraceSelectionFn,
->(
+pub fn mutation_test_search<UpdateInputFn, TraceFitnessFn, TraceEvolutionFn>(
     sexe: &mut SymbolicExecutor,
     symbolic_trace: &SymbolicTrace,
     side_constraints: &SymbolicConstraints,
     base_config: &BaseVerificationConfig,
     base_mutation_config: &MutationConfig,
-    trace_initialization_fn: TraceInitializationFn,
     update_input_fn: UpdateInputFn,
     trace_fitness_fn: TraceFitnessFn,
     trace_evolution_fn: TraceEvolutionFn,
-    trace_mutation_fn: TraceMutationFn,
-    trace_crossover_fn: TraceCrossoverFn,
-    trace_selection_fn: TraceSelectionFn,
+    trace_mutation: &dyn TraceMutation,
+    trace_crossover: &dyn Crossover,
+    trace_selection: &dyn Selection,
+    progress_fn: &mut dyn FnMut(ProgressEvent),
 ) -> MutationTestResult
 where
-    TraceInitializationFn: Fn(
+    UpdateInputFn: Fn(
+        &mut SymbolicExecutor,
+        &[SymbolicName],
+        &mut Vec<FxHashMap<SymbolicName, BigInt>>,
+        &Vec<BigInt>,
+        &BaseVerificationConfig,
+        &MutationConfig,
+        &mut StdRng,
+    ),
+    TraceFitnessFn: Fn(
+        &mut SymbolicExecutor,
+        &BaseVerificationConfig,
+        &MutationConfig,
+        &SymbolicTrace,
+        &SymbolicConstraints,
+        &FxHashMap<usize, Direction>,
+        &Gene,
+        &Vec<FxHashMap<SymbolicName, BigInt>>,
+        &mut Vec<BigInt>,
+    ) -> (usize, BigInt, Option<CounterExample>, usize),
+    TraceEvolutionFn: Fn(
         &[usize],
-        usize,
         &SymbolicTrace,
+        &[Gene],
+        &[BigInt],
         &BaseVerificationConfig,
         &MutationConfig,
         &mut StdRng,
+        &dyn TraceMutation,
+        &dyn Crossover,
+        &dyn Selection,
     ) -> Vec<Gene>,
+{
+    mutation_test_search_with_population(
+        sexe,
+        symbolic_trace,
+        side_constraints,
+        base_config,
+        base_mutation_config,
+        update_input_fn,
+        trace_fitness_fn,
+        trace_evolution_fn,
+        trace_mutation,
+        trace_crossover,
+        trace_selection,
+        None,
+        progress_fn,
+    )
+}
+
+/// Conducts the search of `mutation_test_search`, optionally starting from the population of an
+/// earlier run, e.g., loaded with `load_population_from_json`.
+///
+/// When `initial_population` is given, `trace_mutation` is not used to build the first
+/// generation, and the loaded inputs replace the first update of the input population. Later
+/// generations are handled exactly as in `mutation_test_search`.
+pub fn mutation_test_search_with_population<UpdateInputFn, TraceFitnessFn, TraceEvolutionFn>(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &SymbolicTrace,
+    side_constraints: &SymbolicConstraints,
+    base_config: &BaseVerificationConfig,
+    base_mutation_config: &MutationConfig,
+    update_input_fn: UpdateInputFn,
+    trace_fitness_fn: TraceFitnessFn,
+    trace_evolution_fn: TraceEvolutionFn,
+    trace_mutation: &dyn TraceMutation,
+    trace_crossover: &dyn Crossover,
+    trace_selection: &dyn Selection,
+    initial_population: Option<Population>,
+    progress_fn: &mut dyn FnMut(ProgressEvent),
+) -> MutationTestResult
+where
     UpdateInputFn: Fn(
         &mut SymbolicExecutor,
         &[SymbolicName],
@@ -153,30 +441,19 @@ where
         &BaseVerificationConfig,
         &MutationConfig,
         &mut StdRng,
-        &TraceMutationFn,
-        &TraceCrossoverFn,
-        &TraceSelectionFn,
+        &dyn TraceMutation,
+        &dyn Crossover,
+        &dyn Selection,
     ) -> Vec<Gene>,
-    TraceMutationFn: Fn(
-        &[usize],
-        &SymbolicTrace,
-        &mut Gene,
-        &BaseVerificationConfig,
-        &MutationConfig,
-        &mut StdRng,
-    ),
-    TraceCrossoverFn: Fn(&Gene, &Gene, &mut StdRng) -> Gene,
-    TraceSelectionFn: for<'a> Fn(&'a [Gene], &[BigInt], &mut StdRng) -> &'a Gene,
 {
     let mut mutation_config = base_mutation_config.clone();
 
+    let start = Instant::now();
+
     // Set random seed
-    let seed = if mutation_config.seed.is_zero() {
-        let mut seed_rng = rand::thread_rng();
-        seed_rng.gen()
-    } else {
-        mutation_config.seed
-    };
+    let seed = mutation_config
+        .seed
+        .unwrap_or_else(|| rand::thread_rng().gen());
     let mut rng = StdRng::seed_from_u64(seed);
 
     // Gather mutable locations
@@ -208,6 +485,7 @@ where
                 [&sexe.symbolic_library.name2id[&base_config.target_template_name]]
                 .input_ids
                 .contains(&v.id)
+            && !base_config.input_constraints.contains_key(v)
         {
             input_variables.push(v.clone());
         }
@@ -236,31 +514,87 @@ where
         assign_pos.len().to_string().bright_yellow()
     );
 
-    // Initial Pupulation of Mutated Inputs
-    let mut trace_population = trace_initialization_fn(
-        &assign_pos,
-        mutation_config.program_population_size,
-        &symbolic_trace,
+    let prefilter_verdict = run_prefilter(
+        sexe,
+        side_constraints,
         base_config,
         &mutation_config,
-        &mut rng,
+        &input_variables,
+        assign_pos.is_empty() && runtime_mutable_positions.is_empty(),
+        seed,
     );
-    let mut fitness_scores =
-        vec![-base_config.prime.clone(); mutation_config.program_population_size + 1];
-    let mut fitness_scores_inputs =
-        vec![-base_config.prime.clone(); mutation_config.input_population_size];
-    let mut input_population = Vec::new();
+    match prefilter_verdict {
+        _ if mutation_config.quiet => {}
+        PrefilterVerdict::InfeasibleTrace => {
+            println!(
+                "{}",
+                "🚧 Infeasible trace (possible bug): no random input passes the trace".yellow()
+            );
+        }
+        PrefilterVerdict::LooksWellConstrained => {
+            println!(
+                "{}",
+                "🛡️ The circuit looks well-constrained: no mutable assignment and every random input satisfies the side constraints".green()
+            );
+        }
+        PrefilterVerdict::Undecided => {}
+    }
+    if prefilter_verdict == PrefilterVerdict::LooksWellConstrained {
+        return MutationTestResult {
+            random_seed: seed,
+            mutation_config: mutation_config.clone(),
+            counter_example: None,
+            counter_examples: Vec::new(),
+            generation: 0,
+            fitness_score_log: Vec::new(),
+            best_genome_log: Vec::new(),
+            coverage_log: Vec::new(),
+            prefilter_verdict: prefilter_verdict,
+            final_population: Population::default(),
+            top_candidates: Vec::new(),
+        };
+    }
+
+    // Initial Pupulation of Mutated Inputs
+    let (mut trace_population, mut input_population) = match initial_population {
+        Some(population) => (population.traces, population.inputs),
+        None => (
+            trace_mutation.initialize_population(
+                &assign_pos,
+                mutation_config.program_population_size,
+                &symbolic_trace,
+                base_config,
+                &mutation_config,
+                &mut rng,
+            ),
+            Vec::new(),
+        ),
+    };
+    let mut fitness_scores = vec![-base_config.prime.clone(); trace_population.len() + 1];
+    let mut fitness_scores_inputs = vec![
+        -base_config.prime.clone();
+        std::cmp::max(
+            mutation_config.input_population_size,
+            input_population.len()
+        )
+    ];
     let mut fitness_score_log = if mutation_config.save_fitness_scores {
         Vec::with_capacity(mutation_config.max_generations)
     } else {
         Vec::new()
     };
+    let mut best_genome_log = Vec::new();
+    let mut coverage_log = Vec::new();
+    let mut cumulative_coverage = CoverageTracker::new();
+    let mut top_candidates = TopCandidates::new(mutation_config.num_top_candidates);
 
-    println!(
-        "{} {}",
-        "🎲 Random Seed:",
-        seed.to_string().bold().bright_yellow(),
-    );
+    if !mutation_config.quiet {
+        println!(
+            "{} {}",
+            "🎲 Random Seed:",
+            seed.to_string().bold().bright_yellow(),
+        );
+    }
 
     let mut binary_input_mode = false;
     let mut partial_binary_mode = false;
@@ -273,6 +607,8 @@ where
 
     let potential_zero_div_positions = gather_potential_zero_division(symbolic_trace);
     let mut zero_div_cache = FxHashMap::default();
+    let mut candidates_tried = 0;
+    let mut counter_examples: Vec<CounterExample> = Vec::new();
 
     for generation in 0..mutation_config.max_generations {
         if partial_binary_mode
@@ -285,9 +621,12 @@ where
         } else {
             mutation_config.binary_mode_prob = original_binary_mode_prob;
         }
+        mutation_config.mutation_rate = mutation_config.mutation_rate_at(generation);
 
-        // Generate input population for this generation
-        if generation % mutation_config.input_update_interval == 0 {
+        // Generate input population for this generation, unless the inputs were loaded
+        if generation % mutation_config.input_update_interval == 0
+            && (generation > 0 || input_population.is_empty())
+        {
             update_input_fn(
                 sexe,
                 &input_variables,
@@ -309,9 +648,9 @@ where
                 base_config,
                 &mutation_config,
                 &mut rng,
-                &trace_mutation_fn,
-                &trace_crossover_fn,
-                &trace_selection_fn,
+                trace_mutation,
+                trace_crossover,
+                trace_selection,
             );
         }
         trace_population.push(FxHashMap::default());
@@ -333,6 +672,13 @@ where
             }
         }
 
+        // Inputs fixed by the user are never mutated
+        if !base_config.input_constraints.is_empty() {
+            for inp in input_population.iter_mut() {
+                base_config.pin_input_constraints(inp);
+            }
+        }
+
         // Evaluate the trace population
         let mut evaluations = Vec::new();
         let mut is_extincted_due_to_illegal_subscript = true;
@@ -354,7 +700,11 @@ where
             );
             if fitness.1.is_zero() {
                 evaluations.push(fitness);
-                break;
+                // The other individuals may exhibit other bugs
+                if base_config.max_findings <= 1 {
+                    break;
+                }
+                continue;
             }
             is_extincted_due_to_illegal_subscript =
                 is_extincted_due_to_illegal_subscript && fitness.3 == input_population.len();
@@ -375,45 +725,133 @@ where
             mutation_config.random_value_probs = vec![1.0];
         }
 
+        for (individual, evaluation) in trace_population.iter().zip(evaluations.iter()) {
+            if let Some(assignment) = input_population.get(evaluation.0) {
+                if top_candidates.admits(&evaluation.1) {
+                    top_candidates.offer(Candidate {
+                        fitness: evaluation.1.clone(),
+                        generation: generation,
+                        trace_mutation: render_trace_mutation(
+                            symbolic_trace,
+                            individual,
+                            &sexe.symbolic_library.id2name,
+                        ),
+                        assignment: assignment.clone(),
+                    });
+                }
+            }
+        }
+
         let mut evaluation_indices: Vec<usize> = (0..evaluations.len()).collect();
         evaluation_indices.sort_by(|&i, &j| evaluations[i].1.cmp(&evaluations[j].1));
 
         // Pick the best one
         let best_idx = evaluation_indices.last().unwrap();
 
-        if evaluations[*best_idx].1.is_zero() {
-            print!(
-                "\r\x1b[2K🧬 Generation: {}/{} ({:.3})",
-                generation, mutation_config.max_generations, 0
+        if mutation_config.log_best_genome {
+            let rendered = format!(
+                "🧬 Best genome of generation {}: {}",
+                generation,
+                render_trace_mutation(
+                    symbolic_trace,
+                    &trace_population[*best_idx],
+                    &sexe.symbolic_library.id2name
+                )
             );
-            println!("\n    └─ Solution found in generation {}", generation);
+            debug!("{}", rendered);
+            best_genome_log.push(rendered);
+        }
+
+        if mutation_config.save_coverage_log {
+            // The input updater may reset the tracker of `sexe`, so paths are accumulated separately
+            sexe.clear_coverage_tracker();
+            for inp in &input_population {
+                evaluate_coverage(sexe, inp, base_config);
+            }
+            cumulative_coverage.merge(sexe.coverage_tracker());
+            coverage_log.push(cumulative_coverage.coverage_count());
+        }
+
+        candidates_tried += evaluations.len();
+        if generation % mutation_config.progress_interval == 0 || evaluations[*best_idx].1.is_zero()
+        {
+            progress_fn(ProgressEvent {
+                iteration: generation,
+                best_fitness: evaluations[*best_idx].1.clone(),
+                coverage_count: if mutation_config.save_coverage_log {
+                    cumulative_coverage.coverage_count()
+                } else {
+                    sexe.coverage_count()
+                },
+                elapsed_ms: start.elapsed().as_millis() as u64,
+                candidates_tried: candidates_tried,
+            });
+        }
+
+        let num_known_findings = counter_examples.len();
+        for evaluation in &evaluations {
+            if let (true, Some(counter_example)) = (evaluation.1.is_zero(), &evaluation.2) {
+                push_distinct_finding(&mut counter_examples, counter_example.clone());
+            }
+        }
+
+        if !counter_examples.is_empty() && counter_examples.len() >= base_config.max_findings {
+            if !mutation_config.quiet {
+                print!(
+                    "\r{}🧬 Generation: {}/{} ({:.3})",
+                    clear_line(),
+                    generation,
+                    mutation_config.max_generations,
+                    0
+                );
+                println!("\n    └─ Solution found in generation {}", generation);
+            }
 
             return MutationTestResult {
                 random_seed: seed,
                 mutation_config: mutation_config.clone(),
-                counter_example: evaluations[*best_idx].2.clone(),
+                counter_example: counter_examples.first().cloned(),
+                counter_examples: counter_examples,
                 generation: generation,
                 fitness_score_log: fitness_score_log,
+                best_genome_log: best_genome_log,
+                coverage_log: coverage_log,
+                prefilter_verdict: prefilter_verdict,
+                final_population: into_population(trace_population, input_population),
+                top_candidates: top_candidates.into_sorted_vec(),
             };
         }
 
+        if !mutation_config.quiet && counter_examples.len() > num_known_findings {
+            println!(
+                "\n    ├─ {} distinct counterexample(s) found by generation {}",
+                counter_examples.len(),
+                generation
+            );
+        }
+
         // Extract the fitness scores
         if mutation_config.fitness_function != "const" {
             fitness_scores = evaluations.iter().map(|v| v.1.clone()).collect();
         }
 
-        print!(
-            "\r\x1b[2K🧬 Generation: {}/{} ({:.3})",
-            generation, mutation_config.max_generations, fitness_scores[*best_idx]
-        );
-        io::stdout().flush().unwrap();
+        if !mutation_config.quiet {
+            print!(
+                "\r{}🧬 Generation: {}/{} ({:.3})",
+                clear_line(),
+                generation,
+                mutation_config.max_generations,
+                fitness_scores[*best_idx]
+            );
+            io::stdout().flush().unwrap();
+        }
 
         if mutation_config.save_fitness_scores {
             fitness_score_log.push(fitness_scores[*best_idx].clone());
         }
 
         // Reset individuals with poor fitness score
-        let new_trace_population = trace_initialization_fn(
+        let new_trace_population = trace_mutation.initialize_population(
             &assign_pos,
             mutation_config.num_eliminated_individuals,
             &symbolic_trace,
@@ -430,17 +868,132 @@ where
         }
     }
 
-    println!(
-        "\n └─ No solution found after {} generations",
-        mutation_config.max_generations
-    );
+    if !mutation_config.quiet {
+        if counter_examples.is_empty() {
+            println!(
+                "\n └─ No solution found after {} generations",
+                mutation_config.max_generations
+            );
+        } else {
+            println!(
+                "\n └─ {} distinct counterexample(s) found after {} generations",
+                counter_examples.len(),
+                mutation_config.max_generations
+            );
+        }
+    }
 
     MutationTestResult {
         random_seed: seed,
         mutation_config: mutation_config.clone(),
-        counter_example: None,
+        counter_example: counter_examples.first().cloned(),
+        counter_examples: counter_examples,
         generation: mutation_config.max_generations,
         fitness_score_log: fitness_score_log,
+        best_genome_log: best_genome_log,
+        coverage_log: coverage_log,
+        prefilter_verdict: prefilter_verdict,
+        final_population: into_population(trace_population, input_population),
+        top_candidates: top_candidates.into_sorted_vec(),
+    }
+}
+
+/// Returns the ANSI code erasing the current line, or nothing when colors are disabled, so that
+/// redirected progress lines stay free of escape codes.
+fn clear_line() -> &'static str {
+    if colored::control::SHOULD_COLORIZE.should_colorize() {
+        "\x1b[2K"
+    } else {
+        ""
+    }
+}
+
+/// Packs the populations of the search, leaving out the unmutated traces, which every generation
+/// adds anew.
+fn into_population(
+    trace_population: Vec<Gene>,
+    input_population: Vec<FxHashMap<SymbolicName, BigInt>>,
+) -> Population {
+    Population {
+        traces: trace_population
+            .into_iter()
+            .filter(|gene| !gene.is_empty())
+            .collect(),
+        inputs: input_population,
+    }
+}
+
+/// Concretely executes a few random inputs to detect cases where the genetic search is pointless.
+///
+/// The pre-filter uses its own random number generator seeded with `seed`, so enabling or
+/// disabling it does not change the course of the search itself.
+fn run_prefilter(
+    sexe: &mut SymbolicExecutor,
+    side_constraints: &SymbolicConstraints,
+    base_config: &BaseVerificationConfig,
+    mutation_config: &MutationConfig,
+    input_variables: &[SymbolicName],
+    has_no_mutable_position: bool,
+    seed: u64,
+) -> PrefilterVerdict {
+    if mutation_config.prefilter_samples == 0 {
+        return PrefilterVerdict::Undecided;
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut num_feasible = 0;
+    let mut num_satisfied = 0;
+    for _ in 0..mutation_config.prefilter_samples {
+        let mut inputs: FxHashMap<SymbolicName, BigInt> = input_variables
+            .iter()
+            .map(|var| {
+                (
+                    var.clone(),
+                    draw_bigint_with_probabilities(mutation_config, &mut rng).unwrap(),
+                )
+            })
+            .collect();
+        base_config.pin_input_constraints(&mut inputs);
+
+        sexe.clear();
+        sexe.cur_state.add_owner(&OwnerName {
+            id: sexe.symbolic_library.name2id["main"],
+            counter: 0,
+            access: None,
+        });
+        sexe.feed_arguments(
+            &base_config.template_param_names,
+            &base_config.template_param_values,
+        )
+        .expect("Template arguments are validated when the main component is instantiated");
+        sexe.concrete_execute(&base_config.target_template_name, &inputs);
+        if sexe.cur_state.is_failed {
+            continue;
+        }
+        num_feasible += 1;
+
+        let mut assignment = FxHashMap::default();
+        for (k, v) in sexe.cur_state.symbol_binding_map.iter() {
+            if let SymbolicValue::ConstantInt(num) = v.as_ref() {
+                assignment.insert(k.clone(), num.clone());
+            }
+        }
+        if evaluate_constraints(
+            &base_config.prime,
+            side_constraints,
+            &assignment,
+            &mut sexe.symbolic_library,
+        ) {
+            num_satisfied += 1;
+        }
+    }
+
+    if num_feasible == 0 {
+        PrefilterVerdict::InfeasibleTrace
+    } else if has_no_mutable_position && num_satisfied == mutation_config.prefilter_samples {
+        PrefilterVerdict::LooksWellConstrained
+    } else {
+        PrefilterVerdict::Undecided
     }
 }
 
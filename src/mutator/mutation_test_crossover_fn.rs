@@ -1,7 +1,10 @@
+use num_bigint_dig::BigInt;
 use rand::rngs::StdRng;
 use rand::Rng;
 use rustc_hash::FxHashMap;
 
+use crate::executor::symbolic_value::SymbolicName;
+
 /// Generates a new `FxHashMap` by performing a random crossover between two parent maps.
 ///
 /// # Parameters
@@ -65,3 +68,47 @@ where
         })
         .collect()
 }
+
+/// Generates a new input assignment by a uniform crossover between two parent assignments.
+///
+/// Unlike `random_crossover`, which follows the keys of `parent1`, the child assigns every signal
+/// of either parent. The value of a signal assigned by both parents is picked from each of them
+/// with probability 1/2, independently of the other signals, and the value of a signal assigned
+/// by only one parent is taken from that parent.
+///
+/// # Parameters
+/// - `parent1`: The first parent assignment.
+/// - `parent2`: The second parent assignment.
+/// - `rng`: A mutable reference to the random number generator.
+///
+/// # Returns
+/// The child assignment, each of whose values comes from one of the parents.
+pub fn input_uniform_crossover(
+    parent1: &FxHashMap<SymbolicName, BigInt>,
+    parent2: &FxHashMap<SymbolicName, BigInt>,
+    rng: &mut StdRng,
+) -> FxHashMap<SymbolicName, BigInt> {
+    let mut names: Vec<&SymbolicName> = parent1
+        .keys()
+        .chain(parent2.keys().filter(|name| !parent1.contains_key(*name)))
+        .collect();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| {
+            let value = match (parent1.get(name), parent2.get(name)) {
+                (Some(val1), Some(val2)) => {
+                    if rng.gen::<bool>() {
+                        val1
+                    } else {
+                        val2
+                    }
+                }
+                (Some(val), None) | (None, Some(val)) => val,
+                (None, None) => unreachable!(),
+            };
+            (name.clone(), value.clone())
+        })
+        .collect()
+}
@@ -0,0 +1,300 @@
+use num_bigint_dig::BigInt;
+use rand::rngs::StdRng;
+use rustc_hash::FxHashMap;
+
+use crate::executor::symbolic_state::SymbolicTrace;
+use crate::mutator::mutation_config::MutationConfig;
+use crate::mutator::mutation_test::Gene;
+use crate::mutator::mutation_test_crossover_fn::random_crossover;
+use crate::mutator::mutation_test_trace_initialization_fn::{
+    initialize_population_with_constant_replacement, initialize_population_with_index_offset,
+    initialize_population_with_operator_or_const_replacement,
+    initialize_population_with_operator_or_const_replacement_or_addition,
+    initialize_population_with_operator_or_const_replacement_or_deletion,
+    initialize_population_with_signal_swap,
+};
+use crate::mutator::mutation_test_trace_mutation_fn::{
+    mutate_trace_with_constant_replacement, mutate_trace_with_constraint_deletion,
+    mutate_trace_with_index_offset, mutate_trace_with_operator_or_const_replacement,
+    mutate_trace_with_operator_or_const_replacement_or_addition,
+    mutate_trace_with_operator_or_const_replacement_or_deletion, mutate_trace_with_signal_swap,
+};
+use crate::mutator::mutation_test_trace_selection_fn::{roulette_selection, tournament_selection};
+use crate::mutator::utils::BaseVerificationConfig;
+
+/// A mutation of the traces of the genetic search.
+///
+/// A trace mutation builds the first generation of traces as well as mutates the offspring of
+/// the later ones, so that both are drawn from the same kind of mutations.
+pub trait TraceMutation {
+    /// Builds `program_population_size` mutated traces from the mutable positions `pos`.
+    fn initialize_population(
+        &self,
+        pos: &[usize],
+        program_population_size: usize,
+        symbolic_trace: &SymbolicTrace,
+        base_config: &BaseVerificationConfig,
+        mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    ) -> Vec<Gene>;
+
+    /// Mutates `individual` in place.
+    fn mutate(
+        &self,
+        pos: &[usize],
+        symbolic_trace: &SymbolicTrace,
+        individual: &mut Gene,
+        base_config: &BaseVerificationConfig,
+        mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    );
+}
+
+/// A crossover combining two parent traces into an offspring.
+pub trait Crossover {
+    fn crossover(&self, parent1: &Gene, parent2: &Gene, rng: &mut StdRng) -> Gene;
+}
+
+/// A selection of the parents of the next generation of traces.
+pub trait Selection {
+    /// Selects an individual of `population`, whose fitness scores are `fitness_scores`.
+    fn select<'a>(
+        &self,
+        population: &'a [Gene],
+        fitness_scores: &[BigInt],
+        mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    ) -> &'a Gene;
+}
+
+pub type TraceInitializationFn = fn(
+    &[usize],
+    usize,
+    &SymbolicTrace,
+    &BaseVerificationConfig,
+    &MutationConfig,
+    &mut StdRng,
+) -> Vec<Gene>;
+
+pub type TraceMutationFn =
+    fn(&[usize], &SymbolicTrace, &mut Gene, &BaseVerificationConfig, &MutationConfig, &mut StdRng);
+
+/// A `TraceMutation` made of an initialization function and a mutation function, such as the
+/// ones of `mutation_test_trace_initialization_fn` and `mutation_test_trace_mutation_fn`.
+pub struct FnTraceMutation {
+    pub initialization_fn: TraceInitializationFn,
+    pub mutation_fn: TraceMutationFn,
+}
+
+impl TraceMutation for FnTraceMutation {
+    fn initialize_population(
+        &self,
+        pos: &[usize],
+        program_population_size: usize,
+        symbolic_trace: &SymbolicTrace,
+        base_config: &BaseVerificationConfig,
+        mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    ) -> Vec<Gene> {
+        (self.initialization_fn)(
+            pos,
+            program_population_size,
+            symbolic_trace,
+            base_config,
+            mutation_config,
+            rng,
+        )
+    }
+
+    fn mutate(
+        &self,
+        pos: &[usize],
+        symbolic_trace: &SymbolicTrace,
+        individual: &mut Gene,
+        base_config: &BaseVerificationConfig,
+        mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    ) {
+        (self.mutation_fn)(
+            pos,
+            symbolic_trace,
+            individual,
+            base_config,
+            mutation_config,
+            rng,
+        )
+    }
+}
+
+/// See `random_crossover`.
+pub struct RandomCrossover;
+
+impl Crossover for RandomCrossover {
+    fn crossover(&self, parent1: &Gene, parent2: &Gene, rng: &mut StdRng) -> Gene {
+        random_crossover(parent1, parent2, rng)
+    }
+}
+
+/// See `roulette_selection`.
+pub struct RouletteSelection;
+
+impl Selection for RouletteSelection {
+    fn select<'a>(
+        &self,
+        population: &'a [Gene],
+        fitness_scores: &[BigInt],
+        _mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    ) -> &'a Gene {
+        roulette_selection(population, fitness_scores, rng)
+    }
+}
+
+/// See `tournament_selection`, whose tournaments have `tournament_size` competitors.
+pub struct TournamentSelection;
+
+impl Selection for TournamentSelection {
+    fn select<'a>(
+        &self,
+        population: &'a [Gene],
+        fitness_scores: &[BigInt],
+        mutation_config: &MutationConfig,
+        rng: &mut StdRng,
+    ) -> &'a Gene {
+        tournament_selection(
+            population,
+            fitness_scores,
+            rng,
+            mutation_config.tournament_size,
+        )
+    }
+}
+
+/// The genetic operators of the search, keyed by the names used in `MutationConfig`.
+///
+/// The default registry holds the built-in operators, looked up by `trace_mutation_method`,
+/// `crossover_method`, and `selection_method`. Registering an operator under a new name makes it
+/// available to the configuration, and registering it under an existing name replaces the
+/// built-in one.
+pub struct OperatorRegistry {
+    trace_mutations: FxHashMap<String, Box<dyn TraceMutation>>,
+    crossovers: FxHashMap<String, Box<dyn Crossover>>,
+    selections: FxHashMap<String, Box<dyn Selection>>,
+}
+
+impl OperatorRegistry {
+    /// Creates a registry without any operator.
+    pub fn new() -> Self {
+        OperatorRegistry {
+            trace_mutations: FxHashMap::default(),
+            crossovers: FxHashMap::default(),
+            selections: FxHashMap::default(),
+        }
+    }
+
+    pub fn register_trace_mutation(&mut self, name: &str, operator: Box<dyn TraceMutation>) {
+        self.trace_mutations.insert(name.to_string(), operator);
+    }
+
+    pub fn register_crossover(&mut self, name: &str, operator: Box<dyn Crossover>) {
+        self.crossovers.insert(name.to_string(), operator);
+    }
+
+    pub fn register_selection(&mut self, name: &str, operator: Box<dyn Selection>) {
+        self.selections.insert(name.to_string(), operator);
+    }
+
+    pub fn trace_mutation(&self, name: &str) -> Option<&dyn TraceMutation> {
+        self.trace_mutations.get(name).map(|op| op.as_ref())
+    }
+
+    pub fn crossover(&self, name: &str) -> Option<&dyn Crossover> {
+        self.crossovers.get(name).map(|op| op.as_ref())
+    }
+
+    pub fn selection(&self, name: &str) -> Option<&dyn Selection> {
+        self.selections.get(name).map(|op| op.as_ref())
+    }
+
+    /// Returns the names of the registered trace mutations in lexicographic order.
+    pub fn trace_mutation_names(&self) -> Vec<String> {
+        sorted_names(&self.trace_mutations)
+    }
+
+    /// Returns the names of the registered crossovers in lexicographic order.
+    pub fn crossover_names(&self) -> Vec<String> {
+        sorted_names(&self.crossovers)
+    }
+
+    /// Returns the names of the registered selections in lexicographic order.
+    pub fn selection_names(&self) -> Vec<String> {
+        sorted_names(&self.selections)
+    }
+}
+
+impl Default for OperatorRegistry {
+    fn default() -> Self {
+        let mut registry = OperatorRegistry::new();
+        let trace_mutations: [(&str, TraceInitializationFn, TraceMutationFn); 8] = [
+            (
+                "naive",
+                initialize_population_with_constant_replacement,
+                mutate_trace_with_constant_replacement,
+            ),
+            (
+                "constant",
+                initialize_population_with_constant_replacement,
+                mutate_trace_with_constant_replacement,
+            ),
+            (
+                "constant_operator",
+                initialize_population_with_operator_or_const_replacement,
+                mutate_trace_with_operator_or_const_replacement,
+            ),
+            (
+                "constant_operator_add",
+                initialize_population_with_operator_or_const_replacement_or_addition,
+                mutate_trace_with_operator_or_const_replacement_or_addition,
+            ),
+            (
+                "constant_operator_delete",
+                initialize_population_with_operator_or_const_replacement_or_deletion,
+                mutate_trace_with_operator_or_const_replacement_or_deletion,
+            ),
+            (
+                "constraint_delete",
+                initialize_population_with_operator_or_const_replacement,
+                mutate_trace_with_constraint_deletion,
+            ),
+            (
+                "signal_swap",
+                initialize_population_with_signal_swap,
+                mutate_trace_with_signal_swap,
+            ),
+            (
+                "index_offset",
+                initialize_population_with_index_offset,
+                mutate_trace_with_index_offset,
+            ),
+        ];
+        for (name, initialization_fn, mutation_fn) in trace_mutations {
+            registry.register_trace_mutation(
+                name,
+                Box::new(FnTraceMutation {
+                    initialization_fn,
+                    mutation_fn,
+                }),
+            );
+        }
+        registry.register_crossover("random", Box::new(RandomCrossover));
+        registry.register_selection("roulette", Box::new(RouletteSelection));
+        registry.register_selection("tournament", Box::new(TournamentSelection));
+        registry
+    }
+}
+
+fn sorted_names<T: ?Sized>(operators: &FxHashMap<String, Box<T>>) -> Vec<String> {
+    let mut names: Vec<String> = operators.keys().cloned().collect();
+    names.sort();
+    names
+}
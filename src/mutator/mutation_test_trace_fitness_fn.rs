@@ -3,11 +3,14 @@ use num_traits::Zero;
 use rustc_hash::FxHashMap;
 
 use crate::executor::symbolic_execution::SymbolicExecutor;
-use crate::executor::symbolic_value::{SymbolicName, SymbolicValue, SymbolicValueRef};
+use crate::executor::symbolic_value::{
+    SymbolicLibrary, SymbolicName, SymbolicValue, SymbolicValueRef,
+};
+use crate::mutator::compiled_trace::CompiledTrace;
 use crate::mutator::mutation_config::MutationConfig;
 use crate::mutator::mutation_utils::apply_trace_mutation;
 use crate::mutator::utils::{
-    accumulate_error_of_constraints, count_error_constraints, emulate_symbolic_trace,
+    accumulate_distance_of_constraints, accumulate_error_of_constraints, count_error_constraints,
     evaluate_constraints, is_equal_mod, max_error_of_constraints, BaseVerificationConfig,
     CounterExample, Direction, UnderConstrainedType, VerificationResult,
 };
@@ -62,24 +65,104 @@ pub fn evaluate_trace_fitness_by_error(
     trace_mutation: &FxHashMap<usize, SymbolicValue>,
     inputs_assignment: &Vec<FxHashMap<SymbolicName, BigInt>>,
     fitness_scores_inputs: &mut Vec<BigInt>,
+) -> (usize, BigInt, Option<CounterExample>, usize) {
+    let error_of_constraints: ErrorOfConstraintsFn =
+        if mutation_config.fitness_function == "count-error" {
+            count_error_constraints
+        } else if mutation_config.fitness_function == "max-error" {
+            max_error_of_constraints
+        } else {
+            accumulate_error_of_constraints
+        };
+    evaluate_trace_fitness(
+        sexe,
+        base_config,
+        symbolic_trace,
+        side_constraints,
+        runtime_mutable_positions,
+        trace_mutation,
+        inputs_assignment,
+        fitness_scores_inputs,
+        error_of_constraints,
+    )
+}
+
+/// Evaluates the fitness of a mutated symbolic execution trace by the field distance of the
+/// violated side constraints.
+///
+/// This function behaves like `evaluate_trace_fitness_by_error`, except that the error of an
+/// equality `lhs == rhs` is `min(d, prime - d)` with `d = (lhs - rhs) mod prime` (see
+/// `accumulate_distance_of_constraints`). A value slightly below zero in the field is thus as
+/// close to satisfying the constraint as a value slightly above it, which gives the genetic
+/// search a smoother gradient to climb. The `fitness_function` key of `mutation_config` is
+/// ignored.
+pub fn evaluate_trace_fitness_by_distance(
+    sexe: &mut SymbolicExecutor,
+    base_config: &BaseVerificationConfig,
+    _mutation_config: &MutationConfig,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    runtime_mutable_positions: &FxHashMap<usize, Direction>,
+    trace_mutation: &FxHashMap<usize, SymbolicValue>,
+    inputs_assignment: &Vec<FxHashMap<SymbolicName, BigInt>>,
+    fitness_scores_inputs: &mut Vec<BigInt>,
+) -> (usize, BigInt, Option<CounterExample>, usize) {
+    evaluate_trace_fitness(
+        sexe,
+        base_config,
+        symbolic_trace,
+        side_constraints,
+        runtime_mutable_positions,
+        trace_mutation,
+        inputs_assignment,
+        fitness_scores_inputs,
+        accumulate_distance_of_constraints,
+    )
+}
+
+type ErrorOfConstraintsFn = fn(
+    &BigInt,
+    &[SymbolicValueRef],
+    &FxHashMap<SymbolicName, BigInt>,
+    &mut SymbolicLibrary,
+) -> BigInt;
+
+fn evaluate_trace_fitness(
+    sexe: &mut SymbolicExecutor,
+    base_config: &BaseVerificationConfig,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    runtime_mutable_positions: &FxHashMap<usize, Direction>,
+    trace_mutation: &FxHashMap<usize, SymbolicValue>,
+    inputs_assignment: &Vec<FxHashMap<SymbolicName, BigInt>>,
+    fitness_scores_inputs: &mut Vec<BigInt>,
+    error_of_constraints: ErrorOfConstraintsFn,
 ) -> (usize, BigInt, Option<CounterExample>, usize) {
     // Apply the given mutations to the symbolic trace.
     let mutated_symbolic_trace = apply_trace_mutation(symbolic_trace, trace_mutation);
 
+    // Both traces are emulated for every input, so they are compiled once up front.
+    let compiled_trace = CompiledTrace::new(symbolic_trace);
+    let compiled_mutated_trace = CompiledTrace::new(&mutated_symbolic_trace);
+
     let mut max_idx = 0_usize;
     let mut max_score = -base_config.prime.clone();
     let mut counter_example = None;
     let mut num_invalida_assignments = 0; // invalid assignments causing out-of-range subscript
 
     for (i, inp) in inputs_assignment.iter().enumerate() {
+        // The inputs ruled out by the assumptions of the user cannot be counterexamples.
+        if !base_config.satisfies_assumptions(inp, &mut sexe.symbolic_library) {
+            continue;
+        }
+
         // Clone the input assignment for evaluation with the original program.
         let mut assignment_for_original = inp.clone();
 
         // Emulate the original trace to evaluate its behavior on the given input.
         // Even if an assertion fails, the function proceeds, treating it as a modified trace with no assertions.
-        let emulation_result = emulate_symbolic_trace(
+        let emulation_result = compiled_trace.emulate(
             &base_config.prime,
-            &symbolic_trace,
             runtime_mutable_positions,
             &mut assignment_for_original,
             &mut sexe.symbolic_library,
@@ -102,6 +185,8 @@ pub fn evaluate_trace_fitness_by_error(
                 flag: VerificationResult::OverConstrained,
                 target_output: None,
                 assignment: assignment_for_original.clone(),
+                field_reductions: Vec::new(),
+                provenance: None,
             });
             max_idx = i;
             max_score = BigInt::zero();
@@ -116,9 +201,12 @@ pub fn evaluate_trace_fitness_by_error(
                     original_program_failure_pos,
                     symbolic_trace[original_program_failure_pos]
                         .lookup_fmt(&sexe.symbolic_library.id2name),
+                    None,
                 )),
                 target_output: None,
                 assignment: assignment_for_original.clone(),
+                field_reductions: Vec::new(),
+                provenance: None,
             });
             max_idx = i;
             max_score = BigInt::zero();
@@ -129,9 +217,8 @@ pub fn evaluate_trace_fitness_by_error(
         let mut assignment_for_mutation = inp.clone();
 
         // Emulate the mutated trace and evaluate the error in side constraints.
-        let mutated_emulation_result = emulate_symbolic_trace(
+        let mutated_emulation_result = compiled_mutated_trace.emulate(
             &base_config.prime,
-            &mutated_symbolic_trace,
             runtime_mutable_positions,
             &mut assignment_for_mutation,
             &mut sexe.symbolic_library.clone(),
@@ -143,29 +230,12 @@ pub fn evaluate_trace_fitness_by_error(
             mutated_emulation_result.unwrap();
         // Calculate the error in side constraints for the mutated trace.
 
-        let error_of_side_constraints_for_mutated_assignment =
-            if mutation_config.fitness_function == "count-error" {
-                count_error_constraints(
-                    &base_config.prime,
-                    side_constraints,
-                    &assignment_for_mutation,
-                    &mut sexe.symbolic_library,
-                )
-            } else if mutation_config.fitness_function == "max-error" {
-                max_error_of_constraints(
-                    &base_config.prime,
-                    side_constraints,
-                    &assignment_for_mutation,
-                    &mut sexe.symbolic_library,
-                )
-            } else {
-                accumulate_error_of_constraints(
-                    &base_config.prime,
-                    side_constraints,
-                    &assignment_for_mutation,
-                    &mut sexe.symbolic_library,
-                )
-            };
+        let error_of_side_constraints_for_mutated_assignment = error_of_constraints(
+            &base_config.prime,
+            side_constraints,
+            &assignment_for_mutation,
+            &mut sexe.symbolic_library,
+        );
         let mut score = -error_of_side_constraints_for_mutated_assignment.clone();
 
         // Check for valid solutions that satisfy all side constraints.
@@ -178,10 +248,13 @@ pub fn evaluate_trace_fitness_by_error(
                             original_program_failure_pos,
                             symbolic_trace[original_program_failure_pos]
                                 .lookup_fmt(&sexe.symbolic_library.id2name),
+                            None,
                         ),
                     ),
                     target_output: None,
                     assignment: assignment_for_mutation.clone(),
+                    field_reductions: Vec::new(),
+                    provenance: None,
                 });
                 max_idx = i;
                 max_score = BigInt::zero();
@@ -210,6 +283,8 @@ pub fn evaluate_trace_fitness_by_error(
                                 ),
                                 target_output: Some(k.clone()),
                                 assignment: assignment_for_mutation,
+                                field_reductions: Vec::new(),
+                                provenance: None,
                             });
                             break;
                         }
@@ -14,7 +14,8 @@ use crate::executor::symbolic_value::SymbolicValue;
 use crate::mutator::mutation_config::MutationConfig;
 use crate::mutator::mutation_test::Gene;
 use crate::mutator::mutation_utils::{
-    draw_bigint_with_probabilities, draw_operator_mutation_or_random_constant,
+    draw_bigint_with_probabilities, draw_index_offset, draw_operator_mutation_or_random_constant,
+    draw_signal_swap, gather_array_dimensions, gather_swappable_signals, get_assigned_value,
 };
 use crate::mutator::utils::BaseVerificationConfig;
 
@@ -187,3 +188,69 @@ pub fn initialize_population_with_operator_or_const_replacement_or_deletion(
         })
         .collect()
 }
+
+/// Initializes a population of `Gene` instances by swapping the signals read by randomly
+/// selected assignments (see `draw_signal_swap`).
+///
+/// Selected positions whose right-hand side reads no swappable signal are skipped, so a `Gene`
+/// may be empty.
+pub fn initialize_population_with_signal_swap(
+    pos: &[usize],
+    program_population_size: usize,
+    symbolic_trace: &SymbolicTrace,
+    _base_config: &BaseVerificationConfig,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) -> Vec<Gene> {
+    let candidates = gather_swappable_signals(symbolic_trace);
+    (0..program_population_size)
+        .map(|_| {
+            let num_mutations = if pos.len() > 1 {
+                rng.gen_range(1, min(pos.len(), mutation_config.max_num_mutation_points))
+            } else {
+                1
+            };
+            let selected_pos: Vec<_> = pos.choose_multiple(rng, num_mutations).cloned().collect();
+            selected_pos
+                .iter()
+                .filter_map(|p| {
+                    draw_signal_swap(get_assigned_value(&symbolic_trace[*p]), &candidates, rng)
+                        .map(|swapped| (p.clone(), swapped))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Initializes a population of `Gene` instances by shifting the constant array indices read by
+/// randomly selected assignments by one (see `draw_index_offset`).
+///
+/// Selected positions whose right-hand side reads no array element are skipped, so a `Gene` may
+/// be empty.
+pub fn initialize_population_with_index_offset(
+    pos: &[usize],
+    program_population_size: usize,
+    symbolic_trace: &SymbolicTrace,
+    _base_config: &BaseVerificationConfig,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) -> Vec<Gene> {
+    let dimensions = gather_array_dimensions(symbolic_trace);
+    (0..program_population_size)
+        .map(|_| {
+            let num_mutations = if pos.len() > 1 {
+                rng.gen_range(1, min(pos.len(), mutation_config.max_num_mutation_points))
+            } else {
+                1
+            };
+            let selected_pos: Vec<_> = pos.choose_multiple(rng, num_mutations).cloned().collect();
+            selected_pos
+                .iter()
+                .filter_map(|p| {
+                    draw_index_offset(get_assigned_value(&symbolic_trace[*p]), &dimensions, rng)
+                        .map(|shifted| (p.clone(), shifted))
+                })
+                .collect()
+        })
+        .collect()
+}
@@ -11,7 +11,9 @@ use crate::executor::symbolic_value::SymbolicValue;
 use crate::mutator::mutation_config::MutationConfig;
 use crate::mutator::mutation_test::Gene;
 use crate::mutator::mutation_utils::{
-    draw_bigint_with_probabilities, draw_operator_mutation_or_random_constant,
+    draw_bigint_with_probabilities, draw_index_offset, draw_operator_mutation_or_random_constant,
+    draw_signal_swap, gather_array_dimensions, gather_swappable_signals, get_assigned_value,
+    is_deletable_constraint,
 };
 use crate::mutator::utils::BaseVerificationConfig;
 
@@ -221,3 +223,160 @@ pub fn mutate_trace_with_operator_or_const_replacement_or_deletion(
         }
     }
 }
+
+/// Mutates a trace by swapping a signal read by an assignment with another signal of the same
+/// component and dimensionality.
+///
+/// # Parameters
+/// - `pos`: A slice of indices representing mutable positions in the symbolic trace.
+/// - `symbolic_trace`: The symbolic trace, which also provides the candidate signals.
+/// - `individual`: A mutable reference to a `Gene` representing the mutation of the trace.
+/// - `_base_config`: A reference to the `BaseVerificationConfig` (unused).
+/// - `mutation_config`: A reference to the `MutationConfig`.
+/// - `rng`: A mutable reference to a random number generator.
+///
+/// # Behavior
+/// - If the `individual` is not empty, one of its mutated assignments gets one more signal
+///   swapped (see `draw_signal_swap`). Like the other mutation functions, a mutation point may
+///   then be added or removed.
+/// - Positions whose right-hand side reads no swappable signal are left unchanged, so constants
+///   are never mutated.
+pub fn mutate_trace_with_signal_swap(
+    pos: &[usize],
+    symbolic_trace: &SymbolicTrace,
+    individual: &mut Gene,
+    _base_config: &BaseVerificationConfig,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) {
+    if !individual.is_empty() {
+        let candidates = gather_swappable_signals(symbolic_trace);
+        let mut keys: Vec<usize> = individual.keys().copied().collect();
+        keys.sort();
+        let var = keys.iter().choose(rng).unwrap();
+        if let Some(swapped) = draw_signal_swap(&individual[var], &candidates, rng) {
+            individual.insert(var.clone(), swapped);
+        }
+        if individual.len() < mutation_config.max_num_mutation_points && rng.gen::<bool>() {
+            let var = pos.into_iter().choose(rng).unwrap();
+            if let Some(swapped) =
+                draw_signal_swap(get_assigned_value(&symbolic_trace[*var]), &candidates, rng)
+            {
+                individual.insert(var.clone(), swapped);
+            }
+        } else if individual.len() > 1 && rng.gen::<bool>() {
+            let mut keys: Vec<usize> = individual.keys().copied().collect();
+            keys.sort();
+            let var = keys.iter().choose(rng).unwrap();
+            individual.remove(&var);
+        }
+    }
+}
+
+/// Mutates a trace by shifting a constant array index read by an assignment by one, which models
+/// an off-by-one indexing error.
+///
+/// # Parameters
+/// - `pos`: A slice of indices representing mutable positions in the symbolic trace.
+/// - `symbolic_trace`: The symbolic trace, which also provides the dimensions of the arrays.
+/// - `individual`: A mutable reference to a `Gene` representing the mutation of the trace.
+/// - `_base_config`: A reference to the `BaseVerificationConfig` (unused).
+/// - `mutation_config`: A reference to the `MutationConfig`.
+/// - `rng`: A mutable reference to a random number generator.
+///
+/// # Behavior
+/// - If the `individual` is not empty, one of its mutated assignments gets one more index
+///   shifted (see `draw_index_offset`). Like the other mutation functions, a mutation point may
+///   then be added or removed.
+/// - Shifted indices never leave the bounds of their dimension (see `gather_array_dimensions`),
+///   and positions whose right-hand side reads no array element are left unchanged.
+pub fn mutate_trace_with_index_offset(
+    pos: &[usize],
+    symbolic_trace: &SymbolicTrace,
+    individual: &mut Gene,
+    _base_config: &BaseVerificationConfig,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) {
+    if !individual.is_empty() {
+        let dimensions = gather_array_dimensions(symbolic_trace);
+        let mut keys: Vec<usize> = individual.keys().copied().collect();
+        keys.sort();
+        let var = keys.iter().choose(rng).unwrap();
+        if let Some(shifted) = draw_index_offset(&individual[var], &dimensions, rng) {
+            individual.insert(var.clone(), shifted);
+        }
+        if individual.len() < mutation_config.max_num_mutation_points && rng.gen::<bool>() {
+            let var = pos.into_iter().choose(rng).unwrap();
+            if let Some(shifted) =
+                draw_index_offset(get_assigned_value(&symbolic_trace[*var]), &dimensions, rng)
+            {
+                individual.insert(var.clone(), shifted);
+            }
+        } else if individual.len() > 1 && rng.gen::<bool>() {
+            let mut keys: Vec<usize> = individual.keys().copied().collect();
+            keys.sort();
+            let var = keys.iter().choose(rng).unwrap();
+            individual.remove(&var);
+        }
+    }
+}
+
+/// Mutates a trace by deleting a constraint, which models a forgotten `===`, the most common
+/// cause of an under-constrained circuit.
+///
+/// # Parameters
+/// - `pos`: A slice of indices representing mutable positions in the symbolic trace.
+/// - `symbolic_trace`: The symbolic trace, which also provides the deletable constraints.
+/// - `individual`: A mutable reference to a `Gene` representing the mutation of the trace.
+/// - `base_config`: A reference to the `BaseVerificationConfig`.
+/// - `mutation_config`: A reference to the `MutationConfig`.
+/// - `rng`: A mutable reference to a random number generator.
+///
+/// # Behavior
+/// - With probability `statement_deletion_prob`, one constraint of the trace that is not deleted
+///   yet (see `is_deletable_constraint`) is deleted by mapping it to `SymbolicValue::NOP`. A
+///   deleted `<==` keeps its assignment (see `apply_trace_mutation`), so that the witness is
+///   still computed.
+/// - Otherwise, the assignments of the `individual` are mutated as in
+///   `mutate_trace_with_operator_or_const_replacement`, and the deleted constraints stay deleted.
+pub fn mutate_trace_with_constraint_deletion(
+    pos: &[usize],
+    symbolic_trace: &SymbolicTrace,
+    individual: &mut Gene,
+    base_config: &BaseVerificationConfig,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) {
+    if rng.gen::<f64>() < mutation_config.statement_deletion_prob {
+        let candidates = symbolic_trace
+            .iter()
+            .enumerate()
+            .filter(|(i, value)| is_deletable_constraint(value) && !individual.contains_key(i))
+            .map(|(i, _)| i);
+        if let Some(i) = candidates.choose(rng) {
+            individual.insert(i, SymbolicValue::NOP);
+            return;
+        }
+    }
+
+    let deleted: Vec<usize> = individual
+        .keys()
+        .filter(|i| is_deletable_constraint(&symbolic_trace[**i]))
+        .copied()
+        .collect();
+    for i in &deleted {
+        individual.remove(i);
+    }
+    mutate_trace_with_operator_or_const_replacement(
+        pos,
+        symbolic_trace,
+        individual,
+        base_config,
+        mutation_config,
+        rng,
+    );
+    for i in deleted {
+        individual.insert(i, SymbolicValue::NOP);
+    }
+}
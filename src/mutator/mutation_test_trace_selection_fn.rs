@@ -2,6 +2,7 @@ use num_bigint_dig::BigInt;
 use num_bigint_dig::RandBigInt;
 use num_traits::{One, Signed, Zero};
 use rand::rngs::StdRng;
+use rand::seq::index::sample;
 
 /// Selects an individual from the population using roulette-wheel selection.
 ///
@@ -82,3 +83,56 @@ pub fn roulette_selection<'a, T: Clone>(
     }
     &population[0]
 }
+
+/// Selects an individual from the population using tournament selection.
+///
+/// This function draws `tournament_size` distinct individuals uniformly at random and returns the
+/// one with the highest fitness score. Unlike `roulette_selection`, the selection pressure only
+/// depends on the ranking of the fitness scores, so it does not vanish when the scores of a
+/// near-converged population are close to each other.
+///
+/// # Parameters
+/// - `population`: A slice of individuals in the population.
+/// - `fitness_scores`: A slice of fitness scores corresponding to the individuals in the population.
+/// - `rng`: A mutable reference to a random number generator used to draw the competitors.
+/// - `tournament_size`: The number of competitors. It is clamped to `[1, population.len()]`, and
+///   a value equal to the population size always selects the fittest individual.
+///
+/// # Returns
+/// A reference to the selected individual in the population. Ties are broken in favor of the
+/// individual appearing first in the population.
+///
+/// # Example
+/// ```rust
+/// use rand::{SeedableRng, rngs::StdRng};
+/// use num_bigint_dig::BigInt;
+///
+/// use zkfuzz::mutator::mutation_test_trace_selection_fn::tournament_selection;
+///
+/// let population = vec!["A", "B", "C"];
+/// let fitness_scores = vec![BigInt::from(10), BigInt::from(20), BigInt::from(30)];
+/// let mut rng = StdRng::seed_from_u64(42);
+///
+/// let selected = tournament_selection(&population, &fitness_scores, &mut rng, 2);
+/// println!("Selected individual: {}", selected);
+/// ```
+///
+/// # Panics
+/// - Panics if the population is empty.
+pub fn tournament_selection<'a, T: Clone>(
+    population: &'a [T],
+    fitness_scores: &[BigInt],
+    rng: &mut StdRng,
+    tournament_size: usize,
+) -> &'a T {
+    let amount = tournament_size.max(1).min(population.len());
+    let mut competitors = sample(rng, population.len(), amount).into_vec();
+    competitors.sort();
+    let mut best = competitors[0];
+    for i in competitors.into_iter().skip(1) {
+        if fitness_scores[i] > fitness_scores[best] {
+            best = i;
+        }
+    }
+    &population[best]
+}
@@ -7,9 +7,9 @@ use crate::executor::symbolic_execution::SymbolicExecutor;
 use crate::executor::symbolic_value::{OwnerName, SymbolicName};
 
 use crate::mutator::mutation_config::MutationConfig;
-use crate::mutator::mutation_test_crossover_fn::random_crossover;
+use crate::mutator::mutation_test_crossover_fn::{input_uniform_crossover, random_crossover};
 use crate::mutator::mutation_test_trace_selection_fn::roulette_selection;
-use crate::mutator::mutation_utils::draw_bigint_with_probabilities;
+use crate::mutator::mutation_utils::{draw_bigint_with_probabilities, draw_bigint_within_bits};
 use crate::mutator::utils::BaseVerificationConfig;
 
 /// Updates the input population with randomly generated samples.
@@ -19,7 +19,7 @@ use crate::mutator::utils::BaseVerificationConfig;
 /// in the mutation configuration.
 ///
 /// # Parameters
-/// - `_sexe`: A mutable reference to the symbolic executor. Not used in this implementation.
+/// - `sexe`: A mutable reference to the symbolic executor, used to look up the names of the inputs.
 /// - `input_variables`: A slice of symbolic names representing the input variables.
 /// - `inputs_population`: A mutable vector of hash maps representing the current input population.
 ///   This will be cleared and replaced with the new randomly generated population.
@@ -31,9 +31,13 @@ use crate::mutator::utils::BaseVerificationConfig;
 /// # Behavior
 /// The function creates a new population of inputs, with each input consisting of values
 /// randomly sampled according to the mutation configuration. The existing input population is replaced
-/// with the new one.
+/// with the new one. When `input_mutation_weights` is set, an input with a weight `w < 1` of an
+/// existing individual is redrawn only with probability `w` and otherwise keeps its value.
+/// An input with a hint of `b` bits in `input_bit_hints` is drawn within `[0, 2^b)`.
+/// When `input_uniform_crossover` is set, each new individual is recombined with a random
+/// individual of the existing population with probability `input_generation_crossover_rate`.
 pub fn update_input_population_with_random_sampling(
-    _sexe: &mut SymbolicExecutor,
+    sexe: &mut SymbolicExecutor,
     input_variables: &[SymbolicName],
     inputs_population: &mut Vec<FxHashMap<SymbolicName, BigInt>>,
     _inputs_population_score: &Vec<BigInt>,
@@ -42,18 +46,36 @@ pub fn update_input_population_with_random_sampling(
     rng: &mut StdRng,
 ) {
     let mut new_inputs_population: Vec<_> = (0..mutation_config.input_population_size)
-        .map(|_| {
+        .map(|i| {
             input_variables
                 .iter()
                 .map(|var| {
-                    (
-                        var.clone(),
-                        draw_bigint_with_probabilities(&mutation_config, rng).unwrap(),
-                    )
+                    let previous = if mutation_config.input_mutation_weights.is_empty() {
+                        None
+                    } else {
+                        inputs_population.get(i).and_then(|inp| inp.get(var))
+                    };
+                    let value = match previous {
+                        Some(prev)
+                            if !should_mutate_input(sexe, var, 1.0, mutation_config, rng) =>
+                        {
+                            prev.clone()
+                        }
+                        _ => draw_input_value(sexe, var, mutation_config, rng),
+                    };
+                    (var.clone(), value)
                 })
                 .collect::<FxHashMap<SymbolicName, BigInt>>()
         })
         .collect();
+    if mutation_config.input_uniform_crossover && !inputs_population.is_empty() {
+        for child in new_inputs_population.iter_mut() {
+            if rng.gen::<f64>() < mutation_config.input_generation_crossover_rate {
+                let other = &inputs_population[rng.gen_range(0, inputs_population.len())];
+                *child = input_uniform_crossover(child, other, rng);
+            }
+        }
+    }
     inputs_population.clear();
     inputs_population.append(&mut new_inputs_population);
 }
@@ -83,7 +105,7 @@ pub fn update_input_population_with_fitness_score(
             let parent1 = roulette_selection(inputs_population, inputs_population_score, rng);
             let parent2 = roulette_selection(inputs_population, inputs_population_score, rng);
             let mut child = if rng.gen::<f64>() < mutation_config.crossover_rate {
-                random_crossover(&parent1, &parent2, rng)
+                crossover_inputs(&parent1, &parent2, mutation_config, rng)
             } else {
                 parent1.clone()
             };
@@ -91,11 +113,12 @@ pub fn update_input_population_with_fitness_score(
             keys.sort();
             for k in keys.iter() {
                 //let val = child.get(k).unwrap();
-                if rng.gen::<f64>() < mutation_config.mutation_rate {
+                if should_mutate_input(sexe, k, mutation_config.mutation_rate, mutation_config, rng)
+                {
                     //*val = draw_bigint_with_probabilities(&mutation_config, rng).unwrap();
                     child.insert(
                         k.clone().clone(),
-                        draw_bigint_with_probabilities(&mutation_config, rng).unwrap(),
+                        draw_input_value(sexe, k, mutation_config, rng),
                     );
                 }
             }
@@ -137,8 +160,15 @@ pub fn evaluate_coverage(
     sexe.feed_arguments(
         &base_config.template_param_names,
         &base_config.template_param_values,
-    );
-    sexe.concrete_execute(&base_config.target_template_name, inputs);
+    )
+    .expect("Template arguments are validated when the main component is instantiated");
+    if base_config.input_constraints.is_empty() {
+        sexe.concrete_execute(&base_config.target_template_name, inputs);
+    } else {
+        let mut pinned_inputs = inputs.clone();
+        base_config.pin_input_constraints(&mut pinned_inputs);
+        sexe.concrete_execute(&base_config.target_template_name, &pinned_inputs);
+    }
     sexe.record_path();
     sexe.turn_off_coverage_tracking();
     sexe.coverage_count()
@@ -162,9 +192,11 @@ pub fn evaluate_coverage(
 ///
 /// # Behavior
 /// 1. Initializes the population with random inputs.
-/// 2. Evaluates each input for coverage and retains those that increase coverage.
+/// 2. Evaluates each input for coverage and retains those that increase coverage. Inputs hitting
+///    previously uncovered branch outcomes are always retained, while inputs that only take a new
+///    path through known branch outcomes are retained only while the population has room.
 /// 3. Iteratively performs mutations and crossovers on the population to explore new inputs,
-///    retaining inputs that further increase coverage.
+///    retaining inputs that further increase coverage in the same way.
 /// 4. The process stops when the population reaches the maximum size or the specified number
 ///    of iterations is completed.
 pub fn update_input_population_with_coverage_maximization(
@@ -191,16 +223,22 @@ pub fn update_input_population_with_coverage_maximization(
         rng,
     );
 
+    let mut path_only_inputs = Vec::new();
     for input in &initial_input_population {
-        let new_coverage = evaluate_coverage(sexe, &input, base_config);
-        if new_coverage > total_coverage {
-            inputs_population.push(input.clone());
-            total_coverage = new_coverage;
-        }
+        retain_if_covering(
+            sexe,
+            input,
+            base_config,
+            &mut total_coverage,
+            inputs_population,
+            &mut path_only_inputs,
+        );
     }
+    fill_with_path_only_inputs(inputs_population, path_only_inputs, mutation_config);
 
     for _ in 0..mutation_config.input_generation_max_iteration {
         let mut new_inputs_population = Vec::new();
+        let mut path_only_inputs = Vec::new();
 
         // Iterate through the population and attempt mutations
         for input in inputs_population.iter() {
@@ -209,20 +247,21 @@ pub fn update_input_population_with_coverage_maximization(
             if rng.gen::<f64>() < mutation_config.input_generation_crossover_rate {
                 // Crossover
                 let other = inputs_population[rng.gen_range(0, inputs_population.len())].clone();
-                new_input = random_crossover(input, &other, rng);
+                new_input = crossover_inputs(input, &other, mutation_config, rng);
             }
             if rng.gen::<f64>() < mutation_config.input_generation_mutation_rate {
                 if rng.gen::<f64>() < mutation_config.input_generation_singlepoint_mutation_rate {
                     // Mutate only one input variable
                     let var = &input_variables[rng.gen_range(0, input_variables.len())];
-                    let mutation = draw_bigint_with_probabilities(&mutation_config, rng).unwrap();
-                    new_input.insert(var.clone(), mutation);
+                    if should_mutate_input(sexe, var, 1.0, mutation_config, rng) {
+                        let mutation = draw_input_value(sexe, var, mutation_config, rng);
+                        new_input.insert(var.clone(), mutation);
+                    }
                 } else {
                     // Mutate each input variable with a small probability
                     for var in input_variables {
-                        if rng.gen::<bool>() {
-                            let mutation =
-                                draw_bigint_with_probabilities(&mutation_config, rng).unwrap();
+                        if should_mutate_input(sexe, var, 0.5, mutation_config, rng) {
+                            let mutation = draw_input_value(sexe, var, mutation_config, rng);
                             new_input.insert(var.clone(), mutation);
                         }
                     }
@@ -230,16 +269,115 @@ pub fn update_input_population_with_coverage_maximization(
             }
 
             // Evaluate the new input
-            let new_coverage = evaluate_coverage(sexe, &new_input, base_config);
-            if new_coverage > total_coverage {
-                new_inputs_population.push(new_input);
-                total_coverage = new_coverage;
-            }
+            retain_if_covering(
+                sexe,
+                &new_input,
+                base_config,
+                &mut total_coverage,
+                &mut new_inputs_population,
+                &mut path_only_inputs,
+            );
         }
         inputs_population.append(&mut new_inputs_population);
+        fill_with_path_only_inputs(inputs_population, path_only_inputs, mutation_config);
 
         if inputs_population.len() > mutation_config.input_population_size {
             break;
         }
     }
 }
+
+/// Evaluates the coverage of `input` and sorts it by what it newly covers.
+///
+/// An input hitting a branch outcome that no earlier input hit is pushed to `new_branch_inputs`.
+/// An input that only takes a new combination of known branch outcomes is pushed to
+/// `path_only_inputs`, and any other input is dropped.
+fn retain_if_covering(
+    sexe: &mut SymbolicExecutor,
+    input: &FxHashMap<SymbolicName, BigInt>,
+    base_config: &BaseVerificationConfig,
+    total_coverage: &mut usize,
+    new_branch_inputs: &mut Vec<FxHashMap<SymbolicName, BigInt>>,
+    path_only_inputs: &mut Vec<FxHashMap<SymbolicName, BigInt>>,
+) {
+    let baseline = sexe.coverage_tracker().branch_hits().clone();
+    let new_coverage = evaluate_coverage(sexe, input, base_config);
+    if sexe.coverage_tracker().new_coverage_since(&baseline) > 0 {
+        new_branch_inputs.push(input.clone());
+    } else if new_coverage > *total_coverage {
+        path_only_inputs.push(input.clone());
+    }
+    *total_coverage = new_coverage;
+}
+
+/// Appends the inputs that only cover new paths, as long as the population has room for them.
+fn fill_with_path_only_inputs(
+    inputs_population: &mut Vec<FxHashMap<SymbolicName, BigInt>>,
+    mut path_only_inputs: Vec<FxHashMap<SymbolicName, BigInt>>,
+    mutation_config: &MutationConfig,
+) {
+    let room = mutation_config
+        .input_population_size
+        .saturating_sub(inputs_population.len());
+    path_only_inputs.truncate(room);
+    inputs_population.append(&mut path_only_inputs);
+}
+
+/// Recombines two input assignments with `input_uniform_crossover` when it is enabled by the
+/// configuration, and with `random_crossover` otherwise.
+fn crossover_inputs(
+    parent1: &FxHashMap<SymbolicName, BigInt>,
+    parent2: &FxHashMap<SymbolicName, BigInt>,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) -> FxHashMap<SymbolicName, BigInt> {
+    if mutation_config.input_uniform_crossover {
+        input_uniform_crossover(parent1, parent2, rng)
+    } else {
+        random_crossover(parent1, parent2, rng)
+    }
+}
+
+/// Draws a new value of an input, within the bit range of its hint in `input_bit_hints` if any.
+fn draw_input_value(
+    sexe: &SymbolicExecutor,
+    var: &SymbolicName,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) -> BigInt {
+    let bits = if mutation_config.input_bit_hints.is_empty() {
+        None
+    } else {
+        mutation_config.input_bit_hint(&sexe.symbolic_library.id2name[&var.id])
+    };
+    match bits {
+        Some(bits) => draw_bigint_within_bits(mutation_config, bits, rng).unwrap(),
+        None => draw_bigint_with_probabilities(mutation_config, rng).unwrap(),
+    }
+}
+
+/// Decides whether an input is mutated, scaling `rate` by the input's mutation weight.
+///
+/// An input with weight zero is never mutated, and the decision consumes no randomness when the
+/// weight is zero or when `rate * weight` is at least one.
+fn should_mutate_input(
+    sexe: &SymbolicExecutor,
+    var: &SymbolicName,
+    rate: f64,
+    mutation_config: &MutationConfig,
+    rng: &mut StdRng,
+) -> bool {
+    let weight = if mutation_config.input_mutation_weights.is_empty() {
+        1.0
+    } else {
+        mutation_config.input_mutation_weight(&sexe.symbolic_library.id2name[&var.id])
+    };
+    let probability = rate * weight;
+    if probability <= 0.0 {
+        false
+    } else if probability >= 1.0 {
+        true
+    } else {
+        rng.gen::<f64>() < probability
+    }
+}
@@ -2,16 +2,19 @@ use std::rc::Rc;
 
 use num_bigint_dig::BigInt;
 use num_bigint_dig::RandBigInt;
-use num_traits::Zero;
+use num_traits::{Signed, ToPrimitive, Zero};
 use program_structure::ast::ExpressionInfixOpcode;
 use rand::rngs::StdRng;
 use rand::seq::IteratorRandom;
 use rand::Rng;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::executor::debug_ast::DebuggableExpressionInfixOpcode;
 use crate::executor::symbolic_state::SymbolicTrace;
-use crate::executor::symbolic_value::SymbolicValue;
+use crate::executor::symbolic_value::{
+    extract_variables, extract_variables_from_symbolic_value, SymbolicAccess, SymbolicName,
+    SymbolicValue, SymbolicValueRef,
+};
 use crate::mutator::mutation_config::MutationConfig;
 
 /// Draws a random BigInt from specified ranges based on given probabilities.
@@ -57,6 +60,33 @@ pub fn draw_bigint_with_probabilities(
     }
 }
 
+/// Draws a random BigInt that fits in `bits` bits, i.e., within `[0, 2^bits)`.
+///
+/// The value is first drawn with `draw_bigint_with_probabilities`, so that the small values
+/// favored by the configured ranges stay likely, and is redrawn uniformly within the bounds
+/// when it falls outside of them.
+///
+/// # Arguments
+/// - `mutation_config`: The configuration holding the ranges and their probabilities.
+/// - `bits`: The number of bits of the sampled signal.
+/// - `rng`: A mutable reference to the random number generator.
+///
+/// # Returns
+/// A random BigInt within `[0, 2^bits)`, or `None` if the configured ranges are invalid.
+pub fn draw_bigint_within_bits(
+    mutation_config: &MutationConfig,
+    bits: usize,
+    rng: &mut StdRng,
+) -> Option<BigInt> {
+    let upper = BigInt::from(1) << bits;
+    let value = draw_bigint_with_probabilities(mutation_config, rng)?;
+    if !value.is_negative() && value < upper {
+        Some(value)
+    } else {
+        Some(rng.gen_bigint_range(&BigInt::zero(), &upper))
+    }
+}
+
 /// Applies trace mutations to a symbolic trace by replacing specific symbolic values.
 ///
 /// # Parameters
@@ -74,12 +104,15 @@ pub fn draw_bigint_with_probabilities(
 ///      using the provided value while preserving the left-hand side and safety flag.
 ///    - If the value is a `SymbolicValue::AssignCall`, it is replaced with a new assignment,
 ///      flipping the mutability flag while preserving the left-hand side.
-/// 3. Panics if an entry at the specified index is neither `SymbolicValue::Assign` nor `SymbolicValue::AssignCall`,
-///    as these are the only supported mutation targets.
+///    - If the value is a constraint (see `is_deletable_constraint`) and the provided value is
+///      `SymbolicValue::NOP`, the constraint is deleted. A `===` check is replaced with `NOP`,
+///      while a `<==` becomes a plain assignment, so that the witness is still computed.
+/// 3. Panics if an entry at the specified index is none of the above, as these are the only
+///    supported mutation targets.
 ///
 /// # Panics
 /// - The function panics if a mutation is attempted on a value that is not of type `SymbolicValue::Assign`
-///   or `SymbolicValue::AssignCall`.
+///   or `SymbolicValue::AssignCall`, unless it deletes a constraint.
 ///
 /// # Notes
 /// - The original `symbolic_trace` is not modified; all changes are applied to a cloned version.
@@ -112,6 +145,15 @@ pub fn apply_trace_mutation(
                 !is_mutable,
                 None,
             ));
+        } else if *value == SymbolicValue::NOP
+            && is_deletable_constraint(&mutated_constraints[*index])
+        {
+            mutated_constraints[*index] = match mutated_constraints[*index].as_ref() {
+                SymbolicValue::AssignEq(lv, rv) => {
+                    Rc::new(SymbolicValue::Assign(lv.clone(), rv.clone(), true, None))
+                }
+                _ => Rc::new(SymbolicValue::NOP),
+            };
         } else {
             panic!("We can only mutate SymbolicValue::Assign");
         }
@@ -119,6 +161,70 @@ pub fn apply_trace_mutation(
     mutated_constraints
 }
 
+/// Returns whether `value` is a trace entry that may produce a side constraint, i.e., a `<==`
+/// assignment or an equality check such as `===`, which `mutate_trace_with_constraint_deletion`
+/// may delete.
+///
+/// The trace does not tell a `===` apart from an equality `assert`, so both are deletable.
+pub fn is_deletable_constraint(value: &SymbolicValue) -> bool {
+    matches!(
+        value,
+        SymbolicValue::AssignEq(_, _)
+            | SymbolicValue::BinaryOp(
+                _,
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+                _
+            )
+    )
+}
+
+/// Renders a trace mutation against the trace it is applied to.
+///
+/// Each mutated site is shown as `[index] original => mutated`, where `original` is the
+/// right-hand side of the assignment at `index` in `symbolic_trace`. Sites are listed in
+/// ascending order, and an empty mutation is rendered as `(no mutation)`.
+///
+/// # Parameters
+/// - `symbolic_trace`: The base symbolic trace.
+/// - `trace_mutation`: A mapping from trace indices to the values replacing their right-hand side.
+/// - `lookup`: A hash map associating variable IDs with their string representations.
+///
+/// # Returns
+/// A single-line, human-readable representation of the mutation.
+pub fn render_trace_mutation(
+    symbolic_trace: &SymbolicTrace,
+    trace_mutation: &FxHashMap<usize, SymbolicValue>,
+    lookup: &FxHashMap<usize, String>,
+) -> String {
+    if trace_mutation.is_empty() {
+        return "(no mutation)".to_string();
+    }
+
+    let mut keys: Vec<_> = trace_mutation.keys().collect();
+    keys.sort();
+    keys.into_iter()
+        .map(|index| {
+            let original = match symbolic_trace[*index].as_ref() {
+                SymbolicValue::Assign(_, rv, _, _) | SymbolicValue::AssignCall(_, rv, _) => {
+                    rv.lookup_fmt(lookup)
+                }
+                other => other.lookup_fmt(lookup),
+            };
+            format!(
+                "[{}] {} => {}",
+                index,
+                compact(&original),
+                compact(&trace_mutation[index].lookup_fmt(lookup))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn compact(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 lazy_static::lazy_static! {
     static ref OPERATOR_MUTATION_CANDIDATES_STRICT: Vec<(ExpressionInfixOpcode,Vec<ExpressionInfixOpcode>)> = {
         vec![
@@ -181,3 +287,182 @@ pub fn draw_operator_mutation_or_random_constant(
         ),
     }
 }
+
+/// Collects the signals referenced by a symbolic trace, sorted so that the draws of
+/// `draw_signal_swap` are reproducible for a fixed seed.
+pub fn gather_swappable_signals(symbolic_trace: &SymbolicTrace) -> Vec<SymbolicName> {
+    let mut signals = extract_variables(symbolic_trace);
+    signals.sort();
+    signals
+}
+
+/// Returns the right-hand side of the assignment at a mutable position of the trace.
+pub fn get_assigned_value(target: &SymbolicValue) -> &SymbolicValue {
+    match target {
+        SymbolicValue::Assign(_, rhs, _, _) | SymbolicValue::AssignCall(_, rhs, _) => rhs,
+        _ => target,
+    }
+}
+
+/// Replaces a randomly chosen variable of `target` with another signal, modeling a circuit that
+/// assigns the wrong signal (e.g., `out <-- a` instead of `out <-- b`).
+///
+/// The replacement is drawn from `candidates` among the signals that belong to the same component
+/// and have the same number of array accesses as the replaced one. Every occurrence of the
+/// replaced variable is rewritten, and constants are left untouched.
+///
+/// # Returns
+/// The rewritten value, or `None` if `target` contains no variable with a valid replacement.
+pub fn draw_signal_swap(
+    target: &SymbolicValue,
+    candidates: &[SymbolicName],
+    rng: &mut StdRng,
+) -> Option<SymbolicValue> {
+    let mut variables = FxHashSet::default();
+    extract_variables_from_symbolic_value(target, &mut variables);
+    let mut variables: Vec<SymbolicName> = variables.into_iter().collect();
+    variables.sort();
+
+    let swaps: Vec<(&SymbolicName, Vec<&SymbolicName>)> = variables
+        .iter()
+        .map(|from| {
+            let replacements = candidates
+                .iter()
+                .filter(|to| {
+                    *to != from && to.owner == from.owner && to.get_dim() == from.get_dim()
+                })
+                .collect::<Vec<_>>();
+            (from, replacements)
+        })
+        .filter(|(_, replacements)| !replacements.is_empty())
+        .collect();
+
+    let (from, replacements) = swaps.iter().choose(rng)?;
+    let to = replacements.iter().choose(rng)?;
+    Some(substitute_variable(target, from, to))
+}
+
+/// Infers the dimensions of the arrays whose elements are referenced by a symbolic trace.
+///
+/// The trace mutation functions have no access to the `id2dimensions` of the executor, so the size
+/// of each dimension is taken as one more than the largest constant index used for it in the
+/// trace. The arrays are keyed by their name without access, since the components of different
+/// templates may declare arrays of different sizes under the same id.
+pub fn gather_array_dimensions(
+    symbolic_trace: &SymbolicTrace,
+) -> FxHashMap<SymbolicName, Vec<usize>> {
+    let mut dimensions: FxHashMap<SymbolicName, Vec<usize>> = FxHashMap::default();
+    for name in extract_variables(symbolic_trace) {
+        let indices = match get_constant_indices(&name) {
+            Some(indices) if !indices.is_empty() => indices,
+            _ => continue,
+        };
+        let dims = dimensions
+            .entry(SymbolicName::new(name.id, name.owner.clone(), None))
+            .or_insert_with(|| vec![0; indices.len()]);
+        if dims.len() == indices.len() {
+            for (dim, index) in dims.iter_mut().zip(indices) {
+                *dim = (*dim).max(index + 1);
+            }
+        }
+    }
+    dimensions
+}
+
+/// Shifts a constant array index of a randomly chosen variable of `target` by one, modeling an
+/// off-by-one indexing error (e.g., `in[i+1]` instead of `in[i]`).
+///
+/// The shifted index is kept within the bounds given by `dimensions` (see
+/// `gather_array_dimensions`), and every occurrence of the chosen variable is rewritten.
+///
+/// # Returns
+/// The rewritten value, or `None` if `target` reads no array element whose index can be shifted.
+pub fn draw_index_offset(
+    target: &SymbolicValue,
+    dimensions: &FxHashMap<SymbolicName, Vec<usize>>,
+    rng: &mut StdRng,
+) -> Option<SymbolicValue> {
+    let mut variables = FxHashSet::default();
+    extract_variables_from_symbolic_value(target, &mut variables);
+    let mut variables: Vec<SymbolicName> = variables.into_iter().collect();
+    variables.sort();
+
+    let offsets: Vec<(&SymbolicName, Vec<SymbolicName>)> = variables
+        .iter()
+        .map(|from| {
+            let array = SymbolicName::new(from.id, from.owner.clone(), None);
+            let mut shifted = Vec::new();
+            if let (Some(indices), Some(dims)) =
+                (get_constant_indices(from), dimensions.get(&array))
+            {
+                if indices.len() == dims.len() {
+                    for (k, (index, dim)) in indices.iter().zip(dims.iter()).enumerate() {
+                        if *index > 0 {
+                            shifted.push(shift_index(from, k, index - 1));
+                        }
+                        if index + 1 < *dim {
+                            shifted.push(shift_index(from, k, index + 1));
+                        }
+                    }
+                }
+            }
+            (from, shifted)
+        })
+        .filter(|(_, shifted)| !shifted.is_empty())
+        .collect();
+
+    let (from, shifted) = offsets.iter().choose(rng)?;
+    let to = shifted.iter().choose(rng)?;
+    Some(substitute_variable(target, from, to))
+}
+
+fn get_constant_indices(name: &SymbolicName) -> Option<Vec<usize>> {
+    name.access
+        .as_ref()?
+        .iter()
+        .map(|access| match access {
+            SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(index)) => index.to_usize(),
+            _ => None,
+        })
+        .collect()
+}
+
+fn shift_index(name: &SymbolicName, k: usize, index: usize) -> SymbolicName {
+    let mut shifted = name.clone();
+    shifted.access.as_mut().unwrap()[k] =
+        SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(BigInt::from(index)));
+    // The clone carries the hash of `name`, which is stale after changing the access
+    shifted.update_hash();
+    shifted
+}
+
+fn substitute_variable(
+    value: &SymbolicValue,
+    from: &SymbolicName,
+    to: &SymbolicName,
+) -> SymbolicValue {
+    let substitute = |v: &SymbolicValueRef| Rc::new(substitute_variable(v, from, to));
+    match value {
+        SymbolicValue::Variable(name) if name == from => SymbolicValue::Variable(to.clone()),
+        SymbolicValue::BinaryOp(lv, op, rv) => {
+            SymbolicValue::BinaryOp(substitute(lv), op.clone(), substitute(rv))
+        }
+        SymbolicValue::AuxBinaryOp(lv, op, rv) => {
+            SymbolicValue::AuxBinaryOp(substitute(lv), op.clone(), substitute(rv))
+        }
+        SymbolicValue::UnaryOp(op, v) => SymbolicValue::UnaryOp(op.clone(), substitute(v)),
+        SymbolicValue::Conditional(cond, then_val, else_val) => {
+            SymbolicValue::Conditional(substitute(cond), substitute(then_val), substitute(else_val))
+        }
+        SymbolicValue::Array(elements) => {
+            SymbolicValue::Array(elements.iter().map(|e| substitute(e)).collect())
+        }
+        SymbolicValue::UniformArray(v, size) => {
+            SymbolicValue::UniformArray(substitute(v), substitute(size))
+        }
+        SymbolicValue::Call(id, args) => {
+            SymbolicValue::Call(*id, args.iter().map(|a| substitute(a)).collect())
+        }
+        _ => value.clone(),
+    }
+}
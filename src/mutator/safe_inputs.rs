@@ -0,0 +1,272 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use num_traits::{One, ToPrimitive, Zero};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde_json::{json, Value};
+
+use crate::executor::symbolic_execution::SymbolicExecutor;
+use crate::executor::symbolic_state::{SymbolicConstraints, SymbolicTrace};
+use crate::executor::symbolic_value::{
+    extract_variables, OwnerName, SymbolicAccess, SymbolicName, SymbolicValue,
+};
+use crate::mutator::compiled_trace::{CompiledConstraints, CompiledTrace};
+use crate::mutator::utils::BaseVerificationConfig;
+
+/// Searches for input assignments of the main template that satisfy all side constraints.
+///
+/// This is the inverse of the counterexample search: instead of looking for an assignment that
+/// breaks the agreement between the symbolic trace and the side constraints, it looks for inputs
+/// whose computed witness passes every assertion of the trace and every side constraint. Such
+/// assignments are valid test vectors for a prover.
+///
+/// # Parameters
+/// - `sexe`: A mutable reference to the symbolic executor holding the symbolic library.
+/// - `symbolic_trace`: The symbolic trace used to propagate the inputs to the whole witness.
+/// - `side_constraints`: The side constraints that every sampled witness must satisfy.
+/// - `base_config`: The verification configuration. `quick_mode` restricts the candidates of each
+///   input to `{0, 1, p - 1}`; otherwise the candidates are `[0, range]` followed by `[p - range, p)`.
+/// - `num_samples`: The maximum number of distinct assignments to return.
+///
+/// # Returns
+/// A vector of at most `num_samples` distinct assignments, each mapping the input variables of the
+/// main template to their values.
+pub fn sample_safe_inputs(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &SymbolicTrace,
+    side_constraints: &SymbolicConstraints,
+    base_config: &BaseVerificationConfig,
+    num_samples: usize,
+) -> Vec<FxHashMap<SymbolicName, BigInt>> {
+    let mut variables = extract_variables(symbolic_trace);
+    variables.append(&mut extract_variables(side_constraints));
+    let variables_set: HashSet<SymbolicName> = variables.iter().cloned().collect();
+    let mut unique_variables: Vec<SymbolicName> = variables_set.into_iter().collect();
+    unique_variables.sort();
+
+    let input_ids = &sexe.symbolic_library.template_library
+        [&sexe.symbolic_library.name2id[&base_config.target_template_name]]
+        .input_ids;
+    let input_variables: Vec<SymbolicName> = unique_variables
+        .into_iter()
+        .filter(|v| v.owner.len() == 1 && input_ids.contains(&v.id))
+        .collect();
+
+    let candidates = if base_config.quick_mode {
+        vec![
+            BigInt::zero(),
+            BigInt::one(),
+            &base_config.prime - BigInt::one(),
+        ]
+    } else {
+        let mut candidates = Vec::new();
+        let mut value = BigInt::zero();
+        while value <= base_config.range && value < base_config.prime {
+            candidates.push(value.clone());
+            value += BigInt::one();
+        }
+        let mut value = &base_config.prime - &base_config.range;
+        while value < base_config.prime {
+            if value > base_config.range {
+                candidates.push(value.clone());
+            }
+            value += BigInt::one();
+        }
+        candidates
+    };
+
+    let symbolic_trace = CompiledTrace::new(symbolic_trace);
+    let side_constraints = CompiledConstraints::new(side_constraints);
+    let context = SampleContext {
+        symbolic_trace: &symbolic_trace,
+        side_constraints: &side_constraints,
+        base_config,
+        input_variables: &input_variables,
+        candidates: &candidates,
+        num_samples,
+    };
+    let mut samples = Vec::new();
+    let mut assignment = FxHashMap::default();
+    search(sexe, &context, 0, &mut assignment, &mut samples);
+    samples
+}
+
+/// The arguments of `search` that stay the same across its recursive calls.
+struct SampleContext<'a> {
+    symbolic_trace: &'a CompiledTrace,
+    side_constraints: &'a CompiledConstraints,
+    base_config: &'a BaseVerificationConfig,
+    /// The inputs of the main template to enumerate, in order.
+    input_variables: &'a [SymbolicName],
+    /// The values tried for each input.
+    candidates: &'a [BigInt],
+    num_samples: usize,
+}
+
+/// Enumerates the values of `context.input_variables[index..]`, appending the assignments that
+/// pass the trace and satisfy the side constraints to `samples`, until there are `num_samples`
+/// of them.
+fn search(
+    sexe: &mut SymbolicExecutor,
+    context: &SampleContext,
+    index: usize,
+    assignment: &mut FxHashMap<SymbolicName, BigInt>,
+    samples: &mut Vec<FxHashMap<SymbolicName, BigInt>>,
+) {
+    if samples.len() >= context.num_samples {
+        return;
+    }
+
+    if index == context.input_variables.len() {
+        let prime = &context.base_config.prime;
+        let mut full_assignment = assignment.clone();
+        let emulation_result = context.symbolic_trace.emulate(
+            prime,
+            &FxHashMap::default(),
+            &mut full_assignment,
+            &mut sexe.symbolic_library,
+        );
+        if let Some((true, _)) = emulation_result {
+            if context.side_constraints.evaluate(
+                prime,
+                &full_assignment,
+                &mut sexe.symbolic_library,
+            ) {
+                samples.push(assignment.clone());
+            }
+        }
+        return;
+    }
+
+    let var = &context.input_variables[index];
+    for c in context.candidates {
+        assignment.insert(var.clone(), c.clone());
+        search(sexe, context, index + 1, assignment, samples);
+        if samples.len() >= context.num_samples {
+            break;
+        }
+    }
+    assignment.remove(var);
+}
+
+/// Converts an assignment of main-template signals into the input JSON format used by circom.
+///
+/// Each signal is keyed by its bare name. Array elements are gathered into (nested) JSON arrays
+/// according to their constant subscripts, and every value is rendered as a decimal string.
+///
+/// # Parameters
+/// - `assignment`: A mapping from symbolic names to their concrete values.
+/// - `lookup`: A hash map associating variable IDs with their string representations.
+///
+/// # Returns
+/// A JSON object that can be saved as `input.json` and fed to the witness generator.
+pub fn to_circom_input_json(
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    lookup: &FxHashMap<usize, String>,
+) -> Value {
+    let mut names: Vec<&SymbolicName> = assignment.keys().collect();
+    names.sort_by_key(|n| n.lookup_fmt(lookup));
+
+    let mut result = json!({});
+    for name in names {
+        let indices: Vec<usize> = name
+            .access
+            .iter()
+            .flatten()
+            .filter_map(|a| match a {
+                SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(i)) => i.to_usize(),
+                _ => None,
+            })
+            .collect();
+
+        let mut slot = &mut result[lookup[&name.id].as_str()];
+        for i in indices {
+            if !slot.is_array() {
+                *slot = json!([]);
+            }
+            let arr = slot.as_array_mut().unwrap();
+            while arr.len() <= i {
+                arr.push(Value::Null);
+            }
+            slot = &mut arr[i];
+        }
+        *slot = json!(assignment[name].to_string());
+    }
+    result
+}
+
+/// Parses an assignment of main-template inputs from the input JSON format used by circom.
+///
+/// This is the inverse of `to_circom_input_json`: each key is the bare name of an input signal,
+/// arrays are (nested) JSON arrays, and values are decimal strings or JSON numbers.
+///
+/// # Parameters
+/// - `json`: The parsed JSON object.
+/// - `name2id`: A hash map associating variable names with their IDs.
+/// - `main_owner`: The owner of the signals of the main template.
+/// - `input_ids`: The IDs of the input signals of the main template.
+///
+/// # Returns
+/// The assignment, or an error message naming the offending entry.
+pub fn from_circom_input_json(
+    json: &Value,
+    name2id: &FxHashMap<String, usize>,
+    main_owner: &Rc<Vec<OwnerName>>,
+    input_ids: &FxHashSet<usize>,
+) -> Result<FxHashMap<SymbolicName, BigInt>, String> {
+    let entries = json
+        .as_object()
+        .ok_or_else(|| "the input JSON must be an object".to_string())?;
+
+    let mut assignment = FxHashMap::default();
+    for (name, value) in entries {
+        let id = match name2id.get(name) {
+            Some(id) if input_ids.contains(id) => *id,
+            _ => return Err(format!("`{}` is not an input of the main template", name)),
+        };
+        let mut elements = Vec::new();
+        collect_input_values(name, value, &mut Vec::new(), &mut elements)?;
+        for (indices, v) in elements {
+            let access = if indices.is_empty() {
+                None
+            } else {
+                Some(
+                    indices
+                        .into_iter()
+                        .map(|i| SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(i.into())))
+                        .collect(),
+                )
+            };
+            assignment.insert(SymbolicName::new(id, main_owner.clone(), access), v);
+        }
+    }
+    Ok(assignment)
+}
+
+/// Flattens a (nested) JSON value into pairs of array subscripts and integer values.
+fn collect_input_values(
+    name: &str,
+    value: &Value,
+    indices: &mut Vec<usize>,
+    elements: &mut Vec<(Vec<usize>, BigInt)>,
+) -> Result<(), String> {
+    let literal = match value {
+        Value::Array(values) => {
+            for (i, v) in values.iter().enumerate() {
+                indices.push(i);
+                collect_input_values(name, v, indices, elements)?;
+                indices.pop();
+            }
+            return Ok(());
+        }
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return Err(format!("`{}` has an unsupported value `{}`", name, value)),
+    };
+    let v = BigInt::from_str(&literal)
+        .map_err(|_| format!("`{}` has a non-integer value `{}`", name, literal))?;
+    elements.push((indices.clone(), v));
+    Ok(())
+}
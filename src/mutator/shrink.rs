@@ -0,0 +1,148 @@
+use num_bigint_dig::BigInt;
+use num_traits::Zero;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::executor::symbolic_execution::SymbolicExecutor;
+use crate::executor::symbolic_value::{extract_variables, SymbolicName, SymbolicValueRef};
+use crate::mutator::compiled_trace::{CompiledConstraints, CompiledTrace};
+use crate::mutator::utils::{
+    is_equal_mod, is_vulnerable, verify_compiled_assignment, BaseVerificationConfig,
+    CounterExample, UnderConstrainedType, VerificationResult,
+};
+
+/// Minimizes the input assignment of a counterexample.
+///
+/// Each input signal of the main template is set to zero one at a time, and the change is kept
+/// only if the resulting assignment still triggers a bug when verified with `verify_assignment`.
+/// Signals on which the counterexample agrees with the honest execution of the original trace
+/// are recomputed from the shrunk inputs, while the deviating ones (the witnesses that actually
+/// exhibit the bug) keep their values.
+///
+/// # Parameters
+/// - `sexe`: A mutable reference to the concrete executor used during the search.
+/// - `symbolic_trace`: The symbolic trace of the main template.
+/// - `side_constraints`: The side constraints of the main template.
+/// - `counter_example`: The counterexample to shrink.
+/// - `base_config`: The verification configuration of the main template.
+///
+/// # Returns
+/// A `CounterExample` whose assignment sets as many inputs as possible to zero. Inputs fixed by
+/// `input_constraints` are left untouched. If the honest execution cannot be emulated, the
+/// counterexample, or one that does not assign every input of the trace, is returned as is.
+pub fn shrink_counterexample(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &[SymbolicValueRef],
+    side_constraints: &[SymbolicValueRef],
+    counter_example: &CounterExample,
+    base_config: &BaseVerificationConfig,
+) -> CounterExample {
+    let input_ids = sexe.symbolic_library.template_library
+        [&sexe.symbolic_library.name2id[&base_config.target_template_name]]
+        .input_ids
+        .clone();
+    let mut input_names: Vec<SymbolicName> = extract_variables(symbolic_trace)
+        .into_iter()
+        .filter(|name| name.owner.len() == 1 && input_ids.contains(&name.id))
+        .collect();
+    input_names.sort();
+
+    let mut shrunk = counter_example.clone();
+    if input_names
+        .iter()
+        .any(|name| !shrunk.assignment.contains_key(name))
+    {
+        return shrunk;
+    }
+    // Every candidate is emulated and verified against the same constraints.
+    let compiled_trace = CompiledTrace::new(symbolic_trace);
+    let compiled_symbolic_trace = CompiledConstraints::new(symbolic_trace);
+    let compiled_side_constraints = CompiledConstraints::new(side_constraints);
+    let honest = match emulate_honest_execution(
+        sexe,
+        &compiled_trace,
+        &shrunk.assignment,
+        &input_names,
+        base_config,
+    ) {
+        Some(honest) => honest,
+        None => return shrunk,
+    };
+    let deviations: FxHashSet<SymbolicName> = shrunk
+        .assignment
+        .iter()
+        .filter(|(name, value)| match honest.get(*name) {
+            Some(honest_value) => !is_equal_mod(value, honest_value, &base_config.prime),
+            None => false,
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    for name in &input_names {
+        if base_config.input_constraints.contains_key(name) || shrunk.assignment[name].is_zero() {
+            continue;
+        }
+
+        let mut candidate = shrunk.assignment.clone();
+        candidate.insert(name.clone(), BigInt::zero());
+        let recomputed = match emulate_honest_execution(
+            sexe,
+            &compiled_trace,
+            &candidate,
+            &input_names,
+            base_config,
+        ) {
+            Some(recomputed) => recomputed,
+            None => continue,
+        };
+        for (k, v) in candidate.iter_mut() {
+            if !input_names.contains(k) && !deviations.contains(k) {
+                if let Some(recomputed_value) = recomputed.get(k) {
+                    *v = recomputed_value.clone();
+                }
+            }
+        }
+
+        let flag = verify_compiled_assignment(
+            sexe,
+            &compiled_symbolic_trace,
+            &compiled_side_constraints,
+            &candidate,
+            base_config,
+        );
+        if is_vulnerable(&flag) {
+            if let VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                target,
+                _,
+                _,
+            )) = &flag
+            {
+                shrunk.target_output = Some(target.clone());
+            }
+            shrunk.flag = flag;
+            shrunk.assignment = candidate;
+        }
+    }
+
+    shrunk
+}
+
+/// Emulates the original trace from the inputs of `assignment` alone.
+fn emulate_honest_execution(
+    sexe: &mut SymbolicExecutor,
+    compiled_trace: &CompiledTrace,
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    input_names: &[SymbolicName],
+    base_config: &BaseVerificationConfig,
+) -> Option<FxHashMap<SymbolicName, BigInt>> {
+    let mut honest: FxHashMap<SymbolicName, BigInt> = input_names
+        .iter()
+        .map(|name| (name.clone(), assignment[name].clone()))
+        .collect();
+    compiled_trace.emulate(
+        &base_config.prime,
+        &FxHashMap::default(),
+        &mut honest,
+        sexe.symbolic_library,
+    )?;
+    Some(honest)
+}
@@ -0,0 +1,383 @@
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use num_traits::{One, Signed, ToPrimitive};
+use rustc_hash::FxHashMap;
+
+use z3::ast::{Ast, Bool, Int};
+use z3::{Config, Context, SatResult, Solver};
+
+use program_structure::ast::{ExpressionInfixOpcode, ExpressionPrefixOpcode};
+
+use crate::executor::symbolic_execution::SymbolicExecutor;
+use crate::executor::symbolic_value::{SymbolicName, SymbolicValue, SymbolicValueRef};
+use crate::mutator::utils::{
+    is_vulnerable, verify_assignment, BaseVerificationConfig, CounterExample, SearchCompleteness,
+    SearchOutcome,
+};
+
+/// Timeout of a single `check` of the solver.
+const SMT_TIMEOUT_MSEC: u64 = 60_000;
+/// Number of spurious models that are blocked before a query is given up.
+const MAX_SPURIOUS_MODELS: usize = 16;
+/// Largest constant exponent that is unrolled into multiplications.
+const MAX_UNROLLED_EXPONENT: u64 = 32;
+
+/// Translates symbolic values into Z3 terms over the integers modulo `prime`.
+///
+/// Every variable is encoded as an integer in `[0, p)`. Sub-terms without an encoding (e.g.,
+/// bitwise operators or function calls) are replaced by fresh unconstrained variables, which
+/// over-approximates the constraints. Models are therefore only candidates and are confirmed
+/// with `verify_assignment`.
+struct SmtEncoder<'ctx> {
+    ctx: &'ctx Context,
+    prime: BigInt,
+    prime_term: Int<'ctx>,
+    variables: FxHashMap<SymbolicName, Int<'ctx>>,
+    definitions: Vec<Bool<'ctx>>,
+    num_fresh: usize,
+}
+
+impl<'ctx> SmtEncoder<'ctx> {
+    fn new(ctx: &'ctx Context, prime: &BigInt) -> Self {
+        SmtEncoder {
+            ctx: ctx,
+            prime: prime.clone(),
+            prime_term: Int::from_str(ctx, &prime.to_string()).unwrap(),
+            variables: FxHashMap::default(),
+            definitions: Vec::new(),
+            num_fresh: 0,
+        }
+    }
+
+    fn constant(&self, value: &BigInt) -> Int<'ctx> {
+        let mut normalized = value % &self.prime;
+        if normalized.is_negative() {
+            normalized += &self.prime;
+        }
+        Int::from_str(self.ctx, &normalized.to_string()).unwrap()
+    }
+
+    fn in_range(&self, term: &Int<'ctx>) -> Bool<'ctx> {
+        Bool::and(
+            self.ctx,
+            &[
+                &term.ge(&Int::from_i64(self.ctx, 0)),
+                &term.lt(&self.prime_term),
+            ],
+        )
+    }
+
+    fn fresh(&mut self) -> Int<'ctx> {
+        let term = Int::new_const(self.ctx, format!("fresh_{}", self.num_fresh));
+        self.num_fresh += 1;
+        self.definitions.push(self.in_range(&term));
+        term
+    }
+
+    fn variable(&mut self, name: &SymbolicName) -> Int<'ctx> {
+        if let Some(term) = self.variables.get(name) {
+            return term.clone();
+        }
+        let term = Int::new_const(self.ctx, format!("var_{}", self.variables.len()));
+        self.definitions.push(self.in_range(&term));
+        self.variables.insert(name.clone(), term.clone());
+        term
+    }
+
+    fn reduce(&self, term: &Int<'ctx>) -> Int<'ctx> {
+        term.modulo(&self.prime_term)
+    }
+
+    /// Maps a field element to its signed representative, as done by `val_for_relational_operators`.
+    fn signed(&self, term: &Int<'ctx>) -> Int<'ctx> {
+        let half = self.constant(&(&self.prime / BigInt::from(2)));
+        term.gt(&half)
+            .ite(&Int::sub(self.ctx, &[term, &self.prime_term]), term)
+    }
+
+    fn bool_to_int(&self, term: &Bool<'ctx>) -> Int<'ctx> {
+        term.ite(&Int::from_i64(self.ctx, 1), &Int::from_i64(self.ctx, 0))
+    }
+
+    fn encode_int(&mut self, value: &SymbolicValue) -> Int<'ctx> {
+        match value {
+            SymbolicValue::ConstantInt(v) => self.constant(v),
+            SymbolicValue::ConstantBool(b) => Int::from_i64(self.ctx, *b as i64),
+            SymbolicValue::Variable(name) => self.variable(name),
+            SymbolicValue::BinaryOp(lhs, op, rhs) | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+                match &op.0 {
+                    ExpressionInfixOpcode::Add => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        self.reduce(&Int::add(self.ctx, &[&l, &r]))
+                    }
+                    ExpressionInfixOpcode::Sub => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        self.reduce(&Int::sub(self.ctx, &[&l, &r]))
+                    }
+                    ExpressionInfixOpcode::Mul => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        self.reduce(&Int::mul(self.ctx, &[&l, &r]))
+                    }
+                    ExpressionInfixOpcode::Div => {
+                        // `l / r` is the `q` with `r * q = l`, and `0` when `r = 0`.
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        let q = self.fresh();
+                        let zero = Int::from_i64(self.ctx, 0);
+                        let is_zero = r._eq(&zero);
+                        let product = self.reduce(&Int::mul(self.ctx, &[&r, &q]));
+                        self.definitions.push(Bool::or(
+                            self.ctx,
+                            &[
+                                &Bool::and(self.ctx, &[&is_zero, &q._eq(&zero)]),
+                                &product._eq(&l),
+                            ],
+                        ));
+                        q
+                    }
+                    ExpressionInfixOpcode::IntDiv => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        l.div(&r)
+                    }
+                    ExpressionInfixOpcode::Mod => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        l.modulo(&r)
+                    }
+                    ExpressionInfixOpcode::Pow => match rhs.as_ref() {
+                        SymbolicValue::ConstantInt(e)
+                            if e.to_u64().map_or(false, |e| e <= MAX_UNROLLED_EXPONENT) =>
+                        {
+                            let base = self.encode_int(lhs);
+                            let mut result = Int::from_i64(self.ctx, 1);
+                            for _ in 0..e.to_u64().unwrap() {
+                                result = self.reduce(&Int::mul(self.ctx, &[&result, &base]));
+                            }
+                            result
+                        }
+                        _ => self.fresh(),
+                    },
+                    ExpressionInfixOpcode::Eq
+                    | ExpressionInfixOpcode::NotEq
+                    | ExpressionInfixOpcode::Lesser
+                    | ExpressionInfixOpcode::Greater
+                    | ExpressionInfixOpcode::LesserEq
+                    | ExpressionInfixOpcode::GreaterEq
+                    | ExpressionInfixOpcode::BoolAnd
+                    | ExpressionInfixOpcode::BoolOr => {
+                        let b = self.encode_bool(value);
+                        self.bool_to_int(&b)
+                    }
+                    _ => self.fresh(),
+                }
+            }
+            SymbolicValue::UnaryOp(op, expr) => match &op.0 {
+                ExpressionPrefixOpcode::Sub => {
+                    let e = self.encode_int(expr);
+                    self.reduce(&Int::sub(self.ctx, &[&self.prime_term, &e]))
+                }
+                ExpressionPrefixOpcode::BoolNot => {
+                    let b = self.encode_bool(value);
+                    self.bool_to_int(&b)
+                }
+                _ => self.fresh(),
+            },
+            SymbolicValue::Conditional(cond, then_val, else_val) => {
+                let c = self.encode_bool(cond);
+                let (t, e) = (self.encode_int(then_val), self.encode_int(else_val));
+                c.ite(&t, &e)
+            }
+            _ => self.fresh(),
+        }
+    }
+
+    fn encode_bool(&mut self, value: &SymbolicValue) -> Bool<'ctx> {
+        match value {
+            SymbolicValue::ConstantBool(b) => Bool::from_bool(self.ctx, *b),
+            SymbolicValue::BinaryOp(lhs, op, rhs) | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+                match &op.0 {
+                    ExpressionInfixOpcode::BoolAnd => {
+                        let (l, r) = (self.encode_bool(lhs), self.encode_bool(rhs));
+                        Bool::and(self.ctx, &[&l, &r])
+                    }
+                    ExpressionInfixOpcode::BoolOr => {
+                        let (l, r) = (self.encode_bool(lhs), self.encode_bool(rhs));
+                        Bool::or(self.ctx, &[&l, &r])
+                    }
+                    ExpressionInfixOpcode::Eq => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        l._eq(&r)
+                    }
+                    ExpressionInfixOpcode::NotEq => {
+                        let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                        l._eq(&r).not()
+                    }
+                    ExpressionInfixOpcode::Lesser
+                    | ExpressionInfixOpcode::Greater
+                    | ExpressionInfixOpcode::LesserEq
+                    | ExpressionInfixOpcode::GreaterEq => {
+                        let l = self.encode_int(lhs);
+                        let r = self.encode_int(rhs);
+                        let (l, r) = (self.signed(&l), self.signed(&r));
+                        match &op.0 {
+                            ExpressionInfixOpcode::Lesser => l.lt(&r),
+                            ExpressionInfixOpcode::Greater => l.gt(&r),
+                            ExpressionInfixOpcode::LesserEq => l.le(&r),
+                            _ => l.ge(&r),
+                        }
+                    }
+                    _ => {
+                        let v = self.encode_int(value);
+                        v._eq(&Int::from_i64(self.ctx, 0)).not()
+                    }
+                }
+            }
+            SymbolicValue::UnaryOp(op, expr) if matches!(op.0, ExpressionPrefixOpcode::BoolNot) => {
+                self.encode_bool(expr).not()
+            }
+            _ => {
+                let v = self.encode_int(value);
+                v._eq(&Int::from_i64(self.ctx, 0)).not()
+            }
+        }
+    }
+
+    /// Encodes an element of a symbolic trace or of the side constraints as a formula.
+    fn encode_constraint(&mut self, constraint: &SymbolicValue) -> Bool<'ctx> {
+        match constraint {
+            SymbolicValue::Assign(lhs, rhs, _, _)
+            | SymbolicValue::AssignEq(lhs, rhs)
+            | SymbolicValue::AssignTemplParam(lhs, rhs)
+            | SymbolicValue::AssignCall(lhs, rhs, _) => {
+                let (l, r) = (self.encode_int(lhs), self.encode_int(rhs));
+                l._eq(&r)
+            }
+            SymbolicValue::NOP => Bool::from_bool(self.ctx, true),
+            _ => self.encode_bool(constraint),
+        }
+    }
+
+    fn encode_constraints(&mut self, constraints: &[SymbolicValueRef]) -> Bool<'ctx> {
+        let encoded: Vec<Bool<'ctx>> = constraints
+            .iter()
+            .map(|c| self.encode_constraint(c))
+            .collect();
+        Bool::and(self.ctx, &encoded.iter().collect::<Vec<_>>())
+    }
+}
+
+/// Searches for a counterexample by asking Z3 for an assignment on which the symbolic trace and
+/// the side constraints disagree.
+///
+/// Two queries are issued: one for an assignment that satisfies the side constraints but violates
+/// the trace (a candidate for under-constrained circuits), and one for the opposite case (a
+/// candidate for over-constrained circuits). Each model is replayed with `verify_assignment`, and
+/// spurious models are blocked and the query is repeated.
+///
+/// # Parameters
+/// - `sexe`: A mutable reference to the symbolic executor.
+/// - `symbolic_trace`: A vector of constraints representing the program trace.
+/// - `side_constraints`: A vector of additional constraints for validation.
+/// - `base_config`: The verification base_configs.
+///
+/// # Returns
+/// A `SearchOutcome` with at most one counterexample. Since the encoding over-approximates the
+/// constraints, the search is exhaustive when the solver proves both queries unsatisfiable, and
+/// incomplete when a query times out or keeps yielding spurious models.
+pub fn smt_search(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &Vec<SymbolicValueRef>,
+    side_constraints: &Vec<SymbolicValueRef>,
+    base_config: &BaseVerificationConfig,
+) -> SearchOutcome {
+    let mut cfg = Config::new();
+    cfg.set_model_generation(true);
+    cfg.set_timeout_msec(SMT_TIMEOUT_MSEC);
+    let ctx = Context::new(&cfg);
+
+    let mut encoder = SmtEncoder::new(&ctx, &base_config.prime);
+    let trace_formula = encoder.encode_constraints(symbolic_trace);
+    let side_formula = encoder.encode_constraints(side_constraints);
+
+    let solver = Solver::new(&ctx);
+    for definition in &encoder.definitions {
+        solver.assert(definition);
+    }
+    for (name, value) in &base_config.input_constraints {
+        if let Some(term) = encoder.variables.get(name) {
+            solver.assert(&term._eq(&encoder.constant(value)));
+        }
+    }
+
+    let mut num_queries = 0;
+    let mut completeness = SearchCompleteness::Exhaustive;
+    for query in [
+        Bool::and(&ctx, &[&side_formula, &trace_formula.not()]),
+        Bool::and(&ctx, &[&trace_formula, &side_formula.not()]),
+    ] {
+        solver.push();
+        solver.assert(&query);
+        let mut is_unsat = false;
+        for _ in 0..MAX_SPURIOUS_MODELS {
+            num_queries += 1;
+            match solver.check() {
+                SatResult::Sat => {}
+                SatResult::Unsat => {
+                    is_unsat = true;
+                    break;
+                }
+                SatResult::Unknown => break,
+            }
+            let model = match solver.get_model() {
+                Some(model) => model,
+                None => break,
+            };
+
+            let mut assignment = base_config.input_constraints.clone();
+            let mut same_as_model = Vec::new();
+            for (name, term) in &encoder.variables {
+                let value = model
+                    .eval(term, true)
+                    .and_then(|v| BigInt::from_str(&v.to_string()).ok())
+                    .unwrap_or(BigInt::one());
+                same_as_model.push(term._eq(&encoder.constant(&value)));
+                assignment.insert(name.clone(), value);
+            }
+
+            let flag = verify_assignment(
+                sexe,
+                symbolic_trace,
+                side_constraints,
+                &assignment,
+                base_config,
+            );
+            if is_vulnerable(&flag) {
+                println!(" • SMT search completed");
+                println!("     ├─ Total queries: {}", num_queries);
+                println!("     └─ Verification result: {}", flag);
+                return SearchOutcome {
+                    counter_examples: vec![CounterExample {
+                        flag: flag,
+                        target_output: None,
+                        assignment: assignment,
+                        field_reductions: Vec::new(),
+                        provenance: None,
+                    }],
+                    completeness: SearchCompleteness::Incomplete,
+                };
+            }
+            solver.assert(&Bool::and(&ctx, &same_as_model.iter().collect::<Vec<_>>()).not());
+        }
+        if !is_unsat {
+            completeness = SearchCompleteness::Incomplete;
+        }
+        solver.pop(1);
+    }
+
+    println!(" • SMT search completed");
+    println!("     ├─ Total queries: {}", num_queries);
+    println!("     └─ Verification result: {}", completeness.verdict());
+    SearchOutcome {
+        counter_examples: Vec::new(),
+        completeness: completeness,
+    }
+}
@@ -0,0 +1,146 @@
+use std::rc::Rc;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::executor::symbolic_execution::{FunctionExecution, SymbolicExecutor};
+use crate::executor::symbolic_setting::SymbolicExecutorSetting;
+use crate::executor::symbolic_value::{
+    register_array_elements, SymbolicLibrary, SymbolicName, SymbolicValue,
+};
+
+/// A user-defined function whose return value is not fully determined by its arguments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UndeterminedFunction {
+    /// The name of the function.
+    pub name: String,
+    /// The local variables of the function that flow into its return value without ever being
+    /// assigned, sorted.
+    pub free_variables: Vec<SymbolicName>,
+}
+
+/// Symbolically executes every user-defined function over symbolic arguments and collects the
+/// ones whose return value depends on a local variable that is never assigned.
+///
+/// Such a function computes a witness value that the circuit cannot pin down from the inputs of
+/// the function, which is worth a look when the value ends up in a constraint. Each function is
+/// executed on its own, through the same inlining path as a call in a template, so a free
+/// variable of a callee is only reported for the callee. Functions whose execution contains a
+/// symbolic loop or a branch on a symbolic condition, or times out, are skipped.
+///
+/// # Parameters
+/// - `symbolic_library`: The library holding the functions of the program.
+/// - `setting`: The setting of the symbolic execution of the functions.
+///
+/// # Returns
+/// The undetermined functions, sorted by name.
+pub fn find_undetermined_functions(
+    symbolic_library: &mut SymbolicLibrary,
+    setting: &SymbolicExecutorSetting,
+) -> Vec<UndeterminedFunction> {
+    let mut function_ids: Vec<usize> = symbolic_library.function_library.keys().cloned().collect();
+    function_ids.sort_by_key(|id| symbolic_library.id2name[id].clone());
+
+    let mut sexe = SymbolicExecutor::new(symbolic_library, setting);
+    let mut undetermined = Vec::new();
+    for id in function_ids {
+        let args: Vec<_> = sexe.symbolic_library.function_library[&id]
+            .function_argument_names
+            .iter()
+            .map(|arg_id| {
+                Rc::new(SymbolicValue::Variable(SymbolicName::new(
+                    *arg_id,
+                    sexe.cur_state.owner_name.clone(),
+                    None,
+                )))
+            })
+            .collect();
+
+        sexe.clear();
+        if let Some(execution) = sexe.execute_function(id, &args) {
+            let mut free_variables = Vec::new();
+            let mut visited = FxHashSet::default();
+            collect_free_variables(
+                &execution.return_value,
+                &execution,
+                &mut visited,
+                &mut free_variables,
+            );
+            if !free_variables.is_empty() {
+                free_variables.sort();
+                undetermined.push(UndeterminedFunction {
+                    name: sexe.symbolic_library.id2name[&id].clone(),
+                    free_variables: free_variables,
+                });
+            }
+        }
+    }
+    undetermined
+}
+
+/// Collects the variables local to the executed function that `value` depends on but that are
+/// never assigned, following the bindings of the other local variables.
+///
+/// A whole local array, which is bound to itself once declared, depends on each of its elements.
+fn collect_free_variables(
+    value: &SymbolicValue,
+    execution: &FunctionExecution,
+    visited: &mut FxHashSet<SymbolicName>,
+    free_variables: &mut Vec<SymbolicName>,
+) {
+    match value {
+        SymbolicValue::Variable(sym_name) => {
+            // The arguments and the variables of nested calls are owned by someone else
+            if sym_name.owner != execution.state.owner_name || !visited.insert(sym_name.clone()) {
+                return;
+            }
+            match execution.state.get_sym_val(sym_name) {
+                Some(bound) if **bound != *value => {
+                    collect_free_variables(bound, execution, visited, free_variables)
+                }
+                _ => match execution.id2dimensions.get(&sym_name.id) {
+                    Some(dims) if !dims.is_empty() && sym_name.access.is_none() => {
+                        let mut elements: FxHashMap<SymbolicName, Option<()>> =
+                            FxHashMap::default();
+                        register_array_elements(
+                            sym_name.id,
+                            dims,
+                            Some(sym_name.owner.clone()),
+                            &mut elements,
+                        );
+                        for element in elements.into_keys() {
+                            collect_free_variables(
+                                &SymbolicValue::Variable(element),
+                                execution,
+                                visited,
+                                free_variables,
+                            );
+                        }
+                    }
+                    _ => free_variables.push(sym_name.clone()),
+                },
+            }
+        }
+        SymbolicValue::BinaryOp(lv, _, rv) | SymbolicValue::AuxBinaryOp(lv, _, rv) => {
+            collect_free_variables(lv, execution, visited, free_variables);
+            collect_free_variables(rv, execution, visited, free_variables);
+        }
+        SymbolicValue::UnaryOp(_, v) => {
+            collect_free_variables(v, execution, visited, free_variables);
+        }
+        SymbolicValue::Conditional(cond, then_val, else_val) => {
+            collect_free_variables(cond, execution, visited, free_variables);
+            collect_free_variables(then_val, execution, visited, free_variables);
+            collect_free_variables(else_val, execution, visited, free_variables);
+        }
+        SymbolicValue::UniformArray(element, dimension) => {
+            collect_free_variables(element, execution, visited, free_variables);
+            collect_free_variables(dimension, execution, visited, free_variables);
+        }
+        SymbolicValue::Array(elements) | SymbolicValue::Call(_, elements) => {
+            for element in elements {
+                collect_free_variables(element, execution, visited, free_variables);
+            }
+        }
+        _ => {}
+    }
+}
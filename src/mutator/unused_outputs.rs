@@ -4,13 +4,15 @@ use num_bigint_dig::BigInt;
 use num_traits::Zero;
 use rustc_hash::FxHashMap;
 
+use program_structure::ast::{SignalType, VariableType};
+
 use crate::executor::symbolic_execution::SymbolicExecutor;
 use crate::executor::symbolic_value::{extract_variables, register_array_elements, SymbolicName};
 use crate::mutator::utils::{
     BaseVerificationConfig, CounterExample, UnderConstrainedType, VerificationResult,
 };
 
-/// Checks for unused outputs in the symbolic execution trace and returns a counterexample if any are found.
+/// Checks for unused outputs in the symbolic execution trace and returns a counterexample for each of them.
 ///
 /// # Parameters
 /// - `sexe`: A mutable reference to the `SymbolicExecutor`, which holds the current state of symbolic execution,
@@ -19,25 +21,23 @@ use crate::mutator::utils::{
 ///   such as the target template name to analyze.
 ///
 /// # Returns
-/// An `Option<CounterExample>` containing:
-/// - `Some(CounterExample)` if unused outputs are detected, providing details about the unused outputs
-///   and a dummy assignment.
-/// - `None` if all outputs are used, indicating no under-constrained outputs.
+/// A `Vec<CounterExample>` with one counterexample per unused output, sorted by output, each of which
+/// assigns a dummy value to its output. The vector is empty if all outputs are used, indicating no
+/// under-constrained outputs.
 ///
 /// # Behavior
 /// 1. Extracts all variables used in the current symbolic execution trace.
 /// 2. Collects all outputs defined in the target template specified in `base_config`.
 /// 3. Compares the collected outputs against the used variables to identify unused outputs.
-/// 4. If unused outputs are found:
-///    - Constructs a `CounterExample` with the unused outputs marked as under-constrained.
-///    - Assigns dummy values (e.g., zero) to the unused outputs for illustrative purposes.
-/// 5. If all outputs are used, returns `None`.
+/// 4. For each unused output, constructs a `CounterExample` marking it as under-constrained, with a dummy
+///    value (e.g., zero) assigned to it for illustrative purposes.
 ///
 /// # Notes
 /// - This function assumes that the `SymbolicExecutor` contains a valid symbolic trace and a populated
 ///   symbolic library.
-/// - The returned `CounterExample` highlights unused outputs as a potential issue, classified under
-///   `UnderConstrainedType::UnusedOutput`.
+/// - The returned counterexamples highlight unused outputs as a potential issue, classified under
+///   `UnderConstrainedType::UnusedOutput`. Each unused output is a finding of its own, so that
+///   `--find_all` and `--max_findings` count them separately.
 ///
 /// # Performance
 /// - The function iterates through the symbolic trace and template outputs, which may incur overhead
@@ -45,7 +45,7 @@ use crate::mutator::utils::{
 pub fn check_unused_outputs(
     sexe: &mut SymbolicExecutor,
     base_config: &BaseVerificationConfig,
-) -> Option<CounterExample> {
+) -> Vec<CounterExample> {
     let mut variables: Vec<SymbolicName> = Vec::new();
     variables.append(&mut extract_variables(
         &sexe.cur_state.symbolic_trace.clone(),
@@ -75,22 +75,62 @@ pub fn check_unused_outputs(
             &mut used_outputs,
         );
     }
-    let unused_outputs: Vec<SymbolicName> = used_outputs
+    let mut unused_outputs: Vec<SymbolicName> = used_outputs
         .keys()
         .filter(|key| !variables_set.contains(*key))
         .cloned()
         .collect();
-    if !unused_outputs.is_empty() {
-        let dummy_assignment: FxHashMap<SymbolicName, BigInt> = unused_outputs
-            .iter()
-            .map(|uo| (uo.clone(), BigInt::zero()))
-            .collect();
-        Some(CounterExample {
+    unused_outputs.sort();
+    unused_outputs
+        .into_iter()
+        .map(|unused_output| CounterExample {
             flag: VerificationResult::UnderConstrained(UnderConstrainedType::UnusedOutput),
             target_output: None,
-            assignment: dummy_assignment,
+            assignment: FxHashMap::from_iter([(unused_output, BigInt::zero())]),
+            field_reductions: Vec::new(),
+            provenance: None,
         })
-    } else {
-        None
-    }
+        .collect()
+}
+
+/// Collects the intermediate signals of the main template that are assigned but never appear in
+/// any side constraint.
+///
+/// Such signals are a common under-constraint smell: their value is computed by the witness
+/// generator, yet nothing in the circuit checks it. Since an intermediate signal may legitimately
+/// be left out of the constraints (e.g., a debugging aid), the result is a heuristic warning
+/// rather than a counterexample.
+///
+/// # Parameters
+/// - `sexe`: A reference to the `SymbolicExecutor` after the symbolic execution of the main template.
+/// - `template_name`: The name of the main template.
+///
+/// # Returns
+/// The sorted list of dangling intermediate signals.
+pub fn check_dangling_intermediates(
+    sexe: &SymbolicExecutor,
+    template_name: &str,
+) -> Vec<SymbolicName> {
+    let template =
+        &sexe.symbolic_library.template_library[&sexe.symbolic_library.name2id[template_name]];
+    let constrained: HashSet<SymbolicName> = extract_variables(&sexe.cur_state.side_constraints)
+        .into_iter()
+        .collect();
+
+    let mut dangling: Vec<SymbolicName> = sexe
+        .cur_state
+        .symbol_binding_map
+        .keys()
+        .filter(|name| {
+            name.owner.len() == 1
+                && matches!(
+                    template.id2type.get(&name.id),
+                    Some(VariableType::Signal(SignalType::Intermediate, _))
+                )
+                && !constrained.contains(*name)
+        })
+        .cloned()
+        .collect();
+    dangling.sort();
+    dangling
 }
@@ -1,6 +1,9 @@
 use core::panic;
 use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
 use std::rc::Rc;
+use std::str::FromStr;
 
 use colored::Colorize;
 use num_bigint_dig::BigInt;
@@ -11,26 +14,38 @@ use rustc_hash::{FxHashMap, FxHashSet};
 use program_structure::ast::Expression;
 use program_structure::ast::ExpressionInfixOpcode;
 use program_structure::ast::ExpressionPrefixOpcode;
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use serde_with::{serde_as, DisplayFromStr};
 
 use crate::executor::debug_ast::DebuggableExpressionInfixOpcode;
 use crate::executor::symbolic_execution::SymbolicExecutor;
-use crate::executor::symbolic_setting::SymbolicExecutorSetting;
+use crate::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, SymbolicExecutorSetting, WhitelistMode,
+};
+use crate::executor::symbolic_state::{FieldReductionEvent, SymbolicState};
 use crate::executor::symbolic_value::{
     evaluate_binary_op, evaluate_binary_op_integer_mode, extract_variables_from_symbolic_value,
     normalize_to_bool, normalize_to_int, val_for_relational_operators, OwnerName, QuadraticPoly,
-    SymbolicAccess, SymbolicLibrary, SymbolicName, SymbolicValue, SymbolicValueRef,
+    SourceLocation, SymbolicAccess, SymbolicLibrary, SymbolicName, SymbolicValue, SymbolicValueRef,
 };
+use crate::mutator::compiled_trace::CompiledConstraints;
+use crate::mutator::safe_inputs::to_circom_input_json;
 
-#[derive(Clone)]
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum UnderConstrainedType {
     UnusedOutput,
-    UnexpectedInput(usize, String),
-    NonDeterministic(SymbolicName, String, BigInt),
+    UnexpectedInput(usize, String, Option<SourceLocation>),
+    NonDeterministic(
+        SymbolicName,
+        String,
+        #[serde_as(as = "DisplayFromStr")] BigInt,
+    ),
 }
 
 /// Represents the result of a constraint verification process.
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum VerificationResult {
     UnderConstrained(UnderConstrainedType),
     OverConstrained,
@@ -48,8 +63,12 @@ impl fmt::Display for VerificationResult {
                 UnderConstrainedType::UnusedOutput => {
                     "👻 UnderConstrained (Unused-Output) 👻".red().bold().to_string()
                 }
-                UnderConstrainedType::UnexpectedInput(_pos, violated_condition) => {
-                    format!("{} {}", "🧟 UnderConstrained (Unexpected-Input) 🧟\n║           Violated Condition:".red().bold(), violated_condition)
+                UnderConstrainedType::UnexpectedInput(_pos, violated_condition, location) => {
+                    let location = match location {
+                        Some(location) => format!(" at {}", location),
+                        None => String::new(),
+                    };
+                    format!("{} {}{}", "🧟 UnderConstrained (Unexpected-Input) 🧟\n║           Violated Condition:".red().bold(), violated_condition, location)
                 }
                 UnderConstrainedType::NonDeterministic(_sym_name, name, value) => format!(
                     "🔥 UnderConstrained (Non-Deterministic) 🔥\n║           ➡️ `{}` is expected to be `{}`",
@@ -72,8 +91,12 @@ impl VerificationResult {
                 UnderConstrainedType::UnusedOutput => {
                     json!({"1_type": "UnderConstrained-UnusedOutput"})
                 }
-                UnderConstrainedType::UnexpectedInput(pos, _violated_condition) => {
-                    json!({"1_type": "UnderConstrained-UnexpectedInput", "2_violated_condition":json!({"pos":pos})})
+                UnderConstrainedType::UnexpectedInput(pos, _violated_condition, location) => {
+                    let mut violated_condition = json!({"pos":pos});
+                    if let Some(location) = location {
+                        violated_condition["location"] = json!(location.to_string());
+                    }
+                    json!({"1_type": "UnderConstrained-UnexpectedInput", "2_violated_condition":violated_condition})
                 }
                 UnderConstrainedType::NonDeterministic(_sym_name, name, value) => {
                     json!({"1_type": "UnderConstrained-NonDeterministic", "2_expected_output": json!({"name": name, "value":value.to_string()})})
@@ -85,12 +108,72 @@ impl VerificationResult {
     }
 }
 
+/// The side constraints and the assignments involving a signal, explaining why the constraints
+/// do not pin it down.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SignalProvenance {
+    pub signal: SymbolicName,
+    /// The side constraints referencing the signal, along with their indices, which is empty
+    /// when nothing constrains it.
+    pub constraints: Vec<(usize, SymbolicValueRef)>,
+    /// The entries of the symbolic trace assigning the signal.
+    pub assignments: Vec<SymbolicValueRef>,
+}
+
+impl SignalProvenance {
+    /// Gathers the provenance of `signal` in `state`, the state of the symbolic execution of the
+    /// main template.
+    pub fn new(signal: &SymbolicName, state: &SymbolicState) -> Self {
+        let assignments = state
+            .symbolic_trace
+            .iter()
+            .filter(|c| match c.as_ref() {
+                SymbolicValue::Assign(lhs, _, _, _)
+                | SymbolicValue::AssignEq(lhs, _)
+                | SymbolicValue::AssignCall(lhs, _, _) => {
+                    matches!(lhs.as_ref(), SymbolicValue::Variable(name) if name == signal)
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect();
+        SignalProvenance {
+            signal: signal.clone(),
+            constraints: state.constraints_referencing(signal),
+            assignments: assignments,
+        }
+    }
+
+    pub fn to_json(&self, lookup: &FxHashMap<usize, String>) -> Value {
+        json!({
+            "signal": self.signal.lookup_fmt(lookup),
+            "constraints": self
+                .constraints
+                .iter()
+                .map(|(i, c)| json!({"index": i, "constraint": c.lookup_fmt(lookup)}))
+                .collect::<Vec<_>>(),
+            "assignments": self
+                .assignments
+                .iter()
+                .map(|c| c.lookup_fmt(lookup))
+                .collect::<Vec<_>>(),
+        })
+    }
+}
+
 /// Represents a counterexample when constraints are found to be invalid.
-#[derive(Clone)]
+#[serde_as]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct CounterExample {
     pub flag: VerificationResult,
     pub target_output: Option<SymbolicName>,
+    #[serde_as(as = "Vec<(_, DisplayFromStr)>")]
     pub assignment: FxHashMap<SymbolicName, BigInt>,
+    /// Reductions modulo the prime observed when replaying the counterexample with
+    /// `track_field_reductions` enabled.
+    pub field_reductions: Vec<FieldReductionEvent>,
+    /// The provenance of the under-constrained signal, attached by `explain`.
+    pub provenance: Option<SignalProvenance>,
 }
 
 impl CounterExample {
@@ -117,9 +200,180 @@ impl CounterExample {
             .map(|(var_name, value)| (var_name.lookup_fmt(lookup), value.to_string()))
             .collect::<FxHashMap<String, String>>());
 
+        if !self.field_reductions.is_empty() {
+            base_json["9_field_reductions"] = json!(self
+                .field_reductions
+                .iter()
+                .map(|event| event.to_json())
+                .collect::<Vec<Value>>());
+        }
+
+        if let Some(provenance) = &self.provenance {
+            base_json["10_provenance"] = provenance.to_json(lookup);
+        }
+
         base_json
     }
 
+    /// Returns the signal the counterexample is about: the output given an unexpected value, the
+    /// unused output, or the target output of the other results, if any.
+    pub fn under_constrained_signal(&self) -> Option<SymbolicName> {
+        match &self.flag {
+            VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                sym_name,
+                _,
+                _,
+            )) => Some(sym_name.clone()),
+            VerificationResult::UnderConstrained(UnderConstrainedType::UnusedOutput) => {
+                self.assignment.keys().min().cloned()
+            }
+            _ => self.target_output.clone(),
+        }
+    }
+
+    /// Attaches the provenance of the under-constrained signal in `state`, the state of the
+    /// symbolic execution of the main template, i.e., the constraints that should have pinned
+    /// the signal down and the assignments that computed it.
+    pub fn explain(&mut self, state: &SymbolicState) {
+        self.provenance = self
+            .under_constrained_signal()
+            .map(|signal| SignalProvenance::new(&signal, state));
+    }
+
+    /// Renders the inputs of the main template in the counterexample as a circom `input.json`.
+    ///
+    /// Unlike `to_json_with_meta`, the output only contains the assignments to the input signals
+    /// of the main template, so that it can be fed directly to the witness generator of circom
+    /// or to `snarkjs`.
+    ///
+    /// # Parameters
+    /// - `lookup`: A hash map associating variable IDs with their string representations.
+    /// - `input_ids`: The IDs of the input signals of the main template.
+    ///
+    /// # Returns
+    /// A flat JSON object mapping each input signal to its decimal value, where array inputs are
+    /// nested JSON arrays.
+    pub fn to_circom_input_json(
+        &self,
+        lookup: &FxHashMap<usize, String>,
+        input_ids: &FxHashSet<usize>,
+    ) -> Value {
+        let inputs: FxHashMap<SymbolicName, BigInt> = self
+            .assignment
+            .iter()
+            .filter(|(name, _)| name.owner.len() == 1 && input_ids.contains(&name.id))
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        to_circom_input_json(&inputs, lookup)
+    }
+
+    /// Renders the counterexample as a circom harness reproducing it.
+    ///
+    /// The harness includes `circuit_path` and instantiates the main template as `main`, so that
+    /// circom's witness generator can be run on it with the input of `to_circom_input_json`. A
+    /// leading comment block describes the violation and lists the whole assignment of the
+    /// counterexample, which the witness computed by the circuit can be compared against.
+    ///
+    /// # Parameters
+    /// - `lookup`: A hash map associating variable IDs with their string representations.
+    /// - `circuit_path`: The path of the circuit declaring the main template, which must not
+    ///   declare a main component itself.
+    /// - `template_name`: The name of the main template.
+    /// - `template_args`: The arguments of the main template, as circom expressions.
+    ///
+    /// # Returns
+    /// The source of the harness.
+    pub fn to_circom_test_harness(
+        &self,
+        lookup: &FxHashMap<usize, String>,
+        circuit_path: &str,
+        template_name: &str,
+        template_args: &[String],
+    ) -> String {
+        let description = match &self.flag {
+            VerificationResult::UnderConstrained(UnderConstrainedType::UnusedOutput) => {
+                "UnderConstrained (Unused-Output): an output signal is not constrained".to_string()
+            }
+            VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+                _,
+                violated_condition,
+                location,
+            )) => format!(
+                "UnderConstrained (Unexpected-Input): the assignment satisfies the constraints but violates `{}`{}",
+                violated_condition,
+                match location {
+                    Some(location) => format!(" at {}", location),
+                    None => String::new(),
+                }
+            ),
+            VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                _,
+                name,
+                value,
+            )) => format!(
+                "UnderConstrained (Non-Deterministic): the assignment satisfies the constraints but `{}` is expected to be `{}`",
+                name, value
+            ),
+            VerificationResult::OverConstrained => "OverConstrained".to_string(),
+            VerificationResult::WellConstrained => "WellConstrained".to_string(),
+        };
+
+        let mut assignment = self
+            .assignment
+            .iter()
+            .map(|(name, value)| format!("//   {} = {}", name.lookup_fmt(lookup), value))
+            .collect::<Vec<_>>();
+        assignment.sort();
+
+        format!(
+            "pragma circom 2.0.0;\n\ninclude \"{}\";\n\n// Counterexample found by zkFuzz\n//\n// {}\n//\n// Assignment:\n{}\n//\n// Compare the witness generated from input.json against the assignment above.\n\ncomponent main = {}({});\n",
+            circuit_path,
+            description,
+            assignment.join("\n"),
+            template_name,
+            template_args.join(", ")
+        )
+    }
+
+    /// Checks whether `other` exhibits the same bug, i.e., violates the same condition, gives an
+    /// unexpected value to the same output, leaves the same output unused, or is of the same kind
+    /// for the other results.
+    pub fn is_same_finding(&self, other: &CounterExample) -> bool {
+        match (&self.flag, &other.flag) {
+            (
+                VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+                    pos,
+                    _,
+                    _,
+                )),
+                VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+                    other_pos,
+                    _,
+                    _,
+                )),
+            ) => pos == other_pos,
+            (
+                VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                    name,
+                    _,
+                    _,
+                )),
+                VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                    other_name,
+                    _,
+                    _,
+                )),
+            ) => name == other_name,
+            (
+                VerificationResult::UnderConstrained(UnderConstrainedType::UnusedOutput),
+                VerificationResult::UnderConstrained(UnderConstrainedType::UnusedOutput),
+            ) => self.under_constrained_signal() == other.under_constrained_signal(),
+            (VerificationResult::OverConstrained, VerificationResult::OverConstrained)
+            | (VerificationResult::WellConstrained, VerificationResult::WellConstrained) => true,
+            _ => false,
+        }
+    }
+
     /// Generates a detailed, user-friendly debug output for the counterexample.
     ///
     /// # Parameters
@@ -183,6 +437,52 @@ impl CounterExample {
                 );
             }
         }
+        if !self.field_reductions.is_empty() {
+            s += &format!("{}", "║".red());
+            s += &format!("    {} \n", "🔁 Field Reductions:".blue().bold());
+            for event in &self.field_reductions {
+                s += &format!("{}", "║".red());
+                s += &format!(
+                    "           {} {} {} {} = {} ≡ {} \n",
+                    "➡️".cyan(),
+                    event.lhs,
+                    event.op,
+                    event.rhs,
+                    event.unreduced.to_string().bright_yellow(),
+                    event.reduced.to_string().bright_yellow()
+                );
+            }
+        }
+        if let Some(provenance) = &self.provenance {
+            s += &format!("{}", "║".red());
+            s += &format!(
+                "    {} {} \n",
+                "🧭 Constraints on".blue().bold(),
+                provenance.signal.lookup_fmt(lookup).magenta().bold()
+            );
+            if provenance.constraints.is_empty() {
+                s += &format!("{}", "║".red());
+                s += &format!("           {} {} \n", "➡️".cyan(), "(none)".yellow());
+            }
+            for (i, constraint) in &provenance.constraints {
+                s += &format!("{}", "║".red());
+                s += &format!(
+                    "           {} #{} {} \n",
+                    "➡️".cyan(),
+                    i,
+                    constraint.lookup_fmt(lookup).replace("\n", "")
+                );
+            }
+            for assignment in &provenance.assignments {
+                s += &format!("{}", "║".red());
+                s += &format!(
+                    "           {} {} {} \n",
+                    "✏️".cyan(),
+                    "assigned by".blue(),
+                    assignment.lookup_fmt(lookup).replace("\n", "")
+                );
+            }
+        }
         s += &format!(
             "{}",
             "╚══════════════════════════════════════════════════════════════╝\n".red()
@@ -192,6 +492,23 @@ impl CounterExample {
     }
 }
 
+/// Saves counterexamples as a CBOR file, which is much smaller and faster to load back than the
+/// JSON output for circuits with large input arrays.
+pub fn save_counter_examples_to_cbor(
+    counter_examples: &[CounterExample],
+    file_path: &str,
+) -> Result<(), String> {
+    let mut writer = BufWriter::new(File::create(file_path).map_err(|e| e.to_string())?);
+    ciborium::ser::into_writer(counter_examples, &mut writer).map_err(|e| e.to_string())?;
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// Loads counterexamples saved by `save_counter_examples_to_cbor`.
+pub fn load_counter_examples_from_cbor(file_path: &str) -> Result<Vec<CounterExample>, String> {
+    let file = File::open(file_path).map_err(|e| e.to_string())?;
+    ciborium::de::from_reader(BufReader::new(file)).map_err(|e| e.to_string())
+}
+
 /// Determines if a given verification result indicates a vulnerability.
 ///
 /// # Parameters
@@ -207,7 +524,58 @@ pub fn is_vulnerable(vr: &VerificationResult) -> bool {
     }
 }
 
+/// Appends `counter_example` to `findings` unless it exhibits the same bug as one of them, as
+/// decided by `CounterExample::is_same_finding`.
+///
+/// # Returns
+/// Whether `counter_example` was appended.
+pub fn push_distinct_finding(
+    findings: &mut Vec<CounterExample>,
+    counter_example: CounterExample,
+) -> bool {
+    if findings
+        .iter()
+        .any(|ce| ce.is_same_finding(&counter_example))
+    {
+        false
+    } else {
+        findings.push(counter_example);
+        true
+    }
+}
+
+/// How much the absence of a counterexample tells about the circuit.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SearchCompleteness {
+    /// Every assignment was covered, e.g., by enumerating the whole field or by an SMT solver
+    /// proving the queries unsatisfiable, so that the circuit is proven safe.
+    Exhaustive,
+    /// Only some assignments were covered, so that a counterexample may still exist.
+    Incomplete,
+}
+
+impl SearchCompleteness {
+    /// Returns the verdict reported when no counterexample is found.
+    pub fn verdict(&self) -> &'static str {
+        match self {
+            SearchCompleteness::Exhaustive => "Exhaustively verified safe",
+            SearchCompleteness::Incomplete => "No counterexample found (incomplete search)",
+        }
+    }
+}
+
+/// Result of a search backend.
+#[derive(Clone)]
+pub struct SearchOutcome {
+    /// The distinct counterexamples, in the order in which they were found.
+    pub counter_examples: Vec<CounterExample>,
+    /// Whether the search covered every assignment, which matters only when
+    /// `counter_examples` is empty.
+    pub completeness: SearchCompleteness,
+}
+
 /// Configures the settings for the verification process.
+#[derive(Clone)]
 pub struct BaseVerificationConfig {
     pub target_template_name: String,
     pub prime: BigInt,
@@ -217,6 +585,68 @@ pub struct BaseVerificationConfig {
     pub progress_interval: usize,
     pub template_param_names: Vec<String>,
     pub template_param_values: Vec<Expression>,
+    /// Inputs of the main template that keep the given values during the search.
+    pub input_constraints: FxHashMap<SymbolicName, BigInt>,
+    /// Whether the brute-force search tries the boundary values of the field, such as
+    /// `prime - 1`, before the values of its search mode.
+    pub boundary_values: bool,
+    /// Conditions on the inputs of the main template given with `--assume`, which the inputs of
+    /// a counterexample must satisfy.
+    pub assumptions: Vec<SymbolicValueRef>,
+    /// The number of distinct counterexamples a search collects before it stops, `1` stopping
+    /// at the first one.
+    pub max_findings: usize,
+}
+
+impl Default for BaseVerificationConfig {
+    /// Searches the whole BN254 field for a single counterexample, without any constraint on
+    /// the inputs.
+    fn default() -> Self {
+        let prime = BigInt::from_str(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+        )
+        .unwrap();
+        BaseVerificationConfig {
+            target_template_name: String::new(),
+            prime: prime.clone(),
+            range: prime,
+            quick_mode: false,
+            heuristics_mode: false,
+            progress_interval: 10000,
+            template_param_names: Vec::new(),
+            template_param_values: Vec::new(),
+            input_constraints: FxHashMap::default(),
+            boundary_values: false,
+            assumptions: Vec::new(),
+            max_findings: 1,
+        }
+    }
+}
+
+impl BaseVerificationConfig {
+    /// Overwrites the inputs fixed by `input_constraints` in `assignment`.
+    pub fn pin_input_constraints(&self, assignment: &mut FxHashMap<SymbolicName, BigInt>) {
+        for (name, value) in &self.input_constraints {
+            assignment.insert(name.clone(), value.clone());
+        }
+    }
+
+    /// Checks whether `assignment` satisfies every assumption of `assumptions`.
+    ///
+    /// An assumption that cannot be evaluated, e.g., because it refers to an input missing from
+    /// `assignment`, does not rule the assignment out.
+    pub fn satisfies_assumptions(
+        &self,
+        assignment: &FxHashMap<SymbolicName, BigInt>,
+        symbolic_library: &mut SymbolicLibrary,
+    ) -> bool {
+        self.assumptions.iter().all(|assumption| {
+            !matches!(
+                evaluate_symbolic_value(&self.prime, assumption, assignment, symbolic_library),
+                Some(SymbolicValue::ConstantBool(false))
+            )
+        })
+    }
 }
 
 /// Determines whether a collection of symbolic values contains a binary equality check against zero.  
@@ -321,22 +751,33 @@ pub fn evaluate_constraints(
     } else {
         constraints.iter().all(|constraint| {
             let sv = evaluate_symbolic_value(prime, constraint, assignment, symbolic_library);
-            match sv {
-                Some(SymbolicValue::ConstantBool(b)) => b,
-                Some(v) => {
-                    panic!(
-                        "Non-bool output value is detected when evaluating a constraint: {}",
-                        v.lookup_fmt(&symbolic_library.id2name)
-                    )
-                }
-                _ => {
-                    panic!("Non-bool output value is detected when evaluating a constraint: None",)
-                }
-            }
+            is_satisfied_constraint(sv, symbolic_library)
         })
     }
 }
 
+/// Interprets the evaluated value of a constraint as `evaluate_constraints` does.
+///
+/// # Panics
+/// Panics if the value is not a boolean.
+pub fn is_satisfied_constraint(
+    value: Option<SymbolicValue>,
+    symbolic_library: &SymbolicLibrary,
+) -> bool {
+    match value {
+        Some(SymbolicValue::ConstantBool(b)) => b,
+        Some(v) => {
+            panic!(
+                "Non-bool output value is detected when evaluating a constraint: {}",
+                v.lookup_fmt(&symbolic_library.id2name)
+            )
+        }
+        _ => {
+            panic!("Non-bool output value is detected when evaluating a constraint: None",)
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub enum Direction {
     Left,
@@ -471,304 +912,259 @@ pub fn emulate_symbolic_trace(
 ) -> Option<(bool, usize)> {
     let mut success = true;
     let mut failure_pos = 0;
-    // let input_variables: FxHashSet<SymbolicName> = assignment.keys().cloned().collect();
     for (i, inst) in trace.iter().enumerate() {
-        // println!("{}", inst.lookup_fmt(&symbolic_library.id2name));
-        match inst.as_ref() {
-            SymbolicValue::NOP => {}
-            SymbolicValue::ConstantBool(b) => {
-                if !b {
-                    success = false;
-                    failure_pos = i;
-                }
-            }
-            SymbolicValue::Assign(lhs, rhs, _, _)
-            | SymbolicValue::AssignEq(lhs, rhs)
-            | SymbolicValue::AssignTemplParam(lhs, rhs)
-            | SymbolicValue::AssignCall(lhs, rhs, _) => {
-                if let SymbolicValue::Variable(sym_name) = lhs.as_ref() {
-                    let rhs_val = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library);
-                    match &rhs_val {
-                        Some(SymbolicValue::NOP) => {
-                            if !assignment.contains_key(sym_name) {
-                                assignment.insert(sym_name.clone(), BigInt::zero());
-                            }
-                        }
-                        Some(SymbolicValue::ConstantInt(num)) => {
-                            assignment.insert(sym_name.clone(), num.clone());
-                        }
-                        Some(SymbolicValue::ConstantBool(b)) => {
-                            assignment.insert(
-                                sym_name.clone(),
-                                if *b { BigInt::one() } else { BigInt::zero() },
-                            );
-                        }
-                        Some(SymbolicValue::Array(arr)) => {
-                            for (i, a) in arr.iter().enumerate() {
-                                if let SymbolicValue::ConstantInt(v) = a.as_ref() {
-                                    let mut name = sym_name.clone();
-                                    let mut accsess = if name.access.is_some() {
-                                        name.access.unwrap().clone()
-                                    } else {
-                                        Vec::new()
-                                    };
-                                    accsess.push(SymbolicAccess::ArrayAccess(
-                                        SymbolicValue::ConstantInt(BigInt::from(i)),
-                                    ));
-                                    name.access = Some(accsess);
-                                    name.update_hash();
-                                    assignment.insert(name, v.clone());
-                                } else {
-                                    todo!("Support nested-arrays for template parameters");
-                                }
-                            }
-                        }
-                        None => {
-                            return None;
-                        }
-                        _ => {
-                            success = false;
-                            failure_pos = i;
-                        }
-                    }
-                } else {
-                    panic!(
-                        "Left hand of the assignment is not a variable: {}",
-                        inst.lookup_fmt(&symbolic_library.id2name)
-                    );
-                }
-            }
-            SymbolicValue::BinaryOp(lhs, op, rhs) => {
-                let mut lhs_val = evaluate_symbolic_value(prime, lhs, assignment, symbolic_library);
-                let mut rhs_val = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library);
-
-                if let Some(dir) = runtime_mutable_positions.get(&i) {
-                    match dir {
-                        Direction::Left => {
-                            if let SymbolicValue::Variable(var_name) = &**lhs {
-                                if let Some(SymbolicValue::ConstantInt(ref num)) = rhs_val {
-                                    assignment.insert(var_name.clone(), num.clone());
-                                    lhs_val = rhs_val.clone();
-                                }
-                            }
-                        }
-                        Direction::Right => {
-                            if let SymbolicValue::Variable(var_name) = &**rhs {
-                                if let Some(SymbolicValue::ConstantInt(ref num)) = lhs_val {
-                                    assignment.insert(var_name.clone(), num.clone());
-                                    rhs_val = lhs_val.clone();
-                                }
-                            }
-                        }
-                    }
-                }
+        if !emulate_trace_entry(
+            prime,
+            i,
+            inst,
+            runtime_mutable_positions,
+            assignment,
+            symbolic_library,
+        )? {
+            success = false;
+            failure_pos = i;
+        }
+    }
 
-                let (normalized_lhs, normalized_rhs) = match &op.0 {
-                    // Convert booleans to integers for arithmetic or bitwise operators
-                    ExpressionInfixOpcode::Add
-                    | ExpressionInfixOpcode::Sub
-                    | ExpressionInfixOpcode::Mul
-                    | ExpressionInfixOpcode::Pow
-                    | ExpressionInfixOpcode::Div
-                    | ExpressionInfixOpcode::IntDiv
-                    | ExpressionInfixOpcode::Mod
-                    | ExpressionInfixOpcode::BitOr
-                    | ExpressionInfixOpcode::BitAnd
-                    | ExpressionInfixOpcode::BitXor
-                    | ExpressionInfixOpcode::ShiftL
-                    | ExpressionInfixOpcode::ShiftR
-                    | ExpressionInfixOpcode::Lesser
-                    | ExpressionInfixOpcode::Greater
-                    | ExpressionInfixOpcode::LesserEq
-                    | ExpressionInfixOpcode::GreaterEq
-                    | ExpressionInfixOpcode::Eq
-                    | ExpressionInfixOpcode::NotEq => (
-                        normalize_to_int(&lhs_val.unwrap(), prime),
-                        normalize_to_int(&rhs_val.unwrap(), prime),
-                    ),
-                    // Keep booleans as they are for logical operators
-                    ExpressionInfixOpcode::BoolAnd | ExpressionInfixOpcode::BoolOr => (
-                        normalize_to_bool(&lhs_val.unwrap(), prime),
-                        normalize_to_bool(&rhs_val.unwrap(), prime),
-                    ), //_ => (lhs.clone(), rhs.clone()), // Default case
-                };
+    Some((success, failure_pos))
+}
 
-                let flag = match (&normalized_lhs, &normalized_rhs) {
-                    (SymbolicValue::ConstantInt(lv), SymbolicValue::ConstantInt(rv)) => {
-                        match op.0 {
-                            ExpressionInfixOpcode::Lesser => {
-                                val_for_relational_operators(&(lv % prime), prime)
-                                    < val_for_relational_operators(&(rv % prime), prime)
-                            }
-                            ExpressionInfixOpcode::Greater => {
-                                val_for_relational_operators(&(lv % prime), prime)
-                                    > val_for_relational_operators(&(rv % prime), prime)
-                            }
-                            ExpressionInfixOpcode::LesserEq => {
-                                val_for_relational_operators(&(lv % prime), prime)
-                                    <= val_for_relational_operators(&(rv % prime), prime)
-                            }
-                            ExpressionInfixOpcode::GreaterEq => {
-                                val_for_relational_operators(&(lv % prime), prime)
-                                    >= val_for_relational_operators(&(rv % prime), prime)
-                            }
-                            ExpressionInfixOpcode::Eq => lv % prime == rv % prime,
-                            ExpressionInfixOpcode::NotEq => lv % prime != rv % prime,
-                            _ => panic!(
-                                "Non-Boolean Operation: {}",
-                                inst.lookup_fmt(&symbolic_library.id2name)
-                            ),
-                        }
-                    }
-                    (SymbolicValue::ConstantBool(lv), SymbolicValue::ConstantBool(rv)) => {
-                        match &op.0 {
-                            ExpressionInfixOpcode::BoolAnd => *lv && *rv,
-                            ExpressionInfixOpcode::BoolOr => *lv || *rv,
-                            _ => todo!(),
-                        }
-                    }
-                    _ => panic!(
-                        "Unassigned variables exist: {}",
-                        inst.lookup_fmt(&symbolic_library.id2name)
-                    ),
-                };
-                if !flag {
-                    success = false;
-                    failure_pos = i;
-                }
+/// Simulates a single entry of a symbolic trace, as done by `emulate_symbolic_trace`.
+///
+/// # Parameters
+/// - `prime`: A reference to the prime modulus used for modular arithmetic.
+/// - `i`: The position of the entry in the trace.
+/// - `inst`: The entry to simulate.
+/// - `runtime_mutable_positions`: A map of runtime mutable positions.
+/// - `assignment`: A mutable hash map of symbolic variable names to their corresponding `BigInt` values.
+/// - `symbolic_library`: A mutable reference to a symbolic library containing the definitions of symbolic values.
+///
+/// # Returns
+/// `Some(false)` if the entry fails, `Some(true)` if it holds, and `None` if the right-hand side
+/// of an assignment cannot be evaluated, in which case the whole simulation is abandoned.
+pub fn emulate_trace_entry(
+    prime: &BigInt,
+    i: usize,
+    inst: &SymbolicValue,
+    runtime_mutable_positions: &FxHashMap<usize, Direction>,
+    assignment: &mut FxHashMap<SymbolicName, BigInt>,
+    symbolic_library: &mut SymbolicLibrary,
+) -> Option<bool> {
+    match inst {
+        SymbolicValue::NOP => Some(true),
+        SymbolicValue::ConstantBool(b) => Some(*b),
+        SymbolicValue::Assign(lhs, rhs, _, _)
+        | SymbolicValue::AssignEq(lhs, rhs)
+        | SymbolicValue::AssignTemplParam(lhs, rhs)
+        | SymbolicValue::AssignCall(lhs, rhs, _) => {
+            if let SymbolicValue::Variable(sym_name) = lhs.as_ref() {
+                let rhs_val = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library);
+                Some(assign_evaluated_value(sym_name, rhs_val?, assignment))
+            } else {
+                panic!(
+                    "Left hand of the assignment is not a variable: {}",
+                    inst.lookup_fmt(&symbolic_library.id2name)
+                );
             }
-            SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
-                let mut lhs_val = evaluate_symbolic_value(prime, lhs, assignment, symbolic_library);
-                let mut rhs_val = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library);
-
-                if let Some(dir) = runtime_mutable_positions.get(&i) {
-                    match dir {
-                        Direction::Left => {
-                            if let SymbolicValue::Variable(var_name) = &**lhs {
-                                if let Some(SymbolicValue::ConstantInt(ref num)) = rhs_val {
-                                    assignment.insert(var_name.clone(), num.clone());
-                                    lhs_val = rhs_val.clone();
-                                }
+        }
+        SymbolicValue::BinaryOp(lhs, op, rhs) | SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
+            let mut lhs_val = evaluate_symbolic_value(prime, lhs, assignment, symbolic_library);
+            let mut rhs_val = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library);
+
+            if let Some(dir) = runtime_mutable_positions.get(&i) {
+                match dir {
+                    Direction::Left => {
+                        if let SymbolicValue::Variable(var_name) = &**lhs {
+                            if let Some(SymbolicValue::ConstantInt(ref num)) = rhs_val {
+                                assignment.insert(var_name.clone(), num.clone());
+                                lhs_val = rhs_val.clone();
                             }
                         }
-                        Direction::Right => {
-                            if let SymbolicValue::Variable(var_name) = &**rhs {
-                                if let Some(SymbolicValue::ConstantInt(ref num)) = lhs_val {
-                                    assignment.insert(var_name.clone(), num.clone());
-                                    rhs_val = lhs_val.clone();
-                                }
+                    }
+                    Direction::Right => {
+                        if let SymbolicValue::Variable(var_name) = &**rhs {
+                            if let Some(SymbolicValue::ConstantInt(ref num)) = lhs_val {
+                                assignment.insert(var_name.clone(), num.clone());
+                                rhs_val = lhs_val.clone();
                             }
                         }
                     }
                 }
+            }
 
-                let (normalized_lhs, normalized_rhs) = match &op.0 {
-                    // Convert booleans to integers for arithmetic or bitwise operators
-                    ExpressionInfixOpcode::Add
-                    | ExpressionInfixOpcode::Sub
-                    | ExpressionInfixOpcode::Mul
-                    | ExpressionInfixOpcode::Pow
-                    | ExpressionInfixOpcode::Div
-                    | ExpressionInfixOpcode::IntDiv
-                    | ExpressionInfixOpcode::Mod
-                    | ExpressionInfixOpcode::BitOr
-                    | ExpressionInfixOpcode::BitAnd
-                    | ExpressionInfixOpcode::BitXor
-                    | ExpressionInfixOpcode::ShiftL
-                    | ExpressionInfixOpcode::ShiftR
-                    | ExpressionInfixOpcode::Lesser
-                    | ExpressionInfixOpcode::Greater
-                    | ExpressionInfixOpcode::LesserEq
-                    | ExpressionInfixOpcode::GreaterEq
-                    | ExpressionInfixOpcode::Eq
-                    | ExpressionInfixOpcode::NotEq => (
-                        normalize_to_int(&lhs_val.unwrap(), prime),
-                        normalize_to_int(&rhs_val.unwrap(), prime),
-                    ),
-                    // Keep booleans as they are for logical operators
-                    ExpressionInfixOpcode::BoolAnd | ExpressionInfixOpcode::BoolOr => (
-                        normalize_to_bool(&lhs_val.unwrap(), prime),
-                        normalize_to_bool(&rhs_val.unwrap(), prime),
-                    ), //_ => (lhs.clone(), rhs.clone()), // Default case
-                };
-
-                let flag = match (&normalized_lhs, &normalized_rhs) {
-                    (SymbolicValue::ConstantInt(lv), SymbolicValue::ConstantInt(rv)) => {
-                        match op.0 {
-                            ExpressionInfixOpcode::Lesser => lv % prime < rv % prime,
-                            ExpressionInfixOpcode::Greater => lv % prime > rv % prime,
-                            ExpressionInfixOpcode::LesserEq => lv % prime <= rv % prime,
-                            ExpressionInfixOpcode::GreaterEq => lv % prime >= rv % prime,
-                            ExpressionInfixOpcode::Eq => lv % prime == rv % prime,
-                            ExpressionInfixOpcode::NotEq => lv % prime != rv % prime,
-                            _ => panic!(
-                                "Non-Boolean Operation: {}",
-                                inst.lookup_fmt(&symbolic_library.id2name)
-                            ),
-                        }
-                    }
-                    (SymbolicValue::ConstantBool(lv), SymbolicValue::ConstantBool(rv)) => {
-                        match &op.0 {
-                            ExpressionInfixOpcode::BoolAnd => *lv && *rv,
-                            ExpressionInfixOpcode::BoolOr => *lv || *rv,
-                            _ => todo!(),
-                        }
-                    }
+            Some(holds_binary_constraint(
+                prime,
+                inst,
+                op,
+                lhs_val,
+                rhs_val,
+                symbolic_library,
+            ))
+        }
+        SymbolicValue::UnaryOp(op, expr) => {
+            let expr_val = evaluate_symbolic_value(prime, expr, assignment, symbolic_library);
+            let flag = match &expr_val {
+                Some(SymbolicValue::ConstantBool(rv)) => match op.0 {
+                    ExpressionPrefixOpcode::BoolNot => !rv,
                     _ => panic!(
                         "Unassigned variables exist: {}",
                         inst.lookup_fmt(&symbolic_library.id2name)
                     ),
-                };
-                if !flag {
-                    success = false;
-                    failure_pos = i;
-                }
+                },
+                _ => panic!(
+                    "Non-Boolean Operation: {}",
+                    inst.lookup_fmt(&symbolic_library.id2name)
+                ),
+            };
+            Some(flag)
+        }
+        _ => {
+            let val = evaluate_symbolic_value(prime, inst, assignment, symbolic_library);
+            match &val.unwrap() {
+                SymbolicValue::ConstantBool(b) => Some(*b),
+                SymbolicValue::ConstantInt(v) => Some(!v.is_zero()),
+                _ => panic!(
+                    "Unassigned variables exist: {}",
+                    inst.lookup_fmt(&symbolic_library.id2name)
+                ),
             }
-            SymbolicValue::UnaryOp(op, expr) => {
-                let expr_val = evaluate_symbolic_value(prime, expr, assignment, symbolic_library);
-                let flag = match &expr_val {
-                    Some(SymbolicValue::ConstantBool(rv)) => match op.0 {
-                        ExpressionPrefixOpcode::BoolNot => !rv,
-                        _ => panic!(
-                            "Unassigned variables exist: {}",
-                            inst.lookup_fmt(&symbolic_library.id2name)
-                        ),
-                    },
-                    _ => panic!(
-                        "Non-Boolean Operation: {}",
-                        inst.lookup_fmt(&symbolic_library.id2name)
-                    ),
-                };
-                if !flag {
-                    success = false;
-                    failure_pos = i;
-                }
+        }
+    }
+}
+
+/// Stores the evaluated right-hand side of an assignment to `sym_name` in `assignment`.
+///
+/// An array is stored element-wise, under the names obtained by appending the index of each
+/// element to the access of `sym_name`.
+///
+/// # Returns
+/// `false` if the value cannot be stored, i.e., if it is still symbolic.
+pub(crate) fn assign_evaluated_value(
+    sym_name: &SymbolicName,
+    rhs_val: SymbolicValue,
+    assignment: &mut FxHashMap<SymbolicName, BigInt>,
+) -> bool {
+    match rhs_val {
+        SymbolicValue::NOP => {
+            if !assignment.contains_key(sym_name) {
+                assignment.insert(sym_name.clone(), BigInt::zero());
             }
-            _ => {
-                let val = evaluate_symbolic_value(prime, inst, assignment, symbolic_library);
-                match &val.unwrap() {
-                    SymbolicValue::ConstantBool(b) => {
-                        if !b {
-                            success = false;
-                            failure_pos = i;
-                        }
-                    }
-                    SymbolicValue::ConstantInt(v) => {
-                        if v.is_zero() {
-                            success = false;
-                            failure_pos = i;
-                        }
-                    }
-                    _ => panic!(
-                        "Unassigned variables exist: {}",
-                        inst.lookup_fmt(&symbolic_library.id2name)
-                    ),
+        }
+        SymbolicValue::ConstantInt(num) => {
+            assignment.insert(sym_name.clone(), num);
+        }
+        SymbolicValue::ConstantBool(b) => {
+            assignment.insert(
+                sym_name.clone(),
+                if b { BigInt::one() } else { BigInt::zero() },
+            );
+        }
+        SymbolicValue::Array(arr) => {
+            for (i, a) in arr.iter().enumerate() {
+                if let SymbolicValue::ConstantInt(v) = a.as_ref() {
+                    let mut name = sym_name.clone();
+                    let mut accsess = if name.access.is_some() {
+                        name.access.unwrap().clone()
+                    } else {
+                        Vec::new()
+                    };
+                    accsess.push(SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(
+                        BigInt::from(i),
+                    )));
+                    name.access = Some(accsess);
+                    name.update_hash();
+                    assignment.insert(name, v.clone());
+                } else {
+                    todo!("Support nested-arrays for template parameters");
                 }
             }
         }
+        _ => return false,
     }
+    true
+}
 
-    Some((success, failure_pos))
+/// Checks the relation of a `BinaryOp` or `AuxBinaryOp` entry of a trace, given the evaluated
+/// operands.
+///
+/// The relational operators of a `BinaryOp` compare the signed representatives of the field
+/// elements, while those of an `AuxBinaryOp` compare the reduced integers.
+///
+/// # Panics
+/// If an operand is not evaluated, or if the operator is not Boolean.
+pub(crate) fn holds_binary_constraint(
+    prime: &BigInt,
+    inst: &SymbolicValue,
+    op: &DebuggableExpressionInfixOpcode,
+    lhs_val: Option<SymbolicValue>,
+    rhs_val: Option<SymbolicValue>,
+    symbolic_library: &SymbolicLibrary,
+) -> bool {
+    let is_field_comparison = matches!(inst, SymbolicValue::BinaryOp(..));
+    let (normalized_lhs, normalized_rhs) = match &op.0 {
+        // Convert booleans to integers for arithmetic or bitwise operators
+        ExpressionInfixOpcode::Add
+        | ExpressionInfixOpcode::Sub
+        | ExpressionInfixOpcode::Mul
+        | ExpressionInfixOpcode::Pow
+        | ExpressionInfixOpcode::Div
+        | ExpressionInfixOpcode::IntDiv
+        | ExpressionInfixOpcode::Mod
+        | ExpressionInfixOpcode::BitOr
+        | ExpressionInfixOpcode::BitAnd
+        | ExpressionInfixOpcode::BitXor
+        | ExpressionInfixOpcode::ShiftL
+        | ExpressionInfixOpcode::ShiftR
+        | ExpressionInfixOpcode::Lesser
+        | ExpressionInfixOpcode::Greater
+        | ExpressionInfixOpcode::LesserEq
+        | ExpressionInfixOpcode::GreaterEq
+        | ExpressionInfixOpcode::Eq
+        | ExpressionInfixOpcode::NotEq => (
+            normalize_to_int(&lhs_val.unwrap(), prime),
+            normalize_to_int(&rhs_val.unwrap(), prime),
+        ),
+        // Keep booleans as they are for logical operators
+        ExpressionInfixOpcode::BoolAnd | ExpressionInfixOpcode::BoolOr => (
+            normalize_to_bool(&lhs_val.unwrap(), prime),
+            normalize_to_bool(&rhs_val.unwrap(), prime),
+        ),
+    };
+
+    match (&normalized_lhs, &normalized_rhs) {
+        (SymbolicValue::ConstantInt(lv), SymbolicValue::ConstantInt(rv)) => {
+            let (lv, rv) = (lv % prime, rv % prime);
+            let (ord_lv, ord_rv) = if is_field_comparison {
+                (
+                    val_for_relational_operators(&lv, prime),
+                    val_for_relational_operators(&rv, prime),
+                )
+            } else {
+                (lv.clone(), rv.clone())
+            };
+            match op.0 {
+                ExpressionInfixOpcode::Lesser => ord_lv < ord_rv,
+                ExpressionInfixOpcode::Greater => ord_lv > ord_rv,
+                ExpressionInfixOpcode::LesserEq => ord_lv <= ord_rv,
+                ExpressionInfixOpcode::GreaterEq => ord_lv >= ord_rv,
+                ExpressionInfixOpcode::Eq => lv == rv,
+                ExpressionInfixOpcode::NotEq => lv != rv,
+                _ => panic!(
+                    "Non-Boolean Operation: {}",
+                    inst.lookup_fmt(&symbolic_library.id2name)
+                ),
+            }
+        }
+        (SymbolicValue::ConstantBool(lv), SymbolicValue::ConstantBool(rv)) => match &op.0 {
+            ExpressionInfixOpcode::BoolAnd => *lv && *rv,
+            ExpressionInfixOpcode::BoolOr => *lv || *rv,
+            _ => todo!(),
+        },
+        _ => panic!(
+            "Unassigned variables exist: {}",
+            inst.lookup_fmt(&symbolic_library.id2name)
+        ),
+    }
 }
 
 /// Evaluates a symbolic value within the given context of a symbolic library and variable assignments.
@@ -795,7 +1191,8 @@ pub fn emulate_symbolic_trace(
 /// 3. **Array Evaluation**: Arrays and uniform arrays are evaluated element-wise.
 /// 4. **Expression Evaluation**:
 ///    - **Assignments**: Evaluates the left-hand side and right-hand side of the assignment, checking their equality.
-///    - **Binary Operations**: Evaluates the operands and applies the specified operator.
+///    - **Binary Operations**: Evaluates the operands and applies the specified operator. A
+///      division without inverse, possible only for a non-prime modulus, cannot be evaluated.
 ///    - **Unary Operations**: Evaluates the operand and applies the specified unary operator.
 /// 5. **Conditionals**: Evaluates the condition and returns the result of the appropriate branch (then or else).
 /// 6. **Function Calls**: Executes the function body with the provided arguments, returning the result of the function.
@@ -818,6 +1215,7 @@ pub fn evaluate_symbolic_value(
 ) -> Option<SymbolicValue> {
     match value {
         SymbolicValue::NOP => Some(SymbolicValue::NOP),
+        SymbolicValue::Undefined => None,
         SymbolicValue::ConstantBool(_b) => Some(value.clone()),
         SymbolicValue::ConstantInt(_v) => Some(value.clone()),
         SymbolicValue::Variable(sym_name) => {
@@ -890,12 +1288,10 @@ pub fn evaluate_symbolic_value(
                 return None;
             }
 
-            Some(evaluate_binary_op(
-                &lhs_val.unwrap(),
-                &rhs_val.unwrap(),
-                &prime,
-                &op,
-            ))
+            match evaluate_binary_op(&lhs_val.unwrap(), &rhs_val.unwrap(), &prime, &op) {
+                SymbolicValue::Undefined => None,
+                value => Some(value),
+            }
         }
         SymbolicValue::AuxBinaryOp(lhs, op, rhs) => {
             let lhs_val = evaluate_symbolic_value(prime, lhs, assignment, symbolic_library);
@@ -904,12 +1300,11 @@ pub fn evaluate_symbolic_value(
                 return None;
             }
 
-            Some(evaluate_binary_op_integer_mode(
-                &lhs_val.unwrap(),
-                &rhs_val.unwrap(),
-                &prime,
-                &op,
-            ))
+            match evaluate_binary_op_integer_mode(&lhs_val.unwrap(), &rhs_val.unwrap(), &prime, &op)
+            {
+                SymbolicValue::Undefined => None,
+                value => Some(value),
+            }
         }
         SymbolicValue::UnaryOp(op, expr) => {
             let expr_val = evaluate_symbolic_value(prime, expr, assignment, symbolic_library);
@@ -961,6 +1356,9 @@ pub fn evaluate_symbolic_value(
                 ),
             }
         }
+        SymbolicValue::Call(id, args) if symbolic_library.template_library.contains_key(id) => {
+            evaluate_black_box_call(prime, *id, args, assignment, symbolic_library)
+        }
         SymbolicValue::Call(id, args) => {
             let setting = SymbolicExecutorSetting {
                 prime: prime.clone(),
@@ -971,6 +1369,17 @@ pub fn evaluate_symbolic_value(
                 substitute_output: false,
                 propagate_assignments: true,
                 constraint_assert_dissabled: false,
+                simplify_to_fixpoint: false,
+                max_component_depth: None,
+                max_loop_unroll: 0,
+                track_field_reductions: false,
+                deadline: None,
+                max_steps: None,
+                dedup_constraints: false,
+                whitelist_mode: WhitelistMode::Inline,
+                precise_primitives: false,
+                max_expression_nodes: None,
+                intern_names: false,
             };
             let mut subse = SymbolicExecutor::new(symbolic_library, &setting);
 
@@ -990,7 +1399,7 @@ pub fn evaluate_symbolic_value(
                     .cur_state
                     .set_rc_sym_val(sym_name, Rc::new(evaled_arg.unwrap()));
             }
-            subse.execute(&func.body.clone(), 0);
+            subse.execute(&func.body, 0);
             if subse.execution_failed {
                 None
             } else {
@@ -1015,6 +1424,64 @@ pub fn evaluate_symbolic_value(
     }
 }
 
+/// Evaluates a call to a template trusted as a black box with `WhitelistMode::TrustAsBlackBox`.
+///
+/// The arguments are the values of the template parameters, followed by one `AssignEq` per
+/// input of the component and by the output to compute. The template is executed concretely
+/// under the owner of that output.
+///
+/// # Returns
+/// The value of the output, or `None` if an argument cannot be evaluated or if the execution
+/// of the template fails.
+fn evaluate_black_box_call(
+    prime: &BigInt,
+    template_id: usize,
+    args: &[SymbolicValueRef],
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    symbolic_library: &mut SymbolicLibrary,
+) -> Option<SymbolicValue> {
+    let template = symbolic_library.template_library[&template_id].clone();
+    let (output, bindings) = args.split_last()?;
+    let output = match output.as_ref() {
+        SymbolicValue::Variable(name) => name.clone(),
+        _ => return None,
+    };
+
+    let mut values = Vec::new();
+    for (i, binding) in bindings.iter().enumerate() {
+        if i < template.template_parameter_names.len() {
+            let name = SymbolicName::new(
+                template.template_parameter_names[i],
+                output.owner.clone(),
+                None,
+            );
+            let value = evaluate_symbolic_value(prime, binding, assignment, symbolic_library)?;
+            values.push((name, value));
+        } else if let SymbolicValue::AssignEq(lhs, rhs) = binding.as_ref() {
+            if let SymbolicValue::Variable(name) = lhs.as_ref() {
+                let value = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library)?;
+                values.push((name.clone(), value));
+            }
+        }
+    }
+
+    let setting = get_default_setting_for_concrete_execution(prime.clone(), false);
+    let mut subse = SymbolicExecutor::new(symbolic_library, &setting);
+    subse.cur_state.owner_name = output.owner.clone();
+    subse.cur_state.set_template_id(template_id);
+    for (name, value) in values {
+        subse.cur_state.set_sym_val(name, value);
+    }
+    subse.execute(&template.body, 0);
+    if subse.execution_failed || subse.cur_state.is_failed {
+        return None;
+    }
+    match subse.cur_state.get_sym_val(&output).map(|v| v.as_ref()) {
+        Some(SymbolicValue::ConstantInt(value)) => Some(SymbolicValue::ConstantInt(value.clone())),
+        _ => None,
+    }
+}
+
 /// Evaluates the error of a symbolic value for a given assignment under modular arithmetic.
 ///
 /// This function computes the "error" of a symbolic value when evaluated with a specific assignment
@@ -1189,6 +1656,67 @@ pub fn max_error_of_constraints(
         .unwrap_or(prime.clone())
 }
 
+/// Accumulates the field distance of a set of symbolic constraints.
+///
+/// For an equality `lhs == rhs`, the distance is `min(d, prime - d)` with `d = (lhs - rhs) mod
+/// prime`, so that two values on both sides of zero in the field are considered close. The other
+/// constraints contribute their error as in `accumulate_error_of_constraints`.
+///
+/// # Parameters
+/// - `prime`: The prime modulus used for modular arithmetic.
+/// - `constraints`: A slice of symbolic value references representing the constraints.
+/// - `assignment`: A mapping of symbolic names to their concrete integer values.
+/// - `symbolic_library`: A mutable reference to the symbolic library providing variable lookup and
+///   other symbolic operations.
+///
+/// # Returns
+/// The total distance as a `BigInt`, which is zero if and only if every constraint is satisfied.
+pub fn accumulate_distance_of_constraints(
+    prime: &BigInt,
+    constraints: &[SymbolicValueRef],
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    symbolic_library: &mut SymbolicLibrary,
+) -> BigInt {
+    constraints
+        .iter()
+        .map(|constraint| {
+            evaluate_distance_of_symbolic_value(prime, constraint, assignment, symbolic_library)
+        })
+        .sum()
+}
+
+fn evaluate_distance_of_symbolic_value(
+    prime: &BigInt,
+    value: &SymbolicValue,
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    symbolic_library: &mut SymbolicLibrary,
+) -> BigInt {
+    let sides = match value {
+        SymbolicValue::Assign(lhs, rhs, _, _)
+        | SymbolicValue::AssignEq(lhs, rhs)
+        | SymbolicValue::AssignCall(lhs, rhs, _) => Some((lhs, rhs)),
+        SymbolicValue::BinaryOp(lhs, op, rhs) if matches!(op.0, ExpressionInfixOpcode::Eq) => {
+            Some((lhs, rhs))
+        }
+        _ => None,
+    };
+    if let Some((lhs, rhs)) = sides {
+        let lhs_val = evaluate_symbolic_value(prime, lhs, assignment, symbolic_library);
+        let rhs_val = evaluate_symbolic_value(prime, rhs, assignment, symbolic_library);
+        if let (Some(SymbolicValue::ConstantInt(lv)), Some(SymbolicValue::ConstantInt(rv))) =
+            (lhs_val, rhs_val)
+        {
+            let mut d = (lv - rv) % prime;
+            if d.is_negative() {
+                d += prime;
+            }
+            let complement = prime - &d;
+            return d.min(complement);
+        }
+    }
+    evaluate_error_of_symbolic_value(prime, value, assignment, symbolic_library).max(BigInt::zero())
+}
+
 /// Checks if two integers are equivalent modulo a given prime.
 ///
 /// This function determines whether two integers are congruent modulo the specified prime,
@@ -1237,9 +1765,11 @@ pub fn is_equal_mod(a: &BigInt, b: &BigInt, p: &BigInt) -> bool {
 ///   (`UnderConstrainedType::NonDeterministic`).
 ///
 /// # Verification Process
-/// 1. Evaluate the symbolic trace (`symbolic_trace`) and side constraints (`side_constraints`)
+/// 1. Return `WellConstrained` if the `assignment` violates an assumption of `setting`, since
+///    such an input is ruled out by the user.
+/// 2. Evaluate the symbolic trace (`symbolic_trace`) and side constraints (`side_constraints`)
 ///    against the provided `assignment`.
-/// 2. Determine the constraint status:
+/// 3. Determine the constraint status:
 ///     - If the symbolic trace is satisfied but the side constraints are not, return `OverConstrained`.
 ///     - If the side constraints are satisfied but the symbolic trace is not:
 ///         - Perform concrete execution with the given `assignment` to validate the symbolic
@@ -1259,6 +1789,10 @@ pub fn verify_assignment(
     assignment: &FxHashMap<SymbolicName, BigInt>,
     setting: &BaseVerificationConfig,
 ) -> VerificationResult {
+    if !setting.satisfies_assumptions(assignment, &mut sexe.symbolic_library) {
+        return VerificationResult::WellConstrained;
+    }
+
     let is_satisfy_st = evaluate_constraints(
         &setting.prime,
         symbolic_trace,
@@ -1271,7 +1805,40 @@ pub fn verify_assignment(
         assignment,
         &mut sexe.symbolic_library,
     );
+    classify_assignment(sexe, is_satisfy_st, is_satisfy_sc, assignment, setting)
+}
+
+/// Variant of `verify_assignment` that evaluates constraints compiled once for the whole search.
+///
+/// The result is the same as the one of `verify_assignment` over the constraints that
+/// `symbolic_trace` and `side_constraints` are compiled from.
+pub fn verify_compiled_assignment(
+    sexe: &mut SymbolicExecutor,
+    symbolic_trace: &CompiledConstraints,
+    side_constraints: &CompiledConstraints,
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    setting: &BaseVerificationConfig,
+) -> VerificationResult {
+    if !setting.satisfies_assumptions(assignment, &mut sexe.symbolic_library) {
+        return VerificationResult::WellConstrained;
+    }
+
+    let is_satisfy_st =
+        symbolic_trace.evaluate(&setting.prime, assignment, &mut sexe.symbolic_library);
+    let is_satisfy_sc =
+        side_constraints.evaluate(&setting.prime, assignment, &mut sexe.symbolic_library);
+    classify_assignment(sexe, is_satisfy_st, is_satisfy_sc, assignment, setting)
+}
 
+/// Determines the constraint status of `assignment` from whether it satisfies the symbolic
+/// trace and the side constraints, as described in `verify_assignment`.
+fn classify_assignment(
+    sexe: &mut SymbolicExecutor,
+    is_satisfy_st: bool,
+    is_satisfy_sc: bool,
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    setting: &BaseVerificationConfig,
+) -> VerificationResult {
     if is_satisfy_st && !is_satisfy_sc {
         VerificationResult::OverConstrained
     } else if !is_satisfy_st && is_satisfy_sc {
@@ -1284,7 +1851,8 @@ pub fn verify_assignment(
         sexe.feed_arguments(
             &setting.template_param_names,
             &setting.template_param_values,
-        );
+        )
+        .expect("Template arguments are validated when the main component is instantiated");
         sexe.concrete_execute(&setting.target_template_name, assignment);
 
         if sexe.cur_state.is_failed {
@@ -1292,6 +1860,7 @@ pub fn verify_assignment(
             return VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
                 vc.0,
                 vc.1.lookup_fmt(&sexe.symbolic_library.id2name),
+                vc.2,
             ));
         }
 
@@ -1347,3 +1916,116 @@ pub fn verify_assignment(
         VerificationResult::WellConstrained
     }
 }
+
+/// The result of running the circuit on a single input assignment.
+#[derive(Clone)]
+pub struct InputEvaluation {
+    /// The values of the output signals of the main template, sorted by name.
+    pub outputs: Vec<(SymbolicName, BigInt)>,
+    /// The position, the rendered condition, and the location of the constraint or assertion
+    /// that the input violates, if any.
+    pub violated_condition: Option<(usize, String, Option<SourceLocation>)>,
+}
+
+impl InputEvaluation {
+    pub fn is_satisfied(&self) -> bool {
+        self.violated_condition.is_none()
+    }
+}
+
+/// Runs the circuit concretely on `assignment` and reports its outputs and whether it fails.
+///
+/// # Parameters
+/// - `sexe`: A concrete executor, typically set up with `get_default_setting_for_concrete_execution`.
+/// - `assignment`: The values of the input signals of the main template.
+/// - `setting`: The verification configuration of the main template.
+///
+/// # Returns
+/// An `InputEvaluation` holding the outputs computed before the execution stopped and the
+/// violated condition, if any. Outputs that are not determined by `assignment` are left out.
+pub fn evaluate_input(
+    sexe: &mut SymbolicExecutor,
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    setting: &BaseVerificationConfig,
+) -> InputEvaluation {
+    sexe.clear();
+    sexe.violated_condition = None;
+    sexe.cur_state.add_owner(&OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    });
+    sexe.feed_arguments(
+        &setting.template_param_names,
+        &setting.template_param_values,
+    )
+    .expect("Template arguments are validated when the main component is instantiated");
+    sexe.concrete_execute(&setting.target_template_name, assignment);
+
+    let violated_condition = if sexe.cur_state.is_failed {
+        sexe.violated_condition
+            .clone()
+            .map(|vc| (vc.0, vc.1.lookup_fmt(&sexe.symbolic_library.id2name), vc.2))
+    } else {
+        None
+    };
+
+    let output_ids = sexe.symbolic_library.template_library
+        [&sexe.symbolic_library.name2id[&setting.target_template_name]]
+        .output_ids
+        .clone();
+    let bindings: Vec<(SymbolicName, SymbolicValueRef)> = sexe
+        .cur_state
+        .symbol_binding_map
+        .iter()
+        .filter(|(k, _)| k.owner.len() == 1 && output_ids.contains(&k.id))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect();
+    let mut outputs = Vec::new();
+    for (name, value) in bindings {
+        let mut memo = FxHashSet::default();
+        match sexe.simplify_variables(&value, std::usize::MAX, false, false, &mut memo) {
+            SymbolicValue::ConstantInt(num) => outputs.push((name, num)),
+            SymbolicValue::ConstantBool(b) => {
+                outputs.push((name, if b { BigInt::one() } else { BigInt::zero() }))
+            }
+            _ => {}
+        }
+    }
+    outputs.sort_by(|a, b| a.0.cmp(&b.0));
+
+    InputEvaluation {
+        outputs,
+        violated_condition,
+    }
+}
+
+/// Replays the circuit on `assignment` and returns the reductions modulo the prime that occur
+/// along the way.
+///
+/// # Parameters
+/// - `sexe`: A concrete executor whose setting enables `track_field_reductions`.
+/// - `assignment`: The assignment to replay, typically the one of a counterexample.
+/// - `setting`: The verification configuration of the main template.
+///
+/// # Returns
+/// The recorded `FieldReductionEvent`s, in execution order.
+pub fn replay_field_reductions(
+    sexe: &mut SymbolicExecutor,
+    assignment: &FxHashMap<SymbolicName, BigInt>,
+    setting: &BaseVerificationConfig,
+) -> Vec<FieldReductionEvent> {
+    sexe.clear();
+    sexe.cur_state.add_owner(&OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    });
+    sexe.feed_arguments(
+        &setting.template_param_names,
+        &setting.template_param_values,
+    )
+    .expect("Template arguments are validated when the main component is instantiated");
+    sexe.concrete_execute(&setting.target_template_name, assignment);
+    std::mem::take(&mut sexe.cur_state.field_reductions)
+}
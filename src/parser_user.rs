@@ -1,21 +1,48 @@
+use std::collections::hash_map::DefaultHasher;
+use std::env;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
 
+use num_bigint_dig::BigInt;
+use regex::Regex;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use program_structure::ast::{Expression, Statement};
 use program_structure::constants::UsefulConstants;
 use program_structure::error_definition::Report;
 use program_structure::program_archive::ProgramArchive;
 
 use super::input_user::Input;
+use crate::error::ZkFuzzError;
+use crate::type_analysis_user::analyse_project;
 use crate::VERSION;
 
 pub fn parse_project(input_info: &Input) -> Result<ProgramArchive, ()> {
     let initial_file = input_info.input_file().to_string();
     //We get the prime number from the input
     let prime = UsefulConstants::new(&input_info.prime()).get_p().clone();
-    let result_program_archive = parser::run_parser(
-        initial_file,
-        VERSION,
-        input_info.get_link_libraries().to_vec(),
-        &prime,
-    );
+    let mut link_libraries = input_info.get_link_libraries().to_vec();
+    if input_info.synthesize_main() != "none" {
+        let harness = synthesize_main_harness(&initial_file, &input_info.synthesize_main())?;
+        if let Some(parent) = Path::new(&initial_file).parent() {
+            link_libraries.push(parent.to_path_buf());
+        }
+        return parse_file(
+            harness.to_string_lossy().to_string(),
+            link_libraries,
+            &prime,
+        );
+    }
+    parse_file(initial_file, link_libraries, &prime)
+}
+
+pub fn parse_file(
+    initial_file: String,
+    link_libraries: Vec<PathBuf>,
+    prime: &BigInt,
+) -> Result<ProgramArchive, ()> {
+    let result_program_archive = parser::run_parser(initial_file, VERSION, link_libraries, prime);
     match result_program_archive {
         Result::Err((file_library, report_collection)) => {
             Report::print_reports(&report_collection, &file_library);
@@ -27,3 +54,322 @@ pub fn parse_project(input_info: &Input) -> Result<ProgramArchive, ()> {
         }
     }
 }
+
+/// Returns the field prime declared in `initial_file` with a `// pragma prime <prime>;` comment.
+///
+/// Circom has no pragma selecting the field, since the prime is a compiler option, so the
+/// directive is written as a line comment to keep the file compilable. The prime is given as
+/// with `--debug_prime`, i.e., in decimal, in hexadecimal with a `0x` prefix, or by name.
+///
+/// # Returns
+/// The declared prime as written, or `None` if `initial_file` cannot be read or declares no prime.
+pub fn parse_declared_prime(initial_file: &str) -> Option<String> {
+    let content = fs::read_to_string(initial_file).ok()?;
+    content.lines().find_map(|line| {
+        let directive = line.trim().strip_prefix("//")?.trim_start();
+        let prime = directive
+            .strip_prefix("pragma")?
+            .trim_start()
+            .strip_prefix("prime")?;
+        if !prime.starts_with(char::is_whitespace) {
+            return None;
+        }
+        Some(prime.trim().trim_end_matches(';').trim_end().to_string())
+    })
+}
+
+/// Writes a harness file that includes `initial_file` and instantiates a template as `main`.
+///
+/// `template_spec` is either a bare template name such as `IsZero`, or a template call with
+/// explicit parameters such as `Num2Bits(8)`. For a bare name, every template parameter is set
+/// to `1`. The inputs of the synthesized main are left symbolic, as for any main component.
+///
+/// # Returns
+/// The path of the harness, written to the temporary directory, or `Err(())` if `initial_file`
+/// cannot be read or does not declare the template.
+pub fn synthesize_main_harness(initial_file: &str, template_spec: &str) -> Result<PathBuf, ()> {
+    let content = fs::read_to_string(initial_file).map_err(|e| {
+        eprintln!("Cannot read {}: {}", initial_file, e);
+    })?;
+
+    let template_spec = template_spec.trim();
+    let (name, args) = match template_spec.find('(') {
+        Some(start) => (
+            template_spec[..start].trim().to_string(),
+            template_spec[start + 1..]
+                .trim_end()
+                .strip_suffix(')')
+                .ok_or_else(|| {
+                    eprintln!("Malformed template call: {}", template_spec);
+                })?
+                .to_string(),
+        ),
+        None => {
+            let num_params = count_template_params(&content, template_spec).ok_or_else(|| {
+                eprintln!(
+                    "Template {} is not declared in {}",
+                    template_spec, initial_file
+                );
+            })?;
+            (template_spec.to_string(), vec!["1"; num_params].join(", "))
+        }
+    };
+
+    let pragma = content
+        .lines()
+        .map(|line| line.trim())
+        .find(|line| line.starts_with("pragma circom"))
+        .unwrap_or("pragma circom 2.0.0;")
+        .to_string();
+    let included = fs::canonicalize(initial_file).unwrap_or_else(|_| PathBuf::from(initial_file));
+
+    let mut hasher = DefaultHasher::new();
+    included.hash(&mut hasher);
+    template_spec.hash(&mut hasher);
+    let harness =
+        env::temp_dir().join(format!("zkfuzz_main_{}_{:x}.circom", name, hasher.finish()));
+    fs::write(
+        &harness,
+        format!(
+            "{}\ninclude \"{}\";\n\ncomponent main = {}({});\n",
+            pragma,
+            included.display(),
+            name,
+            args
+        ),
+    )
+    .map_err(|e| {
+        eprintln!("Cannot write {}: {}", harness.display(), e);
+    })?;
+    Ok(harness)
+}
+
+/// Returns the source of `initial_file` without its main component, so that a harness declaring
+/// its own main component can include it.
+///
+/// Relative includes that resolve from the directory of `initial_file` are rewritten to absolute
+/// paths, so that the returned source can be written to another directory.
+pub fn strip_main_component(initial_file: &str) -> std::io::Result<String> {
+    let content = fs::read_to_string(initial_file)?;
+    let current = fs::canonicalize(initial_file).unwrap_or_else(|_| PathBuf::from(initial_file));
+
+    let main_component = Regex::new(r"(?m)^[ \t]*component[ \t]+main\b[^;]*;").unwrap();
+    let include = Regex::new(r#"(?m)^([ \t]*include[ \t]+)"([^"]+)""#).unwrap();
+    let content = main_component.replace_all(&content, "");
+    let content = include.replace_all(&content, |caps: &regex::Captures| {
+        match resolve_include(&current, &caps[2], &[]) {
+            Some(resolved) if Path::new(&caps[2]).is_relative() => format!(
+                "{}\"{}\"",
+                &caps[1],
+                fs::canonicalize(&resolved).unwrap_or(resolved).display()
+            ),
+            _ => caps[0].to_string(),
+        }
+    });
+    Ok(content.into_owned())
+}
+
+fn count_template_params(content: &str, name: &str) -> Option<usize> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with("template"))
+        .find_map(|line| {
+            let start = line.find('(')?;
+            let end = line[start..].find(')')? + start;
+            if line[..start].split_whitespace().last()? != name {
+                return None;
+            }
+            Some(
+                line[start + 1..end]
+                    .split(',')
+                    .filter(|p| !p.trim().is_empty())
+                    .count(),
+            )
+        })
+}
+
+/// Parses the circom expressions given with `--assume` with the parser of circom.
+///
+/// Each expression is parsed as the value returned by a function whose parameters are
+/// `input_names`, in a file written to the temporary directory, so that it may refer to the
+/// inputs of the main component, including elements of input arrays such as `in[0]`.
+///
+/// # Returns
+/// The parsed expressions, in the order of `assumptions`.
+pub fn parse_assumptions(
+    assumptions: &[String],
+    input_names: &[String],
+    prime: &BigInt,
+) -> Result<Vec<Expression>, ZkFuzzError> {
+    assumptions
+        .iter()
+        .map(|assumption| {
+            let invalid =
+                |reason: String| ZkFuzzError::InvalidAssumption(assumption.clone(), reason);
+
+            let mut hasher = DefaultHasher::new();
+            assumption.hash(&mut hasher);
+            input_names.hash(&mut hasher);
+            let path = env::temp_dir().join(format!("zkfuzz_assume_{:x}.circom", hasher.finish()));
+            let source = [
+                "pragma circom 2.0.0;".to_string(),
+                format!("function zkfuzz_assumption({}) {{", input_names.join(", ")),
+                format!("    return {};", assumption),
+                "}".to_string(),
+                "template ZkFuzzAssumption() {}".to_string(),
+                "component main = ZkFuzzAssumption();".to_string(),
+            ]
+            .join("\n");
+            fs::write(&path, source).map_err(|e| invalid(e.to_string()))?;
+
+            let result = parser::run_parser(
+                path.to_string_lossy().to_string(),
+                VERSION,
+                Vec::new(),
+                prime,
+            );
+            let _ = fs::remove_file(&path);
+            let program_archive = result
+                .map_err(|(_, reports)| {
+                    invalid(
+                        reports
+                            .iter()
+                            .map(|report| report.get_message().clone())
+                            .collect::<Vec<_>>()
+                            .join("; "),
+                    )
+                })?
+                .0;
+
+            match program_archive.functions["zkfuzz_assumption"].get_body() {
+                Statement::Block { stmts, .. } => stmts.iter().find_map(|stmt| match stmt {
+                    Statement::Return { value, .. } => Some(value.clone()),
+                    _ => None,
+                }),
+                _ => None,
+            }
+            .ok_or_else(|| invalid("it is not a single expression".to_string()))
+        })
+        .collect()
+}
+
+/// In-memory cache of parsed and type-checked `ProgramArchive`s.
+///
+/// Entries are keyed by a hash of the prime and of the contents of the initial file together
+/// with every file reachable from it through `include` directives. Editing any of those files
+/// changes the key, so a stale archive is never returned.
+///
+/// # Example
+/// ```no_run
+/// use num_bigint_dig::BigInt;
+/// use zkfuzz::parser_user::ProgramArchiveCache;
+///
+/// let mut cache = ProgramArchiveCache::new();
+/// let prime = BigInt::from(7);
+/// let _ = cache.parse_and_analyse("circuit.circom", &[], &prime);
+/// let _ = cache.parse_and_analyse("circuit.circom", &[], &prime);
+/// assert_eq!(cache.hits(), 1);
+/// ```
+#[derive(Default)]
+pub struct ProgramArchiveCache {
+    entries: FxHashMap<u64, ProgramArchive>,
+    hits: usize,
+    misses: usize,
+}
+
+impl ProgramArchiveCache {
+    pub fn new() -> Self {
+        ProgramArchiveCache::default()
+    }
+
+    /// Returns the analysed archive of `initial_file`, parsing and type-checking it only when
+    /// no entry exists for the current contents of the file and its includes.
+    pub fn parse_and_analyse(
+        &mut self,
+        initial_file: &str,
+        link_libraries: &[PathBuf],
+        prime: &BigInt,
+    ) -> Result<ProgramArchive, ()> {
+        let key = hash_sources(initial_file, link_libraries, prime);
+        if let Some(program_archive) = self.entries.get(&key) {
+            self.hits += 1;
+            return Ok(program_archive.clone());
+        }
+
+        self.misses += 1;
+        let mut program_archive =
+            parse_file(initial_file.to_string(), link_libraries.to_vec(), prime)?;
+        analyse_project(&mut program_archive)?;
+        self.entries.insert(key, program_archive.clone());
+        Ok(program_archive)
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+fn hash_sources(initial_file: &str, link_libraries: &[PathBuf], prime: &BigInt) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    prime.to_string().hash(&mut hasher);
+
+    let mut visited = FxHashSet::default();
+    let mut stack = vec![PathBuf::from(initial_file)];
+    while let Some(path) = stack.pop() {
+        let canonical = fs::canonicalize(&path).unwrap_or(path.clone());
+        if !visited.insert(canonical.clone()) {
+            continue;
+        }
+        canonical.hash(&mut hasher);
+        if let Ok(content) = fs::read_to_string(&canonical) {
+            content.hash(&mut hasher);
+            for include in extract_includes(&content) {
+                if let Some(resolved) = resolve_include(&canonical, &include, link_libraries) {
+                    stack.push(resolved);
+                }
+            }
+        }
+    }
+    hasher.finish()
+}
+
+fn extract_includes(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with("include"))
+        .filter_map(|line| {
+            let start = line.find('"')?;
+            let end = line[start + 1..].find('"')?;
+            Some(line[start + 1..start + 1 + end].to_string())
+        })
+        .collect()
+}
+
+fn resolve_include(current: &Path, include: &str, link_libraries: &[PathBuf]) -> Option<PathBuf> {
+    let base = current
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+    std::iter::once(base)
+        .chain(link_libraries.iter().cloned())
+        .map(|dir| dir.join(include))
+        .find(|candidate| candidate.is_file())
+}
@@ -0,0 +1,77 @@
+use rustc_hash::FxHashMap;
+
+use crate::executor::symbolic_value::SymbolicValueRef;
+use crate::stats::trace_dump::render_constraints;
+
+/// Constraints present in only one of two circuits.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ConstraintDiff {
+    /// Constraints of the new circuit that the old circuit lacks.
+    pub added: Vec<String>,
+    /// Constraints of the old circuit that the new circuit lacks.
+    pub removed: Vec<String>,
+}
+
+impl ConstraintDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Renders constraints into a canonical form that can be compared across circuits.
+///
+/// Variables are rendered by name with `lookup_fmt`, which leaves out both the numeric ids of
+/// the symbolic library and the counters of the owners. Two circuits thus agree on a constraint
+/// even when their templates are registered in a different order or their components are
+/// instantiated a different number of times. The rendered constraints are sorted.
+///
+/// # Parameters
+/// - `constraints`: The constraints to normalize, typically the side constraints.
+/// - `id2name`: The lookup table used to name the variables.
+///
+/// # Returns
+/// The rendered constraints in lexicographic order.
+pub fn normalize_constraints(
+    constraints: &[SymbolicValueRef],
+    id2name: &FxHashMap<usize, String>,
+) -> Vec<String> {
+    let mut normalized = render_constraints(constraints, id2name);
+    normalized.sort();
+    normalized
+}
+
+/// Computes the constraints present in one of two normalized constraint lists but not the other.
+///
+/// The lists are compared as multisets, so a constraint emitted twice by `old` and once by `new`
+/// is reported as removed once.
+///
+/// # Parameters
+/// - `old`: The normalized constraints of the reference circuit.
+/// - `new`: The normalized constraints of the circuit compared against it.
+///
+/// # Returns
+/// A `ConstraintDiff` whose `added` and `removed` lists are sorted.
+pub fn diff_constraints(old: &[String], new: &[String]) -> ConstraintDiff {
+    let mut counts: FxHashMap<&String, isize> = FxHashMap::default();
+    for c in old {
+        *counts.entry(c).or_insert(0) -= 1;
+    }
+    for c in new {
+        *counts.entry(c).or_insert(0) += 1;
+    }
+
+    let mut diff = ConstraintDiff::default();
+    for (c, count) in counts {
+        let target = if count > 0 {
+            &mut diff.added
+        } else {
+            &mut diff.removed
+        };
+        for _ in 0..count.abs() {
+            target.push(c.clone());
+        }
+    }
+    diff.added.sort();
+    diff.removed.sort();
+    diff
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeSet;
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::executor::symbolic_value::{
+    extract_variables, extract_variables_from_symbolic_value, SymbolicLibrary, SymbolicName,
+    SymbolicValueRef,
+};
+
+const INPUT_COLOR: &str = "lightblue";
+const OUTPUT_COLOR: &str = "lightpink";
+
+/// Renders the constraint graph of a circuit in the Graphviz DOT format.
+///
+/// Each signal appearing in the constraints is a node labeled with its name, and two signals are
+/// connected by an undirected edge when they appear together in at least one constraint. The
+/// input and output signals of the main template are filled with different colors.
+///
+/// # Parameters
+/// - `constraints`: The constraints to visualize, typically the side constraints.
+/// - `symbolic_library`: The symbolic library used to name the signals.
+/// - `template_name`: The name of the main template.
+///
+/// # Returns
+/// The DOT source of the graph. Nodes and edges are sorted, so the output is deterministic.
+pub fn constraint_graph_to_dot(
+    constraints: &[SymbolicValueRef],
+    symbolic_library: &SymbolicLibrary,
+    template_name: &str,
+) -> String {
+    let template = &symbolic_library.template_library[&symbolic_library.name2id[template_name]];
+
+    let mut nodes = extract_variables(constraints);
+    nodes.sort();
+    let node_ids: FxHashMap<&SymbolicName, usize> =
+        nodes.iter().enumerate().map(|(i, n)| (n, i)).collect();
+
+    let mut edges = BTreeSet::new();
+    for constraint in constraints {
+        let mut variables = FxHashSet::default();
+        extract_variables_from_symbolic_value(constraint, &mut variables);
+        let mut ids: Vec<usize> = variables.iter().map(|v| node_ids[v]).collect();
+        ids.sort();
+        for (i, lhs) in ids.iter().enumerate() {
+            for rhs in &ids[i + 1..] {
+                edges.insert((*lhs, *rhs));
+            }
+        }
+    }
+
+    let mut dot = "graph constraints {\n".to_string();
+    for (i, node) in nodes.iter().enumerate() {
+        let label = node
+            .lookup_fmt(&symbolic_library.id2name)
+            .replace('"', "\\\"");
+        let fill = if node.owner.len() != 1 {
+            String::new()
+        } else if template.input_ids.contains(&node.id) {
+            format!(", style=filled, fillcolor={}", INPUT_COLOR)
+        } else if template.output_ids.contains(&node.id) {
+            format!(", style=filled, fillcolor={}", OUTPUT_COLOR)
+        } else {
+            String::new()
+        };
+        dot += &format!("    n{} [label=\"{}\"{}];\n", i, label, fill);
+    }
+    for (lhs, rhs) in edges {
+        dot += &format!("    n{} -- n{};\n", lhs, rhs);
+    }
+    dot += "}\n";
+    dot
+}
@@ -0,0 +1,62 @@
+use serde_json::{json, Value};
+
+use crate::executor::symbolic_execution::SymbolicExecutor;
+
+/// Metrics about the symbolic execution of a circuit, reported alongside the constraint
+/// statistics to gauge its complexity.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExecutionMetrics {
+    /// The number of executed components, counting each element of a component array.
+    pub components_instantiated: usize,
+    /// The length of the longest chain of nested components below the main component.
+    pub max_component_nesting_depth: usize,
+    /// The number of calls of functions, including recursive and nested ones.
+    pub functions_called: usize,
+    /// The number of templates registered in the symbolic library.
+    pub templates_registered: usize,
+}
+
+impl ExecutionMetrics {
+    /// Gathers the metrics of the execution performed by `sexe`.
+    ///
+    /// The components are those recorded in `component_templates`, which requires
+    /// `keep_track_constraints`, and the function calls are those counted since the executor was
+    /// last cleared, so the metrics must be gathered before any concrete execution sharing the
+    /// symbolic library.
+    pub fn from_executor(sexe: &SymbolicExecutor) -> Self {
+        ExecutionMetrics {
+            components_instantiated: sexe.component_templates.len(),
+            max_component_nesting_depth: sexe
+                .component_templates
+                .keys()
+                .map(|owner| owner.len() - 1)
+                .max()
+                .unwrap_or(0),
+            functions_called: sexe.symbolic_library.function_counter.values().sum(),
+            templates_registered: sexe.symbolic_library.template_library.len(),
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        json!({
+            "components_instantiated": self.components_instantiated,
+            "max_component_nesting_depth": self.max_component_nesting_depth,
+            "functions_called": self.functions_called,
+            "templates_registered": self.templates_registered,
+        })
+    }
+}
+
+pub fn print_execution_metrics_pretty(metrics: &ExecutionMetrics) {
+    for (name, value) in [
+        ("Components Instantiated", metrics.components_instantiated),
+        (
+            "Max Component Nesting Depth",
+            metrics.max_component_nesting_depth,
+        ),
+        ("Functions Called", metrics.functions_called),
+        ("Templates Registered", metrics.templates_registered),
+    ] {
+        println!(" • {:<27}: {}", name, value);
+    }
+}
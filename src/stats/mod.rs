@@ -1,2 +1,9 @@
 pub mod ast_stats;
+pub mod constraint_diff;
+pub mod constraint_graph;
+pub mod execution_metrics;
+pub mod sarif;
+pub mod signals;
 pub mod symbolic_stats;
+pub mod template_stats;
+pub mod trace_dump;
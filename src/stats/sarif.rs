@@ -0,0 +1,239 @@
+use rustc_hash::FxHashMap;
+use serde_json::{json, Value};
+
+use crate::executor::symbolic_state::PotentialDivByZero;
+use crate::executor::symbolic_value::{SourceLocation, SymbolicLibrary, SymbolicName};
+use crate::mutator::utils::{CounterExample, UnderConstrainedType, VerificationResult};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// The rules of the findings, with their default level and their short description.
+const RULES: [(&str, &str, &str); 6] = [
+    (
+        "under-constrained",
+        "error",
+        "The circuit accepts a witness that the witness generator does not compute.",
+    ),
+    (
+        "over-constrained",
+        "error",
+        "The circuit rejects a witness computed by the witness generator.",
+    ),
+    (
+        "unused-output",
+        "error",
+        "An output of the main component appears in no constraint.",
+    ),
+    (
+        "dangling-intermediate",
+        "warning",
+        "An intermediate signal is assigned but appears in no constraint.",
+    ),
+    (
+        "potential-div-by-zero",
+        "warning",
+        "A division by a non-constant divisor that may be zero.",
+    ),
+    ("finding", "note", "A finding of zkFuzz."),
+];
+
+/// A finding of zkFuzz, rendered as a result of a SARIF log by `to_sarif_log`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SarifFinding {
+    pub rule_id: &'static str,
+    pub message: String,
+    pub location: Option<SourceLocation>,
+    /// Additional properties of the result, such as the assignment of a counterexample.
+    pub properties: Value,
+}
+
+impl SarifFinding {
+    /// Describes a counterexample, located at the violated condition or at the declaration of the
+    /// offending output of the main template `template_name`.
+    pub fn from_counter_example(
+        counter_example: &CounterExample,
+        symbolic_library: &SymbolicLibrary,
+        template_name: &str,
+    ) -> Self {
+        let lookup = &symbolic_library.id2name;
+        let locate_output = |name: &SymbolicName| {
+            symbolic_library.locate_declaration(symbolic_library.name2id[template_name], name.id)
+        };
+
+        let assignment: serde_json::Map<String, Value> = counter_example
+            .assignment
+            .iter()
+            .map(|(name, value)| (name.lookup_fmt(lookup), json!(value.to_string())))
+            .collect();
+        let properties = json!({ "assignment": assignment });
+
+        let (rule_id, message, location) = match &counter_example.flag {
+            VerificationResult::UnderConstrained(UnderConstrainedType::UnusedOutput) => {
+                let output = counter_example.under_constrained_signal();
+                (
+                    "unused-output",
+                    format!(
+                        "The output `{}` appears in no constraint",
+                        output
+                            .as_ref()
+                            .map(|name| name.lookup_fmt(lookup))
+                            .unwrap_or_default()
+                    ),
+                    output.as_ref().and_then(|name| locate_output(name)),
+                )
+            }
+            VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+                _,
+                violated_condition,
+                location,
+            )) => (
+                "under-constrained",
+                format!(
+                    "The constraints accept an input violating `{}`",
+                    violated_condition
+                ),
+                location.clone(),
+            ),
+            VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                sym_name,
+                name,
+                value,
+            )) => (
+                "under-constrained",
+                format!(
+                    "The constraints accept a value of `{}` other than the computed `{}`",
+                    name, value
+                ),
+                locate_output(sym_name),
+            ),
+            VerificationResult::OverConstrained => (
+                "over-constrained",
+                "The constraints reject the witness computed for an input".to_string(),
+                None,
+            ),
+            VerificationResult::WellConstrained => (
+                "finding",
+                "The circuit is well-constrained".to_string(),
+                None,
+            ),
+        };
+
+        SarifFinding {
+            rule_id,
+            message,
+            location,
+            properties,
+        }
+    }
+
+    /// Describes a dangling intermediate signal of the main template `template_name`, located at
+    /// its declaration.
+    pub fn from_dangling_intermediate(
+        name: &SymbolicName,
+        symbolic_library: &SymbolicLibrary,
+        template_name: &str,
+    ) -> Self {
+        SarifFinding {
+            rule_id: "dangling-intermediate",
+            message: format!(
+                "`{}` is assigned but appears in no constraint",
+                name.lookup_fmt(&symbolic_library.id2name)
+            ),
+            location: symbolic_library
+                .locate_declaration(symbolic_library.name2id[template_name], name.id),
+            properties: json!({}),
+        }
+    }
+
+    /// Describes a potential division by zero, located at the statement containing it.
+    pub fn from_potential_div_by_zero(
+        potential_div_by_zero: &PotentialDivByZero,
+        symbolic_library: &SymbolicLibrary,
+    ) -> Self {
+        SarifFinding {
+            rule_id: "potential-div-by-zero",
+            message: format!(
+                "The divisor `{}` may be zero",
+                potential_div_by_zero
+                    .divisor
+                    .lookup_fmt(&symbolic_library.id2name)
+            ),
+            location: potential_div_by_zero.location.clone(),
+            properties: json!({}),
+        }
+    }
+}
+
+/// Renders findings as a SARIF 2.1.0 log with a single run, for code-scanning platforms.
+///
+/// # Parameters
+/// - `findings`: The findings to report, one result each.
+/// - `artifact_path`: The path of the analyzed circuit, used as the location of the findings
+///   that cannot be located more precisely.
+/// - `run_properties`: The metadata of the run, such as the git hash of zkFuzz.
+///
+/// # Returns
+/// The SARIF log, whose tool is zkFuzz with the version of the crate and the rules of all the
+/// kinds of findings.
+pub fn to_sarif_log(
+    findings: &[SarifFinding],
+    artifact_path: &str,
+    run_properties: &FxHashMap<String, String>,
+) -> Value {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|(id, level, description)| {
+            json!({
+                "id": id,
+                "shortDescription": {"text": description},
+                "defaultConfiguration": {"level": level},
+            })
+        })
+        .collect();
+
+    let results: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            let rule_index = RULES
+                .iter()
+                .position(|(id, _, _)| *id == finding.rule_id)
+                .unwrap_or(RULES.len() - 1);
+            let physical_location = match &finding.location {
+                Some(location) => json!({
+                    "artifactLocation": {"uri": location.path},
+                    "region": {
+                        "startLine": location.line,
+                        "startColumn": location.column,
+                    },
+                }),
+                None => json!({"artifactLocation": {"uri": artifact_path}}),
+            };
+            json!({
+                "ruleId": RULES[rule_index].0,
+                "ruleIndex": rule_index,
+                "level": RULES[rule_index].1,
+                "message": {"text": finding.message},
+                "locations": [{"physicalLocation": physical_location}],
+                "properties": finding.properties,
+            })
+        })
+        .collect();
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "zkFuzz",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                },
+            },
+            "properties": run_properties,
+            "results": results,
+        }],
+    })
+}
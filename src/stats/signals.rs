@@ -0,0 +1,98 @@
+use std::fmt;
+
+use program_structure::ast::{SignalType, VariableType};
+use serde_json::{json, Value};
+
+use crate::executor::symbolic_execution::SymbolicExecutor;
+
+/// The kind of a signal, in the order in which `describe_signals` lists them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SignalKind {
+    Input,
+    Output,
+    Intermediate,
+}
+
+impl SignalKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignalKind::Input => "input",
+            SignalKind::Output => "output",
+            SignalKind::Intermediate => "intermediate",
+        }
+    }
+}
+
+/// A signal of a template, with its resolved dimensions.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SignalDescription {
+    pub name: String,
+    pub kind: SignalKind,
+    /// The length of each dimension of the signal, empty for a scalar signal.
+    pub dimensions: Vec<usize>,
+    /// Whether the signal is an input declared public by the main component.
+    pub is_public: bool,
+}
+
+impl SignalDescription {
+    pub fn to_json(&self) -> Value {
+        json!({
+            "name": self.name,
+            "type": self.kind.as_str(),
+            "dimensions": self.dimensions,
+            "public": self.is_public,
+        })
+    }
+}
+
+impl fmt::Display for SignalDescription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.is_public {
+            format!("{} (public)", self.kind.as_str())
+        } else {
+            self.kind.as_str().to_string()
+        };
+        write!(f, "{:<21} {}", kind, self.name)?;
+        for dimension in &self.dimensions {
+            write!(f, "[{}]", dimension)?;
+        }
+        Ok(())
+    }
+}
+
+/// Lists the signals of a template with their types and dimensions.
+///
+/// The dimensions are those resolved by the symbolic execution, so `sexe` must have executed at
+/// least the initialization blocks of the template, e.g., with `only_initialization_blocks`, after
+/// its template parameters were fed.
+///
+/// # Parameters
+/// - `sexe`: The symbolic executor of the template.
+/// - `template_name`: The name of the template.
+///
+/// # Returns
+/// The inputs, the outputs, and the intermediate signals of the template, each sorted by name.
+pub fn describe_signals(sexe: &SymbolicExecutor, template_name: &str) -> Vec<SignalDescription> {
+    let template =
+        &sexe.symbolic_library.template_library[&sexe.symbolic_library.name2id[template_name]];
+    let mut signals: Vec<SignalDescription> = template
+        .id2type
+        .iter()
+        .filter_map(|(id, xtype)| {
+            let kind = match xtype {
+                VariableType::Signal(SignalType::Input, _) => SignalKind::Input,
+                VariableType::Signal(SignalType::Output, _) => SignalKind::Output,
+                VariableType::Signal(SignalType::Intermediate, _) => SignalKind::Intermediate,
+                _ => return None,
+            };
+            Some(SignalDescription {
+                name: sexe.symbolic_library.id2name[id].clone(),
+                kind,
+                dimensions: sexe.id2dimensions.get(id).cloned().unwrap_or_default(),
+                is_public: kind == SignalKind::Input && sexe.public_input_ids.contains(id),
+            })
+        })
+        .collect();
+    signals.sort_by(|a, b| (a.kind, &a.name).cmp(&(b.kind, &b.name)));
+    signals
+}
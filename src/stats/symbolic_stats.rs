@@ -1,10 +1,77 @@
 use std::collections::{HashMap, HashSet};
 
+use colored::Colorize;
+use serde_json::{json, Map, Value};
+
 use crate::executor::symbolic_value::{SymbolicName, SymbolicValue};
 
-const RESET: &str = "\x1b[0m";
-const WHITE: &str = "\x1b[37m";
-const BBLACK: &str = "\x1b[90m";
+/// Columns of the CSV row printed by `print_constraint_summary_statistics_csv`, which are also
+/// the keys of `ConstraintStatistics::to_json`.
+pub const CONSTRAINT_STATISTICS_CSV_HEADERS: [&str; 35] = [
+    "Total_Constraints",
+    "Constant_Counts",
+    "Conditional_Counts",
+    "Array_Counts",
+    "Avg_Depth",
+    "Max_Depth",
+    "Count_Assign",
+    "Count_AssignEq",
+    "Count_AssignCall",
+    "Count_QuadZeroDiv",
+    "Count_Mul",
+    "Count_Div",
+    "Count_Add",
+    "Count_Sub",
+    "Count_Pow",
+    "Count_IntDiv",
+    "Count_Mod",
+    "Count_ShiftL",
+    "Count_ShiftR",
+    "Count_LesserEq",
+    "Count_GreaterEq",
+    "Count_Lesser",
+    "Count_Greater",
+    "Count_Eq",
+    "Count_NotEq",
+    "Count_BoolOr",
+    "Count_BoolAnd",
+    "Count_BitOr",
+    "Count_BitAnd",
+    "Count_BitXor",
+    "Number_of_Variable",
+    "Variable_Avg_Count",
+    "Variable_Max_Count",
+    "Function_Avg_Count",
+    "Function_Max_Count",
+];
+
+/// Operators counted in the CSV row, in the order of the `Count_*` columns.
+const CSV_OPERATORS: [&str; 24] = [
+    "Assign",
+    "AssignEq",
+    "AssignCall",
+    "QuadZeroDiv",
+    "Mul",
+    "Div",
+    "Add",
+    "Sub",
+    "Pow",
+    "IntDiv",
+    "Mod",
+    "ShL",
+    "ShR",
+    "LEq",
+    "GEq",
+    "Lt",
+    "Gt",
+    "Eq",
+    "NEq",
+    "BoolOr",
+    "BoolAnd",
+    "BitOr",
+    "BitAnd",
+    "BitXor",
+];
 
 /// Collects statistics about constraints encountered during symbolic execution.
 #[derive(Default)]
@@ -34,7 +101,7 @@ impl ConstraintStatistics {
     /// * `depth` - The depth level of this value in its expression tree.
     fn update_from_symbolic_value(&mut self, value: &SymbolicValue, depth: usize) {
         match value {
-            SymbolicValue::NOP => {}
+            SymbolicValue::NOP | SymbolicValue::Undefined => {}
             SymbolicValue::ConstantInt(_) => {
                 self.constant_counts += 1;
             }
@@ -135,6 +202,87 @@ impl ConstraintStatistics {
             self.update_from_symbolic_value(constraint, 0);
         }
     }
+
+    fn avg_depth(&self) -> f64 {
+        if !self.constraint_depths.is_empty() {
+            self.constraint_depths.iter().sum::<usize>() as f64
+                / self.constraint_depths.len() as f64
+        } else {
+            0.0
+        }
+    }
+
+    fn max_depth(&self) -> usize {
+        *self.constraint_depths.iter().max().unwrap_or(&0)
+    }
+
+    fn operator_count(&self, op: &str) -> usize {
+        *self.operator_counts.get(op).unwrap_or(&0)
+    }
+
+    /// Returns the number of variables and the average and maximum number of their usages.
+    fn variable_usage(&self) -> (usize, f64, usize) {
+        let var_counts: Vec<usize> = self.variable_counts.values().cloned().collect();
+        let var_avg = if !var_counts.is_empty() {
+            var_counts.iter().sum::<usize>() as f64 / var_counts.len() as f64
+        } else {
+            0.0
+        };
+        (
+            var_counts.len(),
+            var_avg,
+            *var_counts.iter().max().unwrap_or(&0),
+        )
+    }
+
+    /// Returns the average and maximum number of calls of a function.
+    fn function_call_usage(&self) -> (f64, usize) {
+        let func_counts: Vec<usize> = self.function_call_counts.values().cloned().collect();
+        let func_avg = if !func_counts.is_empty() {
+            func_counts.iter().sum::<usize>() as f64 / func_counts.len() as f64
+        } else {
+            0.0
+        };
+        (func_avg, *func_counts.iter().max().unwrap_or(&0))
+    }
+
+    /// Converts the statistics into a JSON object whose keys are the columns of
+    /// `CONSTRAINT_STATISTICS_CSV_HEADERS`.
+    ///
+    /// Unlike the CSV row, the averages are not rounded.
+    pub fn to_json(&self) -> Value {
+        let mut object = Map::new();
+        object.insert(
+            "Total_Constraints".to_string(),
+            json!(self.total_constraints),
+        );
+        object.insert("Constant_Counts".to_string(), json!(self.constant_counts));
+        object.insert(
+            "Conditional_Counts".to_string(),
+            json!(self.conditional_counts),
+        );
+        object.insert("Array_Counts".to_string(), json!(self.array_counts));
+        object.insert("Avg_Depth".to_string(), json!(self.avg_depth()));
+        object.insert("Max_Depth".to_string(), json!(self.max_depth()));
+
+        for (header, op) in CONSTRAINT_STATISTICS_CSV_HEADERS[6..30]
+            .iter()
+            .zip(CSV_OPERATORS.iter())
+        {
+            object.insert(header.to_string(), json!(self.operator_count(op)));
+        }
+
+        let (num_vars, var_avg, var_max) = self.variable_usage();
+        object.insert("Number_of_Variable".to_string(), json!(num_vars));
+        object.insert("Variable_Avg_Count".to_string(), json!(var_avg));
+        object.insert("Variable_Max_Count".to_string(), json!(var_max));
+
+        let (func_avg, func_max) = self.function_call_usage();
+        object.insert("Function_Avg_Count".to_string(), json!(func_avg));
+        object.insert("Function_Max_Count".to_string(), json!(func_max));
+
+        Value::Object(object)
+    }
 }
 
 pub fn print_constraint_summary_statistics_pretty(stats: &ConstraintStatistics) {
@@ -162,13 +310,12 @@ pub fn print_constraint_summary_statistics_pretty(stats: &ConstraintStatistics)
     println!("\n🔢 Assign Counts:");
     for op in &["Assign", "AssignEq", "AssignCall", "QuadZeroDiv"] {
         let c = stats.operator_counts.get(*op).unwrap_or(&0);
-        println!(
-            " • {:<13}: {}{}{}",
-            op,
-            if *c != 0 { WHITE } else { BBLACK },
-            c,
-            RESET
-        );
+        let c = if *c != 0 {
+            c.to_string().white()
+        } else {
+            c.to_string().bright_black()
+        };
+        println!(" • {:<13}: {}", op, c);
     }
 
     println!("\n🔢 Operator Counts:");
@@ -177,13 +324,12 @@ pub fn print_constraint_summary_statistics_pretty(stats: &ConstraintStatistics)
         "Eq", "NEq", "BoolOr", "BoolAnd", "BitOr", "BitAnd", "BitXor",
     ] {
         let c = stats.operator_counts.get(*op).unwrap_or(&0);
-        println!(
-            " • {:<8}: {}{}{}",
-            op,
-            if *c != 0 { WHITE } else { BBLACK },
-            c,
-            RESET
-        );
+        let c = if *c != 0 {
+            c.to_string().white()
+        } else {
+            c.to_string().bright_black()
+        };
+        println!(" • {:<8}: {}", op, c);
     }
 
     println!("\n📈 Variable Statistics:");
@@ -214,87 +360,38 @@ pub fn print_constraint_summary_statistics_pretty(stats: &ConstraintStatistics)
     );
 }
 
-pub fn print_constraint_summary_statistics_csv(constraint_stats: &ConstraintStatistics) {
+/// Returns the values of the CSV row of `constraint_stats`, in the order of
+/// `CONSTRAINT_STATISTICS_CSV_HEADERS`.
+pub fn constraint_summary_statistics_csv_row(
+    constraint_stats: &ConstraintStatistics,
+) -> Vec<String> {
     let mut values = Vec::new();
     values.push(constraint_stats.total_constraints.to_string());
     values.push(constraint_stats.constant_counts.to_string());
     values.push(constraint_stats.conditional_counts.to_string());
     values.push(constraint_stats.array_counts.to_string());
+    values.push(format!("{:.2}", constraint_stats.avg_depth()));
+    values.push(constraint_stats.max_depth().to_string());
 
-    let avg_depth = if !constraint_stats.constraint_depths.is_empty() {
-        constraint_stats.constraint_depths.iter().sum::<usize>() as f64
-            / constraint_stats.constraint_depths.len() as f64
-    } else {
-        0.0
-    };
-    values.push(format!("{:.2}", avg_depth));
-    values.push(
-        constraint_stats
-            .constraint_depths
-            .iter()
-            .max()
-            .unwrap_or(&0)
-            .to_string(),
-    );
-
-    for op in &[
-        "Assign",
-        "AssignEq",
-        "AssignCall",
-        "QuadZeroDiv",
-        "Mul",
-        "Div",
-        "Add",
-        "Sub",
-        "Pow",
-        "IntDiv",
-        "Mod",
-        "ShL",
-        "ShR",
-        "LEq",
-        "GEq",
-        "Lt",
-        "Gt",
-        "Eq",
-        "NEq",
-        "BoolOr",
-        "BoolAnd",
-        "BitOr",
-        "BitAnd",
-        "BitXor",
-    ] {
-        values.push(
-            constraint_stats
-                .operator_counts
-                .get(*op)
-                .unwrap_or(&0)
-                .to_string(),
-        );
+    for op in &CSV_OPERATORS {
+        values.push(constraint_stats.operator_count(op).to_string());
     }
 
-    let var_counts: Vec<usize> = constraint_stats.variable_counts.values().cloned().collect();
-    let num_vars = var_counts.len();
-    let var_avg = if !var_counts.is_empty() {
-        var_counts.iter().sum::<usize>() as f64 / var_counts.len() as f64
-    } else {
-        0.0
-    };
+    let (num_vars, var_avg, var_max) = constraint_stats.variable_usage();
     values.push(num_vars.to_string());
     values.push(format!("{:.2}", var_avg));
-    values.push(var_counts.iter().max().unwrap_or(&0).to_string());
+    values.push(var_max.to_string());
 
-    let func_counts: Vec<usize> = constraint_stats
-        .function_call_counts
-        .values()
-        .cloned()
-        .collect();
-    let func_avg = if !func_counts.is_empty() {
-        func_counts.iter().sum::<usize>() as f64 / func_counts.len() as f64
-    } else {
-        0.0
-    };
+    let (func_avg, func_max) = constraint_stats.function_call_usage();
     values.push(format!("{:.2}", func_avg));
-    values.push(func_counts.iter().max().unwrap_or(&0).to_string());
+    values.push(func_max.to_string());
+
+    values
+}
 
-    println!("{}", values.join(","));
+pub fn print_constraint_summary_statistics_csv(constraint_stats: &ConstraintStatistics) {
+    println!(
+        "{}",
+        constraint_summary_statistics_csv_row(constraint_stats).join(",")
+    );
 }
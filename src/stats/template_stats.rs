@@ -0,0 +1,116 @@
+use std::collections::{BTreeMap, HashSet};
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::executor::symbolic_value::{
+    extract_variables_from_symbolic_value, OwnerName, SymbolicLibrary, SymbolicValue,
+    SymbolicValueRef,
+};
+
+/// The numbers of trace and side constraints owned by a template.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TemplateConstraintCounts {
+    pub trace_constraints: usize,
+    pub side_constraints: usize,
+}
+
+impl TemplateConstraintCounts {
+    /// Returns the ratio of side constraints to trace constraints, or `0.0` without any trace
+    /// constraint.
+    pub fn compression_rate(&self) -> f64 {
+        if self.trace_constraints == 0 {
+            0.0
+        } else {
+            self.side_constraints as f64 / self.trace_constraints as f64
+        }
+    }
+}
+
+/// Buckets the trace and side constraints by the template owning them.
+///
+/// A constraint is owned by the innermost component enclosing all of its variables, i.e., by the
+/// longest common prefix of their owner chains, since a template only refers to its own signals
+/// and to the inputs and outputs of its subcomponents. The owner chain is then walked up until it
+/// names an executed component, whose template is looked up in `component_templates`.
+/// Constraints without any variable, as well as those owned by the main component, are
+/// attributed to `main_template_name`.
+///
+/// Duplicated constraints are counted once, as in `ConstraintStatistics`, so that the counts of
+/// all templates sum up to its totals.
+///
+/// # Parameters
+/// - `symbolic_trace`: The symbolic trace of the main template.
+/// - `side_constraints`: The side constraints of the main template.
+/// - `component_templates`: The template of each executed component, as recorded by
+///   `SymbolicExecutor`.
+/// - `symbolic_library`: The symbolic library used to name the templates.
+/// - `main_template_name`: The name of the main template.
+///
+/// # Returns
+/// The counts of each template owning at least one constraint, keyed by the template name.
+pub fn count_constraints_per_template(
+    symbolic_trace: &[SymbolicValueRef],
+    side_constraints: &[SymbolicValueRef],
+    component_templates: &FxHashMap<Vec<OwnerName>, usize>,
+    symbolic_library: &SymbolicLibrary,
+    main_template_name: &str,
+) -> BTreeMap<String, TemplateConstraintCounts> {
+    let mut counts: BTreeMap<String, TemplateConstraintCounts> = BTreeMap::new();
+
+    let mut seen = HashSet::new();
+    for constraint in symbolic_trace {
+        if seen.insert(constraint.as_ref()) {
+            let template_name = owning_template(
+                constraint,
+                component_templates,
+                symbolic_library,
+                main_template_name,
+            );
+            counts.entry(template_name).or_default().trace_constraints += 1;
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for constraint in side_constraints {
+        if seen.insert(constraint.as_ref()) {
+            let template_name = owning_template(
+                constraint,
+                component_templates,
+                symbolic_library,
+                main_template_name,
+            );
+            counts.entry(template_name).or_default().side_constraints += 1;
+        }
+    }
+
+    counts
+}
+
+fn owning_template(
+    constraint: &SymbolicValue,
+    component_templates: &FxHashMap<Vec<OwnerName>, usize>,
+    symbolic_library: &SymbolicLibrary,
+    main_template_name: &str,
+) -> String {
+    let mut variables = FxHashSet::default();
+    extract_variables_from_symbolic_value(constraint, &mut variables);
+
+    let mut owners = variables.iter().map(|v| v.owner.as_slice());
+    if let Some(first) = owners.next() {
+        let mut owner = first;
+        for other in owners {
+            let common = owner
+                .iter()
+                .zip(other.iter())
+                .take_while(|(lhs, rhs)| lhs == rhs)
+                .count();
+            owner = &owner[..common];
+        }
+        for len in (1..=owner.len()).rev() {
+            if let Some(template_id) = component_templates.get(&owner[..len]) {
+                return symbolic_library.id2name[template_id].clone();
+            }
+        }
+    }
+    main_template_name.to_string()
+}
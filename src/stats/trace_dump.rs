@@ -0,0 +1,85 @@
+use std::fs;
+use std::io;
+
+use rustc_hash::FxHashMap;
+use serde_json::json;
+
+use crate::executor::symbolic_value::SymbolicValueRef;
+
+/// Renders constraints with `lookup_fmt`, without colors and on a single line each.
+pub fn render_constraints(
+    constraints: &[SymbolicValueRef],
+    id2name: &FxHashMap<usize, String>,
+) -> Vec<String> {
+    let should_colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+    colored::control::set_override(false);
+    let lines = constraints
+        .iter()
+        .map(|c| c.lookup_fmt(id2name).replace('\n', " "))
+        .collect();
+    colored::control::set_override(should_colorize);
+    lines
+}
+
+/// Renders the trace constraints and the side constraints of a circuit, one per line.
+///
+/// The trace constraints come first, in execution order, followed by the side constraints. Each
+/// line starts with `trace` or `side` and a tab, followed by the constraint as rendered by
+/// `render_constraints`, so that the output of two versions of a circuit can be diffed.
+///
+/// # Parameters
+/// - `symbolic_trace`: The trace constraints.
+/// - `side_constraints`: The side constraints.
+/// - `id2name`: The lookup table used to name the variables.
+///
+/// # Returns
+/// One line per constraint, without trailing newlines.
+pub fn render_symbolic_trace(
+    symbolic_trace: &[SymbolicValueRef],
+    side_constraints: &[SymbolicValueRef],
+    id2name: &FxHashMap<usize, String>,
+) -> Vec<String> {
+    let render = |kind: &str, constraints: &[SymbolicValueRef]| -> Vec<String> {
+        render_constraints(constraints, id2name)
+            .into_iter()
+            .map(|c| format!("{}\t{}", kind, c))
+            .collect()
+    };
+    let mut lines = render("trace", symbolic_trace);
+    lines.append(&mut render("side", side_constraints));
+    lines
+}
+
+/// Writes the lines of `render_symbolic_trace` to `path`.
+///
+/// When `with_json` is set, the same constraints are also written to `<path>.json` as an object
+/// with the `symbolic_trace` and `side_constraints` arrays of rendered constraints.
+pub fn dump_symbolic_trace(
+    path: &str,
+    symbolic_trace: &[SymbolicValueRef],
+    side_constraints: &[SymbolicValueRef],
+    id2name: &FxHashMap<usize, String>,
+    with_json: bool,
+) -> io::Result<()> {
+    let lines = render_symbolic_trace(symbolic_trace, side_constraints, id2name);
+    let text: String = lines.iter().map(|line| format!("{}\n", line)).collect();
+    fs::write(path, text)?;
+
+    if with_json {
+        let strip = |kind: &str| -> Vec<&str> {
+            lines
+                .iter()
+                .filter_map(|line| line.strip_prefix(kind))
+                .collect()
+        };
+        let sidecar = json!({
+            "symbolic_trace": strip("trace\t"),
+            "side_constraints": strip("side\t"),
+        });
+        fs::write(
+            format!("{}.json", path),
+            serde_json::to_string_pretty(&sidecar).expect("Failed to serialize to JSON"),
+        )?;
+    }
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use zkfuzz::api::{analyze_all_templates, analyze_circuit, AnalysisConfig, ZkFuzzError};
+
+#[test]
+fn test_analyze_circuit_unused_output() {
+    let report = analyze_circuit(
+        "./tests/sample/test_unused_output.circom",
+        AnalysisConfig::default(),
+    )
+    .unwrap();
+    assert!(report.counter_example.is_some());
+    assert!(report.trace_statistics.total_constraints > 0);
+}
+
+#[test]
+fn test_analyze_circuit_invalid_search_mode() {
+    let mut config = AnalysisConfig::default();
+    config.search_mode = "unknown".to_string();
+    assert!(matches!(
+        analyze_circuit("./tests/sample/test_unused_output.circom", config),
+        Err(ZkFuzzError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_analyze_all_templates() {
+    let mut config = AnalysisConfig::default();
+    config.mutation_config.max_generations = 10;
+    let reports =
+        analyze_all_templates("./tests/sample/test_two_templates.circom", config).unwrap();
+
+    assert_eq!(
+        reports
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["Adder", "PartialOutput"]
+    );
+    for (name, report) in &reports {
+        assert_eq!(&report.main_template_name, name);
+        assert!(report.trace_statistics.total_constraints > 0);
+    }
+    assert!(reports[0].1.counter_example.is_none());
+    assert!(reports[1].1.counter_example.is_some());
+}
+
+#[test]
+fn test_analyze_circuit_execution_metrics() {
+    let mut config = AnalysisConfig::default();
+    config.search_mode = "off".to_string();
+    let report = analyze_circuit("./tests/sample/test_execution_metrics.circom", config).unwrap();
+
+    // `middle`, and `inner[0]` and `inner[1]` within it
+    assert_eq!(report.execution_metrics.components_instantiated, 3);
+    assert_eq!(report.execution_metrics.max_component_nesting_depth, 2);
+    // `square` is called once by `Outer` and once by each `Inner`
+    assert_eq!(report.execution_metrics.functions_called, 3);
+    assert_eq!(report.execution_metrics.templates_registered, 3);
+}
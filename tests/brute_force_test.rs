@@ -0,0 +1,444 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_value::SymbolicValueRef;
+#[cfg(feature = "parallel")]
+use zkfuzz::mutator::brute_force::parallel_brute_force_search;
+use zkfuzz::mutator::brute_force::{boundary_values, brute_force_search, brute_force_search_all};
+use zkfuzz::mutator::utils::{
+    verify_assignment, BaseVerificationConfig, CounterExample, UnderConstrainedType,
+    VerificationResult,
+};
+use zkfuzz::parser_user::parse_assumptions;
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+type SearchFn = fn(
+    &mut SymbolicExecutor,
+    &Vec<SymbolicValueRef>,
+    &Vec<SymbolicValueRef>,
+    &BaseVerificationConfig,
+) -> Option<CounterExample>;
+
+fn conduct_brute_force_search(
+    path: String,
+    search_fn: SearchFn,
+    boundary_values: bool,
+) -> Option<CounterExample> {
+    conduct_brute_force_search_with_assumptions(path, search_fn, boundary_values, &[])
+}
+
+fn conduct_brute_force_search_with_assumptions(
+    path: String,
+    search_fn: SearchFn,
+    boundary_values: bool,
+    assumptions: &[&str],
+) -> Option<CounterExample> {
+    collect_counter_examples(
+        path,
+        |sexe, trace, side_constraints, config| {
+            search_fn(sexe, trace, side_constraints, config)
+                .into_iter()
+                .collect()
+        },
+        boundary_values,
+        assumptions,
+        1,
+    )
+    .pop()
+}
+
+fn collect_counter_examples<F>(
+    path: String,
+    search: F,
+    boundary_values: bool,
+    assumptions: &[&str],
+    max_findings: usize,
+) -> Vec<CounterExample>
+where
+    F: FnOnce(
+        &mut SymbolicExecutor,
+        &Vec<SymbolicValueRef>,
+        &Vec<SymbolicValueRef>,
+        &BaseVerificationConfig,
+    ) -> Vec<CounterExample>,
+{
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+
+    let mut input_names: Vec<String> = sexe.symbolic_library.template_library
+        [&sexe.symbolic_library.name2id[main_template_name]]
+        .input_ids
+        .iter()
+        .map(|id| sexe.symbolic_library.id2name[id].clone())
+        .collect();
+    input_names.sort();
+    let assumptions: Vec<SymbolicValueRef> = parse_assumptions(
+        &assumptions
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>(),
+        &input_names,
+        &prime,
+    )
+    .unwrap()
+    .iter()
+    .map(|expr| Rc::new(sexe.evaluate_standalone_expression(expr)))
+    .collect();
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(5),
+        quick_mode: true,
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        boundary_values: boundary_values,
+        assumptions: assumptions,
+        max_findings: max_findings,
+        ..Default::default()
+    };
+
+    let subse_base_config = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &subse_base_config);
+    conc_executor
+        .feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let counter_examples = search(
+        &mut conc_executor,
+        &symbolic_trace,
+        &side_constraints,
+        &verification_base_config,
+    );
+
+    // The counterexamples must replay on the original executor.
+    for ce in &counter_examples {
+        assert!(matches!(
+            verify_assignment(
+                &mut conc_executor,
+                &symbolic_trace,
+                &side_constraints,
+                &ce.assignment,
+                &verification_base_config,
+            ),
+            VerificationResult::UnderConstrained(_) | VerificationResult::OverConstrained
+        ));
+    }
+    counter_examples
+}
+
+#[test]
+fn test_brute_force_vuln_iszero() {
+    let counter_example = conduct_brute_force_search(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        brute_force_search,
+        false,
+    );
+
+    assert!(matches!(
+        counter_example,
+        Some(CounterExample {
+            flag: VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(..)),
+            ..
+        })
+    ));
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_brute_force_vuln_iszero() {
+    let sequential = conduct_brute_force_search(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        brute_force_search,
+        false,
+    );
+    let parallel = conduct_brute_force_search(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        parallel_brute_force_search,
+        false,
+    );
+
+    for counter_example in [sequential, parallel] {
+        assert!(matches!(
+            counter_example,
+            Some(CounterExample {
+                flag: VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                    ..
+                )),
+                ..
+            })
+        ));
+    }
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallel_brute_force_with_one_thread_matches_sequential() {
+    // The counterexamples hold `Rc`s, so only a summary of them leaves the pool.
+    fn summarize(counter_example: Option<CounterExample>) -> (String, Vec<(usize, String)>) {
+        let counter_example = counter_example.unwrap();
+        let mut assignment: Vec<(usize, String)> = counter_example
+            .assignment
+            .iter()
+            .map(|(name, value)| (name.id, value.to_string()))
+            .collect();
+        assignment.sort();
+        (counter_example.flag.to_string(), assignment)
+    }
+
+    let sequential = summarize(conduct_brute_force_search(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        brute_force_search,
+        false,
+    ));
+
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    let parallel = pool.install(|| {
+        summarize(conduct_brute_force_search(
+            "./tests/sample/test_vuln_iszero.circom".to_string(),
+            parallel_brute_force_search,
+            false,
+        ))
+    });
+
+    assert_eq!(sequential, parallel);
+}
+
+#[test]
+fn test_brute_force_reports_violated_constraint_location() {
+    let counter_example = conduct_brute_force_search(
+        "./tests/sample/test_violated_location.circom".to_string(),
+        brute_force_search,
+        false,
+    )
+    .unwrap();
+
+    let location = match &counter_example.flag {
+        VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+            _,
+            _,
+            location,
+        )) => location.clone().unwrap(),
+        _ => panic!("Expected an Unexpected-Input counterexample"),
+    };
+    assert!(location.path.ends_with("test_violated_location.circom"));
+    assert_eq!(9, location.line);
+    assert_eq!(5, location.column);
+
+    assert!(counter_example
+        .flag
+        .to_string()
+        .contains("test_violated_location.circom:9:5"));
+    assert_eq!(
+        location.to_string(),
+        counter_example.flag.to_json()["2_violated_condition"]["location"]
+    );
+}
+
+#[test]
+fn test_brute_force_vuln_assert() {
+    let counter_example = conduct_brute_force_search(
+        "./tests/sample/test_vuln_assert.circom".to_string(),
+        brute_force_search,
+        false,
+    )
+    .unwrap();
+
+    let location = match &counter_example.flag {
+        VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+            _,
+            _,
+            location,
+        )) => location.clone().unwrap(),
+        _ => panic!("Expected an Unexpected-Input counterexample"),
+    };
+    assert!(location.path.ends_with("test_vuln_assert.circom"));
+    assert_eq!(8, location.line);
+
+    // The only non-binary input whose output is enumerated by the quick mode is -1
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    assert!(counter_example
+        .assignment
+        .values()
+        .any(|v| *v == BigInt::from(-1) || *v == &prime - BigInt::from(1)));
+}
+
+#[test]
+fn test_brute_force_boundary_values() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    assert_eq!(
+        boundary_values(&prime),
+        vec![
+            BigInt::from(0),
+            BigInt::from(1),
+            &prime - BigInt::from(1),
+            &prime - BigInt::from(2),
+            (&prime - BigInt::from(1)) / BigInt::from(2),
+        ]
+    );
+    // The values coincide for tiny primes
+    assert_eq!(
+        boundary_values(&BigInt::from(3)),
+        vec![BigInt::from(0), BigInt::from(1), BigInt::from(2)]
+    );
+}
+
+#[test]
+fn test_brute_force_boundary_minus_one() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let counter_example = conduct_brute_force_search(
+        "./tests/sample/test_boundary_minus_one.circom".to_string(),
+        brute_force_search,
+        true,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        counter_example.flag,
+        VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(..))
+    ));
+    // `prime - 1` is tried before the `-1` of the quick mode
+    assert!(counter_example
+        .assignment
+        .values()
+        .any(|v| *v == &prime - BigInt::from(1)));
+}
+
+#[test]
+fn test_brute_force_boundary_half() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    // The quick mode alone only tries 0, 1, and -1
+    assert!(conduct_brute_force_search(
+        "./tests/sample/test_boundary_half.circom".to_string(),
+        brute_force_search,
+        false,
+    )
+    .is_none());
+
+    let counter_example = conduct_brute_force_search(
+        "./tests/sample/test_boundary_half.circom".to_string(),
+        brute_force_search,
+        true,
+    )
+    .unwrap();
+    assert!(counter_example
+        .assignment
+        .values()
+        .any(|v| *v == (&prime - BigInt::from(1)) / BigInt::from(2)));
+}
+
+#[test]
+fn test_brute_force_assumption_eliminates_counterexample() {
+    // `inv` is only free to be tampered with when `in` is not zero
+    assert!(conduct_brute_force_search(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        brute_force_search,
+        false,
+    )
+    .is_some());
+
+    assert!(conduct_brute_force_search_with_assumptions(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        brute_force_search,
+        false,
+        &["in == 0"],
+    )
+    .is_none());
+
+    // A counterexample satisfying the assumptions is still reported
+    assert!(conduct_brute_force_search_with_assumptions(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        brute_force_search,
+        false,
+        &["in != 0"],
+    )
+    .is_some());
+}
+
+#[test]
+fn test_brute_force_find_all_reports_each_output() {
+    let path = "./tests/sample/test_two_bugs.circom";
+
+    let counter_examples = collect_counter_examples(
+        path.to_string(),
+        |sexe, trace, side_constraints, config| {
+            brute_force_search_all(sexe, trace, side_constraints, config).counter_examples
+        },
+        false,
+        &[],
+        usize::MAX,
+    );
+    let mut names: Vec<String> = counter_examples
+        .iter()
+        .map(|ce| match &ce.flag {
+            VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+                _,
+                name,
+                _,
+            )) => name.clone(),
+            flag => panic!("unexpected finding: {}", flag),
+        })
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["main.x".to_string(), "main.y".to_string()]);
+
+    let counter_examples = collect_counter_examples(
+        path.to_string(),
+        |sexe, trace, side_constraints, config| {
+            brute_force_search_all(sexe, trace, side_constraints, config).counter_examples
+        },
+        false,
+        &[],
+        1,
+    );
+    assert_eq!(counter_examples.len(), 1);
+}
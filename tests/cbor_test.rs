@@ -0,0 +1,130 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use rustc_hash::FxHashMap;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_state::FieldReductionEvent;
+use zkfuzz::executor::symbolic_value::{OwnerName, SourceLocation, SymbolicName};
+use zkfuzz::mutator::brute_force::brute_force_search;
+use zkfuzz::mutator::utils::{
+    load_counter_examples_from_cbor, save_counter_examples_to_cbor, BaseVerificationConfig,
+    CounterExample, UnderConstrainedType, VerificationResult,
+};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_cbor_round_trip() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(
+        "./tests/sample/test_provenance.circom".to_string(),
+        prime.clone(),
+    );
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(5),
+        quick_mode: true,
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let subse_base_config = get_default_setting_for_concrete_execution(prime.clone(), false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &subse_base_config);
+    conc_executor
+        .feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
+    let mut non_deterministic = brute_force_search(
+        &mut conc_executor,
+        &symbolic_trace,
+        &side_constraints,
+        &verification_base_config,
+    )
+    .unwrap();
+    non_deterministic.explain(&sexe.cur_state);
+    assert!(non_deterministic.provenance.is_some());
+
+    // Field elements above 2^64 and negative values must survive the round trip
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let a = SymbolicName::new(sexe.symbolic_library.name2id["a"], main_owner, None);
+    let minus_one = prime.clone() - BigInt::from(1);
+    non_deterministic
+        .assignment
+        .insert(a.clone(), minus_one.clone());
+    non_deterministic
+        .field_reductions
+        .push(FieldReductionEvent {
+            elem_id: 3,
+            op: "Mul".to_string(),
+            lhs: minus_one.clone(),
+            rhs: minus_one.clone(),
+            unreduced: minus_one.clone() * minus_one,
+            reduced: BigInt::from(1),
+        });
+
+    let mut assignment = FxHashMap::default();
+    assignment.insert(a, BigInt::from(-5));
+    let unexpected_input = CounterExample {
+        flag: VerificationResult::UnderConstrained(UnderConstrainedType::UnexpectedInput(
+            2,
+            "(Eq main.a 0)".to_string(),
+            Some(SourceLocation {
+                path: "./tests/sample/test_provenance.circom".to_string(),
+                line: 7,
+                column: 5,
+            }),
+        )),
+        target_output: None,
+        assignment: assignment,
+        field_reductions: Vec::new(),
+        provenance: None,
+    };
+
+    let counter_examples = vec![non_deterministic, unexpected_input];
+    let path = std::env::temp_dir().join("zkfuzz_cbor_round_trip_test.cbor");
+    let path = path.to_str().unwrap();
+    save_counter_examples_to_cbor(&counter_examples, path).unwrap();
+    assert_eq!(
+        load_counter_examples_from_cbor(path).unwrap(),
+        counter_examples
+    );
+    std::fs::remove_file(path).unwrap();
+
+    assert!(load_counter_examples_from_cbor(path).is_err());
+}
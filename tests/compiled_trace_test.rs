@@ -0,0 +1,195 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::{BigInt, RandBigInt};
+use num_traits::Zero;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicName};
+use zkfuzz::mutator::compiled_trace::{CompiledConstraints, CompiledTrace};
+use zkfuzz::mutator::utils::{
+    emulate_symbolic_trace, evaluate_constraints, gather_runtime_mutable_inputs,
+};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+/// Emulates the trace of `path` with 1000 random inputs, both naively and through a
+/// `CompiledTrace`, and checks that both give the same results and assignments. The constraints
+/// are then evaluated over the emulated assignments, both naively and through
+/// `CompiledConstraints`.
+fn compare_with_naive_emulation(path: &str, inputs: &[&str], runtime_mutation: bool) {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    compare_with_naive_emulation_modulo(path, inputs, runtime_mutation, prime);
+}
+
+/// Same as `compare_with_naive_emulation`, over the given modulus.
+fn compare_with_naive_emulation_modulo(
+    path: &str,
+    inputs: &[&str],
+    runtime_mutation: bool,
+    prime: BigInt,
+) {
+    let (mut symbolic_library, program_archive) =
+        prepare_symbolic_library(path.to_string(), prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let input_variables: Vec<SymbolicName> = inputs
+        .iter()
+        .map(|name| {
+            SymbolicName::new(
+                sexe.symbolic_library.name2id[*name],
+                Rc::new(vec![OwnerName {
+                    id: sexe.symbolic_library.name2id["main"],
+                    access: None,
+                    counter: 0,
+                }]),
+                None,
+            )
+        })
+        .collect();
+    let runtime_mutable_positions = if runtime_mutation {
+        gather_runtime_mutable_inputs(
+            &sexe.cur_state.symbolic_trace,
+            sexe.symbolic_library,
+            &input_variables.iter().cloned().collect::<FxHashSet<_>>(),
+        )
+    } else {
+        FxHashMap::default()
+    };
+
+    // Small values exercise the branches of the circuits, and large ones the field arithmetic.
+    let mut rng = StdRng::seed_from_u64(42);
+    let candidates: Vec<FxHashMap<SymbolicName, BigInt>> = (0..1000)
+        .map(|_| {
+            input_variables
+                .iter()
+                .map(|name| {
+                    let value = if rng.gen::<bool>() {
+                        rng.gen_bigint_range(&BigInt::zero(), &BigInt::from(16))
+                    } else {
+                        rng.gen_bigint_range(&BigInt::zero(), &prime)
+                    };
+                    (name.clone(), value)
+                })
+                .collect()
+        })
+        .collect();
+
+    let trace = sexe.cur_state.symbolic_trace.clone();
+
+    let naive: Vec<_> = candidates
+        .iter()
+        .map(|candidate| {
+            let mut assignment = candidate.clone();
+            let result = emulate_symbolic_trace(
+                &prime,
+                &trace,
+                &runtime_mutable_positions,
+                &mut assignment,
+                sexe.symbolic_library,
+            );
+            (result, assignment)
+        })
+        .collect();
+
+    let compiled_trace = CompiledTrace::new(&trace);
+    let compiled: Vec<_> = candidates
+        .iter()
+        .map(|candidate| {
+            let mut assignment = candidate.clone();
+            let result = compiled_trace.emulate(
+                &prime,
+                &runtime_mutable_positions,
+                &mut assignment,
+                sexe.symbolic_library,
+            );
+            (result, assignment)
+        })
+        .collect();
+
+    assert_eq!(naive.len(), compiled.len());
+    for ((naive_result, naive_assignment), (compiled_result, compiled_assignment)) in
+        naive.iter().zip(compiled.iter())
+    {
+        assert_eq!(naive_result, compiled_result);
+        assert_eq!(naive_assignment, compiled_assignment);
+    }
+
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let compiled_trace_constraints = CompiledConstraints::new(&trace);
+    let compiled_side_constraints = CompiledConstraints::new(&side_constraints);
+    for (result, assignment) in &naive {
+        if result.is_none() {
+            continue;
+        }
+        // Shifting the computed values makes most of the constraints fail, while the divisions
+        // by the inputs stay defined.
+        let shifted: FxHashMap<SymbolicName, BigInt> = assignment
+            .iter()
+            .map(|(name, value)| {
+                if input_variables.contains(name) {
+                    (name.clone(), value.clone())
+                } else {
+                    (name.clone(), value + BigInt::from(1))
+                }
+            })
+            .collect();
+        for assignment in [assignment, &shifted] {
+            assert_eq!(
+                evaluate_constraints(&prime, &trace, assignment, sexe.symbolic_library),
+                compiled_trace_constraints.evaluate(&prime, assignment, sexe.symbolic_library)
+            );
+            assert_eq!(
+                evaluate_constraints(&prime, &side_constraints, assignment, sexe.symbolic_library),
+                compiled_side_constraints.evaluate(&prime, assignment, sexe.symbolic_library)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_compiled_trace_lessthan() {
+    compare_with_naive_emulation("./tests/sample/test_lessthan.circom", &["a", "b"], false);
+}
+
+#[test]
+fn test_compiled_trace_montgomerydouble() {
+    compare_with_naive_emulation(
+        "./tests/sample/test_montgomerydouble.circom",
+        &["a", "b"],
+        false,
+    );
+}
+
+#[test]
+fn test_compiled_trace_with_runtime_mutation() {
+    compare_with_naive_emulation(
+        "./tests/sample/test_hash_break.circom",
+        &["x", "y", "z"],
+        true,
+    );
+}
+
+#[test]
+fn test_compiled_trace_with_composite_modulus() {
+    // The even values of `b` have no inverse modulo 2^66 * 3, so that both emulations give up on
+    // `1 / b` in the same way
+    compare_with_naive_emulation_modulo(
+        "./tests/sample/test_public_input.circom",
+        &["a", "b"],
+        false,
+        BigInt::from(1u64 << 62) * BigInt::from(48),
+    );
+}
@@ -0,0 +1,24 @@
+use zkfuzz::api::{gather_side_constraints, AnalysisConfig};
+use zkfuzz::stats::constraint_diff::diff_constraints;
+
+#[test]
+fn test_diff_extra_constraint() {
+    let config = AnalysisConfig::default();
+    let base = gather_side_constraints("./tests/sample/test_diff_base.circom", &config).unwrap();
+    let extra =
+        gather_side_constraints("./tests/sample/test_diff_extra_constraint.circom", &config)
+            .unwrap();
+
+    // The templates are declared in a different order, which changes their ids but not the
+    // normalized constraints
+    let diff = diff_constraints(&base, &extra);
+    assert_eq!(diff.added.len(), 1);
+    assert!(diff.removed.is_empty());
+    assert!(diff.added[0].contains("main.a") && diff.added[0].contains("main.b"));
+
+    let diff = diff_constraints(&extra, &base);
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed.len(), 1);
+
+    assert!(diff_constraints(&base, &base).is_empty());
+}
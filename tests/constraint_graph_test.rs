@@ -0,0 +1,49 @@
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::stats::constraint_graph::constraint_graph_to_dot;
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_constraint_graph_lessthan() {
+    let path = "./tests/sample/test_lessthan.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let dot = constraint_graph_to_dot(
+        &sexe.cur_state.side_constraints,
+        &sexe.symbolic_library,
+        "VulnerableLessThan",
+    );
+
+    assert!(dot.starts_with("graph constraints {\n"));
+    assert!(dot.ends_with("}\n"));
+
+    // Nodes: a, b, out, lt.in[0..2], lt.out, lt.n2b.in, and lt.n2b.out[0..4]
+    let nodes: Vec<&str> = dot.lines().filter(|l| l.contains("[label=")).collect();
+    assert_eq!(nodes.len(), 11);
+    assert_eq!(nodes.iter().filter(|l| l.contains("lightblue")).count(), 2);
+    assert_eq!(nodes.iter().filter(|l| l.contains("lightpink")).count(), 1);
+
+    // Edges:
+    // - lt.in[0] <-> a, and lt.in[1] <-> b
+    // - the triangle of lt.n2b.in, lt.in[0], and lt.in[1]
+    // - the clique of lt.n2b.in and lt.n2b.out[0..4] (10 edges)
+    // - lt.out <-> lt.n2b.out[3], and out <-> lt.out
+    let edges = dot.lines().filter(|l| l.contains(" -- ")).count();
+    assert_eq!(edges, 17);
+}
@@ -0,0 +1,118 @@
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::stats::symbolic_stats::{
+    constraint_summary_statistics_csv_row, ConstraintStatistics, CONSTRAINT_STATISTICS_CSV_HEADERS,
+};
+use zkfuzz::stats::template_stats::{count_constraints_per_template, TemplateConstraintCounts};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_constraint_statistics_json_matches_csv() {
+    let path = "./tests/sample/test_vuln_iszero.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    for constraints in [
+        &sexe.cur_state.symbolic_trace,
+        &sexe.cur_state.side_constraints,
+    ] {
+        let mut stats = ConstraintStatistics::new();
+        for c in constraints {
+            stats.update(c);
+        }
+        assert!(stats.total_constraints > 0);
+
+        let json = stats.to_json();
+        let row = constraint_summary_statistics_csv_row(&stats);
+        assert_eq!(row.len(), CONSTRAINT_STATISTICS_CSV_HEADERS.len());
+        assert_eq!(
+            json.as_object().unwrap().len(),
+            CONSTRAINT_STATISTICS_CSV_HEADERS.len()
+        );
+
+        for (header, value) in CONSTRAINT_STATISTICS_CSV_HEADERS.iter().zip(row.iter()) {
+            let field = &json[*header];
+            if header.contains("Avg") {
+                assert_eq!(
+                    &format!("{:.2}", field.as_f64().unwrap()),
+                    value,
+                    "{}",
+                    header
+                );
+            } else {
+                assert_eq!(&field.as_u64().unwrap().to_string(), value, "{}", header);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_constraint_counts_per_template() {
+    let path = "./tests/sample/test_two_subtemplates.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let counts = count_constraints_per_template(
+        &sexe.cur_state.symbolic_trace,
+        &sexe.cur_state.side_constraints,
+        &sexe.component_templates,
+        &sexe.symbolic_library,
+        "Main",
+    );
+    assert_eq!(
+        counts.keys().collect::<Vec<_>>(),
+        vec!["Main", "Square", "Sum"]
+    );
+    assert_eq!(
+        counts["Square"],
+        TemplateConstraintCounts {
+            trace_constraints: 1,
+            side_constraints: 1,
+        }
+    );
+    assert_eq!(
+        counts["Sum"],
+        TemplateConstraintCounts {
+            trace_constraints: 2,
+            side_constraints: 2,
+        }
+    );
+
+    let mut ts = ConstraintStatistics::new();
+    for c in &sexe.cur_state.symbolic_trace {
+        ts.update(c);
+    }
+    let mut ss = ConstraintStatistics::new();
+    for c in &sexe.cur_state.side_constraints {
+        ss.update(c);
+    }
+    assert_eq!(
+        counts.values().map(|c| c.trace_constraints).sum::<usize>(),
+        ts.total_constraints
+    );
+    assert_eq!(
+        counts.values().map(|c| c.side_constraints).sum::<usize>(),
+        ss.total_constraints
+    );
+}
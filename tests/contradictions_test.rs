@@ -0,0 +1,71 @@
+mod utils;
+
+use std::process::Command;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_value::SymbolicValue;
+use zkfuzz::mutator::contradictions::detect_unsatisfiable_constraints;
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_unsatisfiable_constraints() {
+    let path = "./tests/sample/test_contradiction.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let contradictions = detect_unsatisfiable_constraints(&sexe.cur_state.side_constraints, &prime);
+
+    assert_eq!(contradictions.len(), 1);
+    assert_eq!(
+        sexe.symbolic_library.id2name[&contradictions[0].name.id],
+        "in".to_string()
+    );
+    let constant_of = |constraint: &SymbolicValue| match constraint {
+        SymbolicValue::BinaryOp(_, _, rhs) => (**rhs).clone(),
+        _ => SymbolicValue::NOP,
+    };
+    assert_eq!(
+        constant_of(&contradictions[0].first),
+        SymbolicValue::ConstantInt(BigInt::from(0))
+    );
+    assert_eq!(
+        constant_of(&contradictions[0].second),
+        SymbolicValue::ConstantInt(BigInt::from(1))
+    );
+}
+
+#[test]
+fn test_unsatisfiable_constraints_are_not_reported_as_safe() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_contradiction.circom",
+            "--search_mode",
+            "quick",
+            "--quiet",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Unsatisfiable Constraints:"));
+    assert!(stderr
+        .trim()
+        .lines()
+        .last()
+        .unwrap()
+        .starts_with("result=unsatisfiable "));
+}
@@ -2,10 +2,13 @@ mod utils;
 
 use std::rc::Rc;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use num_bigint_dig::BigInt;
 use num_traits::identities::Zero;
 use num_traits::One;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
 use rustc_hash::FxHashMap;
 
 use program_structure::ast::Expression;
@@ -14,6 +17,11 @@ use program_structure::program_archive::ProgramArchive;
 use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
 use zkfuzz::executor::symbolic_setting::get_default_setting_for_concrete_execution;
 use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicAccess, SymbolicName, SymbolicValue};
+use zkfuzz::mutator::mutation_config::MutationConfig;
+use zkfuzz::mutator::mutation_test_update_input_fn::{
+    evaluate_coverage, update_input_population_with_coverage_maximization,
+};
+use zkfuzz::mutator::utils::BaseVerificationConfig;
 
 use crate::utils::prepare_symbolic_library;
 
@@ -76,7 +84,8 @@ pub fn concrete_execute(
             cexe.cur_state
                 .set_template_id(cexe.symbolic_library.name2id[id]);
 
-            cexe.feed_arguments(template.get_name_of_params(), args);
+            cexe.feed_arguments(template.get_name_of_params(), args)
+                .unwrap();
 
             let assignment = get_inputs(&cexe, inputs);
             cexe.concrete_execute(&"Main".to_string(), &assignment);
@@ -158,3 +167,173 @@ fn test_coverage_test() {
     cexe.record_path();
     assert_eq!(4, cexe.coverage_count());
 }
+
+fn get_output(cexe: &SymbolicExecutor) -> Option<SymbolicValue> {
+    let out = SymbolicName::new(
+        cexe.symbolic_library.name2id["out"],
+        Rc::new(vec![OwnerName {
+            id: cexe.symbolic_library.name2id["main"],
+            access: None,
+            counter: 0,
+        }]),
+        None,
+    );
+    cexe.cur_state.get_sym_val(&out).map(|v| (**v).clone())
+}
+
+#[test]
+fn test_forked_executors_are_independent() {
+    let path = "./tests/sample/test_coverage.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_concrete_execution(prime, false);
+
+    let mut cexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    cexe.turn_on_coverage_tracking();
+
+    let mut forked_library = cexe.symbolic_library.clone();
+    let main_id = forked_library.name2id["Main"];
+    assert!(Arc::ptr_eq(
+        &cexe.symbolic_library.template_library[&main_id],
+        &forked_library.template_library[&main_id]
+    ));
+    let mut forked = cexe.fork(&mut forked_library);
+
+    concrete_execute(
+        &mut forked,
+        &program_archive,
+        &[BigInt::one(), BigInt::one()],
+    );
+    concrete_execute(
+        &mut cexe,
+        &program_archive,
+        &[BigInt::zero(), BigInt::zero()],
+    );
+    forked.record_path();
+
+    assert_eq!(
+        Some(SymbolicValue::ConstantInt(BigInt::from(2))),
+        get_output(&forked)
+    );
+    assert_eq!(
+        Some(SymbolicValue::ConstantInt(BigInt::zero())),
+        get_output(&cexe)
+    );
+    assert_eq!(1, forked.coverage_count());
+    assert_eq!(0, cexe.coverage_count());
+
+    cexe.record_path();
+    cexe.clear();
+    concrete_execute(
+        &mut cexe,
+        &program_archive,
+        &[BigInt::one(), BigInt::zero()],
+    );
+    assert_eq!(
+        Some(SymbolicValue::ConstantInt(BigInt::one())),
+        get_output(&cexe)
+    );
+    assert_eq!(
+        Some(SymbolicValue::ConstantInt(BigInt::from(2))),
+        get_output(&forked)
+    );
+    assert_eq!(1, cexe.coverage_count());
+    assert_eq!(1, forked.coverage_count());
+}
+
+#[test]
+fn test_coverage_retains_inputs_hitting_new_branches() {
+    let path = "./tests/sample/test_nested_branches.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, _) = prepare_symbolic_library(path, prime.clone());
+    symbolic_library
+        .name2id
+        .insert("main".to_string(), symbolic_library.name2id.len());
+    symbolic_library
+        .id2name
+        .insert(symbolic_library.name2id["main"], "main".to_string());
+    let setting = get_default_setting_for_concrete_execution(prime.clone(), false);
+    let mut cexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+
+    let base_config = BaseVerificationConfig {
+        target_template_name: "NestedBranches".to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        ..Default::default()
+    };
+    let input_variables: Vec<_> = ["a", "b"]
+        .iter()
+        .map(|name| {
+            SymbolicName::new(
+                cexe.symbolic_library.name2id[*name],
+                Rc::new(vec![OwnerName {
+                    id: cexe.symbolic_library.name2id["main"],
+                    access: None,
+                    counter: 0,
+                }]),
+                None,
+            )
+        })
+        .collect();
+    let inputs = |a: u32, b: u32| {
+        FxHashMap::from_iter([
+            (input_variables[0].clone(), BigInt::from(a)),
+            (input_variables[1].clone(), BigInt::from(b)),
+        ])
+    };
+
+    // Each pair is an input and the number of branch outcomes it newly covers.
+    for (input, expected) in [
+        (inputs(0, 0), 1),   // a <= 10
+        (inputs(20, 0), 2),  // a > 10 and b <= 10
+        (inputs(5, 3), 0),   // duplicate of the first input
+        (inputs(20, 20), 1), // a > 10 and b > 10
+        (inputs(30, 40), 0), // duplicate of the previous input
+    ] {
+        let baseline = cexe.coverage_tracker().branch_hits().clone();
+        evaluate_coverage(&mut cexe, &input, &base_config);
+        assert_eq!(
+            cexe.coverage_tracker().new_coverage_since(&baseline),
+            expected
+        );
+    }
+    assert!(cexe
+        .coverage_tracker()
+        .branch_hits()
+        .values()
+        .all(|hits| *hits == (true, true)));
+
+    // The retained inputs never duplicate the branches taken by one another.
+    let mutation_config = MutationConfig {
+        random_value_ranges: vec![(BigInt::zero(), BigInt::from(21))],
+        random_value_probs: vec![1.0],
+        ..MutationConfig::default()
+    };
+    let mut inputs_population = Vec::new();
+    let mut rng = StdRng::seed_from_u64(42);
+    update_input_population_with_coverage_maximization(
+        &mut cexe,
+        &input_variables,
+        &mut inputs_population,
+        &Vec::new(),
+        &base_config,
+        &mutation_config,
+        &mut rng,
+    );
+    assert!(!inputs_population.is_empty());
+    assert!(inputs_population.len() <= 3);
+
+    cexe.clear_coverage_tracker();
+    for input in &inputs_population {
+        evaluate_coverage(&mut cexe, input, &base_config);
+    }
+    assert_eq!(cexe.coverage_count(), inputs_population.len());
+}
@@ -0,0 +1,78 @@
+use std::process::Command;
+
+use zkfuzz::input_user::parse_prime;
+use zkfuzz::parser_user::parse_declared_prime;
+
+const GOLDILOCKS: &str = "18446744069414584321";
+
+#[test]
+fn test_parse_declared_prime() {
+    let declared = parse_declared_prime("./tests/sample/test_goldilocks_prime.circom").unwrap();
+    assert_eq!(declared, "goldilocks");
+    assert_eq!(parse_prime(&declared).unwrap().to_string(), GOLDILOCKS);
+
+    assert_eq!(
+        parse_declared_prime("./tests/sample/test_vuln_iszero.circom"),
+        None
+    );
+}
+
+#[test]
+fn test_declared_prime_is_used_by_default() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_goldilocks_prime.circom",
+            "--search_mode",
+            "quick",
+            "--no_color",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains(&format!("Prime Number      : {}\n", GOLDILOCKS)));
+    assert!(!stderr.contains("Prime Mismatch"));
+}
+
+#[test]
+fn test_explicit_debug_prime_overrides_declared_prime() {
+    let bn254 = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_goldilocks_prime.circom",
+            "--search_mode",
+            "quick",
+            "--no_color",
+            "--debug_prime",
+            "bn254",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Prime Mismatch"));
+    assert!(stderr.contains(&format!("Prime Number      : {}\n", bn254)));
+}
+
+#[test]
+fn test_prime_mismatch_is_reported_with_quiet() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_goldilocks_prime.circom",
+            "--search_mode",
+            "quick",
+            "--no_color",
+            "--quiet",
+            "--debug_prime",
+            "bn254",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Prime Mismatch"));
+    assert!(!stderr.contains("Prime Number"));
+}
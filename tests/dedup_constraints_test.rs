@@ -0,0 +1,20 @@
+use std::process::Command;
+
+#[test]
+fn test_dedup_constraints_reports_dropped_duplicates() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_duplicated_component.circom",
+            "--search_mode",
+            "quick",
+            "--no_color",
+            "--dedup_constraints",
+        ])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    // The repeated `0 === 0` of the components is dropped from both the trace and the side
+    // constraints while they are gathered
+    assert!(stderr.contains("Removed Duplicates: 1 trace / 1 side constraints"));
+}
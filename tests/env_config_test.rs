@@ -0,0 +1,25 @@
+use zkfuzz::input_user::Input;
+
+#[test]
+fn test_options_fall_back_to_environment_variables() {
+    std::env::set_var("ZKFUZZ_SEARCH_MODE", "quick");
+    std::env::set_var("ZKFUZZ_PRIME", "goldilocks");
+
+    let input = Input::from_args(["zkfuzz", "./tests/sample/test_vuln_iszero.circom"]).unwrap();
+    assert_eq!(input.search_mode(), "quick");
+    assert_eq!(input.debug_prime(), "goldilocks");
+
+    // A flag on the command line takes precedence over the environment
+    let input = Input::from_args([
+        "zkfuzz",
+        "./tests/sample/test_vuln_iszero.circom",
+        "--search_mode",
+        "full",
+    ])
+    .unwrap();
+    assert_eq!(input.search_mode(), "full");
+    assert_eq!(input.debug_prime(), "goldilocks");
+
+    std::env::remove_var("ZKFUZZ_SEARCH_MODE");
+    std::env::remove_var("ZKFUZZ_PRIME");
+}
@@ -0,0 +1,216 @@
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use num_traits::Zero;
+
+use program_structure::ast::{Expression, Meta};
+
+use zkfuzz::api::{analyze_circuit, main_template_call, AnalysisConfig};
+use zkfuzz::error::ZkFuzzError;
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::input_user::{
+    check_search_mode, load_whitelist, parse_prime, parse_range, parse_signal_ranges,
+};
+use zkfuzz::parser_user::parse_assumptions;
+
+use crate::utils::{parse_project, prepare_symbolic_library};
+
+const BN128_PRIME: &str =
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+#[test]
+fn test_invalid_prime() {
+    assert_eq!(
+        parse_prime(BN128_PRIME).unwrap(),
+        BigInt::from_str(BN128_PRIME).unwrap()
+    );
+    assert!(matches!(
+        parse_prime("not_a_prime"),
+        Err(ZkFuzzError::InvalidPrime(value)) if value == "not_a_prime"
+    ));
+    assert!(matches!(
+        parse_prime("1"),
+        Err(ZkFuzzError::InvalidPrime(_))
+    ));
+}
+
+#[test]
+fn test_prime_formats() {
+    let bn254 = BigInt::from_str(BN128_PRIME).unwrap();
+    for value in [
+        BN128_PRIME,
+        "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001",
+        "0X30644E72E131A029B85045B68181585D2833E84879B9709143E1F593F0000001",
+        "bn254",
+        "bn128",
+    ] {
+        assert_eq!(parse_prime(value).unwrap(), bn254);
+    }
+
+    let bls12381 = BigInt::from_str(
+        "52435875175126190479447740508185965837690552500527637822603658699938581184513",
+    )
+    .unwrap();
+    assert_eq!(parse_prime("bls12381").unwrap(), bls12381);
+    assert_eq!(
+        parse_prime("0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001").unwrap(),
+        bls12381
+    );
+
+    let goldilocks = BigInt::from_str("18446744069414584321").unwrap();
+    assert_eq!(parse_prime("goldilocks").unwrap(), goldilocks);
+    assert_eq!(parse_prime("0xffffffff00000001").unwrap(), goldilocks);
+
+    assert_eq!(parse_range("0x64").unwrap(), BigInt::from(100));
+    assert!(matches!(
+        parse_prime("0x"),
+        Err(ZkFuzzError::InvalidPrime(_))
+    ));
+    assert!(matches!(
+        parse_prime("0xzz"),
+        Err(ZkFuzzError::InvalidPrime(_))
+    ));
+}
+
+#[test]
+fn test_invalid_range() {
+    assert_eq!(parse_range("100").unwrap(), BigInt::from(100));
+    assert!(matches!(
+        parse_range("-3"),
+        Err(ZkFuzzError::InvalidRange(_))
+    ));
+    assert!(matches!(
+        parse_range("ten"),
+        Err(ZkFuzzError::InvalidRange(_))
+    ));
+}
+
+#[test]
+fn test_invalid_signal_range() {
+    let ranges = parse_signal_ranges(&["in=8".to_string(), " nonce = 32 ".to_string()]).unwrap();
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges["in"], 8);
+    assert_eq!(ranges["nonce"], 32);
+
+    for entry in ["in", "in=", "=8", "in=-1", "in=eight"] {
+        assert!(matches!(
+            parse_signal_ranges(&[entry.to_string()]),
+            Err(ZkFuzzError::InvalidSignalRange(_))
+        ));
+    }
+    let err = parse_signal_ranges(&["in=8".to_string(), "in=16".to_string()]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid signal range: `in` is ranged twice"
+    );
+}
+
+#[test]
+fn test_invalid_assumption() {
+    let prime = BigInt::from_str(BN128_PRIME).unwrap();
+    let inputs = vec!["a".to_string(), "b".to_string()];
+    let expressions = parse_assumptions(
+        &["a < 2**32".to_string(), "a * b != 0".to_string()],
+        &inputs,
+        &prime,
+    )
+    .unwrap();
+    assert_eq!(expressions.len(), 2);
+
+    assert!(matches!(
+        parse_assumptions(&["a <".to_string()], &inputs, &prime),
+        Err(ZkFuzzError::InvalidAssumption(assumption, _)) if assumption == "a <"
+    ));
+}
+
+#[test]
+fn test_unsupported_search_mode() {
+    for mode in ["quick", "full", "heuristics", "ga", "off"] {
+        assert!(check_search_mode(mode).is_ok());
+    }
+    let err = check_search_mode("exhaustive").unwrap_err();
+    assert!(matches!(&err, ZkFuzzError::UnsupportedSearchMode(mode) if mode == "exhaustive"));
+    assert_eq!(err.to_string(), "search_mode=exhaustive is not supported");
+}
+
+#[test]
+fn test_whitelist_read_error() {
+    let path = std::env::temp_dir().join("zkfuzz_whitelist_test.txt");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "IsZero\nNum2Bits\n").unwrap();
+    let whitelist = load_whitelist(path).unwrap();
+    assert_eq!(whitelist.len(), 2);
+    assert!(whitelist.contains("IsZero") && whitelist.contains("Num2Bits"));
+    std::fs::remove_file(path).unwrap();
+
+    assert!(matches!(
+        load_whitelist("./tests/sample/missing_whitelist.txt"),
+        Err(ZkFuzzError::WhitelistReadError(path, _)) if path == "./tests/sample/missing_whitelist.txt"
+    ));
+}
+
+#[test]
+fn test_no_main_component() {
+    let prime = BigInt::from_str(BN128_PRIME).unwrap();
+    let mut program_archive =
+        parse_project("./tests/sample/test_vuln_iszero.circom".to_string(), prime).unwrap();
+    let (name, args) = main_template_call(&program_archive).unwrap();
+    assert_eq!(name, "VulnerableIsZero");
+    assert!(args.is_empty());
+
+    program_archive.initial_template_call = Expression::Number(Meta::new(0, 0), BigInt::zero());
+    assert!(matches!(
+        main_template_call(&program_archive),
+        Err(ZkFuzzError::NoMainComponent)
+    ));
+}
+
+#[test]
+fn test_parse_and_type_errors() {
+    assert!(matches!(
+        analyze_circuit(
+            "./tests/sample/missing_circuit.circom",
+            AnalysisConfig::default()
+        ),
+        Err(ZkFuzzError::Parse(_))
+    ));
+    assert!(matches!(
+        analyze_circuit(
+            "./tests/sample/test_type_error.circom",
+            AnalysisConfig::default()
+        ),
+        Err(ZkFuzzError::TypeCheck(_))
+    ));
+}
+
+#[test]
+fn test_wrong_number_of_template_arguments() {
+    let prime = BigInt::from_str(BN128_PRIME).unwrap();
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(
+        "./tests/sample/test_1d_array_component.circom".to_string(),
+        prime.clone(),
+    );
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+
+    // `Caller(N)` is instantiated without any argument
+    let names = program_archive.templates["Caller"]
+        .get_name_of_params()
+        .clone();
+    let err = sexe.feed_arguments(&names, &Vec::new()).unwrap_err();
+    assert_eq!(
+        err,
+        "0 arguments are given while the template parameters are [N]"
+    );
+    assert!(sexe.cur_state.symbolic_trace.is_empty());
+    assert_eq!(
+        ZkFuzzError::InvalidTemplateArguments("Caller".to_string(), err).to_string(),
+        "invalid arguments of template `Caller`: 0 arguments are given while the template parameters are [N]"
+    );
+
+    let args = vec![Expression::Number(Meta::new(0, 0), BigInt::from(2))];
+    assert!(sexe.feed_arguments(&names, &args).is_ok());
+}
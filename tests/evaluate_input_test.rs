@@ -0,0 +1,94 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use serde_json::json;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_value::OwnerName;
+use zkfuzz::mutator::safe_inputs::from_circom_input_json;
+use zkfuzz::mutator::utils::{evaluate_input, BaseVerificationConfig};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_evaluate_input() {
+    let path = "./tests/sample/test_violated_location.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    }]);
+    let input_ids = sexe.symbolic_library.template_library
+        [&sexe.symbolic_library.name2id[main_template_name]]
+        .input_ids
+        .clone();
+    let conc_setting = get_default_setting_for_concrete_execution(prime, false);
+    let mut cexe = SymbolicExecutor::new(&mut sexe.symbolic_library, &conc_setting);
+
+    // `c <-- a + 1` followed by `c === b` holds only when `b = a + 1`
+    let satisfying = from_circom_input_json(
+        &json!({"a": "1", "b": "2"}),
+        &cexe.symbolic_library.name2id,
+        &main_owner,
+        &input_ids,
+    )
+    .unwrap();
+    let evaluation = evaluate_input(&mut cexe, &satisfying, &verification_base_config);
+    assert!(evaluation.is_satisfied());
+    assert_eq!(evaluation.outputs.len(), 1);
+    assert_eq!(
+        evaluation.outputs[0]
+            .0
+            .lookup_fmt(&cexe.symbolic_library.id2name),
+        "main.c"
+    );
+    assert_eq!(evaluation.outputs[0].1, BigInt::from(2));
+
+    let violating = from_circom_input_json(
+        &json!({"a": "1", "b": "3"}),
+        &cexe.symbolic_library.name2id,
+        &main_owner,
+        &input_ids,
+    )
+    .unwrap();
+    let evaluation = evaluate_input(&mut cexe, &violating, &verification_base_config);
+    assert!(!evaluation.is_satisfied());
+    let (_, _, location) = evaluation.violated_condition.unwrap();
+    assert_eq!(location.unwrap().line, 9);
+}
@@ -0,0 +1,152 @@
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use program_structure::ast::ExpressionInfixOpcode;
+
+use zkfuzz::executor::debug_ast::DebuggableExpressionInfixOpcode;
+use zkfuzz::executor::field_ops::{BigIntFieldOps, FieldOps, U64FieldOps};
+use zkfuzz::executor::symbolic_value::{evaluate_binary_op, SymbolicValue};
+
+const GOLDILOCKS: u64 = 18446744069414584321;
+
+fn random_element(rng: &mut StdRng) -> BigInt {
+    // Bias towards the edges of the field, where overflows would show up.
+    match rng.gen_range(0, 4) {
+        0 => BigInt::from(rng.gen_range(0, 4)),
+        1 => BigInt::from(GOLDILOCKS - rng.gen_range(1, 5)),
+        _ => BigInt::from(rng.gen_range(0, GOLDILOCKS)),
+    }
+}
+
+#[test]
+fn test_u64_field_ops_selection() {
+    assert!(U64FieldOps::new(&BigInt::from(GOLDILOCKS)).is_some());
+    assert!(U64FieldOps::new(&BigInt::from(17)).is_some());
+    assert!(U64FieldOps::new(
+        &BigInt::from_str(
+            "21888242871839275222246405745257275088548364400416034343698204186575808495617"
+        )
+        .unwrap()
+    )
+    .is_none());
+}
+
+#[test]
+fn test_u64_field_ops_match_bigint() {
+    let prime = BigInt::from(GOLDILOCKS);
+    let fast = U64FieldOps::new(&prime).unwrap();
+    let reference = BigIntFieldOps {
+        prime: prime.clone(),
+    };
+    let mut rng = StdRng::seed_from_u64(42);
+
+    for _ in 0..2000 {
+        let a = random_element(&mut rng);
+        let b = random_element(&mut rng);
+        assert_eq!(fast.add(&a, &b), reference.add(&a, &b), "{} + {}", a, b);
+        assert_eq!(fast.sub(&a, &b), reference.sub(&a, &b), "{} - {}", a, b);
+        assert_eq!(fast.mul(&a, &b), reference.mul(&a, &b), "{} * {}", a, b);
+        assert_eq!(fast.div(&a, &b), reference.div(&a, &b), "{} / {}", a, b);
+        assert_eq!(fast.pow(&a, &b), reference.pow(&a, &b), "{} ** {}", a, b);
+    }
+}
+
+#[test]
+fn test_evaluate_binary_op_goldilocks() {
+    let prime = BigInt::from(GOLDILOCKS);
+    let reference = BigIntFieldOps {
+        prime: prime.clone(),
+    };
+    let mut rng = StdRng::seed_from_u64(7);
+
+    for _ in 0..500 {
+        let a = random_element(&mut rng);
+        let b = random_element(&mut rng);
+        for (opcode, expected) in [
+            (ExpressionInfixOpcode::Add, reference.add(&a, &b)),
+            (ExpressionInfixOpcode::Sub, reference.sub(&a, &b)),
+            (ExpressionInfixOpcode::Mul, reference.mul(&a, &b)),
+            (ExpressionInfixOpcode::Div, reference.div(&a, &b)),
+            (ExpressionInfixOpcode::Pow, reference.pow(&a, &b)),
+        ] {
+            assert_eq!(
+                evaluate_binary_op(
+                    &SymbolicValue::ConstantInt(a.clone()),
+                    &SymbolicValue::ConstantInt(b.clone()),
+                    &prime,
+                    &DebuggableExpressionInfixOpcode(opcode)
+                ),
+                SymbolicValue::ConstantInt(expected)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_div_by_non_invertible_element_of_composite_modulus() {
+    let div = |a: &BigInt, b: &BigInt, modulus: &BigInt| {
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(a.clone()),
+            &SymbolicValue::ConstantInt(b.clone()),
+            modulus,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Div),
+        )
+    };
+
+    // Both below 2^64, handled by `U64FieldOps`, and above, handled with `BigInt`
+    for modulus in [
+        BigInt::from(12),
+        BigInt::from(1u64 << 62) * BigInt::from(48),
+    ] {
+        // 4 divides both 12 and 2^66 * 3
+        assert_eq!(
+            div(&BigInt::from(5), &BigInt::from(4), &modulus),
+            SymbolicValue::Undefined
+        );
+        // 7 is coprime to both moduli, so that 5 / 7 is still well defined
+        let quotient = div(&BigInt::from(5), &BigInt::from(7), &modulus);
+        match &quotient {
+            SymbolicValue::ConstantInt(q) => {
+                assert_eq!((q * BigInt::from(7)) % &modulus, BigInt::from(5))
+            }
+            _ => panic!("5 / 7 should be defined modulo {}", modulus),
+        }
+        // Dividing zero stays zero, as in circom
+        assert_eq!(
+            div(&BigInt::from(0), &BigInt::from(4), &modulus),
+            SymbolicValue::ConstantInt(BigInt::from(0))
+        );
+
+        // Anything computed from an undefined value is undefined
+        assert_eq!(
+            evaluate_binary_op(
+                &SymbolicValue::Undefined,
+                &SymbolicValue::ConstantInt(BigInt::from(1)),
+                &modulus,
+                &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Add),
+            ),
+            SymbolicValue::Undefined
+        );
+
+        let reference = BigIntFieldOps {
+            prime: modulus.clone(),
+        };
+        assert_eq!(
+            reference.div(&BigInt::from(5), &BigInt::from(4)),
+            BigInt::from(0)
+        );
+    }
+
+    let fast = U64FieldOps::new(&BigInt::from(12)).unwrap();
+    assert_eq!(
+        fast.div(&BigInt::from(5), &BigInt::from(4)),
+        BigInt::from(0)
+    );
+    assert_eq!(
+        fast.div(&BigInt::from(5), &BigInt::from(7)),
+        BigInt::from(11)
+    );
+}
@@ -0,0 +1,94 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use num_traits::One;
+use rustc_hash::FxHashMap;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_concrete_execution;
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicName};
+use zkfuzz::mutator::utils::{
+    replay_field_reductions, BaseVerificationConfig, CounterExample, VerificationResult,
+};
+
+use crate::utils::prepare_symbolic_library;
+
+fn replay(prime: &BigInt, a: BigInt, track_field_reductions: bool) -> CounterExample {
+    let path = "./tests/sample/test_field_overflow.circom".to_string();
+    let (mut symbolic_library, _) = prepare_symbolic_library(path, prime.clone());
+    symbolic_library
+        .name2id
+        .insert("main".to_string(), symbolic_library.name2id.len());
+    symbolic_library
+        .id2name
+        .insert(symbolic_library.name2id["main"], "main".to_string());
+
+    let mut setting = get_default_setting_for_concrete_execution(prime.clone(), false);
+    setting.track_field_reductions = track_field_reductions;
+    let mut cexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+
+    let base_config = BaseVerificationConfig {
+        target_template_name: "SquareOverflow".to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(100),
+        ..Default::default()
+    };
+
+    let mut assignment = FxHashMap::default();
+    assignment.insert(
+        SymbolicName::new(
+            cexe.symbolic_library.name2id["a"],
+            Rc::new(vec![OwnerName {
+                id: cexe.symbolic_library.name2id["main"],
+                counter: 0,
+                access: None,
+            }]),
+            None,
+        ),
+        a,
+    );
+    let field_reductions = replay_field_reductions(&mut cexe, &assignment, &base_config);
+
+    CounterExample {
+        flag: VerificationResult::WellConstrained,
+        target_output: None,
+        assignment: assignment,
+        field_reductions: field_reductions,
+        provenance: None,
+    }
+}
+
+#[test]
+fn test_track_field_reductions_mul_overflow() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let minus_one = &prime - BigInt::one();
+
+    let counter_example = replay(&prime, minus_one.clone(), true);
+    assert_eq!(counter_example.field_reductions.len(), 1);
+    let event = &counter_example.field_reductions[0];
+    assert_eq!(event.op, "Mul");
+    assert_eq!(event.lhs, minus_one);
+    assert_eq!(event.rhs, minus_one);
+    assert_eq!(event.unreduced, &minus_one * &minus_one);
+    assert_eq!(event.reduced, BigInt::one());
+
+    let json = counter_example.to_json_with_meta(&FxHashMap::default(), &FxHashMap::default());
+    assert_eq!(json["9_field_reductions"][0]["op"], "Mul");
+    assert_eq!(json["9_field_reductions"][0]["reduced"], "1");
+
+    // Products that stay below the prime are not reductions.
+    let counter_example = replay(&prime, BigInt::from(3), true);
+    assert!(counter_example.field_reductions.is_empty());
+    let json = counter_example.to_json_with_meta(&FxHashMap::default(), &FxHashMap::default());
+    assert!(json.get("9_field_reductions").is_none());
+
+    // Nothing is recorded unless the setting is enabled.
+    let counter_example = replay(&prime, minus_one, false);
+    assert!(counter_example.field_reductions.is_empty());
+}
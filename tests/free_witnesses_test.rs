@@ -0,0 +1,64 @@
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::mutator::free_witnesses::{find_free_witnesses, find_unconstrained_assignments};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_free_witnesses_if_else() {
+    let path = "./tests/sample/test_if_else.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let free_witnesses = find_free_witnesses(
+        &sexe.cur_state.symbolic_trace,
+        &sexe.cur_state.side_constraints,
+        &prime,
+    );
+
+    assert_eq!(free_witnesses.len(), 1);
+    assert_eq!(
+        sexe.symbolic_library.id2name[&free_witnesses[0].id],
+        "inv".to_string()
+    );
+}
+
+#[test]
+fn test_unconstrained_assignments() {
+    let path = "./tests/sample/test_lone_assign.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let unconstrained = find_unconstrained_assignments(
+        &sexe.cur_state.symbolic_trace,
+        &sexe.cur_state.side_constraints,
+    );
+
+    assert_eq!(unconstrained.len(), 1);
+    assert_eq!(
+        sexe.symbolic_library.id2name[&unconstrained[0].id],
+        "b".to_string()
+    );
+}
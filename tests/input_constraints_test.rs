@@ -0,0 +1,134 @@
+mod utils;
+
+use std::cell::Cell;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use serde_json::json;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicName};
+use zkfuzz::mutator::mutation_config::load_config_from_json;
+use zkfuzz::mutator::mutation_test::mutation_test_search;
+use zkfuzz::mutator::mutation_test_evolution_fn::simple_evolution;
+use zkfuzz::mutator::mutation_test_operators::{
+    FnTraceMutation, RandomCrossover, RouletteSelection,
+};
+use zkfuzz::mutator::mutation_test_trace_fitness_fn::evaluate_trace_fitness_by_error;
+use zkfuzz::mutator::mutation_test_trace_initialization_fn::initialize_population_with_operator_or_const_replacement;
+use zkfuzz::mutator::mutation_test_trace_mutation_fn::mutate_trace_with_operator_or_const_replacement;
+use zkfuzz::mutator::mutation_test_update_input_fn::update_input_population_with_random_sampling;
+use zkfuzz::mutator::safe_inputs::from_circom_input_json;
+use zkfuzz::mutator::utils::BaseVerificationConfig;
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_input_constraints_fix_inputs_across_generations() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let path = "./tests/sample/test_lessthan.circom".to_string();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    }]);
+    let input_ids = sexe.symbolic_library.template_library
+        [&sexe.symbolic_library.name2id["VulnerableLessThan"]]
+        .input_ids
+        .clone();
+    let input_constraints = from_circom_input_json(
+        &json!({"a": "5"}),
+        &sexe.symbolic_library.name2id,
+        &main_owner,
+        &input_ids,
+    )
+    .unwrap();
+
+    let a = SymbolicName::new(sexe.symbolic_library.name2id["a"], main_owner.clone(), None);
+    assert_eq!(input_constraints.len(), 1);
+    assert_eq!(input_constraints[&a], BigInt::from(5));
+
+    assert!(from_circom_input_json(
+        &json!({"out": "1"}),
+        &sexe.symbolic_library.name2id,
+        &main_owner,
+        &input_ids,
+    )
+    .is_err());
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: "VulnerableLessThan".to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        input_constraints: input_constraints,
+        ..Default::default()
+    };
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let conc_setting = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &conc_setting);
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.max_generations = 20;
+
+    let num_evaluated = Cell::new(0_usize);
+    let result = mutation_test_search(
+        &mut conc_executor,
+        &symbolic_trace,
+        &side_constraints,
+        &verification_base_config,
+        &mutation_config,
+        update_input_population_with_random_sampling,
+        |sexe,
+         base_config,
+         mutation_config,
+         symbolic_trace,
+         side_constraints,
+         runtime_mutable_positions,
+         trace_mutation,
+         inputs_assignment,
+         fitness_scores_inputs| {
+            for inp in inputs_assignment {
+                assert_eq!(inp[&a], BigInt::from(5));
+            }
+            num_evaluated.set(num_evaluated.get() + inputs_assignment.len());
+            evaluate_trace_fitness_by_error(
+                sexe,
+                base_config,
+                mutation_config,
+                symbolic_trace,
+                side_constraints,
+                runtime_mutable_positions,
+                trace_mutation,
+                inputs_assignment,
+                fitness_scores_inputs,
+            )
+        },
+        simple_evolution,
+        &FnTraceMutation {
+            initialization_fn: initialize_population_with_operator_or_const_replacement,
+            mutation_fn: mutate_trace_with_operator_or_const_replacement,
+        },
+        &RandomCrossover,
+        &RouletteSelection,
+        &mut |_| {},
+    );
+
+    assert!(num_evaluated.get() > 0);
+    if let Some(counter_example) = result.counter_example {
+        assert_eq!(counter_example.assignment[&a], BigInt::from(5));
+    }
+}
@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+use std::process::Command;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::parser_user::parse_file;
+
+fn parse_templates(link_libraries: &[&str]) -> Vec<String> {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let program_archive = parse_file(
+        "./tests/sample/library_include/circuit.circom".to_string(),
+        link_libraries.iter().map(PathBuf::from).collect(),
+        &prime,
+    )
+    .unwrap();
+    let mut names = program_archive
+        .templates
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    names.sort();
+    names
+}
+
+#[test]
+fn test_library_search_order() {
+    assert_eq!(
+        parse_templates(&[
+            "./tests/sample/library_include/first",
+            "./tests/sample/library_include/second",
+        ]),
+        vec!["FirstGadget", "LocalTemplate", "Main"]
+    );
+    assert_eq!(
+        parse_templates(&[
+            "./tests/sample/library_include/second",
+            "./tests/sample/library_include/first",
+        ]),
+        vec!["LocalTemplate", "Main", "SecondGadget"]
+    );
+}
+
+#[test]
+fn test_library_option() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/library_include/circuit.circom",
+            "--library",
+            "./tests/sample/library_include/first",
+            "-l",
+            "./tests/sample/library_include/second",
+            "--search_mode",
+            "off",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/library_include/circuit.circom",
+            "--search_mode",
+            "off",
+        ])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
@@ -0,0 +1,69 @@
+use std::process::Command;
+
+fn list_signals(path: &str, extra_args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[path, "--list_signals", "--no_color"])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_list_signals() {
+    let (success, stdout) = list_signals("./tests/sample/test_1d_array_component.circom", &[]);
+    assert!(success);
+    let lines: Vec<Vec<&str>> = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            vec!["input", "a"],
+            vec!["input", "b"],
+            vec!["output", "out[2]"],
+        ]
+    );
+}
+
+#[test]
+fn test_list_signals_as_json() {
+    let (success, stdout) = list_signals(
+        "./tests/sample/test_1d_array_component.circom",
+        &["--stats_format", "json"],
+    );
+    assert!(success);
+    let listing: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(listing["template"], "Caller");
+    assert_eq!(
+        listing["signals"],
+        serde_json::json!([
+            {"name": "a", "type": "input", "dimensions": [], "public": false},
+            {"name": "b", "type": "input", "dimensions": [], "public": false},
+            {"name": "out", "type": "output", "dimensions": [2], "public": false},
+        ])
+    );
+}
+
+#[test]
+fn test_list_signals_marks_public_inputs() {
+    let (success, stdout) = list_signals("./tests/sample/test_public_input.circom", &[]);
+    assert!(success);
+    let lines: Vec<Vec<&str>> = stdout
+        .lines()
+        .map(|line| line.split_whitespace().collect())
+        .collect();
+    assert_eq!(
+        lines,
+        vec![
+            vec!["input", "(public)", "a"],
+            vec!["input", "b"],
+            vec!["output", "out"],
+            vec!["intermediate", "inv"],
+        ]
+    );
+}
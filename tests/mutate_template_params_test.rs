@@ -0,0 +1,41 @@
+use std::process::Command;
+
+fn run(extra_args: &[&str]) -> (bool, String, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_param_mutation.circom",
+            "--search_mode",
+            "quick",
+            "--output_format",
+            "circom",
+            "--no_color",
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn test_mutate_template_params() {
+    // The main component instantiates the template with n = 2, which is safe
+    let (success, stdout, _) = run(&[]);
+    assert!(success);
+    assert!(!stdout.contains("\"in\""));
+
+    // The bug only exists for n = 1, which is reached by mutating the parameter
+    let (success, stdout, stderr) = run(&["--mutate_template_params", "--param_bounds", "n=1:3"]);
+    assert!(success);
+    assert!(stderr.contains("n=1"));
+    assert!(stderr.contains("n=3"));
+    assert!(stdout.contains("\"in\""));
+
+    // The grid must be small, and the parameters to mutate must be bounded
+    assert!(!run(&["--mutate_template_params", "--param_bounds", "n=1:1000"]).0);
+    assert!(!run(&["--mutate_template_params"]).0);
+    assert!(!run(&["--mutate_template_params", "--param_bounds", "m=1:3"]).0);
+}
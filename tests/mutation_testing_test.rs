@@ -1,27 +1,49 @@
 mod utils;
 
+use std::cell::Cell;
+use std::rc::Rc;
 use std::str::FromStr;
 
 use num_bigint_dig::BigInt;
+use num_traits::Zero;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rustc_hash::FxHashMap;
 
-use program_structure::ast::Expression;
+use program_structure::ast::{Expression, ExpressionInfixOpcode, ExpressionPrefixOpcode};
 
+use zkfuzz::executor::debug_ast::{
+    DebuggableExpressionInfixOpcode, DebuggableExpressionPrefixOpcode,
+};
 use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
 use zkfuzz::executor::symbolic_setting::{
     get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
 };
+use zkfuzz::executor::symbolic_state::SymbolicTrace;
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicAccess, SymbolicName, SymbolicValue};
 use zkfuzz::mutator::utils::{
     BaseVerificationConfig, CounterExample, UnderConstrainedType, VerificationResult,
 };
 
-use zkfuzz::mutator::mutation_config::load_config_from_json;
-use zkfuzz::mutator::mutation_test::{mutation_test_search, MutationTestResult};
-use zkfuzz::mutator::mutation_test_crossover_fn::random_crossover;
+use zkfuzz::mutator::mutation_config::{
+    load_config_from_json, load_config_from_json_str, MutationConfig, MutationRateSchedule,
+    ScheduleType,
+};
+use zkfuzz::mutator::mutation_test::{
+    load_population_from_json, mutation_test_search, save_population_to_json, Candidate, Gene,
+    MutationTestResult, Population, PrefilterVerdict, ProgressEvent, TopCandidates,
+};
+use zkfuzz::mutator::mutation_test_crossover_fn::input_uniform_crossover;
 use zkfuzz::mutator::mutation_test_evolution_fn::simple_evolution;
-use zkfuzz::mutator::mutation_test_trace_fitness_fn::evaluate_trace_fitness_by_error;
+use zkfuzz::mutator::mutation_test_operators::{
+    FnTraceMutation, OperatorRegistry, RandomCrossover, RouletteSelection, TraceMutation,
+};
+use zkfuzz::mutator::mutation_test_trace_fitness_fn::{
+    evaluate_trace_fitness_by_distance, evaluate_trace_fitness_by_error,
+};
 use zkfuzz::mutator::mutation_test_trace_initialization_fn::initialize_population_with_operator_or_const_replacement;
 use zkfuzz::mutator::mutation_test_trace_mutation_fn::mutate_trace_with_operator_or_const_replacement;
-use zkfuzz::mutator::mutation_test_trace_selection_fn::roulette_selection;
+use zkfuzz::mutator::mutation_test_trace_selection_fn::{roulette_selection, tournament_selection};
 use zkfuzz::mutator::mutation_test_update_input_fn::{
     update_input_population_with_fitness_score, update_input_population_with_random_sampling,
 };
@@ -29,6 +51,44 @@ use zkfuzz::mutator::mutation_test_update_input_fn::{
 use crate::utils::{execute, prepare_symbolic_library};
 
 fn conduct_mutation_testing(path: String, update_input_method: String) -> MutationTestResult {
+    let mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    conduct_mutation_testing_with_config(path, update_input_method, &mutation_config)
+}
+
+fn conduct_mutation_testing_with_config(
+    path: String,
+    update_input_method: String,
+    mutation_config: &MutationConfig,
+) -> MutationTestResult {
+    conduct_mutation_testing_with_progress(path, update_input_method, mutation_config, &mut |_| {})
+}
+
+fn conduct_mutation_testing_with_progress(
+    path: String,
+    update_input_method: String,
+    mutation_config: &MutationConfig,
+    progress_fn: &mut dyn FnMut(ProgressEvent),
+) -> MutationTestResult {
+    let trace_mutation = FnTraceMutation {
+        initialization_fn: initialize_population_with_operator_or_const_replacement,
+        mutation_fn: mutate_trace_with_operator_or_const_replacement,
+    };
+    conduct_mutation_testing_with_trace_mutation(
+        path,
+        update_input_method,
+        mutation_config,
+        &trace_mutation,
+        progress_fn,
+    )
+}
+
+fn conduct_mutation_testing_with_trace_mutation(
+    path: String,
+    update_input_method: String,
+    mutation_config: &MutationConfig,
+    trace_mutation: &dyn TraceMutation,
+    progress_fn: &mut dyn FnMut(ProgressEvent),
+) -> MutationTestResult {
     let prime = BigInt::from_str(
         "21888242871839275222246405745257275088548364400416034343698204186575808495617",
     )
@@ -53,21 +113,19 @@ fn conduct_mutation_testing(path: String, update_input_method: String) -> Mutati
         target_template_name: main_template_name.to_string(),
         prime: prime.clone(),
         range: prime.clone(),
-        quick_mode: false,
-        heuristics_mode: false,
-        progress_interval: 10000,
         template_param_names: template_param_names,
         template_param_values: template_param_values,
+        ..Default::default()
     };
 
     let subse_base_config = get_default_setting_for_concrete_execution(prime, false);
     let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &subse_base_config);
-    conc_executor.feed_arguments(
-        &verification_base_config.template_param_names,
-        &verification_base_config.template_param_values,
-    );
-
-    let mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    conc_executor
+        .feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
 
     let update_func = if update_input_method == "fitness" {
         update_input_population_with_fitness_score
@@ -80,14 +138,14 @@ fn conduct_mutation_testing(path: String, update_input_method: String) -> Mutati
         &sexe.cur_state.symbolic_trace.clone(),
         &sexe.cur_state.side_constraints.clone(),
         &verification_base_config,
-        &mutation_config,
-        initialize_population_with_operator_or_const_replacement,
+        mutation_config,
         update_func,
         evaluate_trace_fitness_by_error,
         simple_evolution,
-        mutate_trace_with_operator_or_const_replacement,
-        random_crossover,
-        roulette_selection,
+        trace_mutation,
+        &RandomCrossover,
+        &RouletteSelection,
+        progress_fn,
     )
 }
 
@@ -266,3 +324,815 @@ fn test_array_template_parameter() {
         })
     ));
 }
+
+#[test]
+fn test_best_genome_log() {
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.log_best_genome = true;
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+
+    assert!(result.counter_example.is_some());
+    assert_eq!(result.best_genome_log.len(), result.generation + 1);
+    for (generation, line) in result.best_genome_log.iter().enumerate() {
+        assert!(line.starts_with(&format!("🧬 Best genome of generation {}: ", generation)));
+    }
+
+    mutation_config.log_best_genome = false;
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+    assert!(result.best_genome_log.is_empty());
+}
+
+#[test]
+fn test_coverage_log() {
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.save_coverage_log = true;
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_coverage.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+
+    let num_generations = if result.counter_example.is_some() {
+        result.generation + 1
+    } else {
+        result.generation
+    };
+    assert_eq!(result.coverage_log.len(), num_generations);
+    assert!(result.coverage_log[0] > 0);
+    assert!(result.coverage_log.windows(2).all(|w| w[0] <= w[1]));
+
+    mutation_config.save_coverage_log = false;
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_coverage.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+    assert!(result.coverage_log.is_empty());
+}
+
+#[test]
+fn test_progress_events() {
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.max_generations = 20;
+    mutation_config.progress_interval = 3;
+    mutation_config.save_coverage_log = true;
+    let mut events = Vec::new();
+    let result = conduct_mutation_testing_with_progress(
+        "./tests/sample/test_coverage.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+        &mut |event| events.push(event),
+    );
+
+    assert!(!events.is_empty());
+    assert_eq!(events[0].iteration, 0);
+    assert!(events.windows(2).all(|w| w[0].iteration < w[1].iteration
+        && w[0].candidates_tried < w[1].candidates_tried
+        && w[0].coverage_count <= w[1].coverage_count
+        && w[0].elapsed_ms <= w[1].elapsed_ms));
+    for event in &events {
+        assert!(event.iteration % 3 == 0 || event.best_fitness == BigInt::from(0));
+    }
+    if result.counter_example.is_some() {
+        let last = events.last().unwrap();
+        assert_eq!(last.iteration, result.generation);
+        assert_eq!(last.best_fitness, BigInt::from(0));
+    } else {
+        assert_eq!(events.len(), 7);
+    }
+
+    let line = serde_json::to_string(&events[0]).unwrap();
+    assert!(line.starts_with("{\"iteration\":0,\"best_fitness\":\""));
+}
+
+#[test]
+fn test_top_candidates() {
+    let candidate = |fitness: i64, generation: usize| Candidate {
+        fitness: BigInt::from(fitness),
+        generation: generation,
+        trace_mutation: format!("mutation {}", generation),
+        assignment: FxHashMap::default(),
+    };
+
+    let mut top_candidates = TopCandidates::new(3);
+    for (generation, fitness) in [-5, -2, -9, -2, -1, -7, -3].into_iter().enumerate() {
+        if top_candidates.admits(&BigInt::from(fitness)) {
+            top_candidates.offer(candidate(fitness, generation));
+        }
+    }
+    // A candidate already kept is not kept twice
+    top_candidates.offer(candidate(-1, 4));
+
+    let kept: Vec<(BigInt, usize)> = top_candidates
+        .into_sorted_vec()
+        .into_iter()
+        .map(|candidate| (candidate.fitness, candidate.generation))
+        .collect();
+    // Among equal fitness scores, the earliest candidate ranks first
+    assert_eq!(
+        kept,
+        vec![
+            (BigInt::from(-1), 4),
+            (BigInt::from(-2), 1),
+            (BigInt::from(-2), 3)
+        ]
+    );
+
+    let mut no_candidates = TopCandidates::new(0);
+    assert!(!no_candidates.admits(&BigInt::from(0)));
+    no_candidates.offer(candidate(0, 0));
+    assert!(no_candidates.into_sorted_vec().is_empty());
+}
+
+#[test]
+fn test_top_candidates_of_search() {
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.max_generations = 20;
+    mutation_config.num_top_candidates = 3;
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_safelessthan.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+
+    assert!(result.counter_example.is_none());
+    assert!(!result.top_candidates.is_empty());
+    assert!(result.top_candidates.len() <= 3);
+    assert!(result
+        .top_candidates
+        .windows(2)
+        .all(|w| w[0].fitness >= w[1].fitness));
+    for candidate in &result.top_candidates {
+        assert!(candidate.fitness < BigInt::from(0));
+        assert!(candidate.generation < result.generation);
+        assert!(!candidate.assignment.is_empty());
+    }
+
+    mutation_config.num_top_candidates = 0;
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_safelessthan.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+    assert!(result.top_candidates.is_empty());
+}
+
+#[test]
+fn test_default_mutation_config_round_trip() {
+    let config = load_config_from_json_str(&MutationConfig::default_json()).unwrap();
+    assert_eq!(
+        serde_json::to_value(&config).unwrap(),
+        serde_json::to_value(&MutationConfig::default()).unwrap()
+    );
+}
+
+#[test]
+fn test_unknown_mutation_config_key() {
+    let error = load_config_from_json_str(r#"{"seed":42,"mutation_rat":0.5}"#)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("unknown field `mutation_rat`"));
+}
+
+#[test]
+fn test_mutation_rate_schedule() {
+    let mut mutation_config = MutationConfig::default();
+    mutation_config.max_generations = 100;
+    assert_eq!(mutation_config.mutation_rate_at(0), 0.3);
+    assert_eq!(mutation_config.mutation_rate_at(50), 0.3);
+
+    mutation_config.mutation_rate_schedule = Some(MutationRateSchedule {
+        start_rate: 0.8,
+        end_rate: 0.2,
+        schedule_type: ScheduleType::Linear,
+    });
+    assert!((mutation_config.mutation_rate_at(0) - 0.8).abs() < 1e-9);
+    assert!((mutation_config.mutation_rate_at(50) - 0.5).abs() < 1e-9);
+    assert!((mutation_config.mutation_rate_at(100) - 0.2).abs() < 1e-9);
+
+    let mut mutation_config =
+        load_config_from_json_str(r#"{"max_generations":100,"mutation_rate_schedule":{"start_rate":0.8,"end_rate":0.2,"schedule_type":"exponential"}}"#)
+            .unwrap();
+    assert!((mutation_config.mutation_rate_at(0) - 0.8).abs() < 1e-9);
+    assert!((mutation_config.mutation_rate_at(50) - 0.4).abs() < 1e-9);
+    assert!((mutation_config.mutation_rate_at(100) - 0.2).abs() < 1e-9);
+
+    // A schedule starting and ending at the same rate is constant
+    mutation_config.mutation_rate_schedule = Some(MutationRateSchedule {
+        start_rate: 0.3,
+        end_rate: 0.3,
+        schedule_type: ScheduleType::Exponential,
+    });
+    for generation in [0, 50, 100] {
+        assert!((mutation_config.mutation_rate_at(generation) - 0.3).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_exponential_schedule_rejects_non_positive_rates() {
+    let error = load_config_from_json_str(r#"{"mutation_rate_schedule":{"start_rate":0.0,"end_rate":0.2,"schedule_type":"exponential"}}"#)
+        .err()
+        .unwrap();
+    assert!(error.to_string().contains("requires positive rates"));
+
+    // The linear schedule is well-defined for a zero rate
+    assert!(load_config_from_json_str(
+        r#"{"mutation_rate_schedule":{"start_rate":0.0,"end_rate":0.2,"schedule_type":"linear"}}"#
+    )
+    .is_ok());
+}
+
+#[test]
+fn test_inline_mutation_config() {
+    let file_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    let inline_config = load_config_from_json_str(r#"{"seed":42}"#).unwrap();
+    assert_eq!(
+        serde_json::to_value(&file_config).unwrap(),
+        serde_json::to_value(&inline_config).unwrap()
+    );
+
+    let file_result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &file_config,
+    );
+    let inline_result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &inline_config,
+    );
+
+    assert_eq!(file_result.random_seed, inline_result.random_seed);
+    assert_eq!(file_result.generation, inline_result.generation);
+    assert_eq!(
+        file_result.counter_example.map(|ce| ce.assignment),
+        inline_result.counter_example.map(|ce| ce.assignment)
+    );
+}
+
+#[test]
+fn test_zero_input_mutation_weight() {
+    let path = "./tests/sample/test_lessthan.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let a = SymbolicName::new(sexe.symbolic_library.name2id["a"], main_owner.clone(), None);
+    let b = SymbolicName::new(sexe.symbolic_library.name2id["b"], main_owner.clone(), None);
+
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.input_population_size = 10;
+    mutation_config.mutation_rate = 1.0;
+    mutation_config
+        .input_mutation_weights
+        .insert("a".to_string(), 0.0);
+
+    let mut rng = StdRng::seed_from_u64(mutation_config.seed.unwrap());
+    let input_variables = vec![a.clone(), b.clone()];
+    let mut inputs_population: Vec<FxHashMap<SymbolicName, BigInt>> = (0..10)
+        .map(|i| FxHashMap::from_iter([(a.clone(), BigInt::from(7)), (b.clone(), BigInt::from(i))]))
+        .collect();
+    let scores = vec![BigInt::from(1); 10];
+
+    for _ in 0..20 {
+        update_input_population_with_fitness_score(
+            &mut sexe,
+            &input_variables,
+            &mut inputs_population,
+            &scores,
+            &verification_base_config,
+            &mutation_config,
+            &mut rng,
+        );
+        update_input_population_with_random_sampling(
+            &mut sexe,
+            &input_variables,
+            &mut inputs_population,
+            &scores,
+            &verification_base_config,
+            &mutation_config,
+            &mut rng,
+        );
+    }
+
+    assert_eq!(inputs_population.len(), 10);
+    for inp in &inputs_population {
+        assert_eq!(inp[&a], BigInt::from(7));
+    }
+    assert!(inputs_population
+        .iter()
+        .any(|inp| inp[&b] >= BigInt::from(10)));
+}
+
+#[test]
+fn test_input_bit_hints() {
+    let path = "./tests/sample/test_bit_width_hint.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let a = SymbolicName::new(sexe.symbolic_library.name2id["a"], main_owner.clone(), None);
+    let b = SymbolicName::new(sexe.symbolic_library.name2id["b"], main_owner.clone(), None);
+
+    // `a` is passed to `Num2Bits(8)`, while `b` is not range-checked
+    assert_eq!(sexe.bit_width_hints.get(&a), Some(&8));
+    assert!(!sexe.bit_width_hints.contains_key(&b));
+
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.input_population_size = 50;
+    mutation_config.mutation_rate = 1.0;
+    mutation_config.input_bit_hints.insert("a".to_string(), 8);
+
+    let mut rng = StdRng::seed_from_u64(mutation_config.seed.unwrap());
+    let input_variables = vec![a.clone(), b.clone()];
+    let mut inputs_population: Vec<FxHashMap<SymbolicName, BigInt>> = Vec::new();
+    let scores = vec![BigInt::from(1); 50];
+
+    let upper = BigInt::from(256);
+    let mut num_large_b = 0;
+    for _ in 0..20 {
+        update_input_population_with_random_sampling(
+            &mut sexe,
+            &input_variables,
+            &mut inputs_population,
+            &scores,
+            &verification_base_config,
+            &mutation_config,
+            &mut rng,
+        );
+        update_input_population_with_fitness_score(
+            &mut sexe,
+            &input_variables,
+            &mut inputs_population,
+            &scores,
+            &verification_base_config,
+            &mutation_config,
+            &mut rng,
+        );
+        assert_eq!(inputs_population.len(), 50);
+        for inp in &inputs_population {
+            assert!(inp[&a] >= BigInt::zero() && inp[&a] < upper);
+            if inp[&b] >= upper {
+                num_large_b += 1;
+            }
+        }
+    }
+    assert!(num_large_b > 0);
+}
+
+#[test]
+fn test_vuln_mod_by_signal() {
+    let path = "./tests/sample/test_vuln_mod_by_signal.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    // Every input goes through the zero-division solver
+    let mutation_config =
+        load_config_from_json_str(r#"{"seed":42,"zero_div_attempt_prob":1.0}"#).unwrap();
+    let result =
+        conduct_mutation_testing_with_config(path.clone(), "random".to_string(), &mutation_config);
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+    let b = SymbolicName::new(
+        sexe.symbolic_library.name2id["b"],
+        Rc::new(vec![OwnerName {
+            id: sexe.symbolic_library.name2id["main"],
+            access: None,
+            counter: 0,
+        }]),
+        None,
+    );
+
+    let counter_example = result.counter_example.unwrap();
+    assert!(matches!(
+        counter_example.flag,
+        VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(..))
+    ));
+    assert_eq!(counter_example.assignment[&b], BigInt::from(0));
+}
+
+#[test]
+fn test_prefilter_trivially_safe() {
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    // The pre-filter is disabled by default
+    assert_eq!(mutation_config.prefilter_samples, 0);
+    mutation_config.prefilter_samples = 8;
+
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_trivially_safe.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+
+    assert_eq!(
+        result.prefilter_verdict,
+        PrefilterVerdict::LooksWellConstrained
+    );
+    assert_eq!(result.generation, 0);
+    assert!(result.counter_example.is_none());
+
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+    assert_eq!(result.prefilter_verdict, PrefilterVerdict::Undecided);
+}
+
+#[test]
+fn test_fixed_seed_replay() {
+    let mut mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+    mutation_config.seed = Some(1234);
+
+    let render = |result: &MutationTestResult| {
+        let counter_example = result.counter_example.as_ref().unwrap();
+        let mut lookup = FxHashMap::default();
+        for name in counter_example.assignment.keys() {
+            lookup.insert(name.id, name.id.to_string());
+            for owner in name.owner.iter() {
+                lookup.insert(owner.id, owner.id.to_string());
+            }
+        }
+        serde_json::to_string(&counter_example.to_json_with_meta(&lookup, &FxHashMap::default()))
+            .unwrap()
+    };
+
+    let first = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+    let second = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+
+    assert_eq!(first.random_seed, 1234);
+    assert_eq!(second.random_seed, 1234);
+    assert_eq!(first.fitness_score_log, second.fitness_score_log);
+    assert_eq!(render(&first), render(&second));
+
+    // A zero seed is a seed like any other
+    mutation_config.seed = Some(0);
+    let result = conduct_mutation_testing_with_config(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+    );
+    assert_eq!(result.random_seed, 0);
+}
+
+#[test]
+fn test_input_uniform_crossover() {
+    let main_owner = Rc::new(vec![OwnerName {
+        id: 0,
+        counter: 0,
+        access: None,
+    }]);
+    let names = (1..=8)
+        .map(|id| SymbolicName::new(id, main_owner.clone(), None))
+        .collect::<Vec<_>>();
+    let parent1: FxHashMap<SymbolicName, BigInt> = names[..7]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.clone(), BigInt::from(i)))
+        .collect();
+    let parent2: FxHashMap<SymbolicName, BigInt> = names[1..]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (name.clone(), BigInt::from(100 + i)))
+        .collect();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut from_parent1 = 0;
+    let mut from_parent2 = 0;
+    for _ in 0..50 {
+        let child = input_uniform_crossover(&parent1, &parent2, &mut rng);
+        assert_eq!(child.len(), names.len());
+        for (name, value) in &child {
+            if parent1.get(name) == Some(value) {
+                from_parent1 += 1;
+            } else {
+                assert_eq!(parent2.get(name), Some(value));
+                from_parent2 += 1;
+            }
+        }
+        // The signals assigned by only one parent keep its value
+        assert_eq!(child[&names[0]], parent1[&names[0]]);
+        assert_eq!(child[&names[7]], parent2[&names[7]]);
+    }
+    assert!(from_parent1 > 50);
+    assert!(from_parent2 > 50);
+}
+
+#[test]
+fn test_tournament_selection() {
+    let population = vec!["A", "B", "C", "D", "E"];
+    let fitness_scores = vec![
+        BigInt::from(-3),
+        BigInt::from(7),
+        BigInt::from(2),
+        BigInt::from(7),
+        BigInt::from(0),
+    ];
+    let mut rng = StdRng::seed_from_u64(42);
+
+    // Every individual competes, so the fittest one always wins, and ties go to the first one
+    for _ in 0..20 {
+        assert_eq!(
+            *tournament_selection(&population, &fitness_scores, &mut rng, population.len()),
+            "B"
+        );
+    }
+
+    // The least fit individual can never win a tournament of two distinct competitors
+    for _ in 0..100 {
+        assert_ne!(
+            *tournament_selection(&population, &fitness_scores, &mut rng, 2),
+            "A"
+        );
+    }
+}
+
+#[test]
+fn test_population_round_trip() {
+    let owner = Rc::new(vec![OwnerName {
+        id: 0,
+        counter: 0,
+        access: Some(vec![SymbolicAccess::ArrayAccess(
+            SymbolicValue::ConstantInt(BigInt::from(1)),
+        )]),
+    }]);
+    let x = SymbolicName::new(1, owner.clone(), None);
+    let y = SymbolicName::new(2, owner, Some(vec![SymbolicAccess::ComponentAccess(3)]));
+    let large = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495616",
+    )
+    .unwrap();
+
+    let mut gene = FxHashMap::default();
+    gene.insert(
+        4,
+        SymbolicValue::BinaryOp(
+            Rc::new(SymbolicValue::Variable(x.clone())),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::ShiftL),
+            Rc::new(SymbolicValue::UnaryOp(
+                DebuggableExpressionPrefixOpcode(ExpressionPrefixOpcode::Sub),
+                Rc::new(SymbolicValue::ConstantInt(large.clone())),
+            )),
+        ),
+    );
+    gene.insert(7, SymbolicValue::ConstantBool(true));
+    let mut input = FxHashMap::default();
+    input.insert(x, large);
+    input.insert(y, BigInt::from(-5));
+    let population = Population {
+        traces: vec![gene],
+        inputs: vec![input],
+    };
+
+    let path = std::env::temp_dir().join("zkfuzz_population_round_trip_test.json");
+    let path = path.to_str().unwrap();
+    save_population_to_json(&population, path).unwrap();
+    assert_eq!(load_population_from_json(path).unwrap(), population);
+
+    let result = conduct_mutation_testing(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+    );
+    assert!(!result.final_population.inputs.is_empty());
+    save_population_to_json(&result.final_population, path).unwrap();
+    assert_eq!(
+        load_population_from_json(path).unwrap(),
+        result.final_population
+    );
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_fitness_by_distance() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let path = "./tests/sample/test_fitness_distance.circom".to_string();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    }]);
+    let a = SymbolicName::new(sexe.symbolic_library.name2id["a"], main_owner.clone(), None);
+    let b = SymbolicName::new(sexe.symbolic_library.name2id["b"], main_owner, None);
+    let assign_pos = sexe
+        .cur_state
+        .symbolic_trace
+        .iter()
+        .position(|v| match v.as_ref() {
+            SymbolicValue::Assign(lhs, ..) => *lhs.as_ref() == SymbolicValue::Variable(b.clone()),
+            _ => false,
+        })
+        .unwrap();
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: "Copy".to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        ..Default::default()
+    };
+    let mutation_config = MutationConfig::default();
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let inputs_assignment = vec![FxHashMap::from_iter([(a, BigInt::from(0))])];
+
+    let conc_setting = get_default_setting_for_concrete_execution(prime.clone(), false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &conc_setting);
+
+    // `b` is set to `-1` and `2` while `b === a` requires `b = 0`
+    let mut scores_by_distance = Vec::new();
+    let mut scores_by_error = Vec::new();
+    for value in [&prime - BigInt::from(1), BigInt::from(2)] {
+        let trace_mutation =
+            FxHashMap::from_iter([(assign_pos, SymbolicValue::ConstantInt(value))]);
+        let (_, score, counter_example, _) = evaluate_trace_fitness_by_distance(
+            &mut conc_executor,
+            &verification_base_config,
+            &mutation_config,
+            &symbolic_trace,
+            &side_constraints,
+            &FxHashMap::default(),
+            &trace_mutation,
+            &inputs_assignment,
+            &mut vec![BigInt::from(0)],
+        );
+        assert!(counter_example.is_none());
+        scores_by_distance.push(score);
+        let (_, score, _, _) = evaluate_trace_fitness_by_error(
+            &mut conc_executor,
+            &verification_base_config,
+            &mutation_config,
+            &symbolic_trace,
+            &side_constraints,
+            &FxHashMap::default(),
+            &trace_mutation,
+            &inputs_assignment,
+            &mut vec![BigInt::from(0)],
+        );
+        scores_by_error.push(score);
+    }
+
+    assert_eq!(scores_by_distance, vec![BigInt::from(-1), BigInt::from(-2)]);
+    assert!(scores_by_error[0] < scores_by_error[1]);
+}
+
+/// A trace mutation leaving every trace unmutated, which counts how often it is used.
+#[derive(Clone, Default)]
+struct NoopTraceMutation {
+    num_initializations: Rc<Cell<usize>>,
+    num_mutations: Rc<Cell<usize>>,
+}
+
+impl TraceMutation for NoopTraceMutation {
+    fn initialize_population(
+        &self,
+        _pos: &[usize],
+        program_population_size: usize,
+        _symbolic_trace: &SymbolicTrace,
+        _base_config: &BaseVerificationConfig,
+        _mutation_config: &MutationConfig,
+        _rng: &mut StdRng,
+    ) -> Vec<Gene> {
+        self.num_initializations
+            .set(self.num_initializations.get() + 1);
+        vec![Gene::default(); program_population_size]
+    }
+
+    fn mutate(
+        &self,
+        _pos: &[usize],
+        _symbolic_trace: &SymbolicTrace,
+        _individual: &mut Gene,
+        _base_config: &BaseVerificationConfig,
+        _mutation_config: &MutationConfig,
+        _rng: &mut StdRng,
+    ) {
+        self.num_mutations.set(self.num_mutations.get() + 1);
+    }
+}
+
+#[test]
+fn test_custom_trace_mutation() {
+    let registry = OperatorRegistry::default();
+    assert_eq!(
+        registry.trace_mutation_names(),
+        vec![
+            "constant",
+            "constant_operator",
+            "constant_operator_add",
+            "constant_operator_delete",
+            "constraint_delete",
+            "index_offset",
+            "naive",
+            "signal_swap"
+        ]
+    );
+    assert_eq!(registry.crossover_names(), vec!["random"]);
+    assert_eq!(registry.selection_names(), vec!["roulette", "tournament"]);
+
+    let noop = NoopTraceMutation::default();
+    let mut registry = OperatorRegistry::default();
+    registry.register_trace_mutation("noop", Box::new(noop.clone()));
+    assert!(registry.trace_mutation("unknown").is_none());
+
+    let mut mutation_config = MutationConfig::default();
+    mutation_config.max_generations = 1;
+    mutation_config.mutation_rate = 1.0;
+    mutation_config.quiet = true;
+    conduct_mutation_testing_with_trace_mutation(
+        "./tests/sample/test_vuln_iszero.circom".to_string(),
+        "random".to_string(),
+        &mutation_config,
+        registry.trace_mutation("noop").unwrap(),
+        &mut |_| {},
+    );
+
+    assert!(noop.num_initializations.get() > 0);
+    assert!(noop.num_mutations.get() > 0);
+}
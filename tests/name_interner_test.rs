@@ -0,0 +1,113 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use rustc_hash::FxHashMap;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_value::{SymbolicValue, SymbolicValueRef};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+/// Collects the distinct allocations of the variables reachable from `value`.
+fn collect_variable_allocations(
+    value: &SymbolicValueRef,
+    allocations: &mut FxHashMap<*const SymbolicValue, SymbolicValueRef>,
+) {
+    match value.as_ref() {
+        SymbolicValue::Variable(_) => {
+            allocations.insert(Rc::as_ptr(value), value.clone());
+        }
+        SymbolicValue::Assign(lhs, rhs, _, _)
+        | SymbolicValue::AssignEq(lhs, rhs)
+        | SymbolicValue::AssignTemplParam(lhs, rhs)
+        | SymbolicValue::AssignCall(lhs, rhs, _)
+        | SymbolicValue::BinaryOp(lhs, _, rhs)
+        | SymbolicValue::AuxBinaryOp(lhs, _, rhs)
+        | SymbolicValue::UniformArray(lhs, rhs) => {
+            collect_variable_allocations(lhs, allocations);
+            collect_variable_allocations(rhs, allocations);
+        }
+        SymbolicValue::Conditional(cond, then_val, else_val) => {
+            collect_variable_allocations(cond, allocations);
+            collect_variable_allocations(then_val, allocations);
+            collect_variable_allocations(else_val, allocations);
+        }
+        SymbolicValue::UnaryOp(_, expr) => collect_variable_allocations(expr, allocations),
+        SymbolicValue::Array(elems) | SymbolicValue::Call(_, elems) => {
+            for elem in elems {
+                collect_variable_allocations(elem, allocations);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn count_variable_allocations(sexe: &SymbolicExecutor) -> usize {
+    let mut allocations = FxHashMap::default();
+    for value in sexe
+        .cur_state
+        .symbolic_trace
+        .iter()
+        .chain(sexe.cur_state.side_constraints.iter())
+    {
+        collect_variable_allocations(value, &mut allocations);
+    }
+    allocations.len()
+}
+
+#[test]
+fn test_intern_names() {
+    let path =
+        "./tests/sample/test_multiplexer_with_decoder_and_escalar_product.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut plain_library, program_archive) =
+        prepare_symbolic_library(path.clone(), prime.clone());
+    let mut plain_setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    plain_setting.intern_names = false;
+    let mut plain_sexe = SymbolicExecutor::new(&mut plain_library, &plain_setting);
+    execute(&mut plain_sexe, &program_archive);
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // Each unique name is allocated once while the constraints are gathered, instead of once
+    // per occurrence
+    let allocations = count_variable_allocations(&sexe);
+    assert_eq!(allocations, sexe.symbolic_library.name_interner.len());
+    assert!(allocations < count_variable_allocations(&plain_sexe));
+
+    // The constraints are unchanged but for the sharing
+    assert_eq!(
+        sexe.cur_state.symbolic_trace,
+        plain_sexe.cur_state.symbolic_trace
+    );
+    assert_eq!(
+        sexe.cur_state.side_constraints,
+        plain_sexe.cur_state.side_constraints
+    );
+    assert!(plain_sexe.symbolic_library.name_interner.is_empty());
+
+    // Interning a name again returns the variable shared by the constraints
+    let mut variables = FxHashMap::default();
+    for value in sexe.cur_state.side_constraints.iter() {
+        collect_variable_allocations(value, &mut variables);
+    }
+    let interner = &mut sexe.symbolic_library.name_interner;
+    let num_interned = interner.len();
+    for variable in variables.values() {
+        if let SymbolicValue::Variable(name) = variable.as_ref() {
+            assert!(Rc::ptr_eq(&interner.intern(name), variable));
+        }
+    }
+    assert_eq!(interner.len(), num_interned);
+}
@@ -0,0 +1,22 @@
+use std::process::Command;
+
+#[test]
+fn test_redirected_output_has_no_escape_codes() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_vuln_iszero.circom",
+            "--search_mode",
+            "quick",
+            "--print_stats",
+        ])
+        .env_remove("CLICOLOR_FORCE")
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Counter Example"));
+    assert!(!stdout.contains('\x1b'));
+    assert!(!stderr.contains('\x1b'));
+}
@@ -0,0 +1,104 @@
+mod utils;
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use num_bigint_dig::BigInt;
+
+use program_structure::ast::Meta;
+
+use zkfuzz::executor::debug_ast::DebuggableStatement;
+use zkfuzz::executor::observer::ExecutionObserver;
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_symbolic_execution, WhitelistMode,
+};
+use zkfuzz::executor::symbolic_value::{SymbolicName, SymbolicValue};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[derive(Default)]
+struct CountingObserver {
+    num_statements: usize,
+    num_substitutions: usize,
+    num_constraint_equalities: usize,
+    num_constraints: usize,
+    num_components: usize,
+}
+
+impl ExecutionObserver for CountingObserver {
+    fn on_statement(&mut self, _meta: &Meta, statement: &DebuggableStatement) {
+        self.num_statements += 1;
+        match statement {
+            DebuggableStatement::Substitution { .. } => self.num_substitutions += 1,
+            DebuggableStatement::ConstraintEquality { .. } => self.num_constraint_equalities += 1,
+            _ => {}
+        }
+    }
+
+    fn on_constraint(&mut self, _constraint: &SymbolicValue) {
+        self.num_constraints += 1;
+    }
+
+    fn on_component_instantiated(&mut self, _component_name: &SymbolicName) {
+        self.num_components += 1;
+    }
+}
+
+#[test]
+fn test_observer_counts_statements() {
+    let path = "./tests/sample/test_whitelist_black_box.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut observer = CountingObserver::default();
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    sexe.set_observer(&mut observer);
+    execute(&mut sexe, &program_archive);
+    let num_side_constraints = sexe.cur_state.side_constraints.len();
+
+    // `isz = IsZero()`, `isz.in <== a - b`, and `c <== isz.out` in `IsEqual`, and `inv <-- ...`
+    // and `out <== ...` in the component
+    assert_eq!(observer.num_substitutions, 5);
+    assert_eq!(observer.num_constraint_equalities, 1);
+    assert!(observer.num_statements > observer.num_substitutions);
+    assert_eq!(observer.num_components, 1);
+    assert_eq!(observer.num_constraints, num_side_constraints);
+}
+
+#[test]
+fn test_observer_skips_deduplicated_model_constraints() {
+    let path = "./tests/sample/test_precise_num2bits_repeated_constraint.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let num2bits_id = symbolic_library.name2id["Num2Bits"];
+    Arc::make_mut(
+        symbolic_library
+            .template_library
+            .get_mut(&num2bits_id)
+            .unwrap(),
+    )
+    .is_safe = true;
+    let mut setting = get_default_setting_for_symbolic_execution(prime, false);
+    setting.whitelist_mode = WhitelistMode::TrustAsBlackBox;
+    setting.precise_primitives = true;
+    setting.dedup_constraints = true;
+
+    let mut observer = CountingObserver::default();
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    sexe.set_observer(&mut observer);
+    execute(&mut sexe, &program_archive);
+    let num_side_constraints = sexe.cur_state.side_constraints.len();
+
+    // The constraints of the `Num2Bits` model dropped as duplicates are not reported
+    assert_eq!(observer.num_constraints, num_side_constraints);
+}
@@ -0,0 +1,117 @@
+mod utils;
+
+use std::process::Command;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    enumerate_param_bindings, get_default_setting_for_symbolic_execution, ParamBound,
+};
+use zkfuzz::executor::symbolic_value::OwnerName;
+
+use crate::utils::prepare_symbolic_library;
+
+#[test]
+fn test_enumerate_param_bindings() {
+    let bounds = vec![
+        ParamBound {
+            name: "m".to_string(),
+            lower: BigInt::from(0),
+            upper: BigInt::from(1),
+        },
+        ParamBound {
+            name: "n".to_string(),
+            lower: BigInt::from(2),
+            upper: BigInt::from(4),
+        },
+    ];
+    let bindings = enumerate_param_bindings(&bounds);
+    assert_eq!(bindings.len(), 6);
+    assert_eq!(
+        bindings[1],
+        vec![
+            ("m".to_string(), BigInt::from(0)),
+            ("n".to_string(), BigInt::from(3))
+        ]
+    );
+    assert_eq!(enumerate_param_bindings(&[]), vec![Vec::new()]);
+}
+
+#[test]
+fn test_bounded_symbolic_template_params() {
+    let path = "./tests/sample/test_param_bounds.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, _) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+    let template_id = symbolic_library.name2id["Bits"];
+    let body = symbolic_library.template_library[&template_id].body.clone();
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    let main_id = sexe.symbolic_library.name2id.len();
+    sexe.symbolic_library
+        .name2id
+        .insert("main".to_string(), main_id);
+    sexe.symbolic_library
+        .id2name
+        .insert(main_id, "main".to_string());
+    sexe.cur_state.add_owner(&OwnerName {
+        id: main_id,
+        counter: 0,
+        access: None,
+    });
+    sexe.cur_state.set_template_id(template_id);
+
+    let bindings = enumerate_param_bindings(&[ParamBound {
+        name: "n".to_string(),
+        lower: BigInt::from(2),
+        upper: BigInt::from(4),
+    }]);
+    assert_eq!(bindings.len(), 3);
+
+    let mut constraint_sets = Vec::new();
+    for binding in &bindings {
+        let mut library = sexe.symbolic_library.clone();
+        let mut forked = sexe.fork(&mut library);
+        forked.feed_param_values(binding);
+        forked.execute(&body, 0);
+        let mut constraints: Vec<String> = forked
+            .cur_state
+            .side_constraints
+            .iter()
+            .map(|c| c.lookup_fmt(&forked.symbolic_library.id2name))
+            .collect();
+        constraints.sort();
+        constraint_sets.push(constraints);
+    }
+
+    // Each extra bit adds a boolean constraint on `out`
+    assert!(constraint_sets[0].len() < constraint_sets[1].len());
+    assert!(constraint_sets[1].len() < constraint_sets[2].len());
+    assert_ne!(constraint_sets[0], constraint_sets[1]);
+    assert_ne!(constraint_sets[1], constraint_sets[2]);
+    assert_ne!(constraint_sets[0], constraint_sets[2]);
+}
+
+#[test]
+fn test_param_bounds_limit_the_number_of_combinations() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_param_bounds.circom",
+            "--symbolic_template_params",
+            "--param_bounds",
+            "n=1:1000000000000",
+            "--no_color",
+        ])
+        .output()
+        .unwrap();
+
+    // The grid is rejected before anything is enumerated
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("at most 64 combinations"));
+}
@@ -0,0 +1,55 @@
+use std::fs;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::parser_user::ProgramArchiveCache;
+
+fn bn128_prime() -> BigInt {
+    BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_cache_hit_on_unchanged_file() {
+    let path = "./tests/sample/test_if_else.circom";
+    let prime = bn128_prime();
+    let mut cache = ProgramArchiveCache::new();
+
+    let first = cache.parse_and_analyse(path, &[], &prime).unwrap();
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.misses(), 1);
+
+    let second = cache.parse_and_analyse(path, &[], &prime).unwrap();
+    assert_eq!(cache.hits(), 1);
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(cache.len(), 1);
+
+    let mut first_names = first.templates.keys().cloned().collect::<Vec<_>>();
+    let mut second_names = second.templates.keys().cloned().collect::<Vec<_>>();
+    first_names.sort();
+    second_names.sort();
+    assert_eq!(first_names, second_names);
+}
+
+#[test]
+fn test_cache_invalidated_on_content_change() {
+    let src = fs::read_to_string("./tests/sample/test_if_else.circom").unwrap();
+    let path = std::env::temp_dir().join("zkfuzz_program_archive_cache_test.circom");
+    fs::write(&path, &src).unwrap();
+
+    let prime = bn128_prime();
+    let mut cache = ProgramArchiveCache::new();
+    let path_str = path.to_str().unwrap();
+
+    cache.parse_and_analyse(path_str, &[], &prime).unwrap();
+    fs::write(&path, format!("{}\n// edited\n", src)).unwrap();
+    cache.parse_and_analyse(path_str, &[], &prime).unwrap();
+
+    assert_eq!(cache.hits(), 0);
+    assert_eq!(cache.misses(), 2);
+
+    let _ = fs::remove_file(&path);
+}
@@ -0,0 +1,103 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicName};
+use zkfuzz::mutator::brute_force::brute_force_search;
+use zkfuzz::mutator::utils::{BaseVerificationConfig, UnderConstrainedType, VerificationResult};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_constraint_provenance() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(
+        "./tests/sample/test_provenance.circom".to_string(),
+        prime.clone(),
+    );
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let out = SymbolicName::new(
+        sexe.symbolic_library.name2id["out"],
+        main_owner.clone(),
+        None,
+    );
+    let free = SymbolicName::new(sexe.symbolic_library.name2id["free"], main_owner, None);
+
+    // The provenance indexes the side constraints referencing each signal
+    let out_constraints = sexe.cur_state.constraints_referencing(&out);
+    assert!(!out_constraints.is_empty());
+    for (i, constraint) in &out_constraints {
+        assert_eq!(sexe.cur_state.side_constraints[*i], *constraint);
+    }
+    assert!(sexe.cur_state.constraints_referencing(&free).is_empty());
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(5),
+        quick_mode: true,
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let subse_base_config = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &subse_base_config);
+    conc_executor
+        .feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
+    let mut counter_example = brute_force_search(
+        &mut conc_executor,
+        &symbolic_trace,
+        &side_constraints,
+        &verification_base_config,
+    )
+    .unwrap();
+    assert!(matches!(
+        &counter_example.flag,
+        VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(name, _, _))
+            if *name == free
+    ));
+
+    // The counterexample explains that nothing constrains `free`, which is computed by `<--`
+    counter_example.explain(&sexe.cur_state);
+    let provenance = counter_example.provenance.unwrap();
+    assert_eq!(provenance.signal, free);
+    assert!(provenance.constraints.is_empty());
+    assert_eq!(provenance.assignments.len(), 1);
+}
@@ -0,0 +1,24 @@
+use std::process::Command;
+
+#[test]
+fn test_quiet_output_is_the_warnings_and_a_single_line() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_vuln_iszero.circom",
+            "--search_mode",
+            "quick",
+            "--quiet",
+            "--no_color",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines = stderr.trim().lines().collect::<Vec<_>>();
+    // The warning about `1/in` is kept, followed by the summary line
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("⚠️ Potential Division by Zero:"));
+    assert!(lines[1].starts_with("result=unsafe compression="));
+    assert!(lines[1].ends_with('s'));
+}
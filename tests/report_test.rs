@@ -0,0 +1,71 @@
+use std::process::Command;
+
+fn run_with_report(path: &str, report_path: &str) -> bool {
+    Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            path,
+            "--search_mode",
+            "quick",
+            "--no_color",
+            "--report",
+            report_path,
+        ])
+        .output()
+        .unwrap()
+        .status
+        .success()
+}
+
+#[test]
+fn test_report() {
+    let report_path =
+        std::env::temp_dir().join(format!("zkfuzz_report_test_{}.json", std::process::id()));
+    let report_path = report_path.to_str().unwrap();
+
+    assert!(run_with_report(
+        "./tests/sample/test_vuln_iszero.circom",
+        report_path
+    ));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report_path).unwrap()).unwrap();
+    assert_eq!(report["instances"].as_array().unwrap().len(), 1);
+    let report = &report["instances"][0];
+    for key in [
+        "target_path",
+        "main_template",
+        "template_params",
+        "search_mode",
+        "git_hash_of_zkfuzz",
+        "prime",
+        "result",
+        "exhaustive",
+        "compression_rate",
+        "free_witnesses",
+        "dangling_signals",
+        "potential_div_by_zeros",
+        "stats",
+        "execution_time_secs",
+        "counter_examples",
+    ] {
+        assert!(report.get(key).is_some(), "missing `{}`", key);
+    }
+    assert_eq!(report["result"], "unsafe");
+    assert_eq!(report["counter_examples"].as_array().unwrap().len(), 1);
+    assert!(report["counter_examples"][0].get("7_assignment").is_some());
+    assert!(report["stats"].get("trace").is_some());
+    assert!(report["stats"].get("side_constraints").is_some());
+
+    // The report of the next run overwrites the previous one at the same path
+    assert!(run_with_report(
+        "./tests/sample/test_trivially_safe.circom",
+        report_path
+    ));
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report_path).unwrap()).unwrap();
+    assert_eq!(report["instances"].as_array().unwrap().len(), 1);
+    let report = &report["instances"][0];
+    assert_eq!(report["result"], "safe");
+    assert!(report["counter_examples"].as_array().unwrap().is_empty());
+
+    std::fs::remove_file(report_path).unwrap();
+}
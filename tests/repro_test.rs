@@ -0,0 +1,50 @@
+use std::process::Command;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::parser_user::parse_file;
+
+#[test]
+fn test_emit_repro() {
+    let dir = std::env::temp_dir().join("zkfuzz_test_emit_repro");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_vuln_iszero.circom",
+            "--search_mode",
+            "quick",
+            "--emit_repro",
+            dir.to_str().unwrap(),
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let harness = std::fs::read_to_string(dir.join("repro.circom")).unwrap();
+    assert!(harness.contains("include \"circuit.circom\";"));
+    assert!(harness.contains("//   main.in = "));
+    assert!(harness.contains("UnderConstrained"));
+    assert!(harness.ends_with("component main = VulnerableIsZero();\n"));
+
+    let circuit = std::fs::read_to_string(dir.join("circuit.circom")).unwrap();
+    assert!(circuit.contains("template VulnerableIsZero()"));
+    assert!(!circuit.contains("component main ="));
+
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    assert!(parse_file(
+        dir.join("repro.circom").to_str().unwrap().to_string(),
+        Vec::new(),
+        &prime
+    )
+    .is_ok());
+
+    let input: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("input.json")).unwrap()).unwrap();
+    let keys = input.as_object().unwrap().keys().collect::<Vec<_>>();
+    assert_eq!(keys, vec!["in"]);
+}
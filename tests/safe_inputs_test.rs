@@ -0,0 +1,147 @@
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use rustc_hash::FxHashMap;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_value::{extract_variables, OwnerName, SymbolicValue};
+use zkfuzz::mutator::safe_inputs::{sample_safe_inputs, to_circom_input_json};
+use zkfuzz::mutator::utils::{
+    evaluate_constraints, BaseVerificationConfig, CounterExample, VerificationResult,
+};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_sample_safe_inputs_satisfy_constraints() {
+    let path = "./tests/sample/test_if_else.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(5),
+        heuristics_mode: true,
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let samples = sample_safe_inputs(
+        &mut sexe,
+        &symbolic_trace,
+        &side_constraints,
+        &verification_base_config,
+        4,
+    );
+    assert_eq!(samples.len(), 4);
+
+    let json_output = to_circom_input_json(&samples[1], &sexe.symbolic_library.id2name);
+    assert_eq!(json_output["in"], "1");
+
+    let conc_setting = get_default_setting_for_concrete_execution(prime.clone(), false);
+    let mut cexe = SymbolicExecutor::new(&mut sexe.symbolic_library, &conc_setting);
+    for sample in &samples {
+        cexe.clear();
+        cexe.cur_state.add_owner(&OwnerName {
+            id: cexe.symbolic_library.name2id["main"],
+            counter: 0,
+            access: None,
+        });
+        cexe.feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
+        cexe.concrete_execute(&verification_base_config.target_template_name, sample);
+        assert!(!cexe.cur_state.is_failed);
+
+        let mut assignment = FxHashMap::default();
+        for (k, v) in cexe.cur_state.symbol_binding_map.iter() {
+            if let SymbolicValue::ConstantInt(num) = v.as_ref() {
+                assignment.insert(k.clone(), num.clone());
+            }
+        }
+        assert!(evaluate_constraints(
+            &prime,
+            &side_constraints,
+            &assignment,
+            &mut cexe.symbolic_library,
+        ));
+    }
+}
+
+#[test]
+fn test_counter_example_to_circom_input_json() {
+    let path = "./tests/sample/test_lessthan.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // Assign every variable, including the outputs and the signals of the subcomponents
+    let mut assignment = FxHashMap::default();
+    for (i, name) in extract_variables(&sexe.cur_state.symbolic_trace)
+        .into_iter()
+        .enumerate()
+    {
+        assignment.insert(name, BigInt::from(i + 2));
+    }
+    let counter_example = CounterExample {
+        flag: VerificationResult::OverConstrained,
+        target_output: None,
+        assignment: assignment.clone(),
+        field_reductions: Vec::new(),
+        provenance: None,
+    };
+
+    let main_id = sexe.symbolic_library.name2id["VulnerableLessThan"];
+    let input_ids = &sexe.symbolic_library.template_library[&main_id].input_ids;
+    let json_output =
+        counter_example.to_circom_input_json(&sexe.symbolic_library.id2name, input_ids);
+
+    let parsed: FxHashMap<String, String> =
+        serde_json::from_str(&serde_json::to_string(&json_output).unwrap()).unwrap();
+    assert_eq!(parsed.len(), 2);
+    for (name, value) in assignment.iter() {
+        if name.owner.len() == 1 && input_ids.contains(&name.id) {
+            assert_eq!(
+                parsed[&sexe.symbolic_library.id2name[&name.id]],
+                value.to_string()
+            );
+        }
+    }
+}
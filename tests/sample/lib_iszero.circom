@@ -0,0 +1,14 @@
+pragma circom 2.0.0;
+
+// A library file without `component main`
+
+template IsZero() {
+    signal input in;
+    signal output out;
+    signal inv;
+
+    inv <-- in!=0 ? 1/in : 0;
+
+    out <== -in*inv +1;
+    in*out === 0;
+}
@@ -0,0 +1,13 @@
+pragma circom 2.0.0;
+
+include "mathlib/gadgets.circom";
+include "local.circom";
+
+template Main() {
+    signal input in;
+    signal output out;
+
+    out <== in;
+}
+
+component main = Main();
@@ -0,0 +1,10 @@
+pragma circom 2.0.0;
+
+// Shadowed by the `local.circom` next to the including circuit
+
+template ShadowedLocalTemplate() {
+    signal input in;
+    signal output out;
+
+    out <== in;
+}
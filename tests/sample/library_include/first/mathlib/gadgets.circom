@@ -0,0 +1,8 @@
+pragma circom 2.0.0;
+
+template FirstGadget() {
+    signal input in;
+    signal output out;
+
+    out <== in + 1;
+}
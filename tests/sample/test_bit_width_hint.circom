@@ -0,0 +1,30 @@
+pragma circom 2.0.0;
+
+template Num2Bits(n) {
+    signal input in;
+    signal output out[n];
+    var lc1=0;
+
+    var e2=1;
+    for (var i = 0; i<n; i++) {
+        out[i] <-- (in >> i) & 1;
+        out[i] * (out[i] -1 ) === 0;
+        lc1 += out[i] * e2;
+        e2 = e2+e2;
+    }
+
+    lc1 === in;
+}
+
+template ByteProduct() {
+    signal input a;
+    signal input b;
+    signal output out;
+
+    // `a` is meant to be a byte
+    component n2b = Num2Bits(8);
+    n2b.in <== a;
+    out <== a * b;
+}
+
+component main = ByteProduct();
@@ -0,0 +1,15 @@
+pragma circom 2.0.0;
+
+// `out` is only pinned to `1` when `t` is not zero, so it is free for `x = (p - 1) / 2` alone,
+// whose double is `-1`.
+template HalfIsFree() {
+    signal input x;
+    signal output out;
+    signal t;
+
+    t <== 2 * x + 1;
+    out <-- 1;
+    out * t === t;
+}
+
+component main = HalfIsFree();
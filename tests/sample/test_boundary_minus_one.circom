@@ -0,0 +1,14 @@
+pragma circom 2.0.0;
+
+// `out` is only pinned to `1` when `t` is not zero, so it is free for `x = p - 1` alone.
+template MinusOneIsFree() {
+    signal input x;
+    signal output out;
+    signal t;
+
+    t <== x + 1;
+    out <-- 1;
+    out * t === t;
+}
+
+component main = MinusOneIsFree();
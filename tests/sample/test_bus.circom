@@ -0,0 +1,15 @@
+pragma circom 2.2.0;
+
+bus Point() {
+    signal x;
+    signal y;
+}
+
+template Area() {
+    Point() input p;
+    signal output out;
+
+    out <== p.x * p.y;
+}
+
+component main = Area();
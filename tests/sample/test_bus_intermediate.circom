@@ -0,0 +1,20 @@
+pragma circom 2.2.0;
+
+bus Point() {
+    signal x;
+    signal y;
+}
+
+template Area() {
+    signal input a;
+    signal input b;
+    signal output out;
+
+    // An intermediate bus, whose fields are assigned and constrained like signals
+    Point() p;
+    p.x <== a;
+    p.y <== b;
+    out <== p.x * p.y;
+}
+
+component main = Area();
@@ -0,0 +1,13 @@
+pragma circom 2.0.0;
+
+// `in` is constrained to be both 0 and 1, so that no witness exists
+template Contradiction() {
+    signal input in;
+    signal output out;
+
+    in === 0;
+    out <== in + 1;
+    in === 1;
+}
+
+component main = Contradiction();
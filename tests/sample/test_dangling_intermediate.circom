@@ -0,0 +1,19 @@
+pragma circom 2.0.0;
+
+/**
+ * @template DanglingIntermediate
+ * @description `used` takes part in the constraints, while `dangling` is assigned with `<--` and
+ *              never constrained.
+ */
+template DanglingIntermediate() {
+    signal input a;
+    signal output out;
+    signal used;
+    signal dangling;
+
+    used <== a * a;
+    dangling <-- a + 1;
+    out <== used + 1;
+}
+
+component main = DanglingIntermediate();
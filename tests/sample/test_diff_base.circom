@@ -0,0 +1,18 @@
+pragma circom 2.0.0;
+
+template Square() {
+    signal input in;
+    signal output out;
+    out <== in * in;
+}
+
+template Main() {
+    signal input a;
+    signal input b;
+    signal output c;
+    component s = Square();
+    s.in <== a;
+    c <== s.out + b;
+}
+
+component main = Main();
@@ -0,0 +1,19 @@
+pragma circom 2.0.0;
+
+template Main() {
+    signal input a;
+    signal input b;
+    signal output c;
+    component s = Square();
+    s.in <== a;
+    c <== s.out + b;
+    a * b === 0;
+}
+
+template Square() {
+    signal input in;
+    signal output out;
+    out <== in * in;
+}
+
+component main = Main();
@@ -0,0 +1,23 @@
+pragma circom 2.0.0;
+
+template Gate(flag) {
+    signal input in;
+    signal output out;
+
+    // `flag` must be boolean; this constraint does not depend on the signals of the instance
+    flag * (flag - 1) === 0;
+    out <== in * flag;
+}
+
+template Twice() {
+    signal input in;
+    signal output out;
+
+    component g1 = Gate(1);
+    component g2 = Gate(1);
+    g1.in <== in;
+    g2.in <== g1.out;
+    out <== g2.out;
+}
+
+component main = Twice();
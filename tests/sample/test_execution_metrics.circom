@@ -0,0 +1,32 @@
+pragma circom 2.0.0;
+
+function square(x) {
+    return x * x;
+}
+
+template Inner() {
+    signal input in;
+    signal output out;
+    out <== in * square(2);
+}
+
+template Middle() {
+    signal input in;
+    signal output out;
+    component inner[2];
+    inner[0] = Inner();
+    inner[0].in <== in;
+    inner[1] = Inner();
+    inner[1].in <== inner[0].out;
+    out <== inner[1].out;
+}
+
+template Outer() {
+    signal input in;
+    signal output out;
+    component middle = Middle();
+    middle.in <== in;
+    out <== middle.out + square(3);
+}
+
+component main = Outer();
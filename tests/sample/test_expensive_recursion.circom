@@ -0,0 +1,17 @@
+pragma circom 2.0.0;
+
+function fib(n) {
+    if (n <= 1) {
+        return n;
+    }
+    return fib(n - 1) + fib(n - 2);
+}
+
+template ExpensiveRecursion() {
+    signal input in;
+    signal output out;
+
+    out <== in * fib(40);
+}
+
+component main = ExpensiveRecursion();
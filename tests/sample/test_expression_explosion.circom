@@ -0,0 +1,15 @@
+pragma circom 2.0.0;
+
+// Each iteration squares `acc`, so that its value doubles in size unless it is capped.
+template Explosion() {
+    signal input in;
+    signal output out;
+
+    var acc = in;
+    for (var i = 0; i < 24; i++) {
+        acc = acc * acc + i;
+    }
+    out <-- acc;
+}
+
+component main = Explosion();
@@ -0,0 +1,10 @@
+pragma circom 2.0.0;
+
+template SquareOverflow() {
+    signal input a;
+    signal output b;
+
+    b <== a * a;
+}
+
+component main = SquareOverflow();
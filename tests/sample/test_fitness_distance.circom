@@ -0,0 +1,11 @@
+pragma circom 2.0.0;
+
+template Copy() {
+    signal input a;
+    signal output b;
+
+    b <-- a;
+    b === a;
+}
+
+component main = Copy();
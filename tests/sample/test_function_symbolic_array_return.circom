@@ -0,0 +1,16 @@
+pragma circom 2.0.0;
+
+function pair(x) {
+    return [x, x + 1];
+}
+
+template Main() {
+    signal input in;
+    signal output out[2];
+
+    var p[2] = pair(in);
+    out[0] <== p[0];
+    out[1] <== p[1];
+}
+
+component main = Main();
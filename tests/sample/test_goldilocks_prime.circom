@@ -0,0 +1,16 @@
+pragma circom 2.0.0;
+// pragma prime goldilocks;
+
+// The same vulnerable IsZero as in `test_vuln_iszero.circom`, declared over the Goldilocks field
+template VulnerableIsZero() {
+    signal input in;
+    signal output out;
+    signal inv;
+
+    inv <-- in!=0 ? 1/in : 0;
+
+    out <== -in*inv +1;
+    out*(out-1) === 0;
+}
+
+component main = VulnerableIsZero();
@@ -0,0 +1,15 @@
+pragma circom 2.0.0;
+
+template InputBoundedLoop() {
+    signal input n;
+    signal output out;
+
+    var acc = 0;
+    for (var i = 0; i < n; i++) {
+        acc += 2;
+    }
+
+    out <-- acc;
+}
+
+component main = InputBoundedLoop();
@@ -0,0 +1,16 @@
+pragma circom 2.0.0;
+
+template LoneAssign() {
+    signal input a;
+    signal output b;
+    signal output c;
+
+    // `b` is never constrained
+    b <-- a + 1;
+
+    // `c` is pinned by the constraint below
+    c <-- a * 2;
+    c === a * 2;
+}
+
+component main = LoneAssign();
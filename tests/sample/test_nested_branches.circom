@@ -0,0 +1,20 @@
+pragma circom 2.0.0;
+
+template NestedBranches() {
+    signal input a;
+    signal input b;
+    signal output out;
+
+    var level = 0;
+    if (a > 10) {
+        if (b > 10) {
+            level = 2;
+        } else {
+            level = 1;
+        }
+    }
+
+    out <-- level;
+}
+
+component main = NestedBranches();
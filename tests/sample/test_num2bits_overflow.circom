@@ -0,0 +1,31 @@
+pragma circom 2.0.0;
+
+template Num2Bits(n) {
+    signal input in;
+    signal output out[n];
+    var lc1=0;
+
+    var e2=1;
+    for (var i = 0; i<n; i++) {
+        out[i] <-- (in >> i) & 1;
+        out[i] * (out[i] -1 ) === 0;
+        lc1 += out[i] * e2;
+        e2 = e2+e2;
+    }
+
+    lc1 === in;
+}
+
+template Decompose() {
+    signal input in;
+    signal output out[300];
+
+    // 300 bits do not fit in the 254-bit BN254 scalar field
+    component n2b = Num2Bits(300);
+    n2b.in <== in;
+    for (var i = 0; i < 300; i++) {
+        out[i] <== n2b.out[i];
+    }
+}
+
+component main = Decompose();
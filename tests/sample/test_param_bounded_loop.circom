@@ -0,0 +1,12 @@
+pragma circom 2.0.0;
+
+template ParamBoundedLoop(N) {
+    signal input in;
+    signal output out[N];
+
+    for (var i = 0; i < N; i++) {
+        out[i] <== in * i;
+    }
+}
+
+component main = ParamBoundedLoop(3);
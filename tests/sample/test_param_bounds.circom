@@ -0,0 +1,18 @@
+pragma circom 2.0.0;
+
+template Bits(n) {
+    signal input in;
+    signal output out[n];
+
+    var lc = 0;
+    var e = 1;
+    for (var i = 0; i < n; i++) {
+        out[i] <-- (in >> i) & 1;
+        out[i] * (out[i] - 1) === 0;
+        lc += out[i] * e;
+        e = e + e;
+    }
+    lc === in;
+}
+
+component main = Bits(3);
@@ -0,0 +1,25 @@
+pragma circom 2.0.0;
+
+/**
+ * @template Product
+ * @description Multiplies its `n` inputs, but forgets to constrain `out` in the special case of
+ *              a single input, so that the bug only shows up when `n` is 1.
+ */
+template Product(n) {
+    signal input in[n];
+    signal output out;
+    signal partial[n];
+
+    partial[0] <== in[0];
+    for (var i = 1; i < n; i++) {
+        partial[i] <== partial[i - 1] * in[i];
+    }
+
+    if (n == 1) {
+        out <-- partial[0];
+    } else {
+        out <== partial[n - 1];
+    }
+}
+
+component main = Product(2);
@@ -0,0 +1,17 @@
+pragma circom 2.0.0;
+
+/**
+ * @template Provenance
+ * @description `out` is pinned down by a constraint, while `free` is assigned with `<--` and
+ *              never constrained.
+ */
+template Provenance() {
+    signal input a;
+    signal output out;
+    signal output free;
+
+    out <== a * a;
+    free <-- a + 1;
+}
+
+component main = Provenance();
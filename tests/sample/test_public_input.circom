@@ -0,0 +1,16 @@
+pragma circom 2.0.0;
+
+// `b` is the private witness of a public offset `a`. As in `VulnerableIsZero`, `inv` is free, so
+// that `out` can be `a` even when `b` is not zero.
+template PublicOffsetIsZero() {
+    signal input a;
+    signal input b;
+    signal output out;
+    signal inv;
+
+    inv <-- b!=0 ? 1/b : 0;
+
+    out <== -b*inv + a;
+}
+
+component main {public [a]} = PublicOffsetIsZero();
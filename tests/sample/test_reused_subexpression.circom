@@ -0,0 +1,14 @@
+pragma circom 2.0.0;
+
+template ReusedSubexpression() {
+    signal input a;
+    signal input b;
+    signal output c;
+
+    var t = (a * b + a) * (a * b + b);
+    var u = t * t + t;
+    c <-- u * u + t;
+    c === (t * t + t) * (t * t + t) + t;
+}
+
+component main = ReusedSubexpression();
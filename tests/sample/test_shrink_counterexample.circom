@@ -0,0 +1,24 @@
+pragma circom 2.0.0;
+
+/**
+ * @template IsZeroWithSum
+ * @description The vulnerable `IsZero` of `test_vuln_iszero.circom` on `a`, alongside a correctly
+ *              constrained sum of the unrelated inputs `b`, `c` and `d`. Only `a` matters for the bug.
+ */
+template IsZeroWithSum() {
+    signal input a;
+    signal input b;
+    signal input c;
+    signal input d;
+    signal output out;
+    signal output sum;
+    signal inv;
+
+    inv <-- a!=0 ? 1/a : 0;
+    out <== -a*inv +1;
+    out*(out-1) === 0;
+
+    sum <== b + c + d;
+}
+
+component main = IsZeroWithSum();
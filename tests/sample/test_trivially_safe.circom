@@ -0,0 +1,11 @@
+pragma circom 2.0.0;
+
+template Square() {
+    signal input in;
+    signal output out;
+
+    // Every signal is assigned with `<==`, so there is no room for a free witness
+    out <== in * in + 1;
+}
+
+component main = Square();
@@ -0,0 +1,20 @@
+pragma circom 2.0.0;
+
+template Square() {
+    signal input in;
+    signal output out;
+
+    out <== in * in + 1;
+}
+
+template SquareOfSquare() {
+    signal input in;
+    signal output out;
+
+    // The search is exhaustive only when the subcomponent is executed, not black-boxed
+    component square = Square();
+    square.in <== in;
+    out <== square.out * square.out;
+}
+
+component main = SquareOfSquare();
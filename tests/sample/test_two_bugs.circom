@@ -0,0 +1,21 @@
+pragma circom 2.0.0;
+
+template TwoBugs() {
+    signal input a;
+    signal input b;
+    signal output x;
+    signal output y;
+
+    signal inva;
+    signal invb;
+
+    inva <-- a!=0 ? 1/a : 0;
+    x <== -a*inva + 1;
+    x * (x - 1) === 0;
+
+    invb <-- b!=0 ? 1/b : 0;
+    y <== -b*invb + 1;
+    y * (y - 1) === 0;
+}
+
+component main = TwoBugs();
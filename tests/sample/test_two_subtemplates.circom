@@ -0,0 +1,30 @@
+pragma circom 2.0.0;
+
+template Square() {
+    signal input in;
+    signal output out;
+    out <== in * in;
+}
+
+template Sum() {
+    signal input a;
+    signal input b;
+    signal output out;
+    signal t;
+    t <== a + b;
+    out <== t * 2;
+}
+
+template Main() {
+    signal input x;
+    signal input y;
+    signal output z;
+    component sq = Square();
+    component su = Sum();
+    sq.in <== x;
+    su.a <== sq.out;
+    su.b <== y;
+    z <== su.out;
+}
+
+component main = Main();
@@ -0,0 +1,16 @@
+pragma circom 2.0.0;
+
+template Adder() {
+    signal input a;
+    signal input b;
+    signal output out;
+    out <== a + b;
+}
+
+template PartialOutput() {
+    signal input a;
+    signal output out[2];
+    out[0] <== a * a;
+}
+
+component main = Adder();
@@ -0,0 +1,10 @@
+pragma circom 2.0.0;
+
+template DimensionMismatch() {
+    signal input a[2];
+    signal output b;
+
+    b <== a;
+}
+
+component main = DimensionMismatch();
@@ -0,0 +1,20 @@
+pragma circom 2.1.6;
+
+template IsBinary() {
+    signal input in;
+    signal output out;
+
+    in * (in - 1) === 0;
+    out <== in;
+}
+
+template Increment() {
+    signal input in;
+    signal output out;
+
+    // The output is discarded, but the component still constrains `in` to be binary
+    _ <== IsBinary()(in);
+    out <== in + 1;
+}
+
+component main = Increment();
@@ -0,0 +1,29 @@
+pragma circom 2.0.0;
+
+function square(a) {
+    return a * a;
+}
+
+function split(a) {
+    var r[2];
+    r[0] = a * 2;
+    r[1] = a + 1;
+    return r;
+}
+
+// `r[1]` is never assigned, so the second element does not depend on `a`
+function partial(a) {
+    var r[2];
+    r[0] = a * 2;
+    return r;
+}
+
+template UndeterminedFunction() {
+    signal input in;
+    signal output out;
+
+    out <-- square(in);
+    out === in * in;
+}
+
+component main = UndeterminedFunction();
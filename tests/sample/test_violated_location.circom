@@ -0,0 +1,12 @@
+pragma circom 2.0.0;
+
+template CheckSum() {
+    signal input a;
+    signal input b;
+    signal output c;
+
+    c <-- a + 1;
+    c === b;
+}
+
+component main = CheckSum();
@@ -0,0 +1,12 @@
+pragma circom 2.0.0;
+
+template Main() {
+    signal input in;
+    signal output out;
+
+    // `in` is only checked to be binary at witness generation
+    assert(in * (in - 1) == 0);
+    out <== in * in;
+}
+
+component main = Main();
@@ -0,0 +1,15 @@
+pragma circom 2.0.0;
+
+// When b = 0, a must be 0 and q is unconstrained
+template ModBySignal() {
+    signal input a;
+    signal input b;
+    signal output q;
+    signal output r;
+
+    q <-- a \ b;
+    r <-- a % b;
+    a === q * b + r;
+}
+
+component main = ModBySignal();
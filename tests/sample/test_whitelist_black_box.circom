@@ -0,0 +1,24 @@
+pragma circom 2.0.0;
+
+template IsZero() {
+    signal input in;
+    signal output out;
+    signal inv;
+
+    inv <-- in!=0 ? 1/in : 0;
+
+    out <== -in*inv +1;
+    in*out === 0;
+}
+
+template IsEqual() {
+    signal input a;
+    signal input b;
+    signal output c;
+
+    component isz = IsZero();
+    isz.in <== a - b;
+    c <== isz.out;
+}
+
+component main = IsEqual();
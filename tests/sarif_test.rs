@@ -0,0 +1,113 @@
+use std::process::Command;
+
+use serde_json::Value;
+
+fn run_with_sarif_output(path: &str, search_mode: &str) -> Value {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            path,
+            "--search_mode",
+            search_mode,
+            "--output_format",
+            "sarif",
+            "--no_color",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+/// Checks the properties required by the SARIF 2.1.0 schema for the log, its runs, and their
+/// results, as well as the rule and the location of each result.
+fn assert_valid_sarif(log: &Value) {
+    assert_eq!(log["version"], "2.1.0");
+    assert!(log["$schema"].as_str().unwrap().contains("sarif-2.1.0"));
+
+    let runs = log["runs"].as_array().unwrap();
+    assert_eq!(runs.len(), 1);
+    for run in runs {
+        let driver = &run["tool"]["driver"];
+        assert_eq!(driver["name"], "zkFuzz");
+        assert_eq!(driver["version"], env!("CARGO_PKG_VERSION"));
+        assert!(run["properties"]["git_hash_of_zkfuzz"].is_string());
+
+        let rule_ids: Vec<&str> = driver["rules"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|rule| rule["id"].as_str().unwrap())
+            .collect();
+        for result in run["results"].as_array().unwrap() {
+            assert!(!result["message"]["text"].as_str().unwrap().is_empty());
+            let rule_id = result["ruleId"].as_str().unwrap();
+            let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+            assert_eq!(rule_ids[rule_index], rule_id);
+            assert!(["error", "warning", "note"].contains(&result["level"].as_str().unwrap()));
+
+            let location = &result["locations"][0]["physicalLocation"];
+            assert!(!location["artifactLocation"]["uri"]
+                .as_str()
+                .unwrap()
+                .is_empty());
+            if let Some(region) = location.get("region") {
+                assert!(region["startLine"].as_u64().unwrap() >= 1);
+                assert!(region["startColumn"].as_u64().unwrap() >= 1);
+            }
+        }
+    }
+}
+
+fn results_of<'a>(log: &'a Value, rule_id: &str) -> Vec<&'a Value> {
+    log["runs"][0]["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|result| result["ruleId"] == rule_id)
+        .collect()
+}
+
+#[test]
+fn test_sarif_counter_example_and_div_by_zero() {
+    let log = run_with_sarif_output("./tests/sample/test_vuln_iszero.circom", "quick");
+    assert_valid_sarif(&log);
+
+    // The non-deterministic output is located at its declaration
+    let under_constrained = results_of(&log, "under-constrained");
+    assert_eq!(under_constrained.len(), 1);
+    assert_eq!(under_constrained[0]["level"], "error");
+    assert_eq!(
+        under_constrained[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+        31
+    );
+    assert!(under_constrained[0]["properties"]["assignment"].is_object());
+
+    // `1/in` is located at the assignment of `inv`
+    let div_by_zeros = results_of(&log, "potential-div-by-zero");
+    assert!(!div_by_zeros.is_empty());
+    for result in div_by_zeros {
+        assert_eq!(result["level"], "warning");
+        assert_eq!(
+            result["locations"][0]["physicalLocation"]["region"]["startLine"],
+            35
+        );
+    }
+}
+
+#[test]
+fn test_sarif_dangling_intermediate() {
+    let log = run_with_sarif_output("./tests/sample/test_dangling_intermediate.circom", "off");
+    assert_valid_sarif(&log);
+
+    let dangling = results_of(&log, "dangling-intermediate");
+    assert_eq!(dangling.len(), 1);
+    assert!(dangling[0]["message"]["text"]
+        .as_str()
+        .unwrap()
+        .contains("dangling"));
+    assert_eq!(
+        dangling[0]["locations"][0]["physicalLocation"]["region"]["startLine"],
+        12
+    );
+    assert!(results_of(&log, "under-constrained").is_empty());
+}
@@ -0,0 +1,189 @@
+mod utils;
+
+use std::process::Command;
+
+use num_bigint_dig::BigInt;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::mutator::brute_force::brute_force_search_all;
+use zkfuzz::mutator::mutation_config::load_config_from_json;
+use zkfuzz::mutator::mutation_test::mutation_test_search;
+use zkfuzz::mutator::mutation_test_evolution_fn::simple_evolution;
+use zkfuzz::mutator::mutation_test_operators::{
+    FnTraceMutation, RandomCrossover, RouletteSelection,
+};
+use zkfuzz::mutator::mutation_test_trace_fitness_fn::evaluate_trace_fitness_by_error;
+use zkfuzz::mutator::mutation_test_trace_initialization_fn::initialize_population_with_operator_or_const_replacement;
+use zkfuzz::mutator::mutation_test_trace_mutation_fn::mutate_trace_with_operator_or_const_replacement;
+use zkfuzz::mutator::mutation_test_update_input_fn::update_input_population_with_random_sampling;
+use zkfuzz::mutator::utils::{BaseVerificationConfig, SearchCompleteness, SearchOutcome};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+/// Searches `path` with the given search mode, over a field small enough for the `full` mode
+/// to enumerate every assignment.
+fn conduct_search(path: String, search_mode: &str) -> SearchOutcome {
+    let prime = BigInt::from(17);
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        quick_mode: search_mode == "quick",
+        heuristics_mode: search_mode == "heuristics",
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let subse_base_config = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &subse_base_config);
+    conc_executor
+        .feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    match search_mode {
+        "ga" => {
+            let mutation_config = load_config_from_json("./tests/parameters/test.json").unwrap();
+            let trace_mutation = FnTraceMutation {
+                initialization_fn: initialize_population_with_operator_or_const_replacement,
+                mutation_fn: mutate_trace_with_operator_or_const_replacement,
+            };
+            mutation_test_search(
+                &mut conc_executor,
+                &symbolic_trace,
+                &side_constraints,
+                &verification_base_config,
+                &mutation_config,
+                update_input_population_with_random_sampling,
+                evaluate_trace_fitness_by_error,
+                simple_evolution,
+                &trace_mutation,
+                &RandomCrossover,
+                &RouletteSelection,
+                &mut |_| {},
+            )
+            .search_outcome()
+        }
+        _ => brute_force_search_all(
+            &mut conc_executor,
+            &symbolic_trace,
+            &side_constraints,
+            &verification_base_config,
+        ),
+    }
+}
+
+#[test]
+fn test_search_completeness_of_full_and_ga() {
+    let full = conduct_search(
+        "./tests/sample/test_trivially_safe.circom".to_string(),
+        "full",
+    );
+    assert!(full.counter_examples.is_empty());
+    assert_eq!(full.completeness, SearchCompleteness::Exhaustive);
+
+    let ga = conduct_search(
+        "./tests/sample/test_trivially_safe.circom".to_string(),
+        "ga",
+    );
+    assert!(ga.counter_examples.is_empty());
+    assert_eq!(ga.completeness, SearchCompleteness::Incomplete);
+    assert_ne!(full.completeness.verdict(), ga.completeness.verdict());
+
+    let quick = conduct_search(
+        "./tests/sample/test_trivially_safe.circom".to_string(),
+        "quick",
+    );
+    assert_eq!(quick.completeness, SearchCompleteness::Incomplete);
+}
+
+#[test]
+fn test_search_disabled_is_reported_as_skipped() {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_lessthan.circom",
+            "--search_mode",
+            "off",
+            "--no_color",
+        ])
+        .output()
+        .unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert!(stderr.contains("Skipped (search disabled)"));
+    assert!(!stderr.contains("No counterexample found"));
+}
+
+/// Runs the full search on `test_trivially_safe_component.circom` over a small field, and
+/// returns the stderr and the report of the main instance.
+fn run_full_search_with_report(extra_args: &[&str]) -> (String, serde_json::Value) {
+    let report_path = std::env::temp_dir().join(format!(
+        "zkfuzz_search_completeness_test_{}_{}.json",
+        std::process::id(),
+        extra_args.len()
+    ));
+    let report_path = report_path.to_str().unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_trivially_safe_component.circom",
+            "--search_mode",
+            "full",
+            "--debug_prime",
+            "17",
+            "--no_color",
+            "--report",
+            report_path,
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    let report: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(report_path).unwrap()).unwrap();
+    std::fs::remove_file(report_path).unwrap();
+    (
+        String::from_utf8_lossy(&output.stderr).to_string(),
+        report["instances"][0].clone(),
+    )
+}
+
+#[test]
+fn test_black_box_search_is_not_exhaustive() {
+    let (stderr, report) = run_full_search_with_report(&[]);
+    assert!(stderr.contains("Exhaustively verified safe"));
+    assert_eq!(report["exhaustive"], true);
+
+    // The black-boxed component leaves its output unconstrained, so that covering every
+    // assignment of the trace proves nothing about the circuit
+    let (stderr, report) = run_full_search_with_report(&["--limit_depth", "0"]);
+    assert!(stderr.contains("Blackboxed Components:"));
+    assert!(!stderr.contains("Exhaustively verified safe"));
+    assert_eq!(report["exhaustive"], false);
+}
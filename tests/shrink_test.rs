@@ -0,0 +1,95 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use num_traits::Zero;
+use rustc_hash::FxHashMap;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicName};
+use zkfuzz::mutator::shrink::shrink_counterexample;
+use zkfuzz::mutator::utils::{
+    is_vulnerable, BaseVerificationConfig, CounterExample, UnderConstrainedType, VerificationResult,
+};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_shrink_counterexample() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let path = "./tests/sample/test_shrink_counterexample.circom".to_string();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        counter: 0,
+        access: None,
+    }]);
+    let name =
+        |s: &str| SymbolicName::new(sexe.symbolic_library.name2id[s], main_owner.clone(), None);
+    let (a, b, c, d) = (name("a"), name("b"), name("c"), name("d"));
+    let (out, sum, inv) = (name("out"), name("sum"), name("inv"));
+
+    // `inv` is forged to zero, so that `out` is one although `a` is not zero.
+    let assignment: FxHashMap<SymbolicName, BigInt> = FxHashMap::from_iter([
+        (a.clone(), BigInt::from(5)),
+        (b.clone(), BigInt::from(3)),
+        (c.clone(), BigInt::from(7)),
+        (d.clone(), BigInt::from(11)),
+        (inv.clone(), BigInt::zero()),
+        (out.clone(), BigInt::from(1)),
+        (sum.clone(), BigInt::from(21)),
+    ]);
+    let counter_example = CounterExample {
+        flag: VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(
+            out.clone(),
+            "out".to_string(),
+            BigInt::zero(),
+        )),
+        target_output: Some(out.clone()),
+        assignment: assignment,
+        field_reductions: Vec::new(),
+        provenance: None,
+    };
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: "IsZeroWithSum".to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        ..Default::default()
+    };
+
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+    let conc_setting = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &conc_setting);
+
+    let shrunk = shrink_counterexample(
+        &mut conc_executor,
+        &symbolic_trace,
+        &side_constraints,
+        &counter_example,
+        &verification_base_config,
+    );
+
+    assert!(is_vulnerable(&shrunk.flag));
+    assert_eq!(shrunk.assignment[&a], BigInt::from(5));
+    assert_eq!(shrunk.assignment[&b], BigInt::zero());
+    assert_eq!(shrunk.assignment[&c], BigInt::zero());
+    assert_eq!(shrunk.assignment[&d], BigInt::zero());
+    assert_eq!(shrunk.assignment[&sum], BigInt::zero());
+    assert_eq!(shrunk.assignment[&inv], BigInt::zero());
+    assert_eq!(shrunk.assignment[&out], BigInt::from(1));
+}
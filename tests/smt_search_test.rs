@@ -0,0 +1,82 @@
+#![cfg(feature = "z3")]
+
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use program_structure::ast::Expression;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+};
+use zkfuzz::mutator::smt_search::smt_search;
+use zkfuzz::mutator::utils::{
+    BaseVerificationConfig, CounterExample, UnderConstrainedType, VerificationResult,
+};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+fn conduct_smt_search(path: String) -> Option<CounterExample> {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+
+    let verification_base_config = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: prime.clone(),
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    let subse_base_config = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &subse_base_config);
+    conc_executor
+        .feed_arguments(
+            &verification_base_config.template_param_names,
+            &verification_base_config.template_param_values,
+        )
+        .unwrap();
+
+    smt_search(
+        &mut conc_executor,
+        &sexe.cur_state.symbolic_trace.clone(),
+        &sexe.cur_state.side_constraints.clone(),
+        &verification_base_config,
+    )
+    .counter_examples
+    .pop()
+}
+
+#[test]
+fn test_smt_vuln_iszero() {
+    let counter_example = conduct_smt_search("./tests/sample/test_vuln_iszero.circom".to_string());
+
+    assert!(matches!(
+        counter_example,
+        Some(CounterExample {
+            flag: VerificationResult::UnderConstrained(UnderConstrainedType::NonDeterministic(..)),
+            ..
+        })
+    ));
+}
@@ -6,6 +6,7 @@ use std::str::FromStr;
 use num_bigint_dig::BigInt;
 use num_traits::identities::Zero;
 use num_traits::One;
+use rustc_hash::FxHashMap;
 
 use program_structure::ast::{Expression, ExpressionInfixOpcode, ExpressionPrefixOpcode};
 
@@ -15,8 +16,9 @@ use zkfuzz::executor::debug_ast::{
 use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
 use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
 use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicAccess, SymbolicName, SymbolicValue};
-use zkfuzz::mutator::unused_outputs::check_unused_outputs;
-use zkfuzz::mutator::utils::BaseVerificationConfig;
+use zkfuzz::mutator::unused_outputs::{check_dangling_intermediates, check_unused_outputs};
+use zkfuzz::mutator::utils::{emulate_symbolic_trace, BaseVerificationConfig};
+use zkfuzz::parser_user::synthesize_main_harness;
 
 use crate::utils::{execute, prepare_symbolic_library};
 
@@ -2202,14 +2204,42 @@ fn test_unused_outputs() {
         target_template_name: main_template_name.to_string(),
         prime: prime.clone(),
         range: range.clone(),
-        quick_mode: false,
-        heuristics_mode: false,
-        progress_interval: 10000,
         template_param_names: template_param_names,
         template_param_values: template_param_values,
+        ..Default::default()
     };
 
-    assert!(check_unused_outputs(&mut sexe, &verification_setting).is_some());
+    let counter_examples = check_unused_outputs(&mut sexe, &verification_setting);
+    assert_eq!(counter_examples.len(), 1);
+    assert_eq!(
+        counter_examples[0]
+            .under_constrained_signal()
+            .unwrap()
+            .lookup_fmt(&sexe.symbolic_library.id2name),
+        "main.out[1]"
+    );
+}
+
+#[test]
+fn test_dangling_intermediates() {
+    let path = "./tests/sample/test_dangling_intermediate.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let dangling = check_dangling_intermediates(&sexe, "DanglingIntermediate");
+    assert_eq!(dangling.len(), 1);
+    assert_eq!(
+        sexe.symbolic_library.id2name[&dangling[0].id],
+        "dangling".to_string()
+    );
 }
 
 #[test]
@@ -2324,3 +2354,571 @@ fn test_var_bulk_assignment() {
     assert_eq!(*sexe.cur_state.symbolic_trace[0], first_cond);
     assert_eq!(*sexe.cur_state.side_constraints[0], first_cond);
 }
+
+#[test]
+fn test_function_symbolic_array_return() {
+    let path = "./tests/sample/test_function_symbolic_array_return.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let in_var = Rc::new(SymbolicValue::Variable(SymbolicName::new(
+        sexe.symbolic_library.name2id["in"],
+        main_owner.clone(),
+        None,
+    )));
+    let out_var = |i: usize| {
+        Rc::new(SymbolicValue::Variable(SymbolicName::new(
+            sexe.symbolic_library.name2id["out"],
+            main_owner.clone(),
+            Some(vec![SymbolicAccess::ArrayAccess(
+                SymbolicValue::ConstantInt(BigInt::from(i)),
+            )]),
+        )))
+    };
+
+    let first_cond = SymbolicValue::AssignEq(out_var(0), in_var.clone());
+    let second_cond = SymbolicValue::AssignEq(
+        out_var(1),
+        Rc::new(SymbolicValue::BinaryOp(
+            in_var.clone(),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Add),
+            Rc::new(SymbolicValue::ConstantInt(BigInt::one())),
+        )),
+    );
+
+    assert_eq!(sexe.cur_state.side_constraints.len(), 2);
+    assert_eq!(*sexe.cur_state.side_constraints[0], first_cond);
+    assert_eq!(*sexe.cur_state.side_constraints[1], second_cond);
+}
+
+#[test]
+fn test_num2bits_overflow_warning() {
+    let path = "./tests/sample/test_num2bits_overflow.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    assert_eq!(sexe.bit_width_warnings.len(), 1);
+    assert!(sexe.bit_width_warnings[0].starts_with("Num2Bits(300)"));
+    assert!(sexe.bit_width_warnings[0].contains("n2b"));
+    assert!(sexe.bit_width_warnings[0].ends_with("the 254-bit field"));
+}
+
+#[test]
+fn test_dedup_constraints() {
+    let path = "./tests/sample/test_duplicated_component.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let flag_check = SymbolicValue::BinaryOp(
+        Rc::new(SymbolicValue::ConstantInt(BigInt::zero())),
+        DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+        Rc::new(SymbolicValue::ConstantInt(BigInt::zero())),
+    );
+    let count_flag_checks = |constraints: &Vec<Rc<SymbolicValue>>| {
+        constraints.iter().filter(|c| ***c == flag_check).count()
+    };
+
+    let num_side_constraints = sexe.cur_state.side_constraints.len();
+    assert_eq!(count_flag_checks(&sexe.cur_state.side_constraints), 2);
+
+    assert_eq!(sexe.cur_state.dedup_constraints(), (1, 1));
+    assert_eq!(
+        sexe.cur_state.side_constraints.len(),
+        num_side_constraints - 1
+    );
+    assert_eq!(count_flag_checks(&sexe.cur_state.side_constraints), 1);
+    assert_eq!(count_flag_checks(&sexe.cur_state.symbolic_trace), 1);
+
+    assert_eq!(sexe.cur_state.dedup_constraints(), (0, 0));
+}
+
+#[test]
+fn test_dedup_constraints_on_push() {
+    let path = "./tests/sample/test_duplicated_component.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let flag_check = SymbolicValue::BinaryOp(
+        Rc::new(SymbolicValue::ConstantInt(BigInt::zero())),
+        DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+        Rc::new(SymbolicValue::ConstantInt(BigInt::zero())),
+    );
+    let count_flag_checks = |constraints: &Vec<Rc<SymbolicValue>>| {
+        constraints.iter().filter(|c| ***c == flag_check).count()
+    };
+
+    // Both executions share the ids of the names, so that their constraints can be compared
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let mut dedup_symbolic_library = symbolic_library.clone();
+
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+    let plain_trace = sexe.cur_state.symbolic_trace.clone();
+    let plain_side_constraints = sexe.cur_state.side_constraints.clone();
+
+    let mut dedup_setting = get_default_setting_for_symbolic_execution(prime, false);
+    dedup_setting.dedup_constraints = true;
+    let mut dedup_sexe = SymbolicExecutor::new(&mut dedup_symbolic_library, &dedup_setting);
+    execute(&mut dedup_sexe, &program_archive);
+
+    assert_eq!(count_flag_checks(&plain_side_constraints), 2);
+    assert_eq!(count_flag_checks(&dedup_sexe.cur_state.side_constraints), 1);
+    assert_eq!(count_flag_checks(&dedup_sexe.cur_state.symbolic_trace), 1);
+    assert_eq!(
+        dedup_sexe.cur_state.side_constraints.len(),
+        plain_side_constraints.len() - 1
+    );
+    assert_eq!(
+        dedup_sexe.cur_state.symbolic_trace.len(),
+        plain_trace.len() - 1
+    );
+
+    // The first occurrences keep their relative order
+    let mut expected_side_constraints = plain_side_constraints.clone();
+    let mut seen_flag_check = false;
+    expected_side_constraints.retain(|c| {
+        if **c != flag_check {
+            return true;
+        }
+        !std::mem::replace(&mut seen_flag_check, true)
+    });
+    assert_eq!(
+        dedup_sexe.cur_state.side_constraints,
+        expected_side_constraints
+    );
+    assert_eq!(dedup_sexe.cur_state.num_trace_duplicates, 1);
+    assert_eq!(dedup_sexe.cur_state.num_side_constraint_duplicates, 1);
+    assert_eq!(dedup_sexe.cur_state.dedup_constraints(), (0, 0));
+}
+
+#[test]
+fn test_synthesize_main_iszero() {
+    let harness = synthesize_main_harness("./tests/sample/lib_iszero.circom", "IsZero").unwrap();
+    let path = harness.to_string_lossy().to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let (main_template_name, template_param_names, template_param_values) =
+        match &program_archive.initial_template_call {
+            Expression::Call { id, args, .. } => {
+                let template = &program_archive.templates[id];
+                (id, template.get_name_of_params().clone(), args.clone())
+            }
+            _ => unimplemented!(),
+        };
+    assert_eq!(main_template_name, "IsZero");
+
+    let verification_setting = BaseVerificationConfig {
+        target_template_name: main_template_name.to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(100),
+        template_param_names: template_param_names,
+        template_param_values: template_param_values,
+        ..Default::default()
+    };
+
+    assert_eq!(sexe.cur_state.side_constraints.len(), 2);
+    assert!(check_unused_outputs(&mut sexe, &verification_setting).is_empty());
+}
+
+#[test]
+fn test_max_component_depth() {
+    let path = "./tests/sample/test_lessthan.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let mut setting = get_default_setting_for_symbolic_execution(prime, false);
+    setting.max_component_depth = Some(1);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // `LessThan` is analyzed, while the `Num2Bits` nested inside it is blackboxed
+    assert_eq!(sexe.num_blackboxed_components, 1);
+    assert!(!sexe.cur_state.symbolic_trace.is_empty());
+    assert!(sexe
+        .cur_state
+        .symbolic_trace
+        .iter()
+        .all(|c| !matches!(c.as_ref(), SymbolicValue::Assign(..))));
+    assert!(sexe
+        .cur_state
+        .side_constraints
+        .iter()
+        .any(|c| c.lookup_fmt(&sexe.symbolic_library.id2name).contains("n2b")));
+}
+
+#[test]
+fn test_bus_input() {
+    let path = "./tests/sample/test_bus.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    assert!(sexe
+        .symbolic_library
+        .bus_library
+        .contains_key(&sexe.symbolic_library.name2id["Point"]));
+
+    let main_owner = OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    };
+    let bus_owner = Rc::new(vec![
+        main_owner.clone(),
+        OwnerName {
+            id: sexe.symbolic_library.name2id["p"],
+            access: None,
+            counter: 0,
+        },
+    ]);
+    let ground_truth_constraint = SymbolicValue::AssignEq(
+        Rc::new(SymbolicValue::Variable(SymbolicName::new(
+            sexe.symbolic_library.name2id["out"],
+            Rc::new(vec![main_owner]),
+            None,
+        ))),
+        Rc::new(SymbolicValue::BinaryOp(
+            Rc::new(SymbolicValue::Variable(SymbolicName::new(
+                sexe.symbolic_library.name2id["x"],
+                bus_owner.clone(),
+                None,
+            ))),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Mul),
+            Rc::new(SymbolicValue::Variable(SymbolicName::new(
+                sexe.symbolic_library.name2id["y"],
+                bus_owner,
+                None,
+            ))),
+        )),
+    );
+
+    assert_eq!(sexe.cur_state.symbolic_trace.len(), 1);
+    assert_eq!(*sexe.cur_state.symbolic_trace[0], ground_truth_constraint);
+    assert_eq!(sexe.cur_state.side_constraints.len(), 1);
+}
+
+#[test]
+fn test_bus_intermediate() {
+    let path = "./tests/sample/test_bus_intermediate.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    assert!(sexe
+        .symbolic_library
+        .bus_library
+        .contains_key(&sexe.symbolic_library.name2id["Point"]));
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let bus_owner = Rc::new(vec![
+        main_owner[0].clone(),
+        OwnerName {
+            id: sexe.symbolic_library.name2id["p"],
+            access: None,
+            counter: 0,
+        },
+    ]);
+    let variable = |name: &str, owner: &Rc<Vec<OwnerName>>| {
+        Rc::new(SymbolicValue::Variable(SymbolicName::new(
+            sexe.symbolic_library.name2id[name],
+            owner.clone(),
+            None,
+        )))
+    };
+
+    // The fields of the bus instance are owned by it, and constrained to the inputs
+    let ground_truth_constraints = [
+        SymbolicValue::AssignEq(variable("x", &bus_owner), variable("a", &main_owner)),
+        SymbolicValue::AssignEq(variable("y", &bus_owner), variable("b", &main_owner)),
+    ];
+    assert_eq!(sexe.cur_state.side_constraints.len(), 3);
+    for ground_truth_constraint in &ground_truth_constraints {
+        assert!(sexe
+            .cur_state
+            .side_constraints
+            .iter()
+            .any(|c| **c == *ground_truth_constraint));
+    }
+}
+
+#[test]
+fn test_loop_unroll_param_bounded() {
+    let path = "./tests/sample/test_param_bounded_loop.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let mut setting = get_default_setting_for_symbolic_execution(prime, false);
+    setting.max_loop_unroll = 1;
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // A loop with a concrete bound is fully executed regardless of the unrolling limit
+    assert!(!sexe.cur_state.contains_symbolic_loop);
+    assert_eq!(sexe.cur_state.symbolic_trace.len(), 3);
+    assert_eq!(sexe.cur_state.side_constraints.len(), 3);
+}
+
+#[test]
+fn test_loop_unroll_input_bounded() {
+    let path = "./tests/sample/test_input_bounded_loop.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let assigned_value_of_out = |max_loop_unroll: usize| {
+        let (mut symbolic_library, program_archive) =
+            prepare_symbolic_library(path.clone(), prime.clone());
+        let mut setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+        setting.max_loop_unroll = max_loop_unroll;
+
+        let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+        execute(&mut sexe, &program_archive);
+
+        assert!(sexe.cur_state.contains_symbolic_loop);
+        assert_eq!(sexe.cur_state.symbolic_trace.len(), 1);
+        match sexe.cur_state.symbolic_trace[0].as_ref() {
+            SymbolicValue::Assign(_, rhs, _, _) => (**rhs).clone(),
+            other => panic!(
+                "Unexpected trace entry: {}",
+                other.lookup_fmt(&sexe.symbolic_library.id2name)
+            ),
+        }
+    };
+
+    // Without unrolling, the body of the symbolic loop is skipped
+    assert_eq!(
+        assigned_value_of_out(0),
+        SymbolicValue::ConstantInt(BigInt::zero())
+    );
+    // The loop is not terminated after the unrolled iterations, since `n` is unbounded, so that
+    // the partially unrolled iterations are dropped as well
+    assert_eq!(
+        assigned_value_of_out(2),
+        SymbolicValue::ConstantInt(BigInt::zero())
+    );
+}
+
+#[test]
+fn test_simplification_cache() {
+    let path = "./tests/sample/test_reused_subexpression.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) =
+        prepare_symbolic_library(path.clone(), prime.clone());
+    let (mut uncached_symbolic_library, _) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut cached = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut cached, &program_archive);
+    let cached_trace = cached.cur_state.symbolic_trace.clone();
+    let cached_side_constraints = cached.cur_state.side_constraints.clone();
+
+    let mut uncached = SymbolicExecutor::new(&mut uncached_symbolic_library, &setting);
+    uncached.turn_off_simplification_cache();
+    execute(&mut uncached, &program_archive);
+
+    assert!(!cached_trace.is_empty());
+    assert_eq!(cached_trace, uncached.cur_state.symbolic_trace);
+    assert_eq!(cached_side_constraints, uncached.cur_state.side_constraints);
+}
+
+#[test]
+fn test_max_steps() {
+    let path = "./tests/sample/test_expensive_recursion.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let mut setting = get_default_setting_for_symbolic_execution(prime, false);
+    setting.max_steps = Some(100);
+
+    // fib(40) would take billions of calls, so the execution must stop at the budget.
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    assert!(sexe.cur_state.timed_out);
+    assert_eq!(sexe.num_steps, 101);
+}
+
+#[test]
+fn test_long_template() {
+    // A template with 50,000 statements used to overflow the stack, as every statement was
+    // executed one recursive call deeper than the previous one.
+    let mut source =
+        "pragma circom 2.0.0;\n\ntemplate Long() {\n    signal input in;\n    signal output out;\n    var x = 0;\n"
+            .to_string();
+    for _ in 0..50000 {
+        source.push_str("    x = x + 1;\n");
+    }
+    source.push_str("    out <== in * x;\n}\n\ncomponent main = Long();\n");
+    let path = std::env::temp_dir().join("zkfuzz_test_long_template.circom");
+    std::fs::write(&path, source).unwrap();
+
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) =
+        prepare_symbolic_library(path.to_str().unwrap().to_string(), prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    assert_eq!(sexe.cur_state.side_constraints.len(), 1);
+    assert!(sexe.cur_state.side_constraints[0]
+        .lookup_fmt(&sexe.symbolic_library.id2name)
+        .contains("50000"));
+}
+
+#[test]
+fn test_underscore_substitution() {
+    let path = "./tests/sample/test_underscore_substitution.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // The constraints of the discarded component are gathered along with those of `main`
+    let lookup = &sexe.symbolic_library.id2name;
+    let constraints: Vec<String> = sexe
+        .cur_state
+        .side_constraints
+        .iter()
+        .map(|c| c.lookup_fmt(lookup))
+        .collect();
+    let constraints_of_component: Vec<&String> = constraints
+        .iter()
+        .filter(|c| c.contains("main.IsBinary_"))
+        .collect();
+    assert_eq!(sexe.cur_state.side_constraints.len(), 4);
+    // `IsBinary.in === in`, `in * (in - 1) === 0`, and `IsBinary.out === IsBinary.in`
+    assert_eq!(constraints_of_component.len(), 3);
+}
+
+#[test]
+fn test_max_expression_nodes() {
+    let path = "./tests/sample/test_expression_explosion.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let mut setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    setting.max_expression_nodes = Some(50);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    assert!(sexe.num_opaque_values > 0);
+    for value in sexe.cur_state.symbol_binding_map.values() {
+        assert!(value.count_nodes(50) <= 50);
+    }
+
+    // The opaque variables are assigned by the trace, which still computes the exact output
+    let main = Rc::new(vec![OwnerName {
+        id: sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let input = SymbolicName::new(sexe.symbolic_library.name2id["in"], main.clone(), None);
+    let output = SymbolicName::new(sexe.symbolic_library.name2id["out"], main, None);
+    let mut assignment = FxHashMap::default();
+    assignment.insert(input, BigInt::from(3));
+    let trace = sexe.cur_state.symbolic_trace.clone();
+    let result = emulate_symbolic_trace(
+        &prime,
+        &trace,
+        &FxHashMap::default(),
+        &mut assignment,
+        sexe.symbolic_library,
+    );
+    assert_eq!(result, Some((true, 0)));
+
+    let mut expected = BigInt::from(3);
+    for i in 0..24 {
+        expected = (&expected * &expected + BigInt::from(i)) % &prime;
+    }
+    assert_eq!(assignment[&output], expected);
+}
@@ -3,10 +3,20 @@ use std::str::FromStr;
 
 use num_bigint_dig::BigInt;
 
-use program_structure::ast::ExpressionInfixOpcode;
+use program_structure::ast::{ExpressionInfixOpcode, ExpressionPrefixOpcode};
 
-use zkfuzz::executor::debug_ast::DebuggableExpressionInfixOpcode;
-use zkfuzz::executor::symbolic_value::{enumerate_array, evaluate_binary_op, SymbolicValue};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use zkfuzz::executor::debug_ast::{
+    DebuggableExpressionInfixOpcode, DebuggableExpressionPrefixOpcode,
+};
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_value::{
+    enumerate_array, evaluate_binary_op, fold_constants, OwnerName, SymbolicAccess,
+    SymbolicLibrary, SymbolicName, SymbolicValue,
+};
+use zkfuzz::executor::utils::modpow;
 
 #[test]
 fn test_arithmetic_operations() {
@@ -131,6 +141,101 @@ fn test_bitwise_operations() {
         ),
         SymbolicValue::ConstantInt(BigInt::from(6))
     );
+
+    // The operands are field elements and the result is reduced modulo the prime
+    assert_eq!(
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(16)),
+            &SymbolicValue::ConstantInt(BigInt::from(1)),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::BitOr)
+        ),
+        SymbolicValue::ConstantInt(BigInt::from(0))
+    );
+    assert_eq!(
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(-1)),
+            &SymbolicValue::ConstantInt(BigInt::from(7)),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::BitAnd)
+        ),
+        SymbolicValue::ConstantInt(BigInt::from(0)) // 16 & 7
+    );
+    assert_eq!(
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(-1)),
+            &SymbolicValue::ConstantInt(BigInt::from(3)),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::BitXor)
+        ),
+        SymbolicValue::ConstantInt(BigInt::from(2)) // (16 ^ 3) % 17
+    );
+}
+
+#[test]
+fn test_shift_operations() {
+    let prime = BigInt::from(17);
+    let shift = |lv: i64, op: ExpressionInfixOpcode, rv: i64| {
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(lv)),
+            &SymbolicValue::ConstantInt(BigInt::from(rv)),
+            &prime,
+            &DebuggableExpressionInfixOpcode(op),
+        )
+    };
+
+    assert_eq!(
+        shift(3, ExpressionInfixOpcode::ShiftL, 2),
+        SymbolicValue::ConstantInt(BigInt::from(12))
+    );
+    // (5 * 2^3) % 17
+    assert_eq!(
+        shift(5, ExpressionInfixOpcode::ShiftL, 3),
+        SymbolicValue::ConstantInt(BigInt::from(6))
+    );
+    assert_eq!(
+        shift(12, ExpressionInfixOpcode::ShiftR, 2),
+        SymbolicValue::ConstantInt(BigInt::from(3))
+    );
+    // Shifting right by the bit width of the prime or more clears the value
+    assert_eq!(
+        shift(16, ExpressionInfixOpcode::ShiftR, 5),
+        SymbolicValue::ConstantInt(BigInt::from(0))
+    );
+    // A shift by k > prime / 2 is a shift by prime - k in the opposite direction
+    assert_eq!(
+        shift(6, ExpressionInfixOpcode::ShiftL, 16),
+        SymbolicValue::ConstantInt(BigInt::from(3))
+    );
+    assert_eq!(
+        shift(5, ExpressionInfixOpcode::ShiftR, -1),
+        SymbolicValue::ConstantInt(BigInt::from(10))
+    );
+
+    // Large shift amounts stay within the field
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let k = BigInt::from_str("1000000000000").unwrap();
+    assert_eq!(
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(1)),
+            &SymbolicValue::ConstantInt(k.clone()),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::ShiftL)
+        ),
+        SymbolicValue::ConstantInt(BigInt::from(2).modpow(&k, &prime))
+    );
+    assert_eq!(
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(1)),
+            &SymbolicValue::ConstantInt(k),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::ShiftR)
+        ),
+        SymbolicValue::ConstantInt(BigInt::from(0))
+    );
 }
 
 #[test]
@@ -291,3 +396,368 @@ fn test_enumerate_empty_array() {
 
     assert_eq!(result.len(), 0);
 }
+
+#[test]
+fn test_simplify_to_fixpoint() {
+    let prime = BigInt::from(17);
+    let binop = |lhs: SymbolicValue, op: ExpressionInfixOpcode, rhs: SymbolicValue| {
+        SymbolicValue::BinaryOp(
+            Rc::new(lhs),
+            DebuggableExpressionInfixOpcode(op),
+            Rc::new(rhs),
+        )
+    };
+    let x = SymbolicValue::Variable(SymbolicName::new(0, Rc::new(Vec::new()), None));
+
+    // (((0 + x) * 0) + 2) * 3
+    let expr = binop(
+        binop(
+            binop(
+                binop(
+                    SymbolicValue::ConstantInt(BigInt::from(0)),
+                    ExpressionInfixOpcode::Add,
+                    x.clone(),
+                ),
+                ExpressionInfixOpcode::Mul,
+                SymbolicValue::ConstantInt(BigInt::from(0)),
+            ),
+            ExpressionInfixOpcode::Add,
+            SymbolicValue::ConstantInt(BigInt::from(2)),
+        ),
+        ExpressionInfixOpcode::Mul,
+        SymbolicValue::ConstantInt(BigInt::from(3)),
+    );
+
+    let mut symbolic_library = SymbolicLibrary::default();
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+
+    // A single round only removes the identities.
+    let mut memo = FxHashSet::default();
+    let single_round = fold_constants(
+        &sexe.simplify_variables(&expr, 0, false, false, &mut memo),
+        &prime,
+    );
+    assert_eq!(
+        single_round,
+        binop(
+            SymbolicValue::ConstantInt(BigInt::from(2)),
+            ExpressionInfixOpcode::Mul,
+            SymbolicValue::ConstantInt(BigInt::from(3)),
+        )
+    );
+
+    // The fixpoint mode also folds the exposed constant arithmetic.
+    assert_eq!(
+        sexe.simplify_to_fixpoint(&expr, 0, false, false),
+        SymbolicValue::ConstantInt(BigInt::from(6))
+    );
+}
+
+fn circom_source_fixture() -> (
+    FxHashMap<usize, String>,
+    impl Fn(usize, Option<Vec<SymbolicAccess>>) -> SymbolicValue,
+) {
+    let id2name = FxHashMap::from_iter(
+        ["main", "a", "b", "c", "in", "out", "lt"]
+            .iter()
+            .enumerate()
+            .map(|(i, n)| (i, n.to_string())),
+    );
+    let var = |id: usize, access: Option<Vec<SymbolicAccess>>| {
+        SymbolicValue::Variable(SymbolicName::new(
+            id,
+            Rc::new(vec![OwnerName {
+                id: 0,
+                access: None,
+                counter: 0,
+            }]),
+            access,
+        ))
+    };
+    (id2name, var)
+}
+
+fn binop(lhs: SymbolicValue, op: ExpressionInfixOpcode, rhs: SymbolicValue) -> SymbolicValue {
+    SymbolicValue::BinaryOp(
+        Rc::new(lhs),
+        DebuggableExpressionInfixOpcode(op),
+        Rc::new(rhs),
+    )
+}
+
+#[test]
+fn test_to_circom_source_nested_binary_ops() {
+    let (id2name, var) = circom_source_fixture();
+    let one = SymbolicValue::ConstantInt(BigInt::from(1));
+
+    // a * b + 1
+    let expr = binop(
+        binop(var(1, None), ExpressionInfixOpcode::Mul, var(2, None)),
+        ExpressionInfixOpcode::Add,
+        one.clone(),
+    );
+    assert_eq!(expr.to_circom_source(&id2name), "a * b + 1");
+
+    // a * (b + 1)
+    let expr = binop(
+        var(1, None),
+        ExpressionInfixOpcode::Mul,
+        binop(var(2, None), ExpressionInfixOpcode::Add, one.clone()),
+    );
+    assert_eq!(expr.to_circom_source(&id2name), "a * (b + 1)");
+
+    // a - b - c and a - (b - c)
+    let left_nested = binop(
+        binop(var(1, None), ExpressionInfixOpcode::Sub, var(2, None)),
+        ExpressionInfixOpcode::Sub,
+        var(3, None),
+    );
+    assert_eq!(left_nested.to_circom_source(&id2name), "a - b - c");
+    let right_nested = binop(
+        var(1, None),
+        ExpressionInfixOpcode::Sub,
+        binop(var(2, None), ExpressionInfixOpcode::Sub, var(3, None)),
+    );
+    assert_eq!(right_nested.to_circom_source(&id2name), "a - (b - c)");
+
+    // (a + b) * c == 1 && a != b
+    let expr = binop(
+        binop(
+            binop(
+                binop(var(1, None), ExpressionInfixOpcode::Add, var(2, None)),
+                ExpressionInfixOpcode::Mul,
+                var(3, None),
+            ),
+            ExpressionInfixOpcode::Eq,
+            one,
+        ),
+        ExpressionInfixOpcode::BoolAnd,
+        binop(var(1, None), ExpressionInfixOpcode::NotEq, var(2, None)),
+    );
+    assert_eq!(
+        expr.to_circom_source(&id2name),
+        "(a + b) * c == 1 && a != b"
+    );
+}
+
+#[test]
+fn test_to_circom_source_unary_negation() {
+    let (id2name, var) = circom_source_fixture();
+    let neg = |v: SymbolicValue| {
+        SymbolicValue::UnaryOp(
+            DebuggableExpressionPrefixOpcode(ExpressionPrefixOpcode::Sub),
+            Rc::new(v),
+        )
+    };
+
+    // -a * b
+    let expr = binop(neg(var(1, None)), ExpressionInfixOpcode::Mul, var(2, None));
+    assert_eq!(expr.to_circom_source(&id2name), "-a * b");
+
+    // -(a * b)
+    let expr = neg(binop(
+        var(1, None),
+        ExpressionInfixOpcode::Mul,
+        var(2, None),
+    ));
+    assert_eq!(expr.to_circom_source(&id2name), "-(a * b)");
+
+    // -(-a)
+    let expr = neg(neg(var(1, None)));
+    assert_eq!(expr.to_circom_source(&id2name), "-(-a)");
+}
+
+#[test]
+fn test_to_circom_source_array_access() {
+    let (id2name, var) = circom_source_fixture();
+    let index = |i: i32| SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(BigInt::from(i)));
+
+    // in[0] <== out
+    let stmt =
+        SymbolicValue::AssignEq(Rc::new(var(4, Some(vec![index(0)]))), Rc::new(var(5, None)));
+    assert_eq!(stmt.to_circom_source(&id2name), "in[0] <== out");
+
+    // in[a + 1][2]
+    let expr = var(
+        4,
+        Some(vec![
+            SymbolicAccess::ArrayAccess(binop(
+                var(1, None),
+                ExpressionInfixOpcode::Add,
+                SymbolicValue::ConstantInt(BigInt::from(1)),
+            )),
+            index(2),
+        ]),
+    );
+    assert_eq!(expr.to_circom_source(&id2name), "in[a + 1][2]");
+
+    // A signal of a subcomponent of main: lt[1].out
+    let expr = SymbolicValue::Variable(SymbolicName::new(
+        5,
+        Rc::new(vec![
+            OwnerName {
+                id: 0,
+                access: None,
+                counter: 0,
+            },
+            OwnerName {
+                id: 6,
+                access: Some(vec![index(1)]),
+                counter: 0,
+            },
+        ]),
+        None,
+    ));
+    assert_eq!(expr.to_circom_source(&id2name), "lt[1].out");
+}
+
+#[test]
+fn test_to_circom_source_conditional() {
+    let (id2name, var) = circom_source_fixture();
+    let zero = SymbolicValue::ConstantInt(BigInt::from(0));
+    let one = SymbolicValue::ConstantInt(BigInt::from(1));
+
+    // in != 0 ? 1 / in : 0
+    let cond = SymbolicValue::Conditional(
+        Rc::new(binop(
+            var(4, None),
+            ExpressionInfixOpcode::NotEq,
+            zero.clone(),
+        )),
+        Rc::new(binop(one.clone(), ExpressionInfixOpcode::Div, var(4, None))),
+        Rc::new(zero),
+    );
+    assert_eq!(cond.to_circom_source(&id2name), "in != 0 ? 1 / in : 0");
+
+    // A conditional as an operand is parenthesized.
+    let expr = binop(cond.clone(), ExpressionInfixOpcode::Add, one);
+    assert_eq!(
+        expr.to_circom_source(&id2name),
+        "(in != 0 ? 1 / in : 0) + 1"
+    );
+
+    let stmt = SymbolicValue::Assign(Rc::new(var(3, None)), Rc::new(cond), false, None);
+    assert_eq!(
+        stmt.to_circom_source(&id2name),
+        "c <-- in != 0 ? 1 / in : 0"
+    );
+}
+
+#[test]
+fn test_pow() {
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+    let pow = |base: i64, exp: i64| {
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(base)),
+            &SymbolicValue::ConstantInt(BigInt::from(exp)),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Pow),
+        )
+    };
+
+    // 2^256 exceeds the prime and is reduced
+    let expected = BigInt::from_str(
+        "6350874878119819312338956282401532410528162663560392320966563075034087161851",
+    )
+    .unwrap();
+    assert_eq!(pow(2, 256), SymbolicValue::ConstantInt(expected));
+    assert_eq!(pow(7, 0), SymbolicValue::ConstantInt(BigInt::from(1)));
+    assert_eq!(pow(0, 0), SymbolicValue::ConstantInt(BigInt::from(1)));
+
+    // A negative exponent raises the inverse of the base
+    let inv = modpow(&BigInt::from(3), &BigInt::from(-1), &prime);
+    assert_eq!((&inv * BigInt::from(3)) % &prime, BigInt::from(1));
+    assert_eq!(
+        modpow(&BigInt::from(3), &BigInt::from(-2), &prime),
+        (&inv * &inv) % &prime
+    );
+    assert_eq!(
+        modpow(&BigInt::from(-2), &BigInt::from(3), &prime),
+        &prime - BigInt::from(8)
+    );
+
+    // Symbolic operands are left as they are
+    assert!(matches!(
+        evaluate_binary_op(
+            &SymbolicValue::ConstantInt(BigInt::from(2)),
+            &SymbolicValue::Variable(SymbolicName::new(0, Rc::new(Vec::new()), None)),
+            &prime,
+            &DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Pow),
+        ),
+        SymbolicValue::BinaryOp(..)
+    ));
+}
+
+#[test]
+fn test_simplify_conditional_with_equal_branches() {
+    let prime = BigInt::from(17);
+    let binop = |lhs: SymbolicValue, op: ExpressionInfixOpcode, rhs: SymbolicValue| {
+        SymbolicValue::BinaryOp(
+            Rc::new(lhs),
+            DebuggableExpressionInfixOpcode(op),
+            Rc::new(rhs),
+        )
+    };
+    let x = SymbolicValue::Variable(SymbolicName::new(0, Rc::new(Vec::new()), None));
+    let y = SymbolicValue::Variable(SymbolicName::new(1, Rc::new(Vec::new()), None));
+    let zero = SymbolicValue::ConstantInt(BigInt::from(0));
+    let x_is_zero = binop(x.clone(), ExpressionInfixOpcode::Eq, zero.clone());
+
+    let mut symbolic_library = SymbolicLibrary::default();
+    let setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    sexe.turn_on_coverage_tracking();
+
+    // (x == 0) ? (2 + 3) : 5, whose branches both simplify to 5
+    let five = SymbolicValue::ConstantInt(BigInt::from(5));
+    let same_branches = SymbolicValue::Conditional(
+        Rc::new(x_is_zero.clone()),
+        Rc::new(binop(
+            SymbolicValue::ConstantInt(BigInt::from(2)),
+            ExpressionInfixOpcode::Add,
+            SymbolicValue::ConstantInt(BigInt::from(3)),
+        )),
+        Rc::new(five.clone()),
+    );
+    let mut memo = FxHashSet::default();
+    assert_eq!(
+        sexe.simplify_variables(&same_branches, 0, false, false, &mut memo),
+        five
+    );
+
+    // (x == 0) ? x : y is kept
+    let different_branches = SymbolicValue::Conditional(
+        Rc::new(x_is_zero.clone()),
+        Rc::new(x.clone()),
+        Rc::new(y.clone()),
+    );
+    let mut memo = FxHashSet::default();
+    assert_eq!(
+        sexe.simplify_variables(&different_branches, 0, false, false, &mut memo),
+        different_branches
+    );
+
+    // The branch taken inside the condition is still recorded when the conditional collapses
+    let nested = SymbolicValue::Conditional(
+        Rc::new(SymbolicValue::Conditional(
+            Rc::new(SymbolicValue::ConstantBool(true)),
+            Rc::new(x_is_zero),
+            Rc::new(binop(x, ExpressionInfixOpcode::Eq, y.clone())),
+        )),
+        Rc::new(y.clone()),
+        Rc::new(y.clone()),
+    );
+    let mut memo = FxHashSet::default();
+    assert_eq!(
+        sexe.simplify_variables(&nested, 7, false, false, &mut memo),
+        y
+    );
+    assert_eq!(
+        sexe.coverage_tracker().branch_hits().get(&7),
+        Some(&(true, false))
+    );
+}
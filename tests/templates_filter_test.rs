@@ -0,0 +1,52 @@
+use std::process::Command;
+
+fn stats_rows(extra_args: &[&str]) -> (bool, Vec<String>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_lessthan.circom",
+            "--show_stats_of_ast",
+            "--no_color",
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut rows: Vec<String> = stdout
+        .lines()
+        .filter(|line| line.matches(',').count() == 11 && !line.starts_with("template_name,"))
+        .map(|line| line.split(',').next().unwrap().to_string())
+        .collect();
+    rows.sort();
+    (
+        output.status.success(),
+        rows,
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn test_show_stats_of_all_templates() {
+    let (success, rows, _) = stats_rows(&[]);
+    assert!(success);
+    assert_eq!(rows, vec!["LessThan", "Num2Bits", "VulnerableLessThan"]);
+}
+
+#[test]
+fn test_templates_filter_restricts_stats() {
+    let (success, rows, _) = stats_rows(&["--templates_filter", "^LessThan$"]);
+    assert!(success);
+    assert_eq!(rows, vec!["LessThan"]);
+
+    let (success, rows, _) = stats_rows(&["--templates_filter", "LessThan"]);
+    assert!(success);
+    assert_eq!(rows, vec!["LessThan", "VulnerableLessThan"]);
+}
+
+#[test]
+fn test_invalid_templates_filter() {
+    let (success, rows, stderr) = stats_rows(&["--templates_filter", "(LessThan"]);
+    assert!(!success);
+    assert!(rows.is_empty());
+    assert!(stderr.contains("is not a valid templates filter"));
+}
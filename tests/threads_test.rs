@@ -0,0 +1,38 @@
+use std::process::Command;
+
+fn run(extra_args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_vuln_iszero.circom",
+            "--search_mode",
+            "quick",
+            "--output_format",
+            "circom",
+            "--no_color",
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    )
+}
+
+#[test]
+fn test_threads() {
+    let (success, single_threaded) = run(&["--threads", "1"]);
+    assert!(success);
+    assert!(single_threaded.contains("\"in\""));
+
+    // A single thread reproduces the counterexample of the sequential search
+    #[cfg(not(feature = "parallel"))]
+    {
+        let (success, sequential) = run(&[]);
+        assert!(success);
+        assert_eq!(single_threaded, sequential);
+    }
+    assert_eq!(run(&["--threads", "1"]).1, single_threaded);
+
+    assert!(!run(&["--threads", "0"]).0);
+}
@@ -0,0 +1,129 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+use rustc_hash::FxHashMap;
+
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_state::SymbolicState;
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicAccess, SymbolicName, SymbolicValue};
+use zkfuzz::stats::trace_dump::dump_symbolic_trace;
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_dump_symbolic_trace() {
+    let path = "./tests/sample/test_lessthan.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    let symbolic_trace = &sexe.cur_state.symbolic_trace;
+    let side_constraints = &sexe.cur_state.side_constraints;
+    let dump_path = std::env::temp_dir().join("zkfuzz_dump_symbolic_trace_test.txt");
+    let dump_path = dump_path.to_str().unwrap();
+    let json_path = format!("{}.json", dump_path);
+    let _ = std::fs::remove_file(&json_path);
+
+    dump_symbolic_trace(
+        dump_path,
+        symbolic_trace,
+        side_constraints,
+        &sexe.symbolic_library.id2name,
+        false,
+    )
+    .unwrap();
+    let dumped = std::fs::read_to_string(dump_path).unwrap();
+    let lines: Vec<&str> = dumped.lines().collect();
+    assert_eq!(lines.len(), symbolic_trace.len() + side_constraints.len());
+    assert!(lines[..symbolic_trace.len()]
+        .iter()
+        .all(|l| l.starts_with("trace\t")));
+    assert!(lines[symbolic_trace.len()..]
+        .iter()
+        .all(|l| l.starts_with("side\t")));
+    assert!(!dumped.contains('\x1b'));
+    assert!(!std::path::Path::new(&json_path).exists());
+
+    // The JSON sidecar holds the same constraints
+    dump_symbolic_trace(
+        dump_path,
+        symbolic_trace,
+        side_constraints,
+        &sexe.symbolic_library.id2name,
+        true,
+    )
+    .unwrap();
+    let sidecar: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&json_path).unwrap()).unwrap();
+    assert_eq!(
+        sidecar["symbolic_trace"].as_array().unwrap().len(),
+        symbolic_trace.len()
+    );
+    assert_eq!(
+        sidecar["side_constraints"].as_array().unwrap().len(),
+        side_constraints.len()
+    );
+    assert_eq!(
+        sidecar["side_constraints"][0].as_str().unwrap(),
+        &lines[symbolic_trace.len()]["side\t".len()..]
+    );
+
+    std::fs::remove_file(dump_path).unwrap();
+    std::fs::remove_file(&json_path).unwrap();
+}
+
+#[test]
+fn test_lookup_fmt_of_state_is_stable() {
+    let id2name: FxHashMap<usize, String> =
+        FxHashMap::from_iter([(0, "main".to_string()), (1, "a".to_string())]);
+    let owner = Rc::new(vec![OwnerName {
+        id: 0,
+        access: None,
+        counter: 0,
+    }]);
+    let names: Vec<SymbolicName> = (0..32)
+        .map(|i| {
+            SymbolicName::new(
+                1,
+                owner.clone(),
+                Some(vec![SymbolicAccess::ArrayAccess(
+                    SymbolicValue::ConstantInt(BigInt::from(i)),
+                )]),
+            )
+        })
+        .collect();
+
+    // The same bindings, inserted in opposite orders
+    let mut state = SymbolicState::new();
+    let mut reversed_state = SymbolicState::new();
+    for (i, name) in names.iter().enumerate() {
+        state.set_sym_val(name.clone(), SymbolicValue::ConstantInt(BigInt::from(i)));
+    }
+    for (i, name) in names.iter().enumerate().rev() {
+        reversed_state.set_sym_val(name.clone(), SymbolicValue::ConstantInt(BigInt::from(i)));
+    }
+
+    let formatted = state.lookup_fmt(&id2name);
+    assert_eq!(formatted, state.lookup_fmt(&id2name));
+    assert_eq!(formatted, reversed_state.lookup_fmt(&id2name));
+
+    let values: Vec<&str> = formatted
+        .lines()
+        .filter(|line| line.trim_start().starts_with("main.a["))
+        .collect();
+    assert_eq!(values.len(), names.len());
+    let mut sorted_values = values.clone();
+    sorted_values.sort();
+    assert_eq!(values, sorted_values);
+}
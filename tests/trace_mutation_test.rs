@@ -0,0 +1,419 @@
+use std::rc::Rc;
+
+use num_bigint_dig::BigInt;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rustc_hash::FxHashMap;
+
+use program_structure::ast::ExpressionInfixOpcode;
+
+use zkfuzz::executor::debug_ast::DebuggableExpressionInfixOpcode;
+use zkfuzz::executor::symbolic_state::SymbolicTrace;
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicAccess, SymbolicName, SymbolicValue};
+use zkfuzz::mutator::mutation_config::MutationConfig;
+use zkfuzz::mutator::mutation_test::Gene;
+use zkfuzz::mutator::mutation_test_trace_initialization_fn::{
+    initialize_population_with_index_offset, initialize_population_with_signal_swap,
+};
+use zkfuzz::mutator::mutation_test_trace_mutation_fn::{
+    mutate_trace_with_constraint_deletion, mutate_trace_with_index_offset,
+    mutate_trace_with_signal_swap,
+};
+use zkfuzz::mutator::mutation_utils::{
+    apply_trace_mutation, draw_index_offset, gather_array_dimensions, get_assigned_value,
+    is_deletable_constraint,
+};
+use zkfuzz::mutator::utils::BaseVerificationConfig;
+
+fn signal(id: usize, index: Option<usize>) -> SymbolicName {
+    SymbolicName::new(
+        id,
+        Rc::new(vec![OwnerName {
+            id: 0,
+            counter: 0,
+            access: None,
+        }]),
+        index.map(|i| {
+            vec![SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(
+                BigInt::from(i),
+            ))]
+        }),
+    )
+}
+
+fn element(id: usize, indices: &[usize]) -> SymbolicName {
+    SymbolicName::new(
+        id,
+        Rc::new(vec![OwnerName {
+            id: 0,
+            counter: 0,
+            access: None,
+        }]),
+        Some(
+            indices
+                .iter()
+                .map(|i| SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(BigInt::from(*i))))
+                .collect(),
+        ),
+    )
+}
+
+fn variable(name: &SymbolicName) -> Rc<SymbolicValue> {
+    Rc::new(SymbolicValue::Variable(name.clone()))
+}
+
+fn constant(value: i64) -> Rc<SymbolicValue> {
+    Rc::new(SymbolicValue::ConstantInt(BigInt::from(value)))
+}
+
+fn binary_op(
+    lhs: Rc<SymbolicValue>,
+    op: ExpressionInfixOpcode,
+    rhs: Rc<SymbolicValue>,
+) -> Rc<SymbolicValue> {
+    Rc::new(SymbolicValue::BinaryOp(
+        lhs,
+        DebuggableExpressionInfixOpcode(op),
+        rhs,
+    ))
+}
+
+/// Checks that `mutated` only differs from `original` by variables of the same dimensionality.
+fn assert_only_signals_swapped(original: &SymbolicValue, mutated: &SymbolicValue) -> bool {
+    match (original, mutated) {
+        (SymbolicValue::ConstantInt(c1), SymbolicValue::ConstantInt(c2)) => {
+            assert_eq!(c1, c2, "a constant was mutated");
+            false
+        }
+        (SymbolicValue::Variable(n1), SymbolicValue::Variable(n2)) => {
+            assert_eq!(n1.get_dim(), n2.get_dim(), "the dimensionality changed");
+            n1 != n2
+        }
+        (SymbolicValue::BinaryOp(l1, op1, r1), SymbolicValue::BinaryOp(l2, op2, r2)) => {
+            assert_eq!(op1, op2, "an operator was mutated");
+            let is_left_swapped = assert_only_signals_swapped(l1, l2);
+            let is_right_swapped = assert_only_signals_swapped(r1, r2);
+            is_left_swapped || is_right_swapped
+        }
+        _ => panic!("the structure of the assignment changed"),
+    }
+}
+
+/// Checks that `mutated` only differs from `original` by array indices within `dimensions`.
+fn assert_only_indices_shifted(
+    original: &SymbolicValue,
+    mutated: &SymbolicValue,
+    dimensions: &FxHashMap<usize, Vec<usize>>,
+) -> bool {
+    match (original, mutated) {
+        (SymbolicValue::ConstantInt(c1), SymbolicValue::ConstantInt(c2)) => {
+            assert_eq!(c1, c2, "a constant was mutated");
+            false
+        }
+        (SymbolicValue::Variable(n1), SymbolicValue::Variable(n2)) => {
+            assert_eq!(n1.id, n2.id, "a different signal was read");
+            assert_eq!(n1.get_dim(), n2.get_dim(), "the dimensionality changed");
+            if let Some(access) = &n2.access {
+                for (a, dim) in access.iter().zip(&dimensions[&n2.id]) {
+                    match a {
+                        SymbolicAccess::ArrayAccess(SymbolicValue::ConstantInt(i)) => {
+                            assert!(*i >= BigInt::from(0) && *i < BigInt::from(*dim))
+                        }
+                        _ => panic!("the index is not a constant"),
+                    }
+                }
+            }
+            n1 != n2
+        }
+        (SymbolicValue::BinaryOp(l1, op1, r1), SymbolicValue::BinaryOp(l2, op2, r2)) => {
+            assert_eq!(op1, op2, "an operator was mutated");
+            let is_left_shifted = assert_only_indices_shifted(l1, l2, dimensions);
+            let is_right_shifted = assert_only_indices_shifted(r1, r2, dimensions);
+            is_left_shifted || is_right_shifted
+        }
+        _ => panic!("the structure of the assignment changed"),
+    }
+}
+
+#[test]
+fn test_signal_swap_mutation() {
+    let (a, b, c) = (signal(1, None), signal(2, None), signal(3, None));
+    let (arr_0, arr_1) = (signal(4, Some(0)), signal(4, Some(1)));
+    let (out, tmp) = (signal(5, None), signal(6, None));
+
+    let symbolic_trace: SymbolicTrace = vec![
+        Rc::new(SymbolicValue::Assign(
+            variable(&out),
+            binary_op(
+                binary_op(variable(&a), ExpressionInfixOpcode::Mul, constant(7)),
+                ExpressionInfixOpcode::Add,
+                variable(&b),
+            ),
+            false,
+            None,
+        )),
+        Rc::new(SymbolicValue::Assign(
+            variable(&tmp),
+            binary_op(variable(&arr_0), ExpressionInfixOpcode::Sub, constant(3)),
+            false,
+            None,
+        )),
+        Rc::new(SymbolicValue::AssignEq(
+            variable(&c),
+            binary_op(variable(&arr_1), ExpressionInfixOpcode::Mul, variable(&out)),
+        )),
+    ];
+    let pos = vec![0, 1];
+
+    let base_config = BaseVerificationConfig {
+        target_template_name: "Main".to_string(),
+        prime: BigInt::from(17),
+        range: BigInt::from(100),
+        ..Default::default()
+    };
+    let mutation_config = MutationConfig::default();
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut population = initialize_population_with_signal_swap(
+        &pos,
+        30,
+        &symbolic_trace,
+        &base_config,
+        &mutation_config,
+        &mut rng,
+    );
+    for individual in population.iter_mut() {
+        for _ in 0..10 {
+            mutate_trace_with_signal_swap(
+                &pos,
+                &symbolic_trace,
+                individual,
+                &base_config,
+                &mutation_config,
+                &mut rng,
+            );
+        }
+    }
+
+    let mut num_swaps = 0;
+    for individual in &population {
+        for (p, mutated) in individual {
+            assert!(pos.contains(p));
+            if assert_only_signals_swapped(get_assigned_value(&symbolic_trace[*p]), mutated) {
+                num_swaps += 1;
+            }
+        }
+    }
+    assert!(num_swaps > 0);
+}
+
+#[test]
+fn test_index_offset_mutation() {
+    let (arr_0, arr_1, arr_2) = (signal(1, Some(0)), signal(1, Some(1)), signal(1, Some(2)));
+    let (mat_01, mat_10) = (element(2, &[0, 1]), element(2, &[1, 0]));
+    let (a, out, tmp) = (signal(3, None), signal(4, None), signal(5, None));
+
+    let symbolic_trace: SymbolicTrace = vec![
+        Rc::new(SymbolicValue::Assign(
+            variable(&out),
+            binary_op(
+                binary_op(variable(&arr_0), ExpressionInfixOpcode::Mul, constant(7)),
+                ExpressionInfixOpcode::Add,
+                variable(&arr_2),
+            ),
+            false,
+            None,
+        )),
+        Rc::new(SymbolicValue::Assign(
+            variable(&tmp),
+            binary_op(variable(&mat_01), ExpressionInfixOpcode::Sub, variable(&a)),
+            false,
+            None,
+        )),
+        Rc::new(SymbolicValue::AssignEq(
+            variable(&arr_1),
+            binary_op(
+                variable(&mat_10),
+                ExpressionInfixOpcode::Mul,
+                variable(&out),
+            ),
+        )),
+    ];
+    let pos = vec![0, 1];
+
+    let dimensions = gather_array_dimensions(&symbolic_trace);
+    assert_eq!(dimensions.len(), 2);
+    assert_eq!(dimensions[&signal(1, None)], vec![3]);
+    assert_eq!(dimensions[&signal(2, None)], vec![2, 2]);
+
+    // The first and the last elements can only be shifted inwards
+    let mut rng = StdRng::seed_from_u64(0);
+    for edge in [&arr_0, &arr_2] {
+        for _ in 0..10 {
+            let shifted = draw_index_offset(
+                &SymbolicValue::Variable(edge.clone()),
+                &dimensions,
+                &mut rng,
+            );
+            assert_eq!(shifted, Some(SymbolicValue::Variable(arr_1.clone())));
+        }
+    }
+    assert_eq!(
+        draw_index_offset(&SymbolicValue::Variable(a.clone()), &dimensions, &mut rng),
+        None
+    );
+    let single: FxHashMap<SymbolicName, Vec<usize>> =
+        vec![(signal(1, None), vec![1])].into_iter().collect();
+    assert_eq!(
+        draw_index_offset(&SymbolicValue::Variable(arr_0.clone()), &single, &mut rng),
+        None
+    );
+
+    let base_config = BaseVerificationConfig {
+        target_template_name: "Main".to_string(),
+        prime: BigInt::from(17),
+        range: BigInt::from(100),
+        ..Default::default()
+    };
+    let mutation_config = MutationConfig::default();
+
+    let mut population = initialize_population_with_index_offset(
+        &pos,
+        30,
+        &symbolic_trace,
+        &base_config,
+        &mutation_config,
+        &mut rng,
+    );
+    for individual in population.iter_mut() {
+        for _ in 0..10 {
+            mutate_trace_with_index_offset(
+                &pos,
+                &symbolic_trace,
+                individual,
+                &base_config,
+                &mutation_config,
+                &mut rng,
+            );
+        }
+    }
+
+    let bounds: FxHashMap<usize, Vec<usize>> =
+        vec![(1, vec![3]), (2, vec![2, 2])].into_iter().collect();
+    let mut num_shifts = 0;
+    for individual in &population {
+        for (p, mutated) in individual {
+            assert!(pos.contains(p));
+            if assert_only_indices_shifted(
+                get_assigned_value(&symbolic_trace[*p]),
+                mutated,
+                &bounds,
+            ) {
+                num_shifts += 1;
+            }
+        }
+    }
+    assert!(num_shifts > 0);
+}
+
+#[test]
+fn test_constraint_deletion_mutation() {
+    let (a, inv, out) = (signal(1, None), signal(2, None), signal(3, None));
+
+    // inv <-- 1 / a; out <== a * inv; out * (out - 1) === 0;
+    let symbolic_trace: SymbolicTrace = vec![
+        Rc::new(SymbolicValue::Assign(
+            variable(&inv),
+            binary_op(constant(1), ExpressionInfixOpcode::Div, variable(&a)),
+            false,
+            None,
+        )),
+        Rc::new(SymbolicValue::AssignEq(
+            variable(&out),
+            binary_op(variable(&a), ExpressionInfixOpcode::Mul, variable(&inv)),
+        )),
+        binary_op(
+            binary_op(
+                variable(&out),
+                ExpressionInfixOpcode::Mul,
+                binary_op(variable(&out), ExpressionInfixOpcode::Sub, constant(1)),
+            ),
+            ExpressionInfixOpcode::Eq,
+            constant(0),
+        ),
+    ];
+    let pos = vec![0];
+
+    let base_config = BaseVerificationConfig {
+        target_template_name: "Main".to_string(),
+        prime: BigInt::from(17),
+        range: BigInt::from(100),
+        ..Default::default()
+    };
+    let mut mutation_config = MutationConfig::default();
+    mutation_config.statement_deletion_prob = 1.0;
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let num_constraints = |trace: &SymbolicTrace| {
+        trace
+            .iter()
+            .filter(|value| is_deletable_constraint(value))
+            .count()
+    };
+    let assigned = |trace: &SymbolicTrace| {
+        trace
+            .iter()
+            .filter_map(|value| match value.as_ref() {
+                SymbolicValue::Assign(lhs, _, _, _) | SymbolicValue::AssignEq(lhs, _) => {
+                    Some(lhs.clone())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    for _ in 0..10 {
+        let mut individual = Gene::default();
+        mutate_trace_with_constraint_deletion(
+            &pos,
+            &symbolic_trace,
+            &mut individual,
+            &base_config,
+            &mutation_config,
+            &mut rng,
+        );
+        assert_eq!(individual.len(), 1);
+        let (deleted, value) = individual.iter().next().unwrap();
+        assert!(is_deletable_constraint(&symbolic_trace[*deleted]));
+        assert_eq!(*value, SymbolicValue::NOP);
+
+        // Exactly one constraint is removed, and every signal is still assigned
+        let mutated_trace = apply_trace_mutation(&symbolic_trace, &individual);
+        assert_eq!(mutated_trace.len(), symbolic_trace.len());
+        assert_eq!(
+            num_constraints(&mutated_trace),
+            num_constraints(&symbolic_trace) - 1
+        );
+        assert_eq!(assigned(&mutated_trace), assigned(&symbolic_trace));
+    }
+
+    // Once every constraint is deleted, the assignments are mutated instead
+    let mut individual: Gene = vec![(1, SymbolicValue::NOP), (2, SymbolicValue::NOP)]
+        .into_iter()
+        .collect();
+    individual.insert(0, SymbolicValue::ConstantInt(BigInt::from(3)));
+    mutate_trace_with_constraint_deletion(
+        &pos,
+        &symbolic_trace,
+        &mut individual,
+        &base_config,
+        &mutation_config,
+        &mut rng,
+    );
+    assert_eq!(individual[&1], SymbolicValue::NOP);
+    assert_eq!(individual[&2], SymbolicValue::NOP);
+    assert_eq!(
+        num_constraints(&apply_trace_mutation(&symbolic_trace, &individual)),
+        0
+    );
+}
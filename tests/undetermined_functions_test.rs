@@ -0,0 +1,42 @@
+mod utils;
+
+use std::str::FromStr;
+
+use num_bigint_dig::BigInt;
+
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_value::{SymbolicAccess, SymbolicValue};
+use zkfuzz::mutator::undetermined_functions::find_undetermined_functions;
+
+use crate::utils::prepare_symbolic_library;
+
+#[test]
+fn test_undetermined_function() {
+    let path = "./tests/sample/test_undetermined_function.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, _) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let undetermined = find_undetermined_functions(&mut symbolic_library, &setting);
+    assert_eq!(
+        undetermined
+            .iter()
+            .map(|function| function.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["partial"]
+    );
+
+    let free_variables = &undetermined[0].free_variables;
+    assert_eq!(free_variables.len(), 1);
+    assert_eq!(free_variables[0].id, symbolic_library.name2id["r"]);
+    assert_eq!(
+        free_variables[0].access,
+        Some(vec![SymbolicAccess::ArrayAccess(
+            SymbolicValue::ConstantInt(BigInt::from(1))
+        )])
+    );
+}
@@ -8,7 +8,7 @@ use program_structure::error_definition::Report;
 use program_structure::program_archive::ProgramArchive;
 
 use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
-use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicLibrary};
+use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicLibrary, SymbolicNameInterner};
 use zkfuzz::type_analysis_user::analyse_project;
 
 pub fn parse_project(initial_file: String, prime: BigInt) -> Result<ProgramArchive, ()> {
@@ -37,8 +37,12 @@ pub fn prepare_symbolic_library(
         name2id: FxHashMap::default(),
         id2name: FxHashMap::default(),
         function_library: FxHashMap::default(),
+        bus_library: FxHashMap::default(),
         function_counter: FxHashMap::default(),
+        source_files: FxHashMap::default(),
+        name_interner: SymbolicNameInterner::default(),
     };
+    symbolic_library.register_source_files(&program_archive.file_library);
 
     let whitelist = FxHashSet::default();
 
@@ -68,6 +72,10 @@ pub fn prepare_symbolic_library(
         symbolic_library.register_function(k.clone(), body.clone(), v.get_name_of_params());
     }
 
+    for (k, v) in program_archive.buses.clone().into_iter() {
+        symbolic_library.register_bus(k, v.get_body().clone(), v.get_name_of_params());
+    }
+
     (symbolic_library, program_archive)
 }
 
@@ -93,7 +101,8 @@ pub fn execute(sexe: &mut SymbolicExecutor, program_archive: &ProgramArchive) {
             sexe.cur_state
                 .set_template_id(sexe.symbolic_library.name2id[id]);
 
-            sexe.feed_arguments(template.get_name_of_params(), args);
+            sexe.feed_arguments(template.get_name_of_params(), args)
+                .unwrap();
 
             let body = sexe.symbolic_library.template_library[&sexe.symbolic_library.name2id[id]]
                 .body
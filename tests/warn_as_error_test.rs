@@ -0,0 +1,29 @@
+use std::process::Command;
+
+fn run(extra_args: &[&str]) -> (Option<i32>, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_zkfuzz"))
+        .args(&[
+            "./tests/sample/test_unused_output.circom",
+            "--search_mode",
+            "quick",
+            "--no_color",
+        ])
+        .args(extra_args)
+        .output()
+        .unwrap();
+    (
+        output.status.code(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+    )
+}
+
+#[test]
+fn test_warn_as_error_fails_on_unused_output() {
+    let (code, stderr) = run(&[]);
+    assert_eq!(code, Some(0));
+    assert!(stderr.contains("Everything went okay"));
+
+    let (code, stderr) = run(&["--warn_as_error"]);
+    assert_eq!(code, Some(1));
+    assert!(stderr.contains("--warn_as_error"));
+}
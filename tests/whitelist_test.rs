@@ -0,0 +1,258 @@
+mod utils;
+
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use num_bigint_dig::BigInt;
+use num_traits::{One, Zero};
+use rustc_hash::FxHashMap;
+
+use program_structure::ast::ExpressionInfixOpcode;
+
+use zkfuzz::executor::debug_ast::DebuggableExpressionInfixOpcode;
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::{
+    get_default_setting_for_concrete_execution, get_default_setting_for_symbolic_execution,
+    WhitelistMode,
+};
+use zkfuzz::executor::symbolic_value::{
+    extract_variables, OwnerName, SymbolicAccess, SymbolicName, SymbolicValue,
+};
+use zkfuzz::mutator::brute_force::brute_force_search;
+use zkfuzz::mutator::utils::{emulate_symbolic_trace, BaseVerificationConfig};
+
+use crate::utils::{execute, prepare_symbolic_library};
+
+#[test]
+fn test_whitelist_black_box() {
+    let path = "./tests/sample/test_whitelist_black_box.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let iszero_id = symbolic_library.name2id["IsZero"];
+    Arc::make_mut(
+        symbolic_library
+            .template_library
+            .get_mut(&iszero_id)
+            .unwrap(),
+    )
+    .is_safe = true;
+    let mut black_box_library = symbolic_library.clone();
+
+    let inline_setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    let mut inline_sexe = SymbolicExecutor::new(&mut symbolic_library, &inline_setting);
+    execute(&mut inline_sexe, &program_archive);
+
+    let mut black_box_setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    black_box_setting.whitelist_mode = WhitelistMode::TrustAsBlackBox;
+    let mut black_box_sexe = SymbolicExecutor::new(&mut black_box_library, &black_box_setting);
+    execute(&mut black_box_sexe, &program_archive);
+
+    let inv_id = inline_sexe.symbolic_library.name2id["inv"];
+    assert!(extract_variables(&inline_sexe.cur_state.side_constraints)
+        .iter()
+        .any(|name| name.id == inv_id));
+    assert!(
+        !extract_variables(&black_box_sexe.cur_state.side_constraints)
+            .iter()
+            .any(|name| name.id == inv_id)
+    );
+    assert!(
+        black_box_sexe.cur_state.side_constraints.len()
+            < inline_sexe.cur_state.side_constraints.len()
+    );
+
+    let main_owner = Rc::new(vec![OwnerName {
+        id: black_box_sexe.symbolic_library.name2id["main"],
+        access: None,
+        counter: 0,
+    }]);
+    let main_a = SymbolicName::new(
+        black_box_sexe.symbolic_library.name2id["a"],
+        main_owner.clone(),
+        None,
+    );
+    let main_b = SymbolicName::new(
+        black_box_sexe.symbolic_library.name2id["b"],
+        main_owner.clone(),
+        None,
+    );
+    let main_c = SymbolicName::new(
+        black_box_sexe.symbolic_library.name2id["c"],
+        main_owner.clone(),
+        None,
+    );
+
+    let runtime_mutable_positions = FxHashMap::default();
+    for (b, expected) in [(3, BigInt::one()), (2, BigInt::zero())] {
+        let mut assignment = FxHashMap::from_iter([
+            (main_a.clone(), BigInt::from(3)),
+            (main_b.clone(), BigInt::from(b)),
+        ]);
+        let _ = emulate_symbolic_trace(
+            &prime,
+            &black_box_sexe.cur_state.symbolic_trace,
+            &runtime_mutable_positions,
+            &mut assignment,
+            &mut black_box_sexe.symbolic_library,
+        );
+        assert_eq!(assignment[&main_c], expected);
+    }
+}
+
+#[test]
+fn test_whitelist_black_box_has_no_counterexample() {
+    let path = "./tests/sample/test_whitelist_black_box.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let iszero_id = symbolic_library.name2id["IsZero"];
+    Arc::make_mut(
+        symbolic_library
+            .template_library
+            .get_mut(&iszero_id)
+            .unwrap(),
+    )
+    .is_safe = true;
+
+    let mut setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    setting.whitelist_mode = WhitelistMode::TrustAsBlackBox;
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+    let symbolic_trace = sexe.cur_state.symbolic_trace.clone();
+    let side_constraints = sexe.cur_state.side_constraints.clone();
+
+    let base_config = BaseVerificationConfig {
+        target_template_name: "IsEqual".to_string(),
+        prime: prime.clone(),
+        range: BigInt::from(5),
+        quick_mode: true,
+        ..Default::default()
+    };
+    let conc_setting = get_default_setting_for_concrete_execution(prime, false);
+    let mut conc_executor = SymbolicExecutor::new(&mut sexe.symbolic_library, &conc_setting);
+    conc_executor
+        .feed_arguments(&Vec::new(), &Vec::new())
+        .unwrap();
+
+    // The outputs of the trusted component are pinned to it in the side constraints as well
+    assert!(brute_force_search(
+        &mut conc_executor,
+        &symbolic_trace,
+        &side_constraints,
+        &base_config,
+    )
+    .is_none());
+}
+
+#[test]
+fn test_whitelist_precise_num2bits() {
+    let path = "./tests/sample/test_bit_width_hint.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let num2bits_id = symbolic_library.name2id["Num2Bits"];
+    Arc::make_mut(
+        symbolic_library
+            .template_library
+            .get_mut(&num2bits_id)
+            .unwrap(),
+    )
+    .is_safe = true;
+    let mut precise_library = symbolic_library.clone();
+
+    let mut black_box_setting = get_default_setting_for_symbolic_execution(prime.clone(), false);
+    black_box_setting.whitelist_mode = WhitelistMode::TrustAsBlackBox;
+    let mut black_box_sexe = SymbolicExecutor::new(&mut symbolic_library, &black_box_setting);
+    execute(&mut black_box_sexe, &program_archive);
+
+    let mut precise_setting = black_box_setting.clone();
+    precise_setting.precise_primitives = true;
+    let mut precise_sexe = SymbolicExecutor::new(&mut precise_library, &precise_setting);
+    execute(&mut precise_sexe, &program_archive);
+
+    let out_id = precise_sexe.symbolic_library.name2id["out"];
+    let n2b_owner = Rc::new(vec![
+        OwnerName {
+            id: precise_sexe.symbolic_library.name2id["main"],
+            access: None,
+            counter: 0,
+        },
+        OwnerName {
+            id: precise_sexe.symbolic_library.name2id["n2b"],
+            access: None,
+            counter: 0,
+        },
+    ]);
+    assert!(
+        !extract_variables(&black_box_sexe.cur_state.side_constraints)
+            .iter()
+            .any(|name| name.id == out_id && name.owner == n2b_owner)
+    );
+
+    // Each output bit is boolean
+    for i in 0..8 {
+        let out_i = Rc::new(SymbolicValue::Variable(SymbolicName::new(
+            out_id,
+            n2b_owner.clone(),
+            Some(vec![SymbolicAccess::ArrayAccess(
+                SymbolicValue::ConstantInt(BigInt::from(i)),
+            )]),
+        )));
+        let is_bit = SymbolicValue::BinaryOp(
+            Rc::new(SymbolicValue::BinaryOp(
+                out_i.clone(),
+                DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Mul),
+                Rc::new(SymbolicValue::BinaryOp(
+                    out_i,
+                    DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Sub),
+                    Rc::new(SymbolicValue::ConstantInt(BigInt::one())),
+                )),
+            )),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Eq),
+            Rc::new(SymbolicValue::ConstantInt(BigInt::zero())),
+        );
+        assert!(precise_sexe
+            .cur_state
+            .side_constraints
+            .iter()
+            .any(|constraint| **constraint == is_bit));
+    }
+
+    // The bits of `a` are assigned and reconstruct it
+    let main_owner = Rc::new(vec![n2b_owner[0].clone()]);
+    let main_a = SymbolicName::new(
+        precise_sexe.symbolic_library.name2id["a"],
+        main_owner.clone(),
+        None,
+    );
+    let main_b = SymbolicName::new(precise_sexe.symbolic_library.name2id["b"], main_owner, None);
+    let mut assignment =
+        FxHashMap::from_iter([(main_a, BigInt::from(5)), (main_b, BigInt::from(2))]);
+    let result = emulate_symbolic_trace(
+        &prime,
+        &precise_sexe.cur_state.symbolic_trace,
+        &FxHashMap::default(),
+        &mut assignment,
+        &mut precise_sexe.symbolic_library,
+    );
+    assert_eq!(result.map(|(success, _)| success), Some(true));
+    let out_2 = SymbolicName::new(
+        out_id,
+        n2b_owner,
+        Some(vec![SymbolicAccess::ArrayAccess(
+            SymbolicValue::ConstantInt(BigInt::from(2)),
+        )]),
+    );
+    assert_eq!(assignment[&out_2], BigInt::one());
+}
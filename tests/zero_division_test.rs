@@ -1,4 +1,7 @@
+mod utils;
+
 use std::rc::Rc;
+use std::str::FromStr;
 
 use num_bigint_dig::BigInt;
 use num_traits::{One, Zero};
@@ -6,9 +9,16 @@ use num_traits::{One, Zero};
 use program_structure::ast::ExpressionInfixOpcode;
 
 use zkfuzz::executor::debug_ast::DebuggableExpressionInfixOpcode;
-use zkfuzz::executor::symbolic_value::{get_coefficient_of_polynomials, get_degree_polynomial};
+use zkfuzz::executor::symbolic_execution::SymbolicExecutor;
+use zkfuzz::executor::symbolic_setting::get_default_setting_for_symbolic_execution;
+use zkfuzz::executor::symbolic_value::{
+    extract_non_constant_divisors, get_coefficient_of_polynomials, get_degree_polynomial,
+};
 use zkfuzz::executor::symbolic_value::{OwnerName, SymbolicName, SymbolicValue};
 use zkfuzz::executor::utils::solve_quadratic_modulus_equation;
+use zkfuzz::mutator::utils::gather_potential_zero_division;
+
+use crate::utils::{execute, prepare_symbolic_library};
 
 // A dummy owner to use for creating SymbolicNames.
 fn dummy_owner() -> OwnerName {
@@ -376,3 +386,125 @@ fn test_quadratic_no_solution() {
     let modulus = BigInt::from(11);
     assert_eq!(solve_quadratic_modulus_equation(&coeffs, &modulus), None);
 }
+
+#[test]
+fn test_extract_non_constant_divisors() {
+    // Expression: (1 / x) + (y \ 2), where only x may be zero.
+    let x = make_symbolic_name(1);
+    let y = make_symbolic_name(2);
+    let expr = SymbolicValue::BinaryOp(
+        Rc::new(SymbolicValue::BinaryOp(
+            Rc::new(SymbolicValue::ConstantInt(BigInt::one())),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Div),
+            Rc::new(SymbolicValue::Variable(x.clone())),
+        )),
+        DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Add),
+        Rc::new(SymbolicValue::BinaryOp(
+            Rc::new(SymbolicValue::Variable(y)),
+            DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::IntDiv),
+            Rc::new(SymbolicValue::ConstantInt(BigInt::from(2))),
+        )),
+    );
+    let mut divisors = Vec::new();
+    extract_non_constant_divisors(&expr, &mut divisors);
+    assert_eq!(divisors, vec![SymbolicValue::Variable(x)]);
+}
+
+#[test]
+fn test_potential_div_by_zero_iszero() {
+    let path = "./tests/sample/test_if_else.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // `inv <-- in!=0 ? 1/in : 0` divides by `in`
+    let in_name = SymbolicName::new(
+        sexe.symbolic_library.name2id["in"],
+        Rc::new(vec![OwnerName {
+            id: sexe.symbolic_library.name2id["main"],
+            access: None,
+            counter: 0,
+        }]),
+        None,
+    );
+    let potential_div_by_zeros = &sexe.cur_state.potential_div_by_zeros;
+    assert_eq!(potential_div_by_zeros.len(), 1);
+    assert_eq!(
+        potential_div_by_zeros[0].divisor,
+        SymbolicValue::Variable(in_name)
+    );
+}
+
+#[test]
+fn test_potential_div_by_zero_mod() {
+    let path = "./tests/sample/test_vuln_mod_by_signal.circom".to_string();
+    let prime = BigInt::from_str(
+        "21888242871839275222246405745257275088548364400416034343698204186575808495617",
+    )
+    .unwrap();
+
+    let (mut symbolic_library, program_archive) = prepare_symbolic_library(path, prime.clone());
+    let setting = get_default_setting_for_symbolic_execution(prime, false);
+
+    let mut sexe = SymbolicExecutor::new(&mut symbolic_library, &setting);
+    execute(&mut sexe, &program_archive);
+
+    // `q <-- a \ b` and `r <-- a % b` both divide by `b`
+    let b_name = SymbolicName::new(
+        sexe.symbolic_library.name2id["b"],
+        Rc::new(vec![OwnerName {
+            id: sexe.symbolic_library.name2id["main"],
+            access: None,
+            counter: 0,
+        }]),
+        None,
+    );
+    let potential_div_by_zeros = &sexe.cur_state.potential_div_by_zeros;
+    assert_eq!(potential_div_by_zeros.len(), 2);
+    assert!(potential_div_by_zeros
+        .iter()
+        .all(|d| d.divisor == SymbolicValue::Variable(b_name.clone())));
+
+    // The search solves both divisors for zero
+    let zero_div_positions = gather_potential_zero_division(&sexe.cur_state.symbolic_trace);
+    assert_eq!(zero_div_positions.len(), 2);
+    for (_, (_, denominator_polys)) in &zero_div_positions {
+        assert!(denominator_polys.iter().any(|(name, _)| *name == b_name));
+    }
+}
+
+#[test]
+fn test_extract_constant_zero_divisor() {
+    let x = make_symbolic_name(1);
+    let zero = SymbolicValue::ConstantInt(BigInt::zero());
+    let expr = SymbolicValue::BinaryOp(
+        Rc::new(SymbolicValue::Variable(x)),
+        DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Mod),
+        Rc::new(zero.clone()),
+    );
+    let mut divisors = Vec::new();
+    extract_non_constant_divisors(&expr, &mut divisors);
+    assert_eq!(divisors, vec![zero]);
+}
+
+#[test]
+fn test_extract_non_constant_divisors_aux_binary_op() {
+    // Expression: x % y as an auxiliary operation, where y may be zero.
+    let x = make_symbolic_name(1);
+    let y = make_symbolic_name(2);
+    let expr = SymbolicValue::AuxBinaryOp(
+        Rc::new(SymbolicValue::Variable(x)),
+        DebuggableExpressionInfixOpcode(ExpressionInfixOpcode::Mod),
+        Rc::new(SymbolicValue::Variable(y.clone())),
+    );
+    let mut divisors = Vec::new();
+    extract_non_constant_divisors(&expr, &mut divisors);
+    assert_eq!(divisors, vec![SymbolicValue::Variable(y)]);
+}